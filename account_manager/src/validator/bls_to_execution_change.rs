@@ -0,0 +1,269 @@
+use crate::common::read_mnemonic_from_cli;
+use crate::validator::create::COUNT_FLAG;
+use crate::validator::recover::{FIRST_INDEX_FLAG, MNEMONIC_FLAG};
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use account_utils::eth2_keystore::keypair_from_secret;
+use bls::{get_withdrawal_credentials, Keypair};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use eth2::types::{GenericResponse, ValidatorData};
+use eth2_wallet::bip39::Seed;
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::path::PathBuf;
+use types::{
+    Address, BeaconState, BlsToExecutionChange, EthSpec, Hash256, PublicKeyBytes,
+    SignedBlsToExecutionChange,
+};
+
+pub const CMD: &str = "bls-to-execution-change";
+pub const EXECUTION_ADDRESS_FLAG: &str = "execution-address";
+pub const BEACON_STATE_FLAG: &str = "beacon-state";
+pub const VALIDATORS_FILE_FLAG: &str = "validators-file";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Generates signed messages which change the withdrawal credentials of validators \
+            from a BLS withdrawal key to an execution address. The withdrawal keys are derived \
+            from a BIP-39 mnemonic and checked against the withdrawal credentials in the supplied \
+            beacon state or validator list. No network access is required; the messages are \
+            written to a JSON file which can later be submitted to the \
+            `/eth/v1/beacon/pool/bls_to_execution_changes` endpoint of any beacon node.",
+        )
+        .arg(
+            Arg::with_name(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help("The first of consecutive key indexes to generate changes for.")
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::with_name(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("COUNT")
+                .help(
+                    "The number of consecutive key indexes to generate changes for, starting \
+                    from `--first-index`.",
+                )
+                .takes_value(true)
+                .default_value("1"),
+        )
+        .arg(
+            Arg::with_name(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help("If present, the mnemonic will be read in from this file.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(EXECUTION_ADDRESS_FLAG)
+                .long(EXECUTION_ADDRESS_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help(
+                    "The execution address to withdraw to. This change is irreversible once \
+                    the messages are included on chain.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(BEACON_STATE_FLAG)
+                .long(BEACON_STATE_FLAG)
+                .value_name("SSZ_STATE")
+                .help("Path to an SSZ-encoded `BeaconState` to read the validator registry from.")
+                .takes_value(true)
+                .conflicts_with(VALIDATORS_FILE_FLAG)
+                .required_unless(VALIDATORS_FILE_FLAG),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FILE_FLAG)
+                .long(VALIDATORS_FILE_FLAG)
+                .value_name("JSON_FILE")
+                .help(
+                    "Path to a JSON validator list, as returned by the \
+                    `/eth/v1/beacon/states/{state_id}/validators` endpoint of a beacon node. \
+                    The genesis validators root is read from the network config.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("PATH")
+                .help("The file to write the signed changes to. Must not already exist.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .takes_value(false)
+                .hidden(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+/// The index and withdrawal credentials of a validator in the registry.
+struct RegistryEntry {
+    validator_index: u64,
+    withdrawal_credentials: Hash256,
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(), String> {
+    let first_index: u32 = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
+    let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
+    let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
+    let execution_address: Address = clap_utils::parse_required(matches, EXECUTION_ADDRESS_FLAG)?;
+    let beacon_state_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, BEACON_STATE_FLAG)?;
+    let validators_file_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, VALIDATORS_FILE_FLAG)?;
+    let output_path: PathBuf = clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?;
+    let stdin_inputs = cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG);
+    let spec = &env.eth2_config.spec;
+
+    if output_path.exists() {
+        return Err(format!("{:?} already exists", output_path));
+    }
+
+    let (registry, genesis_validators_root) = match (beacon_state_path, validators_file_path) {
+        (Some(path), _) => {
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Unable to read beacon state {:?}: {:?}", path, e))?;
+            let state = BeaconState::<E>::from_ssz_bytes(&bytes, spec)
+                .map_err(|e| format!("Unable to decode beacon state: {:?}", e))?;
+            let registry = state
+                .validators()
+                .iter()
+                .enumerate()
+                .map(|(validator_index, validator)| {
+                    (
+                        validator.pubkey,
+                        RegistryEntry {
+                            validator_index: validator_index as u64,
+                            withdrawal_credentials: validator.withdrawal_credentials,
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>();
+            (registry, state.genesis_validators_root())
+        }
+        (None, Some(path)) => {
+            let file = File::open(&path)
+                .map_err(|e| format!("Unable to open validators file {:?}: {:?}", path, e))?;
+            let validators: GenericResponse<Vec<ValidatorData>> = serde_json::from_reader(file)
+                .map_err(|e| format!("Unable to parse validators file: {:?}", e))?;
+            let registry = validators
+                .data
+                .into_iter()
+                .map(|validator| {
+                    (
+                        validator.validator.pubkey,
+                        RegistryEntry {
+                            validator_index: validator.index,
+                            withdrawal_credentials: validator.validator.withdrawal_credentials,
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>();
+            let genesis_validators_root = env
+                .eth2_network_config
+                .as_ref()
+                .ok_or("Unable to load the network config")?
+                .beacon_state::<E>()
+                .map_err(|e| format!("Unable to load the genesis state: {}", e))?
+                .genesis_validators_root();
+            (registry, genesis_validators_root)
+        }
+        (None, None) => {
+            return Err(format!(
+                "One of --{} or --{} is required",
+                BEACON_STATE_FLAG, VALIDATORS_FILE_FLAG
+            ))
+        }
+    };
+
+    let mnemonic = read_mnemonic_from_cli(mnemonic_path, stdin_inputs)?;
+    let seed = Seed::new(&mnemonic, "");
+
+    let derive = |index: u32, key_type: KeyType| -> Result<Keypair, String> {
+        let (secret, _) = recover_validator_secret_from_mnemonic(seed.as_bytes(), index, key_type)
+            .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+        keypair_from_secret(secret.as_bytes())
+            .map_err(|e| format!("Unable to build keypair: {:?}", e))
+    };
+
+    let mut changes: Vec<SignedBlsToExecutionChange> = vec![];
+    for index in first_index..first_index + count {
+        let voting_keypair = derive(index, KeyType::Voting)?;
+        let voting_pubkey = PublicKeyBytes::from(&voting_keypair.pk);
+
+        let entry = match registry.get(&voting_pubkey) {
+            Some(entry) => entry,
+            None => {
+                eprintln!(
+                    "Skipping key index {}: validator {:?} is not in the registry",
+                    index, voting_pubkey
+                );
+                continue;
+            }
+        };
+
+        if entry.withdrawal_credentials.as_bytes()[0] != spec.bls_withdrawal_prefix_byte {
+            eprintln!(
+                "Skipping key index {}: validator {} does not have BLS withdrawal credentials",
+                index, entry.validator_index
+            );
+            continue;
+        }
+
+        let withdrawal_keypair = derive(index, KeyType::Withdrawal)?;
+        let expected_credentials =
+            get_withdrawal_credentials(&withdrawal_keypair.pk, spec.bls_withdrawal_prefix_byte);
+        if entry.withdrawal_credentials.as_bytes() != expected_credentials.as_slice() {
+            return Err(format!(
+                "The withdrawal credentials of validator {} do not match the withdrawal key \
+                 derived at key index {}",
+                entry.validator_index, index
+            ));
+        }
+
+        let change = BlsToExecutionChange {
+            validator_index: entry.validator_index,
+            from_bls_pubkey: PublicKeyBytes::from(&withdrawal_keypair.pk),
+            to_execution_address: execution_address,
+        }
+        .sign(&withdrawal_keypair.sk, genesis_validators_root, spec);
+        changes.push(change);
+
+        println!(
+            "Index: {}\tValidator: {}\t{:?}",
+            index, entry.validator_index, voting_pubkey
+        );
+    }
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+    serde_json::to_writer_pretty(file, &changes)
+        .map_err(|e| format!("Unable to write changes: {:?}", e))?;
+
+    eprintln!(
+        "Wrote {} BLS to execution changes to {:?}",
+        changes.len(),
+        output_path
+    );
+    eprintln!();
+    eprintln!(
+        "WARNING: ONCE SUBMITTED, THE WITHDRAWAL ADDRESS {:?} CANNOT BE CHANGED.",
+        execution_address
+    );
+
+    Ok(())
+}
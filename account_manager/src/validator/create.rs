@@ -15,7 +15,7 @@ use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
 use types::EthSpec;
-use validator_dir::Builder as ValidatorDirBuilder;
+use validator_dir::{Builder as ValidatorDirBuilder, StandardDepositDataJson};
 
 pub const CMD: &str = "create";
 pub const WALLET_NAME_FLAG: &str = "wallet-name";
@@ -24,6 +24,7 @@ pub const DEPOSIT_GWEI_FLAG: &str = "deposit-gwei";
 pub const STORE_WITHDRAW_FLAG: &str = "store-withdrawal-keystore";
 pub const COUNT_FLAG: &str = "count";
 pub const AT_MOST_FLAG: &str = "at-most";
+pub const DEPOSIT_DATA_JSON_FLAG: &str = "deposit-data-json";
 pub const WALLET_PASSWORD_PROMPT: &str = "Enter your wallet's password:";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -103,6 +104,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .conflicts_with("count")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(DEPOSIT_DATA_JSON_FLAG)
+                .long(DEPOSIT_DATA_JSON_FLAG)
+                .value_name("PATH")
+                .help(
+                    "If present, write the deposits for the created validators to this path in \
+                    the `deposit_data-*.json` format used by the staking launchpad. The file \
+                    must not already exist.",
+                )
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(STDIN_INPUTS_FLAG)
                 .takes_value(false)
@@ -139,6 +151,8 @@ pub fn cli_run<T: EthSpec>(
         .unwrap_or(spec.max_effective_balance);
     let count: Option<usize> = clap_utils::parse_optional(matches, COUNT_FLAG)?;
     let at_most: Option<usize> = clap_utils::parse_optional(matches, AT_MOST_FLAG)?;
+    let deposit_data_json_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, DEPOSIT_DATA_JSON_FLAG)?;
 
     // The command will always fail if the wallet dir does not exist.
     if !wallet_base_dir.exists() {
@@ -152,6 +166,10 @@ pub fn cli_run<T: EthSpec>(
         ));
     }
 
+    if let Some(path) = &deposit_data_json_path {
+        ensure_deposit_data_json_does_not_exist(path)?;
+    }
+
     ensure_dir_exists(&validator_dir)?;
     ensure_dir_exists(&secrets_dir)?;
 
@@ -212,6 +230,8 @@ pub fn cli_run<T: EthSpec>(
         )
     })?;
 
+    let mut deposits = vec![];
+
     for i in 0..n {
         let voting_password = random_password();
         let withdrawal_password = random_password();
@@ -241,7 +261,7 @@ pub fn cli_run<T: EthSpec>(
                 )
             })?;
 
-        ValidatorDirBuilder::new(validator_dir.clone())
+        let dir = ValidatorDirBuilder::new(validator_dir.clone())
             .password_dir(secrets_dir.clone())
             .voting_keystore(keystores.voting, voting_password.as_bytes())
             .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
@@ -250,9 +270,52 @@ pub fn cli_run<T: EthSpec>(
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
+        if deposit_data_json_path.is_some() {
+            let deposit = dir
+                .eth1_deposit_data()
+                .map_err(|e| format!("Unable to read deposit data: {:?}", e))?
+                .ok_or("Validator directory is missing deposit data")?;
+            deposits.push(StandardDepositDataJson::new(&deposit.deposit_data, &spec));
+        }
+
         println!("{}/{}\t{}", i + 1, n, voting_pubkey.as_hex_string());
     }
 
+    if let Some(path) = deposit_data_json_path {
+        write_deposit_data_json(&path, &deposits)?;
+    }
+
+    Ok(())
+}
+
+/// Returns an error if there is already a file at `path`, so that we fail before generating any
+/// keys rather than after.
+pub fn ensure_deposit_data_json_does_not_exist(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        Err(format!(
+            "The --{} file {:?} already exists",
+            DEPOSIT_DATA_JSON_FLAG, path
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Writes `deposits` to a new file at `path`, in the format expected by the staking launchpad.
+pub fn write_deposit_data_json(
+    path: &Path,
+    deposits: &[StandardDepositDataJson],
+) -> Result<(), String> {
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+    serde_json::to_writer(file, deposits)
+        .map_err(|e| format!("Unable to write deposit data JSON: {:?}", e))?;
+
+    eprintln!("Wrote {} deposits to {:?}", deposits.len(), path);
+
     Ok(())
 }
 
@@ -1,3 +1,4 @@
+pub mod bls_to_execution_change;
 pub mod create;
 pub mod exit;
 pub mod import;
@@ -37,6 +38,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .subcommand(recover::cli_app())
         .subcommand(slashing_protection::cli_app())
         .subcommand(exit::cli_app())
+        .subcommand(bls_to_execution_change::cli_app())
 }
 
 pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<(), String> {
@@ -53,11 +55,14 @@ pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<
         (modify::CMD, Some(matches)) => modify::cli_run(matches, validator_base_dir),
         (import::CMD, Some(matches)) => import::cli_run(matches, validator_base_dir),
         (list::CMD, Some(_)) => list::cli_run(validator_base_dir),
-        (recover::CMD, Some(matches)) => recover::cli_run(matches, validator_base_dir),
+        (recover::CMD, Some(matches)) => recover::cli_run::<T>(matches, env, validator_base_dir),
         (slashing_protection::CMD, Some(matches)) => {
             slashing_protection::cli_run(matches, env, validator_base_dir)
         }
         (exit::CMD, Some(matches)) => exit::cli_run(matches, env),
+        (bls_to_execution_change::CMD, Some(matches)) => {
+            bls_to_execution_change::cli_run(matches, env)
+        }
         (unknown, _) => Err(format!(
             "{} does not have a {} command. See --help",
             CMD, unknown
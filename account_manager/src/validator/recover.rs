@@ -1,6 +1,9 @@
 use super::create::STORE_WITHDRAW_FLAG;
 use crate::common::read_mnemonic_from_cli;
-use crate::validator::create::COUNT_FLAG;
+use crate::validator::create::{
+    ensure_deposit_data_json_does_not_exist, write_deposit_data_json, COUNT_FLAG,
+    DEPOSIT_DATA_JSON_FLAG, DEPOSIT_GWEI_FLAG,
+};
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::SECRETS_DIR_FLAG;
 use account_utils::eth2_keystore::{keypair_from_secret, Keystore, KeystoreBuilder};
@@ -8,10 +11,12 @@ use account_utils::random_password;
 use clap::{App, Arg, ArgMatches};
 use directory::ensure_dir_exists;
 use directory::{parse_path_or_default_with_flag, DEFAULT_SECRET_DIR};
+use environment::Environment;
 use eth2_wallet::bip39::Seed;
 use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType, ValidatorKeystores};
 use std::path::PathBuf;
-use validator_dir::Builder as ValidatorDirBuilder;
+use types::EthSpec;
+use validator_dir::{Builder as ValidatorDirBuilder, StandardDepositDataJson};
 pub const CMD: &str = "recover";
 pub const FIRST_INDEX_FLAG: &str = "first-index";
 pub const MNEMONIC_FLAG: &str = "mnemonic-path";
@@ -69,6 +74,28 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     instead generate them from the wallet seed when required.",
                 ),
         )
+        .arg(
+            Arg::with_name(DEPOSIT_DATA_JSON_FLAG)
+                .long(DEPOSIT_DATA_JSON_FLAG)
+                .value_name("PATH")
+                .help(
+                    "If present, write the deposits for the recovered validators to this path in \
+                    the `deposit_data-*.json` format used by the staking launchpad. The file \
+                    must not already exist.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DEPOSIT_GWEI_FLAG)
+                .long(DEPOSIT_GWEI_FLAG)
+                .value_name("DEPOSIT_GWEI")
+                .help(
+                    "The GWEI value of the deposits written to --deposit-data-json. Defaults to \
+                    MAX_EFFECTIVE_BALANCE.",
+                )
+                .requires(DEPOSIT_DATA_JSON_FLAG)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(STDIN_INPUTS_FLAG)
                 .takes_value(false)
@@ -78,7 +105,12 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         )
 }
 
-pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), String> {
+pub fn cli_run<T: EthSpec>(
+    matches: &ArgMatches,
+    env: Environment<T>,
+    validator_dir: PathBuf,
+) -> Result<(), String> {
+    let spec = env.core_context().eth2_config.spec;
     let secrets_dir = if matches.value_of("datadir").is_some() {
         let path: PathBuf = clap_utils::parse_required(matches, "datadir")?;
         path.join(DEFAULT_SECRET_DIR)
@@ -89,6 +121,14 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
     let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
     let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
     let stdin_inputs = cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG);
+    let deposit_data_json_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, DEPOSIT_DATA_JSON_FLAG)?;
+    let deposit_gwei = clap_utils::parse_optional(matches, DEPOSIT_GWEI_FLAG)?
+        .unwrap_or(spec.max_effective_balance);
+
+    if let Some(path) = &deposit_data_json_path {
+        ensure_deposit_data_json_does_not_exist(path)?;
+    }
 
     eprintln!("secrets-dir path: {:?}", secrets_dir);
 
@@ -103,6 +143,8 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
 
     let seed = Seed::new(&mnemonic, "");
 
+    let mut deposits = vec![];
+
     for index in first_index..first_index + count {
         let voting_password = random_password();
         let withdrawal_password = random_password();
@@ -128,14 +170,30 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
 
         let voting_pubkey = keystores.voting.pubkey().to_string();
 
-        ValidatorDirBuilder::new(validator_dir.clone())
+        let builder = ValidatorDirBuilder::new(validator_dir.clone())
             .password_dir(secrets_dir.clone())
             .voting_keystore(keystores.voting, voting_password.as_bytes())
             .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
-            .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
+            .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG));
+
+        let builder = if deposit_data_json_path.is_some() {
+            builder.create_eth1_tx_data(deposit_gwei, &spec)
+        } else {
+            builder
+        };
+
+        let dir = builder
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
+        if deposit_data_json_path.is_some() {
+            let deposit = dir
+                .eth1_deposit_data()
+                .map_err(|e| format!("Unable to read deposit data: {:?}", e))?
+                .ok_or("Validator directory is missing deposit data")?;
+            deposits.push(StandardDepositDataJson::new(&deposit.deposit_data, &spec));
+        }
+
         println!(
             "{}/{}\tIndex: {}\t0x{}",
             index - first_index,
@@ -145,5 +203,9 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
         );
     }
 
+    if let Some(path) = deposit_data_json_path {
+        write_deposit_data_json(&path, &deposits)?;
+    }
+
     Ok(())
 }
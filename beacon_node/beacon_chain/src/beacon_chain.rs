@@ -30,6 +30,7 @@ use crate::light_client_finality_update_verification::{
 use crate::light_client_optimistic_update_verification::{
     Error as LightClientOptimisticUpdateError, VerifiedLightClientOptimisticUpdate,
 };
+use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::BackgroundMigrator;
 use crate::naive_aggregation_pool::{
     AggregatedAttestationMap, Error as NaiveAggregationError, NaiveAggregationPool,
@@ -96,6 +97,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::prelude::*;
 use std::marker::PhantomData;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use store::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
@@ -374,6 +376,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub latest_seen_finality_update: Mutex<Option<LightClientFinalityUpdate<T::EthSpec>>>,
     /// The most recently validated light client optimistic update received on gossip.
     pub latest_seen_optimistic_update: Mutex<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// Caches the light client data computed from imported blocks, for serving to light clients.
+    pub light_client_server_cache: LightClientServerCache<T>,
     /// Provides information from the Ethereum 1 (PoW) chain.
     pub eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec>>,
     /// Interfaces with the execution client.
@@ -427,6 +431,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub slasher: Option<Arc<Slasher<T::EthSpec>>>,
     /// Provides monitoring of a set of explicitly defined validators.
     pub validator_monitor: RwLock<ValidatorMonitor<T::EthSpec>>,
+    /// Set whilst era files are being imported in the background.
+    pub(crate) era_import_in_progress: AtomicBool,
 }
 
 type BeaconBlockAndState<T, Payload> = (BeaconBlock<T, Payload>, BeaconState<T>);
@@ -1063,6 +1069,42 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(self.store.get_state(state_root, slot)?)
     }
 
+    /// Returns the `LightClientBootstrap` for the block with the given root, if the block and its
+    /// state are known and the block is from Altair or later.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error.
+    pub fn get_light_client_bootstrap(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<LightClientBootstrap<T::EthSpec>>, Error> {
+        let block = match self.get_blinded_block(block_root)? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        // There are no sync committees prior to Altair.
+        if self.spec.fork_name_at_slot::<T::EthSpec>(block.slot()) == ForkName::Base {
+            return Ok(None);
+        }
+        let mut state = match self.get_state(&block.state_root(), Some(block.slot()))? {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+        Ok(Some(LightClientBootstrap::from_beacon_state(&mut state)?))
+    }
+
+    /// Returns the best `LightClientUpdate` for each of up to `count` consecutive sync committee
+    /// periods, starting at `start_period`.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, Error> {
+        self.light_client_server_cache
+            .get_light_client_updates(&self.store, start_period, count)
+    }
+
     /// Return the sync committee at `slot + 1` from the canonical chain.
     ///
     /// This is useful when dealing with sync committee messages, because messages are signed
@@ -2760,6 +2802,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             );
         }
 
+        // Retain the block needed to compute light client data once it is imported.
+        let light_client_block = self
+            .config
+            .enable_light_client_server
+            .then(|| block.clone());
+
         let chain = self.clone();
         let block_hash = self
             .spawn_blocking_handle(
@@ -2780,6 +2828,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             )
             .await??;
 
+        if let Some(block) = light_client_block {
+            let chain = self.clone();
+            self.task_executor.spawn_blocking(
+                move || {
+                    if let Err(e) = chain.light_client_server_cache.recompute_and_cache_updates(
+                        &chain.store,
+                        &block,
+                        &chain.spec,
+                    ) {
+                        debug!(
+                            chain.log,
+                            "Failed to compute light client updates";
+                            "block_root" => ?block_root,
+                            "error" => ?e,
+                        );
+                    }
+                },
+                "light_client_server_cache",
+            );
+        }
+
         Ok(block_hash)
     }
 
@@ -2947,6 +3016,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             parent_block.slot(),
         );
         self.import_block_update_slasher(block, &state, &mut consensus_context);
+        if self.config.enable_light_client_server {
+            if let Err(e) = self
+                .light_client_server_cache
+                .cache_state_data(block, block_root, &mut state, &self.spec)
+            {
+                debug!(
+                    self.log,
+                    "Failed to cache light client data";
+                    "block_root" => ?block_root,
+                    "error" => ?e,
+                );
+            }
+        }
 
         let db_write_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_DB_WRITE);
 
@@ -1,5 +1,6 @@
 pub use proto_array::ReOrgThreshold;
 use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 use types::{Checkpoint, Epoch};
 
@@ -68,6 +69,10 @@ pub struct ChainConfig {
     ///
     /// This is useful for block builders and testing.
     pub always_prepare_payload: bool,
+    /// Whether to compute and store the data served to light clients.
+    pub enable_light_client_server: bool,
+    /// Directory of era files from which to import historic blocks on startup.
+    pub era_import_dir: Option<PathBuf>,
 }
 
 impl Default for ChainConfig {
@@ -94,6 +99,8 @@ impl Default for ChainConfig {
             optimistic_finalized_sync: true,
             shuffling_cache_size: crate::shuffling_cache::DEFAULT_CACHE_SIZE,
             always_prepare_payload: false,
+            enable_light_client_server: false,
+            era_import_dir: None,
         }
     }
 }
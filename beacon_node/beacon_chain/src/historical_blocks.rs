@@ -1,22 +1,28 @@
 use crate::{errors::BeaconChainError as Error, metrics, BeaconChain, BeaconChainTypes};
 use itertools::Itertools;
-use slog::debug;
+use slog::{debug, error, info};
 use state_processing::{
     per_block_processing::ParallelSignatureSets,
     signature_sets::{block_proposal_signature_set_from_parts, Error as SignatureSetError},
 };
 use std::borrow::Cow;
 use std::iter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
+use store::era;
 use store::{chunked_vector::BlockRoots, AnchorInfo, ChunkWriter, KeyValueStore};
-use types::{Hash256, SignedBlindedBeaconBlock, Slot};
+use types::{EthSpec, Hash256, SignedBlindedBeaconBlock, Slot};
 
 /// Use a longer timeout on the pubkey cache.
 ///
 /// It's ok if historical sync is stalled due to writes from forwards block processing.
 const PUBKEY_CACHE_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Number of blocks from an era file to import in each batch.
+const ERA_IMPORT_BATCH_SIZE: usize = 1024;
+
 #[derive(Debug)]
 pub enum HistoricalBlockError {
     /// Block is not available (only returned when fetching historic blocks).
@@ -189,7 +195,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             oldest_block_parent: expected_block_root,
             ..anchor_info
         };
-        let backfill_complete = new_anchor.block_backfill_complete();
+        let backfill_complete =
+            new_anchor.block_backfill_complete(self.store.get_history_retention_slot());
         self.store
             .compare_and_set_anchor_info_with_write(Some(anchor_info), Some(new_anchor))?;
 
@@ -201,4 +208,106 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         Ok(blocks_to_import.len())
     }
+
+    /// Import the era files in `dir` with `import_era_files` in a background task.
+    ///
+    /// Backfill sync waits for the import to finish before downloading any blocks, see
+    /// `is_importing_era_files`.
+    pub fn spawn_era_import(self: &Arc<Self>, dir: PathBuf) {
+        self.era_import_in_progress.store(true, Ordering::SeqCst);
+
+        let chain = self.clone();
+        self.task_executor.spawn_blocking(
+            move || {
+                info!(chain.log, "Importing era files"; "dir" => ?dir);
+                match chain.import_era_files(&dir) {
+                    Ok(num_imported) => info!(
+                        chain.log,
+                        "Finished importing era files";
+                        "blocks_imported" => num_imported,
+                        "oldest_block_slot" => chain.store.get_oldest_block_slot(),
+                    ),
+                    Err(e) => error!(
+                        chain.log,
+                        "Failed to import era files";
+                        "msg" => "the remaining blocks will be backfilled from the network",
+                        "error" => ?e,
+                    ),
+                }
+                chain.era_import_in_progress.store(false, Ordering::SeqCst);
+            },
+            "era_import",
+        );
+    }
+
+    /// Returns `true` whilst an import started by `spawn_era_import` is running.
+    pub fn is_importing_era_files(&self) -> bool {
+        self.era_import_in_progress.load(Ordering::SeqCst)
+    }
+
+    /// Import the historical blocks held in the era files in `dir`, as an alternative to
+    /// backfilling them from the network.
+    ///
+    /// Era files are imported from the newest to the oldest, skipping those whose blocks are
+    /// already known. Each era must connect to the oldest block imported so far, so the eras
+    /// imported must be contiguous. The states of the era files are not used.
+    ///
+    /// Returns the number of blocks imported.
+    pub fn import_era_files(&self, dir: &Path) -> Result<usize, Error> {
+        let era_files = era::list_era_files(dir).map_err(store::Error::from)?;
+        let slots_per_era = T::EthSpec::slots_per_historical_root() as u64;
+        let mut num_imported = 0;
+
+        for (era, path) in era_files.into_iter().rev() {
+            let oldest_block_slot = match self.store.get_anchor_info() {
+                Some(anchor_info)
+                    if !anchor_info
+                        .block_backfill_complete(self.store.get_history_retention_slot()) =>
+                {
+                    anchor_info.oldest_block_slot
+                }
+                _ => break,
+            };
+
+            // The genesis era holds no blocks.
+            let start_slot = Slot::new(era.saturating_sub(1) * slots_per_era);
+            if era == 0 || start_slot >= oldest_block_slot {
+                continue;
+            }
+
+            let mut reader = era::open_era_file::<T::EthSpec>(&path).map_err(store::Error::from)?;
+            // The genesis block is always known, so it is never imported.
+            let positions = reader
+                .block_positions()
+                .map_err(store::Error::from)?
+                .into_iter()
+                .filter(|(slot, _)| *slot > self.spec.genesis_slot)
+                .map(|(_, position)| position)
+                .collect::<Vec<_>>();
+
+            // Import the newest blocks first, as every batch must connect to the oldest block.
+            // Only one batch of blocks is held in memory at a time.
+            for batch in positions.rchunks(ERA_IMPORT_BATCH_SIZE) {
+                let blocks = batch
+                    .iter()
+                    .map(|position| {
+                        reader
+                            .read_block_at(*position, &self.spec)
+                            .map(|block| Arc::new(block.clone_as_blinded()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(store::Error::from)?;
+                num_imported += self.import_historical_block_batch(blocks)?;
+            }
+
+            info!(
+                self.log,
+                "Imported era file";
+                "era" => era,
+                "oldest_block_slot" => self.store.get_oldest_block_slot(),
+            );
+        }
+
+        Ok(num_imported)
+    }
 }
@@ -0,0 +1,222 @@
+//! Computes the data served to light clients as blocks are imported.
+//!
+//! Every imported block carries a sync aggregate which attests to its parent. The parts of the
+//! parent's post-state that light clients need are taken whilst the parent is imported, and from
+//! them we derive the light client updates for that aggregate. The latest finality and optimistic
+//! updates are held in memory, whilst the best `LightClientUpdate` of each sync committee period is
+//! persisted to the database so that light clients can sync through past periods.
+use crate::{BeaconChainError, BeaconChainTypes, BeaconStore};
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::light_client_update::{
+    Error as LightClientUpdateError, FinalizedRootProofLen, NextSyncCommitteeProofLen,
+    FINALIZED_ROOT_INDEX, NEXT_SYNC_COMMITTEE_INDEX,
+};
+use types::{
+    BeaconBlockHeader, BeaconBlockRef, BeaconState, ChainSpec, Checkpoint, EthSpec, FixedVector,
+    ForkName, Hash256, LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate,
+    SignedBeaconBlock, SyncCommittee,
+};
+
+/// The number of recently imported blocks for which the data needed to build light client updates
+/// is kept. Only the parent of the next block is normally needed, the rest allow for forks.
+const PREV_BLOCK_CACHE_SIZE: usize = 32;
+
+/// The parts of the post-state of a block which are needed to build the light client updates
+/// attested to by the sync aggregate of a child block.
+#[derive(Clone)]
+struct LightClientCachedData<E: EthSpec> {
+    header: BeaconBlockHeader,
+    finalized_checkpoint: Checkpoint,
+    finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
+    next_sync_committee: Arc<SyncCommittee<E>>,
+    next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+}
+
+pub struct LightClientServerCache<T: BeaconChainTypes> {
+    /// The most recent finality update computed from an imported block.
+    latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
+    /// The most recent optimistic update computed from an imported block.
+    latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// Serializes the read-compare-write of the best update of a period on disk.
+    best_update_lock: Mutex<()>,
+    /// Data taken from the post-states of recently imported blocks, keyed by block root.
+    prev_block_cache: Mutex<LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
+    fn default() -> Self {
+        Self {
+            latest_finality_update: RwLock::new(None),
+            latest_optimistic_update: RwLock::new(None),
+            best_update_lock: Mutex::new(()),
+            prev_block_cache: Mutex::new(LruCache::new(PREV_BLOCK_CACHE_SIZE)),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> LightClientServerCache<T> {
+    /// Take the data needed to build light client updates from `post_state`, the state of the
+    /// block being imported, so that it need not be loaded again once a child block arrives.
+    pub fn cache_state_data(
+        &self,
+        block: BeaconBlockRef<T::EthSpec>,
+        block_root: Hash256,
+        post_state: &mut BeaconState<T::EthSpec>,
+        spec: &ChainSpec,
+    ) -> Result<(), BeaconChainError> {
+        // There are no sync committees prior to Altair.
+        if spec.fork_name_at_slot::<T::EthSpec>(block.slot()) == ForkName::Base {
+            return Ok(());
+        }
+
+        let finality_branch = post_state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?;
+        let next_sync_committee_branch =
+            post_state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?;
+        let data = LightClientCachedData {
+            header: block.block_header(),
+            finalized_checkpoint: post_state.finalized_checkpoint(),
+            finality_branch: FixedVector::new(finality_branch)
+                .map_err(LightClientUpdateError::from)?,
+            next_sync_committee: post_state.next_sync_committee()?.clone(),
+            next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)
+                .map_err(LightClientUpdateError::from)?,
+        };
+        self.prev_block_cache.lock().put(block_root, data);
+        Ok(())
+    }
+
+    /// Compute the light client updates attested to by the sync aggregate of `block`, and cache
+    /// or persist them if they improve on what we have.
+    ///
+    /// Nothing is computed if the data of the parent of `block` was not cached when the parent
+    /// was imported, e.g. because the parent precedes Altair or was imported before a restart.
+    pub fn recompute_and_cache_updates(
+        &self,
+        store: &BeaconStore<T>,
+        block: &SignedBeaconBlock<T::EthSpec>,
+        spec: &ChainSpec,
+    ) -> Result<(), BeaconChainError> {
+        let attested = match self.prev_block_cache.lock().get(&block.parent_root()) {
+            Some(attested) => attested.clone(),
+            None => return Ok(()),
+        };
+        let sync_aggregate = block.message().body().sync_aggregate()?;
+        if sync_aggregate.num_set_bits() < spec.min_sync_committee_participants as usize {
+            return Ok(());
+        }
+
+        let optimistic_update = LightClientOptimisticUpdate {
+            attested_header: attested.header.clone(),
+            sync_aggregate: sync_aggregate.clone(),
+            signature_slot: block.slot(),
+        };
+        {
+            let mut latest_optimistic_update = self.latest_optimistic_update.write();
+            let is_newer = latest_optimistic_update.as_ref().map_or(true, |latest| {
+                optimistic_update.attested_header.slot > latest.attested_header.slot
+            });
+            if is_newer {
+                *latest_optimistic_update = Some(optimistic_update);
+            }
+        }
+
+        // Nothing has been finalized yet, so there is no finality to prove.
+        let finalized_root = attested.finalized_checkpoint.root;
+        if finalized_root == Hash256::zero() {
+            return Ok(());
+        }
+        let finalized_header = match store.get_blinded_block(&finalized_root)? {
+            Some(block) => block.message().block_header(),
+            None => return Ok(()),
+        };
+        if finalized_header.tree_hash_root() != finalized_root {
+            return Err(LightClientUpdateError::InvalidFinalizedBlock.into());
+        }
+
+        let finality_update = LightClientFinalityUpdate {
+            attested_header: attested.header.clone(),
+            finalized_header: finalized_header.clone(),
+            finality_branch: attested.finality_branch.clone(),
+            sync_aggregate: sync_aggregate.clone(),
+            signature_slot: block.slot(),
+        };
+        {
+            let mut latest_finality_update = self.latest_finality_update.write();
+            let is_newer = latest_finality_update.as_ref().map_or(true, |latest| {
+                finality_update.attested_header.slot > latest.attested_header.slot
+            });
+            if is_newer {
+                *latest_finality_update = Some(finality_update);
+            }
+        }
+
+        let update = LightClientUpdate {
+            attested_header: attested.header,
+            next_sync_committee: attested.next_sync_committee,
+            next_sync_committee_branch: attested.next_sync_committee_branch,
+            finalized_header,
+            finality_branch: attested.finality_branch,
+            sync_aggregate: sync_aggregate.clone(),
+            signature_slot: block.slot(),
+        };
+
+        // The first block of a sync committee period is signed by a different committee to the
+        // one which its parent attests to, so it cannot be the best update of either period. The
+        // finality and optimistic updates above remain valid.
+        let period = update.attested_period(spec)?;
+        let signature_period = block
+            .slot()
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(spec)?;
+        if period != signature_period {
+            return Ok(());
+        }
+
+        let _best_update_lock = self.best_update_lock.lock();
+        let is_better = match store.get_light_client_update(period)? {
+            Some(best_update) => update.is_better_than(&best_update, spec)?,
+            None => true,
+        };
+        if is_better {
+            store.put_light_client_update(period, &update)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the most recent finality update, if any.
+    pub fn get_latest_finality_update(&self) -> Option<LightClientFinalityUpdate<T::EthSpec>> {
+        self.latest_finality_update.read().clone()
+    }
+
+    /// Returns the most recent optimistic update, if any.
+    pub fn get_latest_optimistic_update(&self) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
+        self.latest_optimistic_update.read().clone()
+    }
+
+    /// Returns the best updates for up to `count` consecutive periods from `start_period`.
+    ///
+    /// The result stops at the first period for which we have no update, so that light clients
+    /// never receive a response with gaps.
+    pub fn get_light_client_updates(
+        &self,
+        store: &BeaconStore<T>,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            match store.get_light_client_update(period)? {
+                Some(update) => updates.push(update),
+                None => break,
+            }
+        }
+        Ok(updates)
+    }
+}
@@ -4,6 +4,8 @@ use crate::head_tracker::{HeadTracker, SszHeadTracker};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use parking_lot::Mutex;
 use slog::{debug, error, info, warn, Logger};
+use state_processing::common::compute_weak_subjectivity_period;
+use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::mem;
 use std::sync::{mpsc, Arc};
@@ -279,6 +281,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             }
         };
 
+        // Prune history older than the retention period, if configured.
+        if let Some(retention_epochs) = db.get_config().history_retention_epochs {
+            if let Err(e) = Self::prune_history(&db, &finalized_state, retention_epochs, log) {
+                warn!(log, "History pruning failed"; "error" => ?e);
+            }
+        }
+
         // Finally, compact the database so that new free space is properly reclaimed.
         if let Err(e) = Self::run_compaction(
             db,
@@ -604,6 +613,32 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         })
     }
 
+    /// Prune blocks and states from more than `retention_epochs` prior to the finalized
+    /// checkpoint, whilst always retaining the weak subjectivity period of `finalized_state`.
+    fn prune_history(
+        db: &HotColdDB<E, Hot, Cold>,
+        finalized_state: &BeaconState<E>,
+        retention_epochs: u64,
+        log: &Logger,
+    ) -> Result<(), BeaconChainError> {
+        let ws_period = compute_weak_subjectivity_period(finalized_state, db.get_chain_spec())?;
+        let retained_epochs = max(Epoch::new(retention_epochs), ws_period);
+        let prune_epoch = finalized_state
+            .current_epoch()
+            .saturating_sub(retained_epochs);
+
+        debug!(
+            log,
+            "Checking history retention";
+            "retained_epochs" => retained_epochs,
+            "weak_subjectivity_period" => ws_period,
+            "prune_epoch" => prune_epoch,
+        );
+
+        db.try_prune_history(prune_epoch.start_slot(E::slots_per_epoch()))?;
+        Ok(())
+    }
+
     /// Compact the database if it has been more than `COMPACTION_PERIOD_SECONDS` since it
     /// was last compacted.
     pub fn run_compaction(
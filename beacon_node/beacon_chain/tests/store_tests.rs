@@ -23,10 +23,14 @@ use std::collections::HashSet;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION};
+use store::metadata::{
+    HistoryRetention, RestorePointMigration, SchemaVersion, CURRENT_SCHEMA_VERSION,
+    RESTORE_POINT_MIGRATION_KEY,
+};
 use store::{
+    era::{self, EraEntry},
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, LevelDB, StoreConfig,
+    BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -46,17 +50,24 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_with_spec(db_path, test_spec::<E>())
 }
 
 fn get_store_with_spec(
     db_path: &TempDir,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
+    get_store_with_config(db_path, spec, StoreConfig::default())
+}
+
+fn get_store_with_config(
+    db_path: &TempDir,
+    spec: ChainSpec,
+    config: StoreConfig,
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
     let log = test_logger();
 
     HotColdDB::open(&hot_path, &cold_path, |_, _, _| Ok(()), config, spec, log)
@@ -64,7 +75,7 @@ fn get_store_with_spec(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     let harness = BeaconChainHarness::builder(MinimalEthSpec)
@@ -2053,6 +2064,314 @@ async fn garbage_collect_temp_states_from_failed_block() {
     assert_eq!(store.iter_temporary_state_roots().count(), 0);
 }
 
+#[tokio::test]
+async fn change_slots_per_restore_point() {
+    let num_blocks_produced = E::slots_per_epoch() * 12;
+    let db_path = tempdir().unwrap();
+    let mut store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > E::slots_per_historical_root() as u64);
+    let state_roots = harness
+        .chain
+        .forwards_iter_state_roots(Slot::new(0))
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .take_while(|(_, slot)| *slot < split_slot)
+        .collect::<Vec<_>>();
+    drop(harness);
+
+    // Make the restore points denser, then sparser again.
+    for slots_per_restore_point in [
+        E::slots_per_epoch(),
+        E::slots_per_historical_root() as u64 / 2,
+    ] {
+        store
+            .migrate_slots_per_restore_point(slots_per_restore_point, 2)
+            .expect("should change slots-per-restore-point");
+        drop(store);
+
+        let config = StoreConfig {
+            slots_per_restore_point,
+            ..StoreConfig::default()
+        };
+        store = get_store_with_config(&db_path, test_spec::<E>(), config);
+        assert_eq!(store.load_restore_point_migration().unwrap(), None);
+
+        for (state_root, slot) in &state_roots {
+            let state = store
+                .load_cold_state_by_slot(*slot)
+                .unwrap()
+                .expect("frozen state should exist");
+            assert_eq!(state.tree_hash_root(), *state_root, "slot {}", slot);
+        }
+    }
+}
+
+#[tokio::test]
+async fn interrupted_change_of_slots_per_restore_point() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let old_sprp = store.get_config().slots_per_restore_point;
+    let new_sprp = E::slots_per_epoch();
+
+    // Simulate a change of slots-per-restore-point which was interrupted before any progress.
+    store
+        .hot_db
+        .put(
+            &RESTORE_POINT_MIGRATION_KEY,
+            &RestorePointMigration {
+                from: old_sprp,
+                to: new_sprp,
+                next_slot: Slot::new(0),
+                finalizing: false,
+            },
+        )
+        .unwrap();
+    drop(store);
+
+    let hot_path = db_path.path().join("hot_db");
+    let cold_path = db_path.path().join("cold_db");
+    let open = || {
+        HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+            &hot_path,
+            &cold_path,
+            |_, _, _| Ok(()),
+            StoreConfig::default(),
+            test_spec::<E>(),
+            test_logger(),
+        )
+    };
+
+    match open() {
+        Err(store::Error::RestorePointMigrationInProgress { from, to }) => {
+            assert_eq!(from, old_sprp);
+            assert_eq!(to, new_sprp);
+        }
+        Err(e) => panic!("unexpected error: {:?}", e),
+        Ok(_) => panic!("store should not open while a migration is in progress"),
+    }
+
+    // The change can be resumed and completed.
+    let store = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open_for_restore_point_migration(
+        &hot_path,
+        &cold_path,
+        |_, _, _| Ok(()),
+        StoreConfig::default(),
+        test_spec::<E>(),
+        test_logger(),
+    )
+    .expect("store should open for migration");
+    store
+        .migrate_slots_per_restore_point(new_sprp, 2)
+        .expect("should resume change of slots-per-restore-point");
+    drop(store);
+
+    let config = StoreConfig {
+        slots_per_restore_point: new_sprp,
+        ..StoreConfig::default()
+    };
+    let store = get_store_with_config(&db_path, test_spec::<E>(), config);
+    assert_eq!(store.load_restore_point_migration().unwrap(), None);
+}
+
+#[tokio::test]
+async fn prune_history() {
+    let num_blocks_produced = E::slots_per_epoch() * 12;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    let prune_slot = Slot::new(E::slots_per_historical_root() as u64);
+    assert!(split_slot > prune_slot);
+    let block_roots = harness
+        .chain
+        .forwards_iter_block_roots(Slot::new(0))
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .take_while(|(_, slot)| *slot < split_slot)
+        .collect::<Vec<_>>();
+
+    // Pruning is rounded down to a restore point.
+    store
+        .try_prune_history(prune_slot + 1)
+        .expect("should prune history");
+
+    // Pruning is recorded separately from the anchor, which is only used by checkpoint sync.
+    assert_eq!(store.get_anchor_info(), None);
+    let retention = store.get_history_retention();
+    assert_eq!(
+        retention,
+        Some(HistoryRetention {
+            oldest_retained_slot: prune_slot,
+            pruned_slot: prune_slot,
+        })
+    );
+    assert_eq!(store.get_history_retention_slot(), prune_slot);
+    assert_eq!(store.get_oldest_block_slot(), prune_slot);
+    assert_eq!(
+        store.get_historic_state_limits(),
+        (Slot::new(0), prune_slot)
+    );
+
+    for (block_root, slot) in &block_roots {
+        let block = store.get_blinded_block(block_root).unwrap();
+        assert_eq!(
+            block.is_some(),
+            *slot == 0 || *slot >= prune_slot,
+            "slot {}",
+            slot
+        );
+    }
+    assert!(harness
+        .chain
+        .forwards_iter_block_roots(prune_slot - 1)
+        .is_err());
+
+    // The genesis state and states from the prune slot onwards remain available.
+    assert!(store
+        .load_cold_state_by_slot(Slot::new(0))
+        .unwrap()
+        .is_some());
+    assert!(store
+        .load_cold_state_by_slot(prune_slot - 1)
+        .unwrap()
+        .is_none());
+    assert!(store
+        .load_cold_state_by_slot(prune_slot + 1)
+        .unwrap()
+        .is_some());
+
+    // Pruning the same history again has no effect.
+    store
+        .try_prune_history(prune_slot)
+        .expect("should prune history");
+    assert_eq!(store.get_history_retention(), retention);
+    assert_eq!(store.get_anchor_info(), None);
+}
+
+#[tokio::test]
+async fn weak_subjectivity_sync_with_pruned_history() {
+    // Build an initial chain on one harness, representing a synced node with full history.
+    let num_initial_blocks = E::slots_per_epoch() * 11;
+
+    let temp1 = tempdir().unwrap();
+    let full_store = get_store(&temp1);
+    let harness = get_harness(full_store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_initial_blocks as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let genesis_state = full_store
+        .get_state(&harness.chain.genesis_state_root, Some(Slot::new(0)))
+        .unwrap()
+        .unwrap();
+    let wss_checkpoint = harness.finalized_checkpoint();
+    let wss_block = harness
+        .chain
+        .store
+        .get_full_block(&wss_checkpoint.root)
+        .unwrap()
+        .unwrap();
+    let wss_state = full_store
+        .get_state(&wss_block.state_root(), None)
+        .unwrap()
+        .unwrap();
+    let wss_slot = wss_block.slot();
+
+    let (shutdown_tx, _shutdown_rx) = futures::channel::mpsc::channel(1);
+    let log = test_logger();
+    let temp2 = tempdir().unwrap();
+    let store = get_store(&temp2);
+    let seconds_per_slot = test_spec::<E>().seconds_per_slot;
+
+    // Initialise a new beacon chain from the finalized checkpoint
+    let beacon_chain = Arc::new(
+        BeaconChainBuilder::new(MinimalEthSpec)
+            .store(store.clone())
+            .custom_spec(test_spec::<E>())
+            .task_executor(harness.chain.task_executor.clone())
+            .weak_subjectivity_state(wss_state, wss_block.clone(), genesis_state)
+            .unwrap()
+            .logger(log.clone())
+            .store_migrator_config(MigratorConfig::default().blocking())
+            .dummy_eth1_backend()
+            .expect("should build dummy backend")
+            .testing_slot_clock(Duration::from_secs(seconds_per_slot))
+            .expect("should configure testing slot clock")
+            .shutdown_sender(shutdown_tx)
+            .chain_config(ChainConfig::default())
+            .event_handler(Some(ServerSentEventHandler::new_with_capacity(
+                log.clone(),
+                1,
+            )))
+            .monitor_validators(true, vec![], DEFAULT_INDIVIDUAL_TRACKING_THRESHOLD, log)
+            .build()
+            .expect("should build"),
+    );
+
+    // Prune before backfill has started. Only the retention boundary is recorded, the anchor is
+    // untouched.
+    let prune_slot = Slot::new(E::slots_per_historical_root() as u64);
+    assert!(wss_slot > prune_slot);
+    let anchor = store.get_anchor_info().unwrap();
+    store
+        .try_prune_history(prune_slot)
+        .expect("should prune history");
+    assert_eq!(store.get_anchor_info(), Some(anchor.clone()));
+    assert_eq!(store.get_history_retention_slot(), prune_slot);
+    assert!(!anchor.block_backfill_complete(prune_slot));
+
+    // Backfill completes once it reaches the retention boundary, without any older blocks.
+    let chain_dump = harness.chain.chain_dump().unwrap();
+    let historical_blocks = chain_dump[prune_slot.as_usize()..wss_slot.as_usize()]
+        .iter()
+        .map(|s| s.beacon_block.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(historical_blocks[0].slot(), prune_slot);
+    beacon_chain
+        .import_historical_block_batch(historical_blocks)
+        .unwrap();
+
+    let anchor = store.get_anchor_info().unwrap();
+    assert_eq!(anchor.oldest_block_slot, prune_slot);
+    assert!(anchor.block_backfill_complete(store.get_history_retention_slot()));
+    assert_eq!(store.get_oldest_block_slot(), prune_slot);
+    assert_eq!(store.get_anchor_slot(), Some(wss_slot));
+
+    for (block_root, slot) in beacon_chain
+        .forwards_iter_block_roots(prune_slot)
+        .unwrap()
+        .map(Result::unwrap)
+    {
+        let block = store.get_blinded_block(&block_root).unwrap().unwrap();
+        assert_eq!(block.slot(), slot);
+    }
+}
+
 #[tokio::test]
 async fn weak_subjectivity_sync() {
     // Build an initial chain on one harness, representing a synced node with full history.
@@ -2249,6 +2568,188 @@ async fn weak_subjectivity_sync() {
     assert_eq!(store.get_anchor_slot(), None);
 }
 
+#[tokio::test]
+async fn era_export_and_import() {
+    // Build a chain on one harness, long enough to finalize several eras.
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let num_initial_blocks = E::slots_per_epoch() * 21;
+
+    let temp1 = tempdir().unwrap();
+    let mut spec = test_spec::<E>();
+    spec.config_name = Some("minimal".to_string());
+    let full_store = get_store_with_spec(&temp1, spec);
+    let harness = get_harness(full_store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_initial_blocks as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Export every era whose state has been frozen.
+    let era_dir = tempdir().unwrap();
+    let exportable_eras = full_store.exportable_eras();
+    assert_eq!(exportable_eras, vec![0, 1, 2]);
+    for era in &exportable_eras {
+        full_store.export_era(*era, era_dir.path()).unwrap();
+    }
+    assert!(matches!(
+        full_store.export_era(3, era_dir.path()),
+        Err(store::Error::HistoryUnavailable)
+    ));
+
+    let era_files = era::list_era_files(era_dir.path()).unwrap();
+    assert_eq!(
+        era_files.iter().map(|(era, _)| *era).collect::<Vec<_>>(),
+        exportable_eras
+    );
+    for (era, path) in &era_files {
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        assert!(file_name.starts_with(&format!("minimal-{:05}-", era)));
+    }
+
+    // Each file holds the blocks of its era, followed by the state at its end.
+    let chain_dump = harness.chain.chain_dump().unwrap();
+    let spec = &harness.chain.spec;
+    for (era, path) in &era_files {
+        let mut reader = era::open_era_file::<E>(path).unwrap();
+        let mut blocks = vec![];
+        let mut states = vec![];
+        while let Some(entry) = reader.next_entry(spec).unwrap() {
+            match entry {
+                EraEntry::Block(block) => blocks.push(block),
+                EraEntry::State(state) => states.push(state),
+            }
+        }
+
+        let start_slot = era.saturating_sub(1) * slots_per_era;
+        let state_slot = era * slots_per_era;
+        let expected_roots = if *era == 0 {
+            vec![]
+        } else {
+            chain_dump[start_slot as usize..state_slot as usize]
+                .iter()
+                .map(|snapshot| snapshot.beacon_block_root)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| block.canonical_root())
+                .collect::<Vec<_>>(),
+            expected_roots
+        );
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].slot(), state_slot);
+        assert_eq!(
+            states[0].canonical_root(),
+            chain_dump[state_slot as usize].beacon_state_root()
+        );
+    }
+
+    // Checkpoint sync a second chain from the finalized checkpoint.
+    let genesis_state = full_store
+        .get_state(&harness.chain.genesis_state_root, Some(Slot::new(0)))
+        .unwrap()
+        .unwrap();
+    let wss_checkpoint = harness.finalized_checkpoint();
+    let wss_block = harness
+        .chain
+        .store
+        .get_full_block(&wss_checkpoint.root)
+        .unwrap()
+        .unwrap();
+    let wss_state = full_store
+        .get_state(&wss_block.state_root(), None)
+        .unwrap()
+        .unwrap();
+    let wss_slot = wss_block.slot();
+    let last_era_slot = Slot::new(*exportable_eras.last().unwrap() * slots_per_era);
+    assert!(wss_slot > last_era_slot);
+
+    let (shutdown_tx, _shutdown_rx) = futures::channel::mpsc::channel(1);
+    let log = test_logger();
+    let temp2 = tempdir().unwrap();
+    let store = get_store(&temp2);
+    let seconds_per_slot = test_spec::<E>().seconds_per_slot;
+
+    let beacon_chain = Arc::new(
+        BeaconChainBuilder::new(MinimalEthSpec)
+            .store(store.clone())
+            .custom_spec(test_spec::<E>())
+            .task_executor(harness.chain.task_executor.clone())
+            .weak_subjectivity_state(wss_state, wss_block.clone(), genesis_state)
+            .unwrap()
+            .logger(log.clone())
+            .store_migrator_config(MigratorConfig::default().blocking())
+            .dummy_eth1_backend()
+            .expect("should build dummy backend")
+            .testing_slot_clock(Duration::from_secs(seconds_per_slot))
+            .expect("should configure testing slot clock")
+            .shutdown_sender(shutdown_tx)
+            .chain_config(ChainConfig::default())
+            .event_handler(Some(ServerSentEventHandler::new_with_capacity(
+                log.clone(),
+                1,
+            )))
+            .monitor_validators(true, vec![], DEFAULT_INDIVIDUAL_TRACKING_THRESHOLD, log)
+            .build()
+            .expect("should build"),
+    );
+
+    // The era files must connect to the oldest known block.
+    assert!(matches!(
+        beacon_chain.import_era_files(era_dir.path()),
+        Err(BeaconChainError::HistoricalBlockError(
+            HistoricalBlockError::MismatchedBlockRoot { .. }
+        ))
+    ));
+    assert_eq!(store.get_oldest_block_slot(), wss_slot);
+
+    // Backfill the blocks after the last era, then import the eras in the background.
+    let historical_blocks = chain_dump[last_era_slot.as_usize()..wss_slot.as_usize()]
+        .iter()
+        .map(|s| s.beacon_block.clone())
+        .collect::<Vec<_>>();
+    beacon_chain
+        .import_historical_block_batch(historical_blocks)
+        .unwrap();
+
+    beacon_chain.spawn_era_import(era_dir.path().to_path_buf());
+    assert!(beacon_chain.is_importing_era_files());
+    while beacon_chain.is_importing_era_files() {
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    let anchor = store.get_anchor_info().unwrap();
+    assert_eq!(anchor.oldest_block_slot, 0);
+    assert!(anchor.block_backfill_complete(store.get_history_retention_slot()));
+
+    // Importing the files again is a no-op.
+    assert_eq!(beacon_chain.import_era_files(era_dir.path()).unwrap(), 0);
+
+    // The forwards iterator should now match the original chain, and all blocks can be loaded.
+    let forwards = beacon_chain
+        .forwards_iter_block_roots(Slot::new(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    let expected = harness
+        .chain
+        .forwards_iter_block_roots(Slot::new(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(forwards, expected);
+
+    for (block_root, slot) in forwards {
+        let block = store.get_blinded_block(&block_root).unwrap().unwrap();
+        assert_eq!(block.slot(), slot);
+    }
+}
+
 #[tokio::test]
 async fn finalizes_after_resuming_from_db() {
     let validator_count = 16;
@@ -2691,7 +3192,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
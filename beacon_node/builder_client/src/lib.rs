@@ -60,6 +60,11 @@ impl BuilderHttpClient {
         })
     }
 
+    /// Returns the URL of the builder this client talks to.
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
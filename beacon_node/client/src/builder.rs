@@ -10,7 +10,7 @@ use beacon_chain::{
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     slot_clock::{SlotClock, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    store::{BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, ServerSentEventHandler,
 };
 use environment::RuntimeContext;
@@ -41,6 +41,10 @@ use types::{
 /// Interval between polling the eth1 node for genesis information.
 pub const ETH1_GENESIS_UPDATE_INTERVAL_MILLIS: u64 = 7_000;
 
+/// Maximum time to wait for peers to serve the checkpoint block when checkpoint syncing from a
+/// state alone.
+const CHECKPOINT_BLOCK_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+
 /// Builds a `Client` instance.
 ///
 /// ## Notes
@@ -202,6 +206,7 @@ where
             ClientGenesis::DepositContract
         } else if chain_exists {
             if matches!(client_genesis, ClientGenesis::WeakSubjSszBytes { .. })
+                || matches!(client_genesis, ClientGenesis::WeakSubjSszState { .. })
                 || matches!(client_genesis, ClientGenesis::CheckpointSyncUrl { .. })
             {
                 info!(
@@ -262,6 +267,87 @@ where
                     .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
                     .map(|v| (v, None))?
             }
+            ClientGenesis::WeakSubjSszState {
+                anchor_state_bytes,
+                genesis_state_bytes,
+            } => {
+                info!(context.log(), "Starting checkpoint sync");
+
+                let mut anchor_state = BeaconState::from_ssz_bytes(&anchor_state_bytes, &spec)
+                    .map_err(|e| format!("Unable to parse weak subj state SSZ: {:?}", e))?;
+                let genesis_state = BeaconState::from_ssz_bytes(&genesis_state_bytes, &spec)
+                    .map_err(|e| format!("Unable to parse genesis state SSZ: {:?}", e))?;
+
+                // Check the state before waiting on the network for its block, since these checks
+                // would otherwise only fail once the block has been downloaded.
+                if anchor_state.slot() % TEthSpec::slots_per_epoch() != 0 {
+                    return Err(format!(
+                        "Checkpoint state at slot {} is not aligned to epoch start. \
+                         Please supply an aligned checkpoint with state.slot % 32 == 0",
+                        anchor_state.slot(),
+                    ));
+                }
+                if anchor_state.latest_block_header().slot != anchor_state.slot() {
+                    return Err(format!(
+                        "Checkpoint state at slot {} has its latest block at slot {}. \
+                         Please supply a checkpoint state whose block is at the same slot",
+                        anchor_state.slot(),
+                        anchor_state.latest_block_header().slot,
+                    ));
+                }
+
+                // The anchor block is the `latest_block_header` of the anchor state, with its
+                // state root filled in as it would be by the next slot's processing.
+                let anchor_state_root = anchor_state
+                    .update_tree_hash_cache()
+                    .map_err(|e| format!("Error computing checkpoint state root: {:?}", e))?;
+                let mut anchor_header = anchor_state.latest_block_header().clone();
+                if anchor_header.state_root.is_zero() {
+                    anchor_header.state_root = anchor_state_root;
+                }
+                let anchor_block_root = anchor_header.canonical_root();
+
+                let current_slot = SystemTimeSlotClock::new(
+                    spec.genesis_slot,
+                    Duration::from_secs(anchor_state.genesis_time()),
+                    Duration::from_secs(spec.seconds_per_slot),
+                )
+                .now()
+                .unwrap_or(spec.genesis_slot);
+
+                let anchor_block = network::fetch_checkpoint_block(
+                    anchor_block_root,
+                    anchor_header.slot,
+                    current_slot,
+                    anchor_state.genesis_validators_root(),
+                    &config.network,
+                    context.executor.clone(),
+                    &spec,
+                    CHECKPOINT_BLOCK_DOWNLOAD_TIMEOUT,
+                )
+                .await?;
+
+                if anchor_block.canonical_root() != anchor_block_root {
+                    return Err(format!(
+                        "Checkpoint block root {:?} does not match the latest block header of the \
+                         checkpoint state {:?}",
+                        anchor_block.canonical_root(),
+                        anchor_block_root
+                    ));
+                }
+
+                info!(
+                    context.log(),
+                    "Loaded checkpoint block and state";
+                    "slot" => anchor_block.slot(),
+                    "block_root" => ?anchor_block_root,
+                    "state_root" => ?anchor_state_root,
+                );
+
+                builder
+                    .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
+                    .map(|v| (v, None))?
+            }
             ClientGenesis::CheckpointSyncUrl {
                 genesis_state_bytes,
                 url,
@@ -866,7 +952,15 @@ where
             .build()
             .map_err(|e| format!("Failed to build beacon chain: {}", e))?;
 
-        self.beacon_chain = Some(Arc::new(chain));
+        let chain = Arc::new(chain);
+
+        // Start importing historic blocks from era files before the network starts, so that
+        // backfill sync knows to wait for the import.
+        if let Some(era_dir) = chain.config.era_import_dir.clone() {
+            chain.spawn_era_import(era_dir);
+        }
+
+        self.beacon_chain = Some(chain);
         self.beacon_chain_builder = None;
 
         // a beacon chain requires a timer
@@ -875,7 +969,15 @@ where
 }
 
 impl<TSlotClock, TEth1Backend, TEthSpec>
-    ClientBuilder<Witness<TSlotClock, TEth1Backend, TEthSpec, LevelDB<TEthSpec>, LevelDB<TEthSpec>>>
+    ClientBuilder<
+        Witness<
+            TSlotClock,
+            TEth1Backend,
+            TEthSpec,
+            BeaconNodeBackend<TEthSpec>,
+            BeaconNodeBackend<TEthSpec>,
+        >,
+    >
 where
     TSlotClock: SlotClock + 'static,
     TEth1Backend: Eth1ChainBackend<TEthSpec> + 'static,
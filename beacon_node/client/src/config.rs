@@ -34,6 +34,12 @@ pub enum ClientGenesis {
         anchor_state_bytes: Vec<u8>,
         anchor_block_bytes: Vec<u8>,
     },
+    /// Loads the anchor state from SSZ-encoded `BeaconState` bytes, and downloads the anchor
+    /// block committed to by its `latest_block_header` from peers.
+    WeakSubjSszState {
+        genesis_state_bytes: Vec<u8>,
+        anchor_state_bytes: Vec<u8>,
+    },
     CheckpointSyncUrl {
         genesis_state_bytes: Vec<u8>,
         url: SensitiveUrl,
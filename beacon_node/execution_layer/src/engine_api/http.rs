@@ -3,6 +3,7 @@
 use super::*;
 use crate::auth::Auth;
 use crate::json_structures::*;
+use eth2::lighthouse::ExecutionClientVersion;
 use reqwest::header::CONTENT_TYPE;
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
@@ -53,6 +54,9 @@ pub const ENGINE_EXCHANGE_TRANSITION_CONFIGURATION_V1_TIMEOUT: Duration = Durati
 pub const ENGINE_EXCHANGE_CAPABILITIES: &str = "engine_exchangeCapabilities";
 pub const ENGINE_EXCHANGE_CAPABILITIES_TIMEOUT: Duration = Duration::from_secs(1);
 
+pub const ENGINE_GET_CLIENT_VERSION_V1: &str = "engine_getClientVersionV1";
+pub const ENGINE_GET_CLIENT_VERSION_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// This error is returned during a `chainId` call by Geth.
 pub const EIP155_ERROR_STR: &str = "chain not synced beyond EIP-155 replay-protection fork block";
 /// This code is returned by all clients when a method is not supported
@@ -86,6 +90,20 @@ pub static PRE_CAPELLA_ENGINE_CAPABILITIES: EngineCapabilities = EngineCapabilit
     exchange_transition_configuration_v1: true,
 };
 
+/// The version of this build of Lighthouse, as sent to the execution engine with
+/// `engine_getClientVersionV1`.
+pub fn lighthouse_client_version() -> ExecutionClientVersion {
+    let commit = lighthouse_version::COMMIT_PREFIX;
+    ExecutionClientVersion {
+        code: "LH".to_string(),
+        name: "Lighthouse".to_string(),
+        version: lighthouse_version::VERSION
+            .trim_start_matches("Lighthouse/")
+            .to_string(),
+        commit: format!("0x{}", commit.get(..8).unwrap_or(commit)),
+    }
+}
+
 /// Contains methods to convert arbitrary bytes to an ETH2 deposit contract object.
 pub mod deposit_log {
     use ssz::Decode;
@@ -589,11 +607,40 @@ impl CapabilitiesCacheEntry {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct ClientVersionCacheEntry {
+    client_versions: Vec<ExecutionClientVersion>,
+    fetch_time: Instant,
+}
+
+impl ClientVersionCacheEntry {
+    pub fn new(client_versions: Vec<ExecutionClientVersion>) -> Self {
+        Self {
+            client_versions,
+            fetch_time: Instant::now(),
+        }
+    }
+
+    pub fn client_versions(&self) -> Vec<ExecutionClientVersion> {
+        self.client_versions.clone()
+    }
+
+    pub fn age(&self) -> Duration {
+        Instant::now().duration_since(self.fetch_time)
+    }
+
+    /// returns `true` if the entry's age is >= age_limit
+    pub fn older_than(&self, age_limit: Option<Duration>) -> bool {
+        age_limit.map_or(false, |limit| self.age() >= limit)
+    }
+}
+
 pub struct HttpJsonRpc {
     pub client: Client,
     pub url: SensitiveUrl,
     pub execution_timeout_multiplier: u32,
     pub engine_capabilities_cache: Mutex<Option<CapabilitiesCacheEntry>>,
+    pub client_version_cache: Mutex<Option<ClientVersionCacheEntry>>,
     auth: Option<Auth>,
 }
 
@@ -607,6 +654,7 @@ impl HttpJsonRpc {
             url,
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
+            client_version_cache: Mutex::new(None),
             auth: None,
         })
     }
@@ -621,6 +669,7 @@ impl HttpJsonRpc {
             url,
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
+            client_version_cache: Mutex::new(None),
             auth: Some(auth),
         })
     }
@@ -988,10 +1037,48 @@ impl HttpJsonRpc {
         }
     }
 
+    pub async fn get_client_version_v1(&self) -> Result<Vec<ExecutionClientVersion>, Error> {
+        let params = json!([lighthouse_client_version()]);
+
+        let response: Vec<ExecutionClientVersion> = self
+            .rpc_request(
+                ENGINE_GET_CLIENT_VERSION_V1,
+                params,
+                ENGINE_GET_CLIENT_VERSION_TIMEOUT * self.execution_timeout_multiplier,
+            )
+            .await?;
+
+        Ok(response)
+    }
+
     pub async fn clear_exchange_capabilties_cache(&self) {
         *self.engine_capabilities_cache.lock().await = None;
     }
 
+    pub async fn clear_client_version_cache(&self) {
+        *self.client_version_cache.lock().await = None;
+    }
+
+    /// Returns the versions of the execution engine resulting from a call to
+    /// engine_getClientVersionV1, using a cached result of age < `age_limit` if there is one.
+    ///
+    /// Set `age_limit` to `None` to always return the cached result
+    /// Set `age_limit` to `Some(Duration::ZERO)` to force fetching from EE
+    pub async fn get_client_version(
+        &self,
+        age_limit: Option<Duration>,
+    ) -> Result<Vec<ExecutionClientVersion>, Error> {
+        let mut lock = self.client_version_cache.lock().await;
+
+        if let Some(lock) = lock.as_ref().filter(|entry| !entry.older_than(age_limit)) {
+            Ok(lock.client_versions())
+        } else {
+            let client_versions = self.get_client_version_v1().await?;
+            *lock = Some(ClientVersionCacheEntry::new(client_versions.clone()));
+            Ok(client_versions)
+        }
+    }
+
     /// Returns the execution engine capabilities resulting from a call to
     /// engine_exchangeCapabilities. If the capabilities cache is not populated,
     /// or if it is populated with a cached result of age >= `age_limit`, this
@@ -1555,6 +1642,68 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn get_client_version_v1_request() {
+        Tester::new(true)
+            .assert_request_equals(
+                |client| async move {
+                    let _ = client.get_client_version_v1().await;
+                },
+                json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "method": ENGINE_GET_CLIENT_VERSION_V1,
+                    "params": [{
+                        "code": "LH",
+                        "name": "Lighthouse",
+                        "version": lighthouse_version::VERSION.trim_start_matches("Lighthouse/"),
+                        "commit": lighthouse_client_version().commit,
+                    }]
+                }),
+            )
+            .await;
+
+        Tester::new(false)
+            .assert_auth_failure(|client| async move { client.get_client_version_v1().await })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn get_client_version_cached() {
+        let tester = Tester::new(true);
+        let client = tester.rpc_client.clone();
+        let mock_version = crate::test_utils::mock_client_version();
+
+        assert_eq!(
+            client.get_client_version(None).await.unwrap(),
+            vec![mock_version.clone()]
+        );
+
+        // A cached version is returned without querying the execution engine.
+        let cached_version = ExecutionClientVersion {
+            code: "XX".to_string(),
+            ..mock_version.clone()
+        };
+        *client.client_version_cache.lock().await =
+            Some(ClientVersionCacheEntry::new(vec![cached_version.clone()]));
+        assert_eq!(
+            client.get_client_version(None).await.unwrap(),
+            vec![cached_version]
+        );
+
+        // A stale version is fetched again.
+        assert_eq!(
+            client
+                .get_client_version(Some(Duration::ZERO))
+                .await
+                .unwrap(),
+            vec![mock_version]
+        );
+
+        client.clear_client_version_cache().await;
+        assert!(client.client_version_cache.lock().await.is_none());
+    }
+
     fn str_to_payload_id(s: &str) -> PayloadId {
         serde_json::from_str::<TransparentJsonPayloadId>(&format!("\"{}\"", s))
             .unwrap()
@@ -5,6 +5,7 @@ use crate::engine_api::{
     PayloadId,
 };
 use crate::HttpJsonRpc;
+use futures::stream::{self, StreamExt};
 use lru::LruCache;
 use slog::{debug, error, info, warn, Logger};
 use std::future::Future;
@@ -46,6 +47,18 @@ pub enum EngineState {
     Offline,
 }
 
+impl EngineStateInternal {
+    /// Orders states from most to least healthy, so that healthier engines are preferred.
+    fn health_rank(self) -> u8 {
+        match self {
+            EngineStateInternal::Synced => 0,
+            EngineStateInternal::Syncing => 1,
+            EngineStateInternal::Offline => 2,
+            EngineStateInternal::AuthFailed => 3,
+        }
+    }
+}
+
 impl From<EngineStateInternal> for EngineState {
     fn from(state: EngineStateInternal) -> Self {
         match state {
@@ -117,6 +130,7 @@ pub enum EngineError {
     Api { error: EngineApiError },
     BuilderApi { error: EngineApiError },
     Auth,
+    Timeout,
 }
 
 /// An execution engine.
@@ -238,6 +252,11 @@ impl Engine {
         **self.state.read().await == EngineStateInternal::Synced
     }
 
+    /// Returns the engine's position in the ordering of engines from most to least healthy.
+    async fn health_rank(&self) -> u8 {
+        self.state.read().await.health_rank()
+    }
+
     /// Run the `EngineApi::upcheck` function if the node's last known state is not synced. This
     /// might be used to recover the node if offline.
     pub async fn upcheck(&self) {
@@ -286,8 +305,8 @@ impl Engine {
 
                 let mut state = self.state.write().await;
                 state.update(EngineStateInternal::Offline);
-                // need to clear the engine capabilities cache if we detect the
-                // execution engine is offline as it is likely the engine is being
+                // need to clear the engine capabilities and client version caches if we
+                // detect the execution engine is offline as it is likely the engine is being
                 // updated to a newer version with new capabilities
                 (**state, CapabilitiesCacheAction::Clear)
             }
@@ -307,7 +326,10 @@ impl Engine {
                     )
                 }
             }
-            CapabilitiesCacheAction::Clear => self.api.clear_exchange_capabilties_cache().await,
+            CapabilitiesCacheAction::Clear => {
+                self.api.clear_exchange_capabilties_cache().await;
+                self.api.clear_client_version_cache().await;
+            }
         }
 
         debug!(
@@ -385,6 +407,145 @@ impl Engine {
     }
 }
 
+/// A collection of execution engines, providing fallback and broadcast behaviour.
+///
+/// Engines are ordered by health when a request needs only one of them, with ties broken by the
+/// order in which they were configured.
+pub struct Engines {
+    engines: Vec<Arc<Engine>>,
+    /// Reports `Online` whilst at least one engine is online.
+    state: watch::Receiver<EngineState>,
+}
+
+impl Engines {
+    /// Creates a new collection of engines, spawning a task to track their combined state.
+    pub fn new(engines: Vec<Engine>, executor: &TaskExecutor) -> Self {
+        let engines: Vec<_> = engines.into_iter().map(Arc::new).collect();
+        let (notifier, state) = watch::channel(EngineState::Offline);
+
+        let watched = engines.clone();
+        executor.spawn(
+            async move {
+                let mut states = vec![EngineState::Offline; watched.len()];
+                let mut streams = Vec::with_capacity(watched.len());
+                for (i, engine) in watched.iter().enumerate() {
+                    streams.push(engine.watch_state().await.map(move |state| (i, state)));
+                }
+
+                let mut updates = stream::select_all(streams);
+                while let Some((i, state)) = updates.next().await {
+                    states[i] = state;
+                    let combined = if states.contains(&EngineState::Online) {
+                        EngineState::Online
+                    } else {
+                        EngineState::Offline
+                    };
+                    notifier.send_if_modified(|last_state| {
+                        let changed = *last_state != combined;
+                        *last_state = combined;
+                        changed
+                    });
+                }
+            },
+            "engines_state",
+        );
+
+        Self { engines, state }
+    }
+
+    /// Gives access to a channel containing whether any engine is online.
+    ///
+    /// This can be called several times.
+    pub fn watch_state(&self) -> WatchStream<EngineState> {
+        WatchStream::new(self.state.clone())
+    }
+
+    /// Returns `true` if any engine has a "synced" status.
+    pub async fn is_synced(&self) -> bool {
+        for engine in &self.engines {
+            if engine.is_synced().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Runs `Engine::upcheck` on all engines, concurrently.
+    pub async fn upcheck(&self) {
+        futures::future::join_all(self.engines.iter().map(|engine| engine.upcheck())).await;
+    }
+
+    /// Stores the latest forkchoice state on all engines, so it can be sent to any engine that
+    /// comes back online.
+    pub async fn set_latest_forkchoice_state(&self, state: ForkchoiceState) {
+        for engine in &self.engines {
+            engine.set_latest_forkchoice_state(state).await;
+        }
+    }
+
+    /// Returns the engines ordered from most to least healthy.
+    pub async fn healthiest_first(&self) -> Vec<&Arc<Engine>> {
+        let mut ranked = Vec::with_capacity(self.engines.len());
+        for engine in &self.engines {
+            ranked.push((engine.health_rank().await, engine));
+        }
+        // The sort is stable, so configuration order is kept between equally healthy engines.
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, engine)| engine).collect()
+    }
+
+    /// Run `func` on each engine in order of health, returning the first successful result.
+    ///
+    /// If all engines fail, the error from the healthiest engine is returned.
+    pub async fn first_success<'a, F, G, H>(&'a self, func: F) -> Result<H, EngineError>
+    where
+        F: Fn(&'a Engine) -> G,
+        G: Future<Output = Result<H, EngineApiError>>,
+    {
+        let mut first_error = None;
+        for engine in self.healthiest_first().await {
+            match engine.request(&func).await {
+                Ok(result) => return Ok(result),
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        Err(first_error.unwrap_or(EngineError::Offline))
+    }
+
+    /// Run `func` on all engines concurrently, returning all results in the order the engines were
+    /// configured.
+    ///
+    /// Each engine is given `timeout` (scaled by its timeout multiplier) to respond, so that a
+    /// hung engine cannot delay the results of the others by more than that.
+    pub async fn broadcast<'a, F, G, H>(
+        &'a self,
+        timeout: Duration,
+        func: F,
+    ) -> Vec<Result<H, EngineError>>
+    where
+        F: Fn(&'a Engine) -> G,
+        G: Future<Output = Result<H, EngineApiError>>,
+    {
+        let func = &func;
+        futures::future::join_all(self.engines.iter().map(|engine| async move {
+            let timeout = timeout * engine.api.execution_timeout_multiplier;
+            tokio::time::timeout(timeout, engine.request(func))
+                .await
+                .unwrap_or_else(|_| {
+                    warn!(
+                        engine.log,
+                        "Execution engine call timed out";
+                        "timeout" => ?timeout,
+                    );
+                    Err(EngineError::Timeout)
+                })
+        }))
+        .await
+    }
+}
+
 impl PayloadIdCacheKey {
     fn new(head_block_hash: &ExecutionBlockHash, attributes: &PayloadAttributes) -> Self {
         Self {
@@ -11,23 +11,29 @@ pub use engine_api::EngineCapabilities;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
-use engines::{Engine, EngineError};
+use engines::{Engine, EngineError, Engines};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::ExecutionClientVersion;
 use eth2::types::builder_bid::SignedBuilderBid;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::join_all;
+use http::{
+    ENGINE_EXCHANGE_TRANSITION_CONFIGURATION_V1_TIMEOUT, ENGINE_FORKCHOICE_UPDATED_TIMEOUT,
+    ENGINE_NEW_PAYLOAD_TIMEOUT,
+};
 use lru::LruCache;
-use payload_status::process_payload_status;
+use payload_status::process_multiple_payload_statuses;
 pub use payload_status::PayloadStatus;
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
-use slog::{crit, debug, error, info, trace, warn, Logger};
+use slog::{crit, debug, error, info, o, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -42,7 +48,8 @@ use types::{AbstractExecPayload, BeaconStateError, ExecPayload, Withdrawals};
 use types::{
     BlindedPayload, BlockType, ChainSpec, Epoch, ExecutionBlockHash, ExecutionPayload,
     ExecutionPayloadCapella, ExecutionPayloadMerge, ForkName, ForkVersionedResponse,
-    ProposerPreparationData, PublicKeyBytes, Signature, SignedBeaconBlock, Slot, Uint256,
+    ProposerPreparationData, PublicKeyBytes, Signature, SignedBeaconBlock,
+    SignedValidatorRegistrationData, Slot, Uint256,
 };
 
 mod block_hash;
@@ -74,8 +81,16 @@ const EXECUTION_BLOCKS_LRU_CACHE_SIZE: usize = 128;
 const DEFAULT_SUGGESTED_FEE_RECIPIENT: [u8; 20] =
     [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
 
+/// The number of recent builder headers for which we remember the builder which provided them, so
+/// that the block can be revealed by the same builder.
+const BUILDER_PAYLOAD_SOURCES_CACHE_SIZE: usize = 32;
+
 const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(60);
 
+/// The execution engine's version is re-fetched at least this often, as well as whenever the engine
+/// goes offline.
+const CACHED_CLIENT_VERSION_AGE_LIMIT: Duration = Duration::from_secs(900); // 15 minutes
+
 /// A payload alongside some information about where it came from.
 pub enum ProvenancedPayload<P> {
     /// A good ol' fashioned farm-to-table payload from your local EE.
@@ -87,6 +102,11 @@ pub enum ProvenancedPayload<P> {
 #[derive(Debug)]
 pub enum Error {
     NoEngine,
+    InvalidExecutionQuorum {
+        quorum: usize,
+        engines: usize,
+    },
+    ConsensusFailure,
     NoPayloadBuilder,
     ApiError(ApiError),
     Builder(builder_client::Error),
@@ -208,8 +228,11 @@ pub enum FailedCondition {
 }
 
 struct Inner<E: EthSpec> {
-    engine: Arc<Engine>,
-    builder: Option<BuilderHttpClient>,
+    engines: Engines,
+    execution_quorum: usize,
+    builders: Vec<BuilderHttpClient>,
+    /// The index of the builder which provided each recent header, keyed by block hash.
+    builder_payload_sources: Mutex<LruCache<ExecutionBlockHash, usize>>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
@@ -227,8 +250,12 @@ pub struct Config {
     /// Endpoint urls for EL nodes that are running the engine api.
     pub execution_endpoints: Vec<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    ///
+    /// Headers are requested from all of them and the most valuable bid is used.
+    pub builder_urls: Vec<SensitiveUrl>,
     /// JWT secrets for the above endpoints running the engine api.
+    ///
+    /// Either one secret per endpoint, or a single secret shared by all endpoints.
     pub secret_files: Vec<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
@@ -242,22 +269,25 @@ pub struct Config {
     /// The minimum value of an external payload for it to be considered in a proposal.
     pub builder_profit_threshold: u128,
     pub execution_timeout_multiplier: Option<u32>,
+    /// The number of execution engines that must agree on a payload status before it is
+    /// considered valid or invalid. Defaults to 1.
+    pub execution_quorum: Option<usize>,
     pub always_prefer_builder_payload: bool,
 }
 
-/// Provides access to one execution engine and provides a neat interface for consumption by the
-/// `BeaconChain`.
+/// Provides access to one or more execution engines and provides a neat interface for consumption
+/// by the `BeaconChain`.
 #[derive(Clone)]
 pub struct ExecutionLayer<T: EthSpec> {
     inner: Arc<Inner<T>>,
 }
 
 impl<T: EthSpec> ExecutionLayer<T> {
-    /// Instantiate `Self` with an Execution engine specified in `Config`, using JSON-RPC via HTTP.
+    /// Instantiate `Self` with the Execution engines specified in `Config`, using JSON-RPC via HTTP.
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoints: urls,
-            builder_url,
+            builder_urls,
             secret_files,
             suggested_fee_recipient,
             jwt_id,
@@ -265,58 +295,60 @@ impl<T: EthSpec> ExecutionLayer<T> {
             default_datadir,
             builder_profit_threshold,
             execution_timeout_multiplier,
+            execution_quorum,
             always_prefer_builder_payload,
         } = config;
 
-        if urls.len() > 1 {
-            warn!(log, "Only the first execution engine url will be used");
+        if urls.is_empty() {
+            return Err(Error::NoEngine);
         }
-        let execution_url = urls.into_iter().next().ok_or(Error::NoEngine)?;
 
-        // Use the default jwt secret path if not provided via cli.
-        let secret_file = secret_files
-            .into_iter()
-            .next()
-            .unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
-
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
+        // Use the default jwt secret path if not provided via cli. A single secret is shared by
+        // all engines, otherwise each engine must have its own.
+        let secret_files = match secret_files.len() {
+            0 => vec![default_datadir.join(DEFAULT_JWT_FILE); urls.len()],
+            1 => vec![secret_files[0].clone(); urls.len()],
+            n if n == urls.len() => secret_files,
+            n => {
+                return Err(Error::InvalidJWTSecret(format!(
+                    "{} JWT secrets provided for {} execution endpoints",
+                    n,
+                    urls.len()
+                )))
+            }
+        };
+
+        let execution_quorum = execution_quorum.unwrap_or(1);
+        if execution_quorum == 0 || execution_quorum > urls.len() {
+            return Err(Error::InvalidExecutionQuorum {
+                quorum: execution_quorum,
+                engines: urls.len(),
+            });
+        }
 
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+        let mut engines = Vec::with_capacity(urls.len());
+        for (execution_url, secret_file) in urls.into_iter().zip(secret_files) {
+            let jwt_key = load_or_create_jwt_secret(&secret_file, &log)?;
+            let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
+            let engine_log = log.new(o!("endpoint" => execution_url.to_string()));
             let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
                 .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
-        };
+            engines.push(Engine::new(api, executor.clone(), &engine_log));
+        }
 
-        let builder = builder_url
+        if engines.len() > 1 {
+            info!(
+                log,
+                "Using multiple execution engines";
+                "count" => engines.len(),
+                "quorum" => execution_quorum,
+            );
+        }
+        let engines = Engines::new(engines, &executor);
+
+        let builders = builder_urls
+            .into_iter()
             .map(|url| {
                 let builder_client = BuilderHttpClient::new(url.clone()).map_err(Error::Builder);
                 info!(log,
@@ -325,11 +357,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     "builder_profit_threshold" => builder_profit_threshold);
                 builder_client
             })
-            .transpose()?;
+            .collect::<Result<Vec<_>, _>>()?;
 
         let inner = Inner {
-            engine: Arc::new(engine),
-            builder,
+            engines,
+            execution_quorum,
+            builders,
+            builder_payload_sources: Mutex::new(LruCache::new(BUILDER_PAYLOAD_SOURCES_CACHE_SIZE)),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
@@ -349,12 +383,18 @@ impl<T: EthSpec> ExecutionLayer<T> {
 }
 
 impl<T: EthSpec> ExecutionLayer<T> {
-    fn engine(&self) -> &Arc<Engine> {
-        &self.inner.engine
+    fn engines(&self) -> &Engines {
+        &self.inner.engines
     }
 
-    pub fn builder(&self) -> &Option<BuilderHttpClient> {
-        &self.inner.builder
+    /// Returns the clients for all connected builders, in the order they were configured.
+    pub fn builders(&self) -> &[BuilderHttpClient] {
+        &self.inner.builders
+    }
+
+    /// Returns `true` if at least one builder is connected.
+    pub fn has_builder(&self) -> bool {
+        !self.inner.builders.is_empty()
     }
 
     /// Cache a full payload, keyed on the `tree_hash_root` of the payload
@@ -374,10 +414,17 @@ impl<T: EthSpec> ExecutionLayer<T> {
     /// Get the current difficulty of the PoW chain.
     pub async fn get_current_difficulty(&self) -> Result<Uint256, ApiError> {
         let block = self
-            .engine()
-            .api
-            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
-            .await?
+            .engines()
+            .first_success(|engine| {
+                engine
+                    .api
+                    .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            })
+            .await
+            .map_err(|e| match e {
+                EngineError::Api { error } => error,
+                e => ApiError::RequestFailed(format!("{:?}", e)),
+            })?
             .ok_or(ApiError::ExecutionHeadBlockNotFound)?;
         Ok(block.total_difficulty)
     }
@@ -388,11 +435,11 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.inner.execution_blocks.lock().await
     }
 
-    /// Gives access to a channel containing if the last engine state is online or not.
+    /// Gives access to a channel containing if any engine is online or not.
     ///
     /// This can be called several times.
     pub async fn get_responsiveness_watch(&self) -> WatchStream<EngineState> {
-        self.engine().watch_state().await
+        self.engines().watch_state()
     }
 
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
@@ -444,7 +491,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        self.engines().upcheck().await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -504,9 +551,9 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.spawn(routine, "exec_config_poll");
     }
 
-    /// Returns `true` if the execution engine is synced and reachable.
+    /// Returns `true` if any execution engine is synced and reachable.
     pub async fn is_synced(&self) -> bool {
-        self.engine().is_synced().await
+        self.engines().is_synced().await
     }
 
     /// Execution nodes return a "SYNCED" response when they do not have any peers.
@@ -519,9 +566,12 @@ impl<T: EthSpec> ExecutionLayer<T> {
         let synced = self.is_synced().await;
         if synced {
             if let Ok(Some(block)) = self
-                .engine()
-                .api
-                .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                .engines()
+                .first_success(|engine| {
+                    engine
+                        .api
+                        .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                })
                 .await
             {
                 if block.block_number == 0 {
@@ -717,7 +767,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContents<T, Payload>>, Error> {
-        if let Some(builder) = self.builder() {
+        if self.has_builder() {
             let slot = builder_params.slot;
             let pubkey = builder_params.pubkey;
 
@@ -725,17 +775,17 @@ impl<T: EthSpec> ExecutionLayer<T> {
                 ChainHealth::Healthy => {
                     info!(
                         self.log(),
-                        "Requesting blinded header from connected builder";
+                        "Requesting blinded header from connected builders";
                         "slot" => ?slot,
                         "pubkey" => ?pubkey,
                         "parent_hash" => ?parent_hash,
+                        "builders" => self.builders().len(),
                     );
 
-                    // Wait for the builder *and* local EL to produce a payload (or return an error).
-                    let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
+                    // Wait for the builders *and* local EL to produce a payload (or return an error).
+                    let ((relay_results, relay_duration), (local_result, local_duration)) = tokio::join!(
                         timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                            builder
-                                .get_builder_header::<T, Payload>(slot, parent_hash, &pubkey)
+                            self.get_builder_headers::<Payload>(slot, parent_hash, &pubkey)
                                 .await
                         }),
                         timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
@@ -749,11 +799,39 @@ impl<T: EthSpec> ExecutionLayer<T> {
                         })
                     );
 
+                    let relay_result = self.select_builder_bid(
+                        relay_results,
+                        parent_hash,
+                        payload_attributes,
+                        local_result
+                            .as_ref()
+                            .ok()
+                            .map(|local| local.payload().block_number()),
+                        current_fork,
+                        spec,
+                    );
+
+                    // Remember which builder provided the best bid, so that it can reveal the
+                    // payload if the bid is used.
+                    if let Ok(Some((builder_index, relay))) = &relay_result {
+                        self.inner
+                            .builder_payload_sources
+                            .lock()
+                            .await
+                            .put(relay.data.message.header.block_hash(), *builder_index);
+                    }
+
                     info!(
                         self.log(),
                         "Requested blinded execution payload";
+                        "relay" => match &relay_result {
+                            Ok(Some((builder_index, _))) => {
+                                self.builders()[*builder_index].server().to_string()
+                            }
+                            _ => "none".to_string(),
+                        },
                         "relay_fee_recipient" => match &relay_result {
-                            Ok(Some(r)) => format!("{:?}", r.data.message.header.fee_recipient()),
+                            Ok(Some((_, r))) => format!("{:?}", r.data.message.header.fee_recipient()),
                             Ok(None) => "empty response".to_string(),
                             Err(_) => "request failed".to_string(),
                         },
@@ -788,7 +866,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
                             );
                             Ok(ProvenancedPayload::Local(local))
                         }
-                        (Ok(Some(relay)), Ok(local)) => {
+                        (Ok(Some((_, relay))), Ok(local)) => {
                             let header = &relay.data.message.header;
 
                             info!(
@@ -857,7 +935,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                 }
                             }
                         }
-                        (Ok(Some(relay)), Err(local_error)) => {
+                        (Ok(Some((_, relay))), Err(local_error)) => {
                             let header = &relay.data.message.header;
 
                             info!(
@@ -1007,8 +1085,10 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         f: fn(&ExecutionLayer<T>, ExecutionPayloadRef<T>) -> Option<ExecutionPayload<T>>,
     ) -> Result<BlockProposalContents<T, Payload>, Error> {
-        self.engine()
-            .request(move |engine| async move {
+        // The payload is produced by the healthiest engine that is able to, falling back to the
+        // others in order of health.
+        self.engines()
+            .first_success(move |engine| async move {
                 let payload_id = if let Some(id) = engine
                     .get_payload_id(&parent_hash, payload_attributes)
                     .await
@@ -1135,21 +1215,26 @@ impl<T: EthSpec> ExecutionLayer<T> {
             "block_number" => execution_payload.block_number(),
         );
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.new_payload(execution_payload.clone()))
+        let broadcast_results = self
+            .engines()
+            .broadcast(ENGINE_NEW_PAYLOAD_TIMEOUT, |engine| {
+                engine.api.new_payload(execution_payload.clone())
+            })
             .await;
 
-        if let Ok(status) = &result {
+        for status in broadcast_results.iter().flatten() {
             metrics::inc_counter_vec(
                 &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
                 &["new_payload", status.status.into()],
             );
         }
 
-        process_payload_status(execution_payload.block_hash(), result, self.log())
-            .map_err(Box::new)
-            .map_err(Error::EngineError)
+        process_multiple_payload_statuses(
+            execution_payload.block_hash(),
+            broadcast_results.into_iter(),
+            self.inner.execution_quorum,
+            self.log(),
+        )
     }
 
     /// Register that the given `validator_index` is going to produce a block at `slot`.
@@ -1275,33 +1360,39 @@ impl<T: EthSpec> ExecutionLayer<T> {
             finalized_block_hash,
         };
 
-        self.engine()
+        self.engines()
             .set_latest_forkchoice_state(forkchoice_state)
             .await;
 
-        let result = self
-            .engine()
-            .request(|engine| async move {
-                engine
-                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
-                    .await
+        // Every engine is sent the payload attributes, so that whichever engine is healthiest at
+        // proposal time has a payload ready.
+        let broadcast_results = self
+            .engines()
+            .broadcast(ENGINE_FORKCHOICE_UPDATED_TIMEOUT, |engine| {
+                let payload_attributes = payload_attributes.clone();
+                async move {
+                    engine
+                        .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
+                        .await
+                }
             })
             .await;
 
-        if let Ok(status) = &result {
+        for response in broadcast_results.iter().flatten() {
             metrics::inc_counter_vec(
                 &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
-                &["forkchoice_updated", status.payload_status.status.into()],
+                &["forkchoice_updated", response.payload_status.status.into()],
             );
         }
 
-        process_payload_status(
+        process_multiple_payload_statuses(
             head_block_hash,
-            result.map(|response| response.payload_status),
+            broadcast_results
+                .into_iter()
+                .map(|result| result.map(|response| response.payload_status)),
+            self.inner.execution_quorum,
             self.log(),
         )
-        .map_err(Box::new)
-        .map_err(Error::EngineError)
     }
 
     pub async fn exchange_transition_configuration(&self, spec: &ChainSpec) -> Result<(), Error> {
@@ -1311,44 +1402,52 @@ impl<T: EthSpec> ExecutionLayer<T> {
             terminal_block_number: 0,
         };
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.exchange_transition_configuration_v1(local))
+        // Every engine is checked, so that a misconfigured engine is reported even whilst another
+        // engine is healthy.
+        let results = self
+            .engines()
+            .broadcast(
+                ENGINE_EXCHANGE_TRANSITION_CONFIGURATION_V1_TIMEOUT,
+                |engine| engine.api.exchange_transition_configuration_v1(local),
+            )
             .await;
 
-        match result {
-            Ok(remote) => {
-                if local.terminal_total_difficulty != remote.terminal_total_difficulty
-                    || local.terminal_block_hash != remote.terminal_block_hash
-                {
+        let mut outcome = Ok(());
+        for result in results {
+            match result {
+                Ok(remote) => {
+                    if local.terminal_total_difficulty != remote.terminal_total_difficulty
+                        || local.terminal_block_hash != remote.terminal_block_hash
+                    {
+                        error!(
+                            self.log(),
+                            "Execution client config mismatch";
+                            "msg" => "ensure lighthouse and the execution client are up-to-date and \
+                                      configured consistently",
+                            "remote" => ?remote,
+                            "local" => ?local,
+                        );
+                        outcome = Err(Error::EngineError(Box::new(EngineError::Api {
+                            error: ApiError::TransitionConfigurationMismatch,
+                        })));
+                    } else {
+                        debug!(
+                            self.log(),
+                            "Execution client config is OK";
+                        );
+                    }
+                }
+                Err(e) => {
                     error!(
                         self.log(),
-                        "Execution client config mismatch";
-                        "msg" => "ensure lighthouse and the execution client are up-to-date and \
-                                  configured consistently",
-                        "remote" => ?remote,
-                        "local" => ?local,
-                    );
-                    Err(Error::EngineError(Box::new(EngineError::Api {
-                        error: ApiError::TransitionConfigurationMismatch,
-                    })))
-                } else {
-                    debug!(
-                        self.log(),
-                        "Execution client config is OK";
+                        "Unable to get transition config";
+                        "error" => ?e,
                     );
-                    Ok(())
+                    outcome = Err(Error::EngineError(Box::new(e)));
                 }
             }
-            Err(e) => {
-                error!(
-                    self.log(),
-                    "Unable to get transition config";
-                    "error" => ?e,
-                );
-                Err(Error::EngineError(Box::new(e)))
-            }
         }
+        outcome
     }
 
     /// Returns the execution engine capabilities resulting from a call to
@@ -1364,8 +1463,24 @@ impl<T: EthSpec> ExecutionLayer<T> {
         &self,
         age_limit: Option<Duration>,
     ) -> Result<EngineCapabilities, Error> {
-        self.engine()
-            .request(|engine| engine.get_engine_capabilities(age_limit))
+        self.engines()
+            .first_success(|engine| engine.get_engine_capabilities(age_limit))
+            .await
+            .map_err(Box::new)
+            .map_err(Error::EngineError)
+    }
+
+    /// Returns the name and version of the execution engine from `engine_getClientVersionV1`.
+    ///
+    /// The result is cached, so that the execution engine is not queried for every block proposal.
+    /// A multiplexer may return the versions of several execution engines.
+    pub async fn get_client_version(&self) -> Result<Vec<ExecutionClientVersion>, Error> {
+        self.engines()
+            .first_success(|engine| {
+                engine
+                    .api
+                    .get_client_version(Some(CACHED_CLIENT_VERSION_AGE_LIMIT))
+            })
             .await
             .map_err(Box::new)
             .map_err(Error::EngineError)
@@ -1389,8 +1504,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         );
 
         let hash_opt = self
-            .engine()
-            .request(|engine| async move {
+            .engines()
+            .first_success(|engine| async move {
                 let terminal_block_hash = spec.terminal_block_hash;
                 if terminal_block_hash != ExecutionBlockHash::zero() {
                     if self
@@ -1516,8 +1631,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
             &[metrics::IS_VALID_TERMINAL_POW_BLOCK_HASH],
         );
 
-        self.engine()
-            .request(|engine| async move {
+        self.engines()
+            .first_success(|engine| async move {
                 if let Some(pow_block) = self.get_pow_block(engine, block_hash).await? {
                     if let Some(pow_parent) =
                         self.get_pow_block(engine, pow_block.parent_hash).await?
@@ -1575,9 +1690,10 @@ impl<T: EthSpec> ExecutionLayer<T> {
         &self,
         hashes: Vec<ExecutionBlockHash>,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<T>>>, Error> {
-        self.engine()
-            .request(|engine: &Engine| async move {
-                engine.api.get_payload_bodies_by_hash_v1(hashes).await
+        self.engines()
+            .first_success(|engine: &Engine| {
+                let hashes = hashes.clone();
+                async move { engine.api.get_payload_bodies_by_hash_v1(hashes).await }
             })
             .await
             .map_err(Box::new)
@@ -1590,8 +1706,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         count: u64,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<T>>>, Error> {
         let _timer = metrics::start_timer(&metrics::EXECUTION_LAYER_GET_PAYLOAD_BODIES_BY_RANGE);
-        self.engine()
-            .request(|engine: &Engine| async move {
+        self.engines()
+            .first_success(|engine: &Engine| async move {
                 engine
                     .api
                     .get_payload_bodies_by_range_v1(start, count)
@@ -1607,8 +1723,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         hash: ExecutionBlockHash,
         fork: ForkName,
     ) -> Result<Option<ExecutionPayload<T>>, Error> {
-        self.engine()
-            .request(|engine| async move {
+        self.engines()
+            .first_success(|engine| async move {
                 self.get_payload_by_block_hash_from_engine(engine, hash, fork)
                     .await
             })
@@ -1717,19 +1833,44 @@ impl<T: EthSpec> ExecutionLayer<T> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
-            let (payload_result, duration) =
-                timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
-                    builder
-                        .post_builder_blinded_blocks(block)
-                        .await
-                        .map_err(Error::Builder)
-                        .map(|d| d.data)
-                })
-                .await;
+        // Reveal the payload with the builder which provided the header. If it is unknown (e.g.
+        // after a restart) try each builder in turn.
+        let source = match block.message().execution_payload() {
+            Ok(payload) => self
+                .inner
+                .builder_payload_sources
+                .lock()
+                .await
+                .get(&payload.block_hash())
+                .copied(),
+            Err(_) => None,
+        };
+        let builder_indices = match source {
+            Some(builder_index) => vec![builder_index],
+            None => (0..self.builders().len()).collect(),
+        };
+
+        let mut payload_result = Err(Error::NoPayloadBuilder);
+        for builder_index in builder_indices {
+            let builder = &self.builders()[builder_index];
+            let relay = builder.server().to_string();
+            let (result, duration) = timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
+                builder
+                    .post_builder_blinded_blocks(block)
+                    .await
+                    .map_err(Error::Builder)
+                    .map(|d| d.data)
+            })
+            .await;
 
-            match &payload_result {
+            match &result {
                 Ok(payload) => {
+                    observe_builder_request(
+                        &relay,
+                        metrics::POST_BUILDER_BLINDED_BLOCKS,
+                        duration,
+                        metrics::SUCCESS,
+                    );
                     metrics::inc_counter_vec(
                         &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
                         &[metrics::SUCCESS],
@@ -1737,6 +1878,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     info!(
                         self.log(),
                         "Builder successfully revealed payload";
+                        "relay" => &relay,
                         "relay_response_ms" => duration.as_millis(),
                         "block_root" => ?block_root,
                         "fee_recipient" => ?payload.fee_recipient(),
@@ -1745,6 +1887,12 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     )
                 }
                 Err(e) => {
+                    observe_builder_request(
+                        &relay,
+                        metrics::POST_BUILDER_BLINDED_BLOCKS,
+                        duration,
+                        metrics::FAILURE,
+                    );
                     metrics::inc_counter_vec(
                         &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
                         &[metrics::FAILURE],
@@ -1754,6 +1902,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
                         "Builder failed to reveal payload";
                         "info" => "this is common behaviour for some builders and may not indicate an issue",
                         "error" => ?e,
+                        "relay" => &relay,
                         "relay_response_ms" => duration.as_millis(),
                         "block_root" => ?block_root,
                         "parent_hash" => ?block
@@ -1765,9 +1914,193 @@ impl<T: EthSpec> ExecutionLayer<T> {
                 }
             }
 
-            payload_result
-        } else {
-            Err(Error::NoPayloadBuilder)
+            payload_result = result;
+            if payload_result.is_ok() {
+                break;
+            }
+        }
+
+        payload_result
+    }
+
+    /// Sends validator registrations to every connected builder in parallel.
+    ///
+    /// Each failure is logged. If any builder failed to accept the registrations, the first error
+    /// is returned so that the caller can retry, even if other builders accepted them.
+    pub async fn post_builder_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        if !self.has_builder() {
+            return Err(Error::NoPayloadBuilder);
+        }
+
+        let results = join_all(self.builders().iter().map(|builder| async move {
+            let relay = builder.server().to_string();
+            let start = Instant::now();
+            let result = builder.post_builder_validators(registrations).await;
+            let outcome = if result.is_ok() {
+                metrics::SUCCESS
+            } else {
+                metrics::FAILURE
+            };
+            observe_builder_request(
+                &relay,
+                metrics::POST_BUILDER_VALIDATORS,
+                start.elapsed(),
+                outcome,
+            );
+            if let Err(e) = &result {
+                warn!(
+                    self.log(),
+                    "Builder failed to register validators";
+                    "relay" => relay,
+                    "count" => registrations.len(),
+                    "error" => ?e,
+                );
+            }
+            result
+        }))
+        .await;
+
+        let failures = results.iter().filter(|result| result.is_err()).count();
+        if failures > 0 {
+            warn!(
+                self.log(),
+                "Validator registrations were not accepted by every builder";
+                "failed" => failures,
+                "builders" => results.len(),
+                "count" => registrations.len(),
+            );
+        }
+
+        results
+            .into_iter()
+            .find_map(Result::err)
+            .map_or(Ok(()), |e| Err(Error::Builder(e)))
+    }
+
+    /// Requests a header from every connected builder in parallel, returning the results in the
+    /// order the builders were configured.
+    async fn get_builder_headers<Payload: AbstractExecPayload<T>>(
+        &self,
+        slot: Slot,
+        parent_hash: ExecutionBlockHash,
+        pubkey: &PublicKeyBytes,
+    ) -> Vec<BuilderHeaderResult<T, Payload>> {
+        join_all(self.builders().iter().map(|builder| async move {
+            let relay = builder.server().to_string();
+            let start = Instant::now();
+            let result = builder
+                .get_builder_header::<T, Payload>(slot, parent_hash, pubkey)
+                .await;
+            let outcome = match &result {
+                Ok(Some(bid)) => {
+                    // Avoid logging values that we can't represent with our Prometheus library.
+                    let bid_value_gwei = bid.data.message.value / 1_000_000_000;
+                    if bid_value_gwei <= Uint256::from(i64::max_value()) {
+                        metrics::set_gauge_vec(
+                            &metrics::EXECUTION_LAYER_BUILDER_BIDS,
+                            &[relay.as_str()],
+                            bid_value_gwei.low_u64() as i64,
+                        );
+                    }
+                    metrics::SUCCESS
+                }
+                Ok(None) => metrics::NO_BID,
+                Err(e) => {
+                    debug!(
+                        self.log(),
+                        "Builder failed to provide header";
+                        "relay" => &relay,
+                        "error" => ?e,
+                        "parent_hash" => ?parent_hash,
+                    );
+                    metrics::FAILURE
+                }
+            };
+            observe_builder_request(
+                &relay,
+                metrics::GET_BUILDER_HEADER,
+                start.elapsed(),
+                outcome,
+            );
+            result
+        }))
+        .await
+    }
+
+    /// Selects the most valuable of the bids returned by the builders, alongside the index of the
+    /// builder which provided it. Ties are won by the builder configured first.
+    ///
+    /// Bids which are objectively invalid are discarded. Bids below the profit threshold are still
+    /// considered, since the threshold is applied when comparing against the local payload.
+    ///
+    /// An error is only returned if no builder responded successfully.
+    fn select_builder_bid<Payload: AbstractExecPayload<T>>(
+        &self,
+        results: Vec<BuilderHeaderResult<T, Payload>>,
+        parent_hash: ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+        block_number: Option<u64>,
+        current_fork: ForkName,
+        spec: &ChainSpec,
+    ) -> Result<Option<(usize, BuilderBid<T, Payload>)>, builder_client::Error> {
+        let mut best_bid: Option<(usize, BuilderBid<T, Payload>)> = None;
+        let mut first_error = None;
+        let mut any_response = false;
+
+        for (builder_index, result) in results.into_iter().enumerate() {
+            let bid = match result {
+                Ok(Some(bid)) => bid,
+                Ok(None) => {
+                    any_response = true;
+                    continue;
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                    continue;
+                }
+            };
+            any_response = true;
+
+            if let Err(reason) = verify_builder_bid(
+                &bid,
+                parent_hash,
+                payload_attributes,
+                block_number,
+                Uint256::zero(),
+                current_fork,
+                spec,
+            ) {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                    &[reason.as_ref().as_ref()],
+                );
+                warn!(
+                    self.log(),
+                    "Builder returned invalid payload";
+                    "info" => "ignoring bid",
+                    "relay" => %self.builders()[builder_index].server(),
+                    "reason" => %reason,
+                    "relay_block_hash" => ?bid.data.message.header.block_hash(),
+                    "parent_hash" => ?parent_hash,
+                );
+                continue;
+            }
+
+            let is_best = best_bid.as_ref().map_or(true, |(_, best)| {
+                bid.data.message.value > best.data.message.value
+            });
+            if is_best {
+                best_bid = Some((builder_index, bid));
+            }
+        }
+
+        match (best_bid, first_error) {
+            (Some(best_bid), _) => Ok(Some(best_bid)),
+            (None, Some(e)) if !any_response => Err(e),
+            (None, _) => Ok(None),
         }
     }
 }
@@ -1874,6 +2207,13 @@ impl fmt::Display for InvalidBuilderPayload {
     }
 }
 
+/// A bid for a payload header from a builder.
+type BuilderBid<T, Payload> = ForkVersionedResponse<SignedBuilderBid<T, Payload>>;
+
+/// The response from a single builder to a request for a header.
+type BuilderHeaderResult<T, Payload> =
+    Result<Option<BuilderBid<T, Payload>>, builder_client::Error>;
+
 /// Perform some cursory, non-exhaustive validation of the bid returned from the builder.
 fn verify_builder_bid<T: EthSpec, Payload: AbstractExecPayload<T>>(
     bid: &ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
@@ -1950,6 +2290,52 @@ fn verify_builder_bid<T: EthSpec, Payload: AbstractExecPayload<T>>(
     }
 }
 
+/// Reads the JWT secret from `secret_file`, or generates a new secret and writes it to
+/// `secret_file` if the file does not exist.
+fn load_or_create_jwt_secret(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    }
+}
+
+/// Records the duration and outcome of a request to the builder at `relay`.
+fn observe_builder_request(relay: &str, method: &str, duration: Duration, outcome: &str) {
+    metrics::observe_timer_vec(
+        &metrics::EXECUTION_LAYER_BUILDER_REQUEST_TIMES,
+        &[relay, method],
+        duration,
+    );
+    metrics::inc_counter_vec(
+        &metrics::EXECUTION_LAYER_BUILDER_REQUEST_OUTCOME,
+        &[relay, method, outcome],
+    );
+}
+
 /// A helper function to record the time it takes to execute a future.
 async fn timed_future<F: Future<Output = T>, T>(metric: &str, future: F) -> (T, Duration) {
     let start = Instant::now();
@@ -2001,7 +2387,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -2028,7 +2414,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -2056,7 +2442,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.is_valid_terminal_pow_block_hash(terminal_block.unwrap().block_hash, &spec)
                         .await
@@ -2073,7 +2459,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 let invalid_terminal_block = terminal_block.unwrap().parent_hash;
 
                 assert_eq!(
@@ -2092,7 +2478,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 let missing_terminal_block = ExecutionBlockHash::repeat_byte(42);
 
                 assert_eq!(
@@ -2106,6 +2492,264 @@ mod test {
     }
 }
 
+#[cfg(test)]
+mod builder_test {
+    use super::*;
+    use crate::test_utils::DEFAULT_JWT_SECRET;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use task_executor::test_utils::TestRuntime;
+    use tempfile::NamedTempFile;
+    use tokio::sync::oneshot;
+    use types::{
+        Address, BeaconBlock, ExecutionPayloadHeaderMerge, Hash256, Keypair, MainnetEthSpec,
+        SignedRoot,
+    };
+    use warp::{http::StatusCode, Filter, Reply};
+
+    type E = MainnetEthSpec;
+
+    const TIMESTAMP: u64 = 42;
+
+    fn parent_hash() -> ExecutionBlockHash {
+        ExecutionBlockHash::repeat_byte(1)
+    }
+
+    fn prev_randao() -> Hash256 {
+        Hash256::repeat_byte(2)
+    }
+
+    /// Returns a signed Bellatrix bid of `value` wei, building on `parent_hash`.
+    fn bid(
+        value: u64,
+        parent_hash: ExecutionBlockHash,
+        spec: &ChainSpec,
+    ) -> BuilderBid<E, BlindedPayload<E>> {
+        let keypair = Keypair::random();
+        let header = ExecutionPayloadHeaderMerge::<E> {
+            parent_hash,
+            prev_randao: prev_randao(),
+            timestamp: TIMESTAMP,
+            ..<_>::default()
+        };
+        let mut bid: BuilderBid<E, BlindedPayload<E>> = serde_json::from_value(json!({
+            "version": "bellatrix",
+            "data": {
+                "message": {
+                    "header": header,
+                    "value": value.to_string(),
+                    "pubkey": keypair.pk.compress(),
+                },
+                "signature": Signature::empty(),
+            },
+        }))
+        .unwrap();
+        let signing_root = bid.data.message.signing_root(spec.get_builder_domain());
+        bid.data.signature = keypair.sk.sign(signing_root);
+        bid
+    }
+
+    /// A relay which serves a fixed bid and records the registrations and reveals it receives.
+    ///
+    /// Reveals always fail, since the relay has no payloads.
+    struct MockRelay {
+        url: SensitiveUrl,
+        registrations: Arc<AtomicUsize>,
+        reveals: Arc<AtomicUsize>,
+        _server_shutdown: oneshot::Sender<()>,
+    }
+
+    impl MockRelay {
+        fn new(bid: Option<BuilderBid<E, BlindedPayload<E>>>, accept_registrations: bool) -> Self {
+            let registrations = Arc::new(AtomicUsize::new(0));
+            let reveals = Arc::new(AtomicUsize::new(0));
+
+            let get_header = warp::get()
+                .and(warp::path!(
+                    "eth" / "v1" / "builder" / "header" / u64 / String / String
+                ))
+                .map(move |_slot, _parent_hash, _pubkey| match &bid {
+                    Some(bid) => warp::reply::json(bid).into_response(),
+                    None => StatusCode::NO_CONTENT.into_response(),
+                });
+            let post_validators = {
+                let registrations = registrations.clone();
+                warp::post()
+                    .and(warp::path!("eth" / "v1" / "builder" / "validators"))
+                    .map(move || {
+                        registrations.fetch_add(1, Ordering::SeqCst);
+                        if accept_registrations {
+                            StatusCode::OK
+                        } else {
+                            StatusCode::BAD_REQUEST
+                        }
+                    })
+            };
+            let post_blinded_blocks = {
+                let reveals = reveals.clone();
+                warp::post()
+                    .and(warp::path!("eth" / "v1" / "builder" / "blinded_blocks"))
+                    .map(move || {
+                        reveals.fetch_add(1, Ordering::SeqCst);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+            };
+
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
+            let (listening_socket, server) =
+                warp::serve(get_header.or(post_validators).or(post_blinded_blocks))
+                    .bind_with_graceful_shutdown(([127, 0, 0, 1], 0), async {
+                        let _ = shutdown_rx.await;
+                    });
+            tokio::spawn(server);
+
+            Self {
+                url: SensitiveUrl::parse(&format!("http://{}", listening_socket)).unwrap(),
+                registrations,
+                reveals,
+                _server_shutdown: shutdown_tx,
+            }
+        }
+
+        fn registrations(&self) -> usize {
+            self.registrations.load(Ordering::SeqCst)
+        }
+
+        fn reveals(&self) -> usize {
+            self.reveals.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Returns an execution layer connected to each of `relays`, in order.
+    fn execution_layer(relays: &[MockRelay], executor: TaskExecutor) -> ExecutionLayer<E> {
+        let jwt_secret_file = NamedTempFile::new().unwrap();
+        std::fs::write(jwt_secret_file.path(), hex::encode(DEFAULT_JWT_SECRET)).unwrap();
+
+        let config = Config {
+            // The execution engine is never contacted.
+            execution_endpoints: vec![SensitiveUrl::parse("http://127.0.0.1:1").unwrap()],
+            builder_urls: relays.iter().map(|relay| relay.url.clone()).collect(),
+            secret_files: vec![jwt_secret_file.path().into()],
+            ..Default::default()
+        };
+        ExecutionLayer::from_config(config, executor.clone(), executor.log().clone()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn selects_best_bid_across_relays() {
+        let runtime = TestRuntime::default();
+        let spec = E::default_spec();
+        let relays = vec![
+            MockRelay::new(Some(bid(1, parent_hash(), &spec)), true),
+            MockRelay::new(Some(bid(3, parent_hash(), &spec)), true),
+            MockRelay::new(None, true),
+            MockRelay::new(Some(bid(2, parent_hash(), &spec)), true),
+            // The most valuable bid is invalid, so it must be ignored.
+            MockRelay::new(
+                Some(bid(10, ExecutionBlockHash::repeat_byte(9), &spec)),
+                true,
+            ),
+        ];
+        let el = execution_layer(&relays, runtime.task_executor.clone());
+
+        let results = el
+            .get_builder_headers::<BlindedPayload<E>>(
+                Slot::new(1),
+                parent_hash(),
+                &PublicKeyBytes::empty(),
+            )
+            .await;
+        assert_eq!(results.len(), relays.len());
+
+        let payload_attributes =
+            PayloadAttributes::new(TIMESTAMP, prev_randao(), Address::zero(), None);
+        let (builder_index, best_bid) = el
+            .select_builder_bid(
+                results,
+                parent_hash(),
+                &payload_attributes,
+                None,
+                ForkName::Merge,
+                &spec,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(builder_index, 1);
+        assert_eq!(best_bid.data.message.value, Uint256::from(3));
+    }
+
+    #[tokio::test]
+    async fn reveals_payload_to_winning_relay() {
+        let runtime = TestRuntime::default();
+        let mut spec = E::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(0));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+        let relays = vec![
+            MockRelay::new(None, true),
+            MockRelay::new(None, true),
+            MockRelay::new(None, true),
+        ];
+        let el = execution_layer(&relays, runtime.task_executor.clone());
+
+        let block = SignedBeaconBlock::<E, BlindedPayload<E>>::from_block(
+            BeaconBlock::empty(&spec),
+            Signature::empty(),
+        );
+        let block_hash = block.message().execution_payload().unwrap().block_hash();
+
+        // The payload is only revealed by the relay which provided the bid.
+        el.inner
+            .builder_payload_sources
+            .lock()
+            .await
+            .put(block_hash, 1);
+        assert!(el
+            .propose_blinded_beacon_block(Hash256::zero(), &block)
+            .await
+            .is_err());
+        let reveals = relays.iter().map(MockRelay::reveals).collect::<Vec<_>>();
+        assert_eq!(reveals, vec![0, 1, 0]);
+
+        // If the relay is unknown, each relay is tried in turn.
+        el.inner
+            .builder_payload_sources
+            .lock()
+            .await
+            .pop(&block_hash);
+        assert!(el
+            .propose_blinded_beacon_block(Hash256::zero(), &block)
+            .await
+            .is_err());
+        let reveals = relays.iter().map(MockRelay::reveals).collect::<Vec<_>>();
+        assert_eq!(reveals, vec![1, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn registers_with_every_relay() {
+        let runtime = TestRuntime::default();
+        let relays = vec![
+            MockRelay::new(None, true),
+            MockRelay::new(None, false),
+            MockRelay::new(None, true),
+        ];
+        let el = execution_layer(&relays, runtime.task_executor.clone());
+
+        // A single relay rejecting the registrations is reported, but the others still receive
+        // them.
+        assert!(el.post_builder_validators(&[]).await.is_err());
+        let registrations = relays
+            .iter()
+            .map(MockRelay::registrations)
+            .collect::<Vec<_>>();
+        assert_eq!(registrations, vec![1, 1, 1]);
+
+        let relays = vec![MockRelay::new(None, true), MockRelay::new(None, true)];
+        let el = execution_layer(&relays, runtime.task_executor.clone());
+        assert!(el.post_builder_validators(&[]).await.is_ok());
+        assert!(relays.iter().all(|relay| relay.registrations() == 1));
+    }
+}
+
 fn noop<T: EthSpec>(
     _: &ExecutionLayer<T>,
     _: ExecutionPayloadRef<T>,
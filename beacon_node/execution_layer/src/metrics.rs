@@ -7,6 +7,9 @@ pub const GET_BLINDED_PAYLOAD: &str = "get_blinded_payload";
 pub const GET_BLINDED_PAYLOAD_LOCAL: &str = "get_blinded_payload_local";
 pub const GET_BLINDED_PAYLOAD_BUILDER: &str = "get_blinded_payload_builder";
 pub const POST_BLINDED_PAYLOAD_BUILDER: &str = "post_blinded_payload_builder";
+pub const GET_BUILDER_HEADER: &str = "get_builder_header";
+pub const POST_BUILDER_BLINDED_BLOCKS: &str = "post_builder_blinded_blocks";
+pub const POST_BUILDER_VALIDATORS: &str = "post_builder_validators";
 pub const NEW_PAYLOAD: &str = "new_payload";
 pub const FORKCHOICE_UPDATED: &str = "forkchoice_updated";
 pub const GET_TERMINAL_POW_BLOCK_HASH: &str = "get_terminal_pow_block_hash";
@@ -15,6 +18,7 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const NO_BID: &str = "no_bid";
 
 lazy_static::lazy_static! {
     pub static ref EXECUTION_LAYER_PROPOSER_INSERTED: Result<IntCounter> = try_create_int_counter(
@@ -84,4 +88,21 @@ lazy_static::lazy_static! {
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::max_value.",
         &["source"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_REQUEST_TIMES: Result<HistogramVec> =
+        try_create_histogram_vec_with_buckets(
+        "execution_layer_builder_request_times",
+        "Duration of calls to each builder relay",
+        decimal_buckets(-2, 1),
+        &["relay", "method"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_REQUEST_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_request_outcome",
+        "The outcomes of calls to each builder relay",
+        &["relay", "method", "outcome"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_BIDS: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "execution_layer_builder_bids",
+        "The gwei bid value of payloads received from each builder relay. Only shows values up to i64::max_value.",
+        &["relay"]
+    );
 }
@@ -1,6 +1,7 @@
 use crate::engine_api::{Error as ApiError, PayloadStatusV1, PayloadStatusV1Status};
 use crate::engines::EngineError;
-use slog::{warn, Logger};
+use crate::Error;
+use slog::{crit, debug, warn, Logger};
 use types::ExecutionBlockHash;
 
 /// Provides a simpler, easier to parse version of `PayloadStatusV1` for upstream users.
@@ -108,3 +109,199 @@ pub fn process_payload_status(
         },
     }
 }
+
+/// Processes the responses from several execution engines to the same request.
+///
+/// A `Valid` or `Invalid` status is only returned once at least `quorum` engines agree on it.
+/// Otherwise the first `Syncing` or `Accepted` status is returned, falling back to `Syncing` if
+/// some engines gave a definitive status but too few to reach the quorum. With a quorum of 1 the
+/// first valid response is used.
+///
+/// If some engines return valid and others invalid, `Error::ConsensusFailure` is returned. An
+/// error is also returned if every engine failed.
+pub fn process_multiple_payload_statuses(
+    head_block_hash: ExecutionBlockHash,
+    statuses: impl Iterator<Item = Result<PayloadStatusV1, EngineError>>,
+    quorum: usize,
+    log: &Logger,
+) -> Result<PayloadStatus, Error> {
+    let mut errors = vec![];
+    let mut valid_statuses = vec![];
+    let mut invalid_statuses = vec![];
+    let mut other_statuses = vec![];
+
+    for status in statuses {
+        match process_payload_status(head_block_hash, status, log) {
+            Ok(status @ PayloadStatus::Valid) => valid_statuses.push(status),
+            Ok(status @ PayloadStatus::Invalid { .. })
+            | Ok(status @ PayloadStatus::InvalidBlockHash { .. }) => invalid_statuses.push(status),
+            Ok(status @ PayloadStatus::Syncing) | Ok(status @ PayloadStatus::Accepted) => {
+                other_statuses.push(status)
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if !valid_statuses.is_empty() && !invalid_statuses.is_empty() {
+        crit!(
+            log,
+            "Consensus failure between execution nodes";
+            "invalid_statuses" => ?invalid_statuses,
+            "valid_statuses" => ?valid_statuses,
+        );
+
+        // Choose to exit and ignore the valid response. This prefers correctness over liveness.
+        return Err(Error::ConsensusFailure);
+    }
+
+    if valid_statuses.len() >= quorum {
+        return Ok(PayloadStatus::Valid);
+    }
+    if invalid_statuses.len() >= quorum {
+        return Ok(invalid_statuses.swap_remove(0));
+    }
+    if let Some(status) = other_statuses.into_iter().next() {
+        return Ok(status);
+    }
+    if !valid_statuses.is_empty() || !invalid_statuses.is_empty() {
+        debug!(
+            log,
+            "Execution engine quorum not reached";
+            "quorum" => quorum,
+            "valid" => valid_statuses.len(),
+            "invalid" => invalid_statuses.len(),
+        );
+        return Ok(PayloadStatus::Syncing);
+    }
+
+    Err(errors
+        .into_iter()
+        .next()
+        .map_or(Error::NoEngine, |error| Error::EngineError(Box::new(error))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use environment::null_logger;
+
+    fn head() -> ExecutionBlockHash {
+        ExecutionBlockHash::repeat_byte(1)
+    }
+
+    fn status(status: PayloadStatusV1Status) -> Result<PayloadStatusV1, EngineError> {
+        let latest_valid_hash = match status {
+            PayloadStatusV1Status::Valid => Some(head()),
+            PayloadStatusV1Status::Invalid => Some(ExecutionBlockHash::repeat_byte(2)),
+            _ => None,
+        };
+        Ok(PayloadStatusV1 {
+            status,
+            latest_valid_hash,
+            validation_error: None,
+        })
+    }
+
+    fn valid() -> Result<PayloadStatusV1, EngineError> {
+        status(PayloadStatusV1Status::Valid)
+    }
+
+    fn invalid() -> Result<PayloadStatusV1, EngineError> {
+        status(PayloadStatusV1Status::Invalid)
+    }
+
+    fn syncing() -> Result<PayloadStatusV1, EngineError> {
+        status(PayloadStatusV1Status::Syncing)
+    }
+
+    fn offline() -> Result<PayloadStatusV1, EngineError> {
+        Err(EngineError::Offline)
+    }
+
+    fn process(
+        statuses: Vec<Result<PayloadStatusV1, EngineError>>,
+        quorum: usize,
+    ) -> Result<PayloadStatus, Error> {
+        process_multiple_payload_statuses(
+            head(),
+            statuses.into_iter(),
+            quorum,
+            &null_logger().unwrap(),
+        )
+    }
+
+    #[test]
+    fn quorum_reached() {
+        assert_eq!(
+            process(vec![valid(), syncing(), valid()], 2).unwrap(),
+            PayloadStatus::Valid
+        );
+        assert!(matches!(
+            process(vec![invalid(), invalid(), offline()], 2).unwrap(),
+            PayloadStatus::Invalid {
+                latest_valid_hash: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn quorum_not_reached() {
+        // A syncing engine provides the status whilst the quorum is not reached.
+        assert_eq!(
+            process(vec![valid(), syncing(), offline()], 2).unwrap(),
+            PayloadStatus::Syncing
+        );
+        // Otherwise the payload is treated as syncing.
+        assert_eq!(
+            process(vec![valid(), offline(), offline()], 2).unwrap(),
+            PayloadStatus::Syncing
+        );
+        assert_eq!(
+            process(vec![invalid(), offline()], 2).unwrap(),
+            PayloadStatus::Syncing
+        );
+        // A valid status with the wrong `latest_valid_hash` is an error, not a vote.
+        let bad_valid = Ok(PayloadStatusV1 {
+            status: PayloadStatusV1Status::Valid,
+            latest_valid_hash: Some(ExecutionBlockHash::repeat_byte(3)),
+            validation_error: None,
+        });
+        assert_eq!(
+            process(vec![valid(), bad_valid], 2).unwrap(),
+            PayloadStatus::Syncing
+        );
+    }
+
+    #[test]
+    fn consensus_failure() {
+        // Disagreement is fatal even when the quorum is reached by one side.
+        assert!(matches!(
+            process(vec![valid(), invalid()], 1),
+            Err(Error::ConsensusFailure)
+        ));
+        assert!(matches!(
+            process(vec![valid(), valid(), invalid()], 2),
+            Err(Error::ConsensusFailure)
+        ));
+    }
+
+    #[test]
+    fn failover() {
+        // With a quorum of one, any engine which responds is used.
+        assert_eq!(
+            process(vec![offline(), valid()], 1).unwrap(),
+            PayloadStatus::Valid
+        );
+        assert_eq!(
+            process(vec![Err(EngineError::Timeout), syncing()], 1).unwrap(),
+            PayloadStatus::Syncing
+        );
+        // The first error is returned when every engine fails.
+        assert!(matches!(
+            process(vec![Err(EngineError::Timeout), offline()], 1),
+            Err(Error::EngineError(error)) if matches!(*error, EngineError::Timeout)
+        ));
+        assert!(matches!(process(vec![], 1), Err(Error::NoEngine)));
+    }
+}
@@ -6,6 +6,7 @@ use crate::engine_api::{
 };
 use bytes::Bytes;
 use environment::null_logger;
+use eth2::lighthouse::ExecutionClientVersion;
 use execution_block_generator::PoWBlock;
 use handle_rpc::handle_rpc;
 use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
@@ -46,6 +47,16 @@ pub const DEFAULT_ENGINE_CAPABILITIES: EngineCapabilities = EngineCapabilities {
     exchange_transition_configuration_v1: true,
 };
 
+/// The version returned by the mock execution engine from `engine_getClientVersionV1`.
+pub fn mock_client_version() -> ExecutionClientVersion {
+    ExecutionClientVersion {
+        code: "MK".to_string(),
+        name: "Mock".to_string(),
+        version: "v1.0.0".to_string(),
+        commit: "0x00000000".to_string(),
+    }
+}
+
 mod execution_block_generator;
 mod handle_rpc;
 mod hook;
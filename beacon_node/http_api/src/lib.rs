@@ -11,7 +11,9 @@ mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod database;
+mod light_client;
 mod metrics;
+mod peer_admin;
 mod proposer_duties;
 mod publish_blocks;
 mod standard_block_rewards;
@@ -25,7 +27,7 @@ mod version;
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
     validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, ProduceBlockVerification, WhenSlotSkipped,
+    BeaconChainTypes, ExecutionStatus, ForkChoiceStore, ProduceBlockVerification, WhenSlotSkipped,
 };
 pub use block_id::BlockId;
 use directory::DEFAULT_ROOT_DIR;
@@ -44,6 +46,7 @@ use slot_clock::SlotClock;
 use ssz::Encode;
 pub use state_id::StateId;
 use std::borrow::Cow;
+use std::fs;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
@@ -56,7 +59,7 @@ use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{
     Attestation, AttestationData, AttestationShufflingId, AttesterSlashing, BeaconStateError,
     BlindedPayload, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName, FullPayload,
-    ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
+    Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
     SignedBeaconBlock, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
     SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
     SyncCommitteeMessage, SyncContributionData,
@@ -120,6 +123,10 @@ pub struct Config {
     pub allow_sync_stalled: bool,
     pub spec_fork_name: Option<ForkName>,
     pub data_dir: PathBuf,
+    /// The file containing the token which authorizes requests to the peer administration
+    /// endpoints, which are disabled if `None`.
+    #[serde(default)]
+    pub admin_token_path: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -133,6 +140,7 @@ impl Default for Config {
             allow_sync_stalled: false,
             spec_fork_name: None,
             data_dir: PathBuf::from(DEFAULT_ROOT_DIR),
+            admin_token_path: None,
         }
     }
 }
@@ -279,8 +287,8 @@ pub fn serve<T: BeaconChainTypes>(
     // Configure CORS.
     let cors_builder = {
         let builder = warp::cors()
-            .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_methods(vec!["GET", "POST", "DELETE"])
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -338,6 +346,29 @@ pub fn serve<T: BeaconChainTypes>(
     let inner_data_dir = ctx.config.data_dir.clone();
     let data_dir_filter = warp::any().map(move || inner_data_dir.clone());
 
+    // Create a `warp` filter that authorizes requests to the peer administration endpoints.
+    let admin_token = match &config.admin_token_path {
+        Some(path) => {
+            let token = fs::read_to_string(path).map_err(|e| {
+                Error::Other(format!(
+                    "Unable to read admin token from {}: {:?}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            let token = token.trim().to_string();
+            if token.is_empty() {
+                return Err(Error::Other(format!(
+                    "Admin token file {} is empty",
+                    path.display()
+                )));
+            }
+            Some(token)
+        }
+        None => None,
+    };
+    let admin_auth_filter = peer_admin::admin_auth_filter(admin_token);
+
     // Create a `warp` filter that provides access to the beacon chain.
     let inner_ctx = ctx.clone();
     let chain_filter =
@@ -1885,6 +1916,91 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    /*
+     * beacon/light_client
+     */
+
+    let beacon_light_client_path = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("light_client"))
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| async move {
+            if chain.config.enable_light_client_server {
+                Ok(chain)
+            } else {
+                Err(warp_utils::reject::custom_not_found(
+                    "The light client server is not enabled. Use --light-client-server on the CLI."
+                        .to_string(),
+                ))
+            }
+        });
+
+    // GET beacon/light_client/bootstrap/{block_root}
+    let get_beacon_light_client_bootstrap = beacon_light_client_path
+        .clone()
+        .and(warp::path("bootstrap"))
+        .and(warp::path::param::<Hash256>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid block root value".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>,
+             block_root: Hash256,
+             accept_header: Option<api_types::Accept>| {
+                blocking_response_task(move || {
+                    light_client::get_light_client_bootstrap(chain, block_root, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/updates?start_period,count
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                blocking_response_task(move || {
+                    light_client::get_light_client_updates(chain, query, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/finality_update
+    let get_beacon_light_client_finality_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("finality_update"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, accept_header: Option<api_types::Accept>| {
+                blocking_response_task(move || {
+                    light_client::get_light_client_finality_update(chain, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/optimistic_update
+    let get_beacon_light_client_optimistic_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("optimistic_update"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, accept_header: Option<api_types::Accept>| {
+                blocking_response_task(move || {
+                    light_client::get_light_client_optimistic_update(chain, accept_header)
+                })
+            },
+        );
+
     let beacon_rewards_path = eth_v1
         .and(warp::path("beacon"))
         .and(warp::path("rewards"))
@@ -2148,6 +2264,63 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET debug/fork_choice
+    let get_debug_fork_choice = eth_v1
+        .and(warp::path("debug"))
+        .and(warp::path("fork_choice"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                let fork_choice = chain.canonical_head.fork_choice_read_lock();
+                let proto_array = fork_choice.proto_array().core_proto_array();
+
+                let fork_choice_nodes = proto_array
+                    .nodes
+                    .iter()
+                    .map(|node| {
+                        let validity = match node.execution_status {
+                            ExecutionStatus::Valid(_) | ExecutionStatus::Irrelevant(_) => {
+                                api_types::ForkChoiceNodeValidity::Valid
+                            }
+                            ExecutionStatus::Invalid(_) => {
+                                api_types::ForkChoiceNodeValidity::Invalid
+                            }
+                            ExecutionStatus::Optimistic(_) => {
+                                api_types::ForkChoiceNodeValidity::Optimistic
+                            }
+                        };
+                        api_types::ForkChoiceNode {
+                            slot: node.slot,
+                            block_root: node.root,
+                            parent_root: node
+                                .parent
+                                .and_then(|index| proto_array.nodes.get(index))
+                                .map(|parent| parent.root),
+                            justified_epoch: node
+                                .justified_checkpoint
+                                .map(|checkpoint| checkpoint.epoch),
+                            finalized_epoch: node
+                                .finalized_checkpoint
+                                .map(|checkpoint| checkpoint.epoch),
+                            weight: node.weight,
+                            validity,
+                            execution_block_hash: node
+                                .execution_status
+                                .block_hash()
+                                .map(|block_hash| block_hash.into_root()),
+                        }
+                    })
+                    .collect();
+
+                Ok(api_types::ForkChoice {
+                    justified_checkpoint: *fork_choice.fc_store().justified_checkpoint(),
+                    finalized_checkpoint: *fork_choice.fc_store().finalized_checkpoint(),
+                    fork_choice_nodes,
+                })
+            })
+        });
+
     /*
      * node
      */
@@ -2161,7 +2334,8 @@ pub fn serve<T: BeaconChainTypes>(
         .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
             blocking_json_task(move || {
                 let enr = network_globals.local_enr();
-                let p2p_addresses = enr.multiaddr_p2p_tcp();
+                let mut p2p_addresses = enr.multiaddr_p2p_tcp();
+                p2p_addresses.extend(enr.multiaddr_p2p_quic());
                 let discovery_addresses = enr.multiaddr_p2p_udp();
                 let meta_data = network_globals.local_metadata.read();
                 Ok(api_types::GenericResponse::from(api_types::IdentityData {
@@ -3032,19 +3206,20 @@ pub fn serve<T: BeaconChainTypes>(
                         ))
                     })?;
 
-                let builder = execution_layer
-                    .builder()
-                    .as_ref()
-                    .ok_or(BeaconChainError::BuilderMissing)
-                    .map_err(warp_utils::reject::beacon_chain_error)?;
+                if !execution_layer.has_builder() {
+                    return Err(warp_utils::reject::beacon_chain_error(
+                        BeaconChainError::BuilderMissing,
+                    ));
+                }
 
                 info!(
                     log,
-                    "Forwarding register validator request to connected builder";
+                    "Forwarding register validator request to connected builders";
                     "count" => filtered_registration_data.len(),
+                    "builders" => execution_layer.builders().len(),
                 );
 
-                builder
+                execution_layer
                     .post_builder_validators(&filtered_registration_data)
                     .await
                     .map(|resp| warp::reply::json(&resp).into_response())
@@ -3057,7 +3232,10 @@ pub fn serve<T: BeaconChainTypes>(
                         );
                         // Forward the HTTP status code if we are able to, otherwise fall back
                         // to a server error.
-                        if let eth2::Error::ServerMessage(message) = e {
+                        if let execution_layer::Error::Builder(eth2::Error::ServerMessage(
+                            message,
+                        )) = e
+                        {
                             if message.code == StatusCode::BAD_REQUEST.as_u16() {
                                 return warp_utils::reject::custom_bad_request(message.message);
                             } else {
@@ -3288,7 +3466,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("peers"))
         .and(warp::path("connected"))
         .and(warp::path::end())
-        .and(network_globals)
+        .and(network_globals.clone())
         .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
             blocking_json_task(move || {
                 Ok(network_globals
@@ -3303,6 +3481,138 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/peers/bans
+    let get_lighthouse_peers_bans = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+        .and(network_globals.clone())
+        .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+            blocking_json_task(move || {
+                Ok(api_types::GenericResponse::from(peer_admin::peer_bans(
+                    &network_globals,
+                )))
+            })
+        });
+
+    // POST lighthouse/peers/dial
+    let post_lighthouse_peers_dial = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("dial"))
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(warp::body::json())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::DialPeerRequest,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let message = peer_admin::dial_message(&request.address)?;
+                    publish_network_message(&network_tx, message)
+                })
+            },
+        );
+
+    // DELETE lighthouse/peers/{peer_id}
+    let delete_lighthouse_peer = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |peer_id: String, network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = peer_admin::parse_peer_id(&peer_id)?;
+                    publish_network_message(&network_tx, NetworkMessage::DisconnectPeer(peer_id))
+                })
+            },
+        );
+
+    // POST lighthouse/peers/bans
+    let post_lighthouse_peers_bans = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(warp::body::json())
+        .and(network_globals)
+        .and(network_tx_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::BanRequest,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let message = peer_admin::ban_message(&request, &network_globals)?;
+                    publish_network_message(&network_tx, message)
+                })
+            },
+        );
+
+    // DELETE lighthouse/peers/bans/{peer_id_or_ip}
+    let delete_lighthouse_peers_ban = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |target: String, network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let message = peer_admin::unban_message(&target)?;
+                    publish_network_message(&network_tx, message)
+                })
+            },
+        );
+
+    // POST lighthouse/peers/trusted
+    let post_lighthouse_peers_trusted = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("trusted"))
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(warp::body::json())
+        .and(network_tx_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::TrustedPeerRequest,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = peer_admin::parse_peer_id(&request.peer_id)?;
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::SetTrustedPeer {
+                            peer_id,
+                            trusted: true,
+                        },
+                    )
+                })
+            },
+        );
+
+    // DELETE lighthouse/peers/trusted/{peer_id}
+    let delete_lighthouse_peers_trusted = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("trusted"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(admin_auth_filter)
+        .and(network_tx_filter)
+        .and_then(
+            |peer_id: String, network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let peer_id = peer_admin::parse_peer_id(&peer_id)?;
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::SetTrustedPeer {
+                            peer_id,
+                            trusted: false,
+                        },
+                    )
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -3570,6 +3880,31 @@ pub fn serve<T: BeaconChainTypes>(
             )
         });
 
+    // GET lighthouse/execution_client_version
+    let get_lighthouse_execution_client_version = warp::path("lighthouse")
+        .and(warp::path("execution_client_version"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| async move {
+            let execution_layer = chain
+                .execution_layer
+                .as_ref()
+                .ok_or(BeaconChainError::ExecutionLayerMissing)
+                .map_err(warp_utils::reject::beacon_chain_error)?;
+
+            let client_versions = execution_layer.get_client_version().await.map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "unable to fetch execution client version: {:?}",
+                    e
+                ))
+            })?;
+
+            Ok::<_, warp::reject::Rejection>(
+                warp::reply::json(&api_types::GenericResponse::from(client_versions))
+                    .into_response(),
+            )
+        });
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -3671,11 +4006,16 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_beacon_pool_bls_to_execution_changes)
                 .uor(get_beacon_deposit_snapshot)
                 .uor(get_beacon_rewards_blocks)
+                .uor(get_beacon_light_client_bootstrap)
+                .uor(get_beacon_light_client_updates)
+                .uor(get_beacon_light_client_finality_update)
+                .uor(get_beacon_light_client_optimistic_update)
                 .uor(get_config_fork_schedule)
                 .uor(get_config_spec)
                 .uor(get_config_deposit_contract)
                 .uor(get_debug_beacon_states)
                 .uor(get_debug_beacon_heads)
+                .uor(get_debug_fork_choice)
                 .uor(get_node_identity)
                 .uor(get_node_version)
                 .uor(get_node_syncing)
@@ -3696,6 +4036,7 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peers_bans)
                 .uor(get_lighthouse_proto_array)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
@@ -3709,6 +4050,7 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_attestation_performance)
                 .uor(get_lighthouse_block_packing_efficiency)
                 .uor(get_lighthouse_merge_readiness)
+                .uor(get_lighthouse_execution_client_version)
                 .uor(get_events)
                 .recover(warp_utils::reject::handle_rejection),
         )
@@ -3739,6 +4081,17 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_lighthouse_block_rewards)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
+                    .uor(post_lighthouse_peers_dial)
+                    .uor(post_lighthouse_peers_bans)
+                    .uor(post_lighthouse_peers_trusted)
+                    .recover(warp_utils::reject::handle_rejection),
+            ),
+        )
+        .uor(
+            warp::delete().and(
+                delete_lighthouse_peer
+                    .uor(delete_lighthouse_peers_ban)
+                    .uor(delete_lighthouse_peers_trusted)
                     .recover(warp_utils::reject::handle_rejection),
             ),
         )
@@ -0,0 +1,146 @@
+use crate::version::add_consensus_version_header;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::{self as api_types, ForkVersionedResponse};
+use lighthouse_network::rpc::methods::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+use serde::Serialize;
+use ssz::Encode;
+use std::sync::Arc;
+use types::{ChainSpec, ForkName, Hash256};
+use warp::{
+    http::Response,
+    reply::{Reply, Response as WarpResponse},
+};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_not_found};
+
+/// `GET beacon/light_client/bootstrap/{block_root}`
+pub fn get_light_client_bootstrap<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_root: Hash256,
+    accept_header: Option<api_types::Accept>,
+) -> Result<WarpResponse, warp::Rejection> {
+    let bootstrap = chain
+        .get_light_client_bootstrap(&block_root)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| {
+            custom_not_found(format!(
+                "no light client bootstrap for block {:?}, it is unknown or precedes Altair",
+                block_root
+            ))
+        })?;
+    let fork_name = chain
+        .spec
+        .fork_name_at_slot::<T::EthSpec>(bootstrap.header.slot);
+    versioned_response(fork_name, bootstrap, accept_header)
+}
+
+/// `GET beacon/light_client/updates?start_period,count`
+pub fn get_light_client_updates<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    query: api_types::LightClientUpdatesQuery,
+    accept_header: Option<api_types::Accept>,
+) -> Result<WarpResponse, warp::Rejection> {
+    if query.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+        return Err(custom_bad_request(format!(
+            "count must not exceed {}",
+            MAX_REQUEST_LIGHT_CLIENT_UPDATES
+        )));
+    }
+    let updates = chain
+        .get_light_client_updates(query.start_period, query.count)
+        .map_err(beacon_chain_error)?;
+
+    match accept_header {
+        Some(api_types::Accept::Ssz) => {
+            // Each update is sent as a chunk prefixed by its length and fork digest, matching
+            // the framing of the equivalent Req/Resp method.
+            let mut bytes = vec![];
+            for update in &updates {
+                let fork_name = chain
+                    .spec
+                    .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot);
+                let fork_digest = ChainSpec::compute_fork_digest(
+                    chain.spec.fork_version_for_name(fork_name),
+                    chain.genesis_validators_root,
+                );
+                let update_bytes = update.as_ssz_bytes();
+                let chunk_len = (fork_digest.len() + update_bytes.len()) as u64;
+                bytes.extend_from_slice(&chunk_len.to_le_bytes());
+                bytes.extend_from_slice(&fork_digest);
+                bytes.extend_from_slice(&update_bytes);
+            }
+            ssz_response(bytes)
+        }
+        _ => {
+            let updates = updates
+                .into_iter()
+                .map(|update| ForkVersionedResponse {
+                    version: Some(
+                        chain
+                            .spec
+                            .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot),
+                    ),
+                    data: update,
+                })
+                .collect::<Vec<_>>();
+            Ok(warp::reply::json(&updates).into_response())
+        }
+    }
+}
+
+/// `GET beacon/light_client/finality_update`
+pub fn get_light_client_finality_update<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<WarpResponse, warp::Rejection> {
+    let update = chain
+        .light_client_server_cache
+        .get_latest_finality_update()
+        .ok_or_else(|| custom_not_found("no light client finality update available".to_string()))?;
+    let fork_name = chain
+        .spec
+        .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot);
+    versioned_response(fork_name, update, accept_header)
+}
+
+/// `GET beacon/light_client/optimistic_update`
+pub fn get_light_client_optimistic_update<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<WarpResponse, warp::Rejection> {
+    let update = chain
+        .light_client_server_cache
+        .get_latest_optimistic_update()
+        .ok_or_else(|| {
+            custom_not_found("no light client optimistic update available".to_string())
+        })?;
+    let fork_name = chain
+        .spec
+        .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot);
+    versioned_response(fork_name, update, accept_header)
+}
+
+fn versioned_response<T: Serialize + Encode>(
+    fork_name: ForkName,
+    data: T,
+    accept_header: Option<api_types::Accept>,
+) -> Result<WarpResponse, warp::Rejection> {
+    match accept_header {
+        Some(api_types::Accept::Ssz) => ssz_response(data.as_ssz_bytes()),
+        _ => Ok(warp::reply::json(&ForkVersionedResponse {
+            version: Some(fork_name),
+            data,
+        })
+        .into_response()),
+    }
+    .map(|resp| add_consensus_version_header(resp, fork_name))
+}
+
+fn ssz_response(bytes: Vec<u8>) -> Result<WarpResponse, warp::Rejection> {
+    Response::builder()
+        .status(200)
+        .header("Content-Type", "application/octet-stream")
+        .body(bytes.into())
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+        })
+}
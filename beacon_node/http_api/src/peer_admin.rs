@@ -0,0 +1,136 @@
+//! Helpers for the `lighthouse/peers` administration endpoints, which allow peers to be dialed,
+//! disconnected, banned and trusted whilst the node is running.
+
+use eth2::lighthouse::{BanRequest, BannedIp, BannedPeer, PeerBans};
+use lighthouse_network::{Enr, Multiaddr, NetworkGlobals, PeerId, MAX_BAN_DURATION};
+use network::NetworkMessage;
+use ring::constant_time::verify_slices_are_equal;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use types::EthSpec;
+use warp::filters::BoxedFilter;
+use warp::Filter;
+
+/// Returns a filter which rejects requests without an `Authorization: Bearer <token>` header
+/// matching `admin_token`, or all requests if there is no `admin_token`.
+pub fn admin_auth_filter(admin_token: Option<String>) -> BoxedFilter<()> {
+    let expected = admin_token.map(|token| format!("Bearer {}", token));
+    warp::any()
+        .map(move || expected.clone())
+        .and(warp::filters::header::optional::<String>("Authorization"))
+        .and_then(
+            |expected: Option<String>, header: Option<String>| async move {
+                let expected = expected.ok_or_else(|| {
+                    warp_utils::reject::custom_not_found(
+                        "peer administration is disabled, see --http-admin-token-file".to_string(),
+                    )
+                })?;
+                let header = header.ok_or_else(|| {
+                    warp_utils::reject::invalid_auth("missing authorization header".to_string())
+                })?;
+                // Compare in constant time so that the token cannot be learned from the response
+                // time.
+                verify_slices_are_equal(expected.as_bytes(), header.as_bytes()).map_err(|_| {
+                    warp_utils::reject::invalid_auth("invalid admin token".to_string())
+                })
+            },
+        )
+        .untuple_one()
+        .boxed()
+}
+
+pub fn parse_peer_id(peer_id: &str) -> Result<PeerId, warp::Rejection> {
+    PeerId::from_str(peer_id).map_err(|e| {
+        warp_utils::reject::custom_bad_request(format!("invalid peer id {}: {}", peer_id, e))
+    })
+}
+
+/// Returns the message which dials `address`, which is either an `enr:`-prefixed ENR or a
+/// multiaddr.
+pub fn dial_message<T: EthSpec>(address: &str) -> Result<NetworkMessage<T>, warp::Rejection> {
+    if address.starts_with("enr:") {
+        Enr::from_str(address)
+            .map(NetworkMessage::DialEnr)
+            .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid ENR: {}", e)))
+    } else {
+        Multiaddr::from_str(address)
+            .map(NetworkMessage::DialAddress)
+            .map_err(|e| {
+                warp_utils::reject::custom_bad_request(format!("invalid multiaddr: {}", e))
+            })
+    }
+}
+
+/// Returns the message which bans the peer or IP address of `request`.
+///
+/// Trusted peers cannot be banned.
+pub fn ban_message<T: EthSpec>(
+    request: &BanRequest,
+    network_globals: &NetworkGlobals<T>,
+) -> Result<NetworkMessage<T>, warp::Rejection> {
+    let duration = Duration::from_secs(request.duration_secs);
+    if duration.is_zero() || duration > MAX_BAN_DURATION {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "duration_secs must be between 1 and {}",
+            MAX_BAN_DURATION.as_secs()
+        )));
+    }
+
+    match (&request.peer_id, request.ip) {
+        (Some(peer_id), None) => {
+            let peer_id = parse_peer_id(peer_id)?;
+            let is_trusted = network_globals
+                .peers
+                .read()
+                .peer_info(&peer_id)
+                .map_or(false, |info| info.is_trusted());
+            if is_trusted {
+                return Err(warp_utils::reject::custom_bad_request(format!(
+                    "peer {} is trusted and cannot be banned",
+                    peer_id
+                )));
+            }
+            Ok(NetworkMessage::BanPeer { peer_id, duration })
+        }
+        (None, Some(ip)) => Ok(NetworkMessage::BanIp { ip, duration }),
+        _ => Err(warp_utils::reject::custom_bad_request(
+            "exactly one of peer_id or ip must be provided".to_string(),
+        )),
+    }
+}
+
+/// Returns the message which lifts the ban on `target`, which is either a peer id or an IP
+/// address.
+pub fn unban_message<T: EthSpec>(target: &str) -> Result<NetworkMessage<T>, warp::Rejection> {
+    match IpAddr::from_str(target) {
+        Ok(ip) => Ok(NetworkMessage::UnbanIp(ip)),
+        Err(_) => parse_peer_id(target).map(NetworkMessage::UnbanPeer),
+    }
+}
+
+/// Returns the peers and IP addresses which are currently banned.
+pub fn peer_bans<T: EthSpec>(network_globals: &NetworkGlobals<T>) -> PeerBans {
+    let peers = network_globals.peers.read();
+    PeerBans {
+        peers: peers
+            .banned_peers()
+            .filter_map(|peer_id| {
+                let info = peers.peer_info(peer_id)?;
+                Some(BannedPeer {
+                    peer_id: peer_id.to_string(),
+                    score: info.score().score(),
+                    ban_remaining_secs: info.score().ban_remaining().as_secs(),
+                })
+            })
+            .collect(),
+        ips: peers
+            .banned_ips()
+            .into_iter()
+            .map(|(ip, ban_remaining)| BannedIp {
+                ip,
+                ban_remaining_secs: ban_remaining.map(|remaining| remaining.as_secs()),
+            })
+            .collect(),
+    }
+}
@@ -26,6 +26,7 @@ use sensitive_url::SensitiveUrl;
 use slog::Logger;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use store::MemoryStore;
@@ -55,6 +56,7 @@ pub struct ApiServer<E: EthSpec, SFut: Future<Output = ()>> {
     pub network_rx: NetworkReceivers<E>,
     pub local_enr: Enr,
     pub external_peer_id: PeerId,
+    pub network_globals: Arc<NetworkGlobals<E>>,
 }
 
 type Initializer<E> = Box<
@@ -138,6 +140,17 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     log: Logger,
     port: u16,
+) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
+    create_api_server_with_admin_token(chain, log, port, None).await
+}
+
+/// Create an API server which serves the peer administration endpoints to requests bearing the
+/// token stored at `admin_token_path`.
+pub async fn create_api_server_with_admin_token<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+    port: u16,
+    admin_token_path: Option<PathBuf>,
 ) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
     let (network_senders, network_receivers) = NetworkSenders::new();
 
@@ -190,12 +203,13 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
             allow_origin: None,
             tls_config: None,
             allow_sync_stalled: false,
-            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+            data_dir: PathBuf::from(DEFAULT_ROOT_DIR),
             spec_fork_name: None,
+            admin_token_path,
         },
         chain: Some(chain.clone()),
         network_senders: Some(network_senders),
-        network_globals: Some(network_globals),
+        network_globals: Some(network_globals.clone()),
         eth1_service: Some(eth1_service),
         log,
     });
@@ -214,5 +228,6 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
         network_rx: network_receivers,
         local_enr: enr,
         external_peer_id: peer_id,
+        network_globals,
     }
 }
@@ -3,6 +3,7 @@ use crate::common::*;
 use beacon_chain::{
     chain_config::ReOrgThreshold,
     test_utils::{AttestationStrategy, BlockStrategy, SyncCommitteeStrategy},
+    ChainConfig,
 };
 use eth2::types::DepositContractData;
 use execution_layer::{ForkchoiceState, PayloadAttributes};
@@ -710,3 +711,187 @@ pub async fn fork_choice_before_proposal() {
     // D's parent is B.
     assert_eq!(block_d.parent_root(), block_root_b.into());
 }
+
+// Test that the light client data computed during block import is served by the HTTP API.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn light_client_server() {
+    let validator_count = 32;
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        None,
+        Some(Box::new(|builder| {
+            builder.chain_config(ChainConfig {
+                enable_light_client_server: true,
+                ..ChainConfig::default()
+            })
+        })),
+    )
+    .await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    // Build enough of a chain to finalize, with full sync committee participation.
+    let num_blocks = 5 * E::slots_per_epoch() as usize;
+    harness.advance_slot();
+    harness
+        .extend_chain_with_sync(
+            num_blocks,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+            SyncCommitteeStrategy::AllValidators,
+        )
+        .await;
+
+    let head_block_root = harness.chain.canonical_head.cached_head().head_block_root();
+    let bootstrap = client
+        .get_beacon_light_client_bootstrap::<E>(head_block_root)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bootstrap.version, Some(ForkName::Altair));
+    assert_eq!(
+        bootstrap.data.header.slot,
+        harness.chain.canonical_head.cached_head().head_slot()
+    );
+
+    // Updates are computed on a background task after import, so wait for the latest one.
+    let mut finality_update = None;
+    for _ in 0..50 {
+        finality_update = client
+            .get_beacon_light_client_finality_update::<E>()
+            .await
+            .unwrap()
+            .filter(|update| update.data.signature_slot == Slot::new(num_blocks as u64));
+        if finality_update.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let finality_update = finality_update.expect("finality update should be computed");
+    assert_ne!(finality_update.data.finalized_header.slot, Slot::new(0));
+
+    let optimistic_update = client
+        .get_beacon_light_client_optimistic_update::<E>()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        optimistic_update.data.attested_header,
+        finality_update.data.attested_header
+    );
+
+    let updates = client
+        .get_beacon_light_client_updates::<E>(0, 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].version, Some(ForkName::Altair));
+
+    // There is no update for a future period.
+    let updates = client
+        .get_beacon_light_client_updates::<E>(1, 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(updates.is_empty());
+}
+
+// Test that the finality update is refreshed by the first block of a sync committee period,
+// whose sync aggregate attests to a block from the previous period.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn light_client_server_sync_committee_period_boundary() {
+    let validator_count = 32;
+    let mut spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    spec.epochs_per_sync_committee_period = Epoch::new(4);
+    let epochs_per_period = spec.epochs_per_sync_committee_period;
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        None,
+        Some(Box::new(|builder| {
+            builder.chain_config(ChainConfig {
+                enable_light_client_server: true,
+                ..ChainConfig::default()
+            })
+        })),
+    )
+    .await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    // Finish on the first slot of the third period.
+    let boundary_slot = (epochs_per_period * 2).start_slot(E::slots_per_epoch());
+    harness.advance_slot();
+    harness
+        .extend_chain_with_sync(
+            boundary_slot.as_usize(),
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+            SyncCommitteeStrategy::AllValidators,
+        )
+        .await;
+    assert_eq!(
+        harness.chain.canonical_head.cached_head().head_slot(),
+        boundary_slot
+    );
+
+    // Updates are computed on a background task after import, so wait for the latest one.
+    let mut finality_update = None;
+    for _ in 0..50 {
+        finality_update = client
+            .get_beacon_light_client_finality_update::<E>()
+            .await
+            .unwrap()
+            .filter(|update| update.data.signature_slot == boundary_slot);
+        if finality_update.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let finality_update = finality_update.expect("finality update should be refreshed");
+    assert_eq!(finality_update.data.attested_header.slot, boundary_slot - 1);
+
+    // The boundary block does not provide an update for either period.
+    let updates = client
+        .get_beacon_light_client_updates::<E>(1, 2)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_ne!(updates[0].data.signature_slot, boundary_slot);
+}
+
+// Test that a bootstrap is not found for a block which precedes Altair.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn light_client_bootstrap_pre_altair() {
+    let validator_count = 32;
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(1));
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        None,
+        Some(Box::new(|builder| {
+            builder.chain_config(ChainConfig {
+                enable_light_client_server: true,
+                ..ChainConfig::default()
+            })
+        })),
+    )
+    .await;
+    let harness = &tester.harness;
+
+    let genesis_block_root = harness.chain.genesis_block_root;
+    let bootstrap = tester
+        .client
+        .get_beacon_light_client_bootstrap::<E>(genesis_block_root)
+        .await
+        .unwrap();
+    assert!(bootstrap.is_none());
+}
@@ -3,4 +3,5 @@
 pub mod common;
 pub mod fork_tests;
 pub mod interactive_tests;
+pub mod peer_admin_tests;
 pub mod tests;
@@ -0,0 +1,344 @@
+//! Tests for the `lighthouse/peers` administration endpoints.
+use crate::common::{create_api_server_with_admin_token, ApiServer};
+use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+use eth2::lighthouse::PeerBans;
+use eth2::reqwest::{Client, Method, StatusCode};
+use eth2::types::GenericResponse;
+use lighthouse_network::{Multiaddr, NetworkGlobals, PeerId, PeerManager, MAX_BAN_DURATION};
+use logging::test_logger;
+use network::{NetworkMessage, NetworkReceivers};
+use serde_json::{json, Value};
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::{tempdir, TempDir};
+use tokio::sync::oneshot;
+use types::MainnetEthSpec;
+
+type E = MainnetEthSpec;
+
+const ADMIN_TOKEN: &str = "hunter2";
+
+struct AdminTester {
+    _harness: BeaconChainHarness<EphemeralHarnessType<E>>,
+    client: Client,
+    url: String,
+    network_rx: NetworkReceivers<E>,
+    network_globals: Arc<NetworkGlobals<E>>,
+    _server_shutdown: oneshot::Sender<()>,
+    _token_dir: TempDir,
+}
+
+impl AdminTester {
+    /// Start an API server, serving the administration endpoints only if `admin_token` is set.
+    async fn new(admin_token: Option<&str>) -> Self {
+        let harness = BeaconChainHarness::builder(E::default())
+            .default_spec()
+            .logger(test_logger())
+            .deterministic_keypairs(8)
+            .fresh_ephemeral_store()
+            .build();
+
+        let token_dir = tempdir().unwrap();
+        let admin_token_path = admin_token.map(|token| {
+            let path = token_dir.path().join("admin-token.txt");
+            // Surrounding whitespace should be ignored.
+            std::fs::write(&path, format!("{}\n", token)).unwrap();
+            path
+        });
+
+        let port = unused_port::unused_tcp4_port().unwrap();
+        let ApiServer {
+            server,
+            listening_socket,
+            shutdown_tx,
+            network_rx,
+            network_globals,
+            ..
+        } = create_api_server_with_admin_token(
+            harness.chain.clone(),
+            harness.logger().clone(),
+            port,
+            admin_token_path,
+        )
+        .await;
+
+        tokio::spawn(server);
+
+        Self {
+            _harness: harness,
+            client: Client::new(),
+            url: format!(
+                "http://{}:{}",
+                listening_socket.ip(),
+                listening_socket.port()
+            ),
+            network_rx,
+            network_globals,
+            _server_shutdown: shutdown_tx,
+            _token_dir: token_dir,
+        }
+    }
+
+    /// Send a request to `path`, with an `Authorization: Bearer <token>` header if `token` is set.
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> StatusCode {
+        let mut builder = self
+            .client
+            .request(method, format!("{}/{}", self.url, path));
+        if let Some(token) = token {
+            builder = builder.bearer_auth(token);
+        }
+        if let Some(body) = body {
+            builder = builder.json(&body);
+        }
+        builder.send().await.unwrap().status()
+    }
+
+    async fn admin_request(&self, method: Method, path: &str, body: Option<Value>) -> StatusCode {
+        self.request(method, path, Some(ADMIN_TOKEN), body).await
+    }
+
+    async fn get_peer_bans(&self) -> PeerBans {
+        self.client
+            .get(format!("{}/lighthouse/peers/bans", self.url))
+            .send()
+            .await
+            .unwrap()
+            .json::<GenericResponse<PeerBans>>()
+            .await
+            .unwrap()
+            .data
+    }
+
+    /// The message most recently published to the network service, if any.
+    fn published_message(&mut self) -> Option<NetworkMessage<E>> {
+        self.network_rx.network_recv.try_recv().ok()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn peer_admin_disabled_without_token() {
+    let mut tester = AdminTester::new(None).await;
+    let peer_id = PeerId::random();
+
+    let status = tester
+        .request(
+            Method::POST,
+            "lighthouse/peers/trusted",
+            Some(ADMIN_TOKEN),
+            Some(json!({ "peer_id": peer_id.to_string() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let status = tester
+        .request(
+            Method::DELETE,
+            &format!("lighthouse/peers/{}", peer_id),
+            Some(ADMIN_TOKEN),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(tester.published_message().is_none());
+
+    // Reading the bans does not require a token.
+    assert!(tester.get_peer_bans().await.ips.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn peer_admin_rejects_missing_or_wrong_token() {
+    let mut tester = AdminTester::new(Some(ADMIN_TOKEN)).await;
+    let body = json!({ "ip": "1.2.3.4", "duration_secs": 60 });
+
+    for token in [None, Some("hunter3"), Some("")] {
+        let status = tester
+            .request(
+                Method::POST,
+                "lighthouse/peers/bans",
+                token,
+                Some(body.clone()),
+            )
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "token: {:?}", token);
+    }
+    let status = tester
+        .request(Method::DELETE, "lighthouse/peers/bans/1.2.3.4", None, None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(tester.published_message().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn peer_admin_round_trip() {
+    let mut tester = AdminTester::new(Some(ADMIN_TOKEN)).await;
+    let peer_id = PeerId::random();
+    let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+
+    // Dial.
+    let address: Multiaddr = "/ip4/1.2.3.4/tcp/9000".parse().unwrap();
+    let status = tester
+        .admin_request(
+            Method::POST,
+            "lighthouse/peers/dial",
+            Some(json!({ "address": address.to_string() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::DialAddress(dialed)) if dialed == address
+    ));
+
+    // Ban and unban a peer.
+    let status = tester
+        .admin_request(
+            Method::POST,
+            "lighthouse/peers/bans",
+            Some(json!({ "peer_id": peer_id.to_string(), "duration_secs": 60 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::BanPeer { peer_id: banned, duration })
+            if banned == peer_id && duration == Duration::from_secs(60)
+    ));
+    let status = tester
+        .admin_request(
+            Method::DELETE,
+            &format!("lighthouse/peers/bans/{}", peer_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::UnbanPeer(unbanned)) if unbanned == peer_id
+    ));
+
+    // Ban and unban an IP address.
+    let status = tester
+        .admin_request(
+            Method::POST,
+            "lighthouse/peers/bans",
+            Some(json!({ "ip": ip, "duration_secs": MAX_BAN_DURATION.as_secs() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::BanIp { ip: banned, duration })
+            if banned == ip && duration == MAX_BAN_DURATION
+    ));
+    let status = tester
+        .admin_request(Method::DELETE, "lighthouse/peers/bans/1.2.3.4", None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::UnbanIp(unbanned)) if unbanned == ip
+    ));
+
+    // Trust and distrust a peer.
+    let status = tester
+        .admin_request(
+            Method::POST,
+            "lighthouse/peers/trusted",
+            Some(json!({ "peer_id": peer_id.to_string() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::SetTrustedPeer { peer_id: trusted_peer, trusted: true })
+            if trusted_peer == peer_id
+    ));
+    let status = tester
+        .admin_request(
+            Method::DELETE,
+            &format!("lighthouse/peers/trusted/{}", peer_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::SetTrustedPeer { peer_id: trusted_peer, trusted: false })
+            if trusted_peer == peer_id
+    ));
+
+    // Disconnect.
+    let status = tester
+        .admin_request(
+            Method::DELETE,
+            &format!("lighthouse/peers/{}", peer_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(matches!(
+        tester.published_message(),
+        Some(NetworkMessage::DisconnectPeer(disconnected)) if disconnected == peer_id
+    ));
+    assert!(tester.published_message().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn peer_admin_rejects_invalid_requests() {
+    let mut tester = AdminTester::new(Some(ADMIN_TOKEN)).await;
+
+    let invalid_bodies = [
+        // The duration must be non-zero and no longer than the maximum.
+        json!({ "ip": "1.2.3.4", "duration_secs": 0 }),
+        json!({ "ip": "1.2.3.4", "duration_secs": MAX_BAN_DURATION.as_secs() + 1 }),
+        json!({ "ip": "1.2.3.4", "duration_secs": u64::MAX }),
+        // Exactly one of `peer_id` and `ip` must be provided.
+        json!({ "duration_secs": 60 }),
+        json!({ "peer_id": PeerId::random().to_string(), "ip": "1.2.3.4", "duration_secs": 60 }),
+        json!({ "peer_id": "not a peer id", "duration_secs": 60 }),
+    ];
+    for body in invalid_bodies {
+        let status = tester
+            .admin_request(Method::POST, "lighthouse/peers/bans", Some(body.clone()))
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "body: {}", body);
+    }
+
+    let status = tester
+        .admin_request(
+            Method::POST,
+            "lighthouse/peers/dial",
+            Some(json!({ "address": "enr:invalid" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(tester.published_message().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_peer_bans() {
+    let tester = AdminTester::new(None).await;
+
+    let bans = tester.get_peer_bans().await;
+    assert!(bans.peers.is_empty());
+    assert!(bans.ips.is_empty());
+
+    // Only a peer manager can ban, so use a dummy one sharing the server's globals.
+    let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+    let config = lighthouse_network::peer_manager::config::Config::default();
+    let mut pm = PeerManager::new(config, tester.network_globals.clone(), &test_logger()).unwrap();
+    pm.ban_ip_for(ip, Duration::from_secs(60));
+
+    let bans = tester.get_peer_bans().await;
+    assert!(bans.peers.is_empty());
+    assert_eq!(bans.ips.len(), 1);
+    assert_eq!(bans.ips[0].ip, ip);
+    let ban_remaining_secs = bans.ips[0].ban_remaining_secs.unwrap();
+    assert!(ban_remaining_secs > 0 && ban_remaining_secs <= 60);
+}
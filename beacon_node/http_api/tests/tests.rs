@@ -2,7 +2,7 @@ use crate::common::{create_api_server, create_api_server_on_port, ApiServer};
 use beacon_chain::test_utils::RelativeSyncCommittee;
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
-    BeaconChain, StateSkipConfig, WhenSlotSkipped, MAXIMUM_GOSSIP_CLOCK_DISPARITY,
+    BeaconChain, ForkChoiceStore, StateSkipConfig, WhenSlotSkipped, MAXIMUM_GOSSIP_CLOCK_DISPARITY,
 };
 use environment::null_logger;
 use eth2::{
@@ -14,7 +14,8 @@ use eth2::{
 use execution_layer::test_utils::TestingBuilder;
 use execution_layer::test_utils::DEFAULT_BUILDER_THRESHOLD_WEI;
 use execution_layer::test_utils::{
-    Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
+    mock_client_version, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI,
+    DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
 };
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
@@ -236,6 +237,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            ..
         } = create_api_server_on_port(chain.clone(), log, port).await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
@@ -322,6 +324,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            ..
         } = create_api_server(chain.clone(), log).await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
@@ -1679,6 +1682,57 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_debug_fork_choice(self) -> Self {
+        let result = self.client.get_debug_fork_choice().await.unwrap();
+
+        let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+
+        assert_eq!(
+            result.justified_checkpoint,
+            *fork_choice.fc_store().justified_checkpoint()
+        );
+        assert_eq!(
+            result.finalized_checkpoint,
+            *fork_choice.fc_store().finalized_checkpoint()
+        );
+
+        let expected_nodes = &fork_choice.proto_array().core_proto_array().nodes;
+        assert_eq!(result.fork_choice_nodes.len(), expected_nodes.len());
+
+        for (node, expected) in result.fork_choice_nodes.iter().zip(expected_nodes) {
+            assert_eq!(node.slot, expected.slot);
+            assert_eq!(node.block_root, expected.root);
+            assert_eq!(
+                node.parent_root,
+                expected.parent.map(|index| expected_nodes[index].root)
+            );
+            assert_eq!(
+                node.justified_epoch,
+                expected
+                    .justified_checkpoint
+                    .map(|checkpoint| checkpoint.epoch)
+            );
+            assert_eq!(
+                node.finalized_epoch,
+                expected
+                    .finalized_checkpoint
+                    .map(|checkpoint| checkpoint.epoch)
+            );
+            assert_eq!(node.weight, expected.weight);
+            assert_eq!(
+                node.execution_block_hash,
+                expected
+                    .execution_status
+                    .block_hash()
+                    .map(|block_hash| block_hash.into_root())
+            );
+        }
+
+        drop(fork_choice);
+
+        self
+    }
+
     fn validator_count(&self) -> usize {
         self.chain.head_snapshot().beacon_state.validators().len()
     }
@@ -3621,6 +3675,19 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_execution_client_version(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_execution_client_version()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result, vec![mock_client_version()]);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_database_info(self) -> Self {
         let info = self.client.get_lighthouse_database_info().await.unwrap();
 
@@ -4148,6 +4215,8 @@ async fn debug_get() {
         .test_get_debug_beacon_states()
         .await
         .test_get_debug_beacon_heads()
+        .await
+        .test_get_debug_fork_choice()
         .await;
 }
 
@@ -4561,6 +4630,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_staking()
         .await
+        .test_get_lighthouse_execution_client_version()
+        .await
         .test_get_lighthouse_database_info()
         .await
         .test_post_lighthouse_database_reconstruct()
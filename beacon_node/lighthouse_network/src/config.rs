@@ -78,6 +78,12 @@ pub struct Config {
     /// The tcp6 port to broadcast to peers in order to reach back for libp2p services.
     pub enr_tcp6_port: Option<u16>,
 
+    /// The quic4 port to broadcast to peers in order to reach back for libp2p services over QUIC.
+    pub enr_quic4_port: Option<u16>,
+
+    /// The quic6 port to broadcast to peers in order to reach back for libp2p services over QUIC.
+    pub enr_quic6_port: Option<u16>,
+
     /// Target number of connected peers.
     pub target_peers: usize,
 
@@ -146,28 +152,24 @@ impl Config {
     /// adjusted accordingly to ensure addresses that are present in the enr are globally
     /// reachable.
     pub fn set_ipv4_listening_address(&mut self, addr: Ipv4Addr, tcp_port: u16, udp_port: u16) {
-        self.listen_addresses = ListenAddress::V4(ListenAddr {
+        self.set_listening_addr(ListenAddress::V4(ListenAddr {
             addr,
             udp_port,
             tcp_port,
-        });
-        self.discv5_config.ip_mode = discv5::IpMode::Ip4;
-        self.discv5_config.table_filter = |enr| enr.ip4().as_ref().map_or(false, is_global_ipv4)
+            quic_port: None,
+        }))
     }
 
     /// Sets the listening address to use an ipv6 address. The discv5 ip_mode and table filter is
     /// adjusted accordingly to ensure addresses that are present in the enr are globally
     /// reachable.
     pub fn set_ipv6_listening_address(&mut self, addr: Ipv6Addr, tcp_port: u16, udp_port: u16) {
-        self.listen_addresses = ListenAddress::V6(ListenAddr {
+        self.set_listening_addr(ListenAddress::V6(ListenAddr {
             addr,
             udp_port,
             tcp_port,
-        });
-        self.discv5_config.ip_mode = discv5::IpMode::Ip6 {
-            enable_mapped_addresses: false,
-        };
-        self.discv5_config.table_filter = |enr| enr.ip6().as_ref().map_or(false, is_global_ipv6)
+            quic_port: None,
+        }))
     }
 
     /// Sets the listening address to use both an ipv4 and ipv6 address. The discv5 ip_mode and
@@ -182,57 +184,52 @@ impl Config {
         tcp6_port: u16,
         udp6_port: u16,
     ) {
-        self.listen_addresses = ListenAddress::DualStack(
+        self.set_listening_addr(ListenAddress::DualStack(
             ListenAddr {
                 addr: v4_addr,
                 udp_port: udp4_port,
                 tcp_port: tcp4_port,
+                quic_port: None,
             },
             ListenAddr {
                 addr: v6_addr,
                 udp_port: udp6_port,
                 tcp_port: tcp6_port,
+                quic_port: None,
             },
-        );
-
-        self.discv5_config.ip_mode = discv5::IpMode::Ip6 {
-            enable_mapped_addresses: true,
-        };
-        self.discv5_config.table_filter = |enr| match (&enr.ip4(), &enr.ip6()) {
-            (None, None) => false,
-            (None, Some(ip6)) => is_global_ipv6(ip6),
-            (Some(ip4), None) => is_global_ipv4(ip4),
-            (Some(ip4), Some(ip6)) => is_global_ipv4(ip4) && is_global_ipv6(ip6),
-        };
+        ))
     }
 
+    /// Sets the listening addresses, including any QUIC ports. The discv5 ip_mode and table
+    /// filter are adjusted accordingly to ensure addresses that are present in the enr are
+    /// globally reachable.
     pub fn set_listening_addr(&mut self, listen_addr: ListenAddress) {
         match listen_addr {
-            ListenAddress::V4(ListenAddr {
-                addr,
-                udp_port,
-                tcp_port,
-            }) => self.set_ipv4_listening_address(addr, tcp_port, udp_port),
-            ListenAddress::V6(ListenAddr {
-                addr,
-                udp_port,
-                tcp_port,
-            }) => self.set_ipv6_listening_address(addr, tcp_port, udp_port),
-            ListenAddress::DualStack(
-                ListenAddr {
-                    addr: ip4addr,
-                    udp_port: udp4_port,
-                    tcp_port: tcp4_port,
-                },
-                ListenAddr {
-                    addr: ip6addr,
-                    udp_port: udp6_port,
-                    tcp_port: tcp6_port,
-                },
-            ) => self.set_ipv4_ipv6_listening_addresses(
-                ip4addr, tcp4_port, udp4_port, ip6addr, tcp6_port, udp6_port,
-            ),
+            ListenAddress::V4(_) => {
+                self.discv5_config.ip_mode = discv5::IpMode::Ip4;
+                self.discv5_config.table_filter =
+                    |enr| enr.ip4().as_ref().map_or(false, is_global_ipv4)
+            }
+            ListenAddress::V6(_) => {
+                self.discv5_config.ip_mode = discv5::IpMode::Ip6 {
+                    enable_mapped_addresses: false,
+                };
+                self.discv5_config.table_filter =
+                    |enr| enr.ip6().as_ref().map_or(false, is_global_ipv6)
+            }
+            ListenAddress::DualStack(..) => {
+                self.discv5_config.ip_mode = discv5::IpMode::Ip6 {
+                    enable_mapped_addresses: true,
+                };
+                self.discv5_config.table_filter = |enr| match (&enr.ip4(), &enr.ip6()) {
+                    (None, None) => false,
+                    (None, Some(ip6)) => is_global_ipv6(ip6),
+                    (Some(ip4), None) => is_global_ipv4(ip4),
+                    (Some(ip4), Some(ip6)) => is_global_ipv4(ip4) && is_global_ipv6(ip6),
+                };
+            }
         }
+        self.listen_addresses = listen_addr;
     }
 
     pub fn listen_addrs(&self) -> &ListenAddress {
@@ -296,12 +293,15 @@ impl Default for Config {
                 addr: Ipv4Addr::UNSPECIFIED,
                 udp_port: 9000,
                 tcp_port: 9000,
+                quic_port: None,
             }),
             enr_address: (None, None),
             enr_udp4_port: None,
             enr_tcp4_port: None,
             enr_udp6_port: None,
             enr_tcp6_port: None,
+            enr_quic4_port: None,
+            enr_quic6_port: None,
             target_peers: 50,
             gs_config,
             discv5_config,
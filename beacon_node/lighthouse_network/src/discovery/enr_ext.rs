@@ -1,4 +1,5 @@
 //! ENR extension trait to support libp2p integration.
+use super::enr::Eth2Enr;
 use crate::{Enr, Multiaddr, PeerId};
 use discv5::enr::{CombinedKey, CombinedPublicKey};
 use libp2p::core::{identity::Keypair, identity::PublicKey, multiaddr::Protocol};
@@ -9,7 +10,7 @@ pub trait EnrExt {
     /// The libp2p `PeerId` for the record.
     fn peer_id(&self) -> PeerId;
 
-    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp` or `udp` key **or** an `ip6` and either a `tcp6` or `udp6`.
+    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp`, `udp` or `quic` key **or** an `ip6` and either a `tcp6`, `udp6` or `quic6`.
     /// The vector remains empty if these fields are not defined.
     fn multiaddr(&self) -> Vec<Multiaddr>;
 
@@ -22,8 +23,14 @@ pub trait EnrExt {
     /// Returns any multiaddrs that contain the UDP protocol with the `PeerId` prepended.
     fn multiaddr_p2p_udp(&self) -> Vec<Multiaddr>;
 
+    /// Returns any multiaddrs that contain the QUIC protocol with the `PeerId` prepended.
+    fn multiaddr_p2p_quic(&self) -> Vec<Multiaddr>;
+
     /// Returns any multiaddrs that contain the TCP protocol.
     fn multiaddr_tcp(&self) -> Vec<Multiaddr>;
+
+    /// Returns any multiaddrs that contain the QUIC protocol.
+    fn multiaddr_quic(&self) -> Vec<Multiaddr>;
 }
 
 /// Extend ENR CombinedPublicKey for libp2p types.
@@ -44,7 +51,7 @@ impl EnrExt for Enr {
         self.public_key().as_peer_id()
     }
 
-    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp` or `udp` key **or** an `ip6` and either a `tcp6` or `udp6`.
+    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp`, `udp` or `quic` key **or** an `ip6` and either a `tcp6`, `udp6` or `quic6`.
     /// The vector remains empty if these fields are not defined.
     fn multiaddr(&self) -> Vec<Multiaddr> {
         let mut multiaddrs: Vec<Multiaddr> = Vec::new();
@@ -60,6 +67,13 @@ impl EnrExt for Enr {
                 multiaddr.push(Protocol::Tcp(tcp));
                 multiaddrs.push(multiaddr);
             }
+
+            if let Some(quic) = self.quic4() {
+                let mut multiaddr: Multiaddr = ip.into();
+                multiaddr.push(Protocol::Udp(quic));
+                multiaddr.push(Protocol::Quic);
+                multiaddrs.push(multiaddr);
+            }
         }
         if let Some(ip6) = self.ip6() {
             if let Some(udp6) = self.udp6() {
@@ -73,48 +87,30 @@ impl EnrExt for Enr {
                 multiaddr.push(Protocol::Tcp(tcp6));
                 multiaddrs.push(multiaddr);
             }
+
+            if let Some(quic6) = self.quic6() {
+                let mut multiaddr: Multiaddr = ip6.into();
+                multiaddr.push(Protocol::Udp(quic6));
+                multiaddr.push(Protocol::Quic);
+                multiaddrs.push(multiaddr);
+            }
         }
         multiaddrs
     }
 
-    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp` or `udp` key **or** an `ip6` and either a `tcp6` or `udp6`.
+    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp`, `udp` or `quic` key **or** an `ip6` and either a `tcp6`, `udp6` or `quic6`.
     /// The vector remains empty if these fields are not defined.
     ///
     /// This also prepends the `PeerId` into each multiaddr with the `P2p` protocol.
     fn multiaddr_p2p(&self) -> Vec<Multiaddr> {
         let peer_id = self.peer_id();
-        let mut multiaddrs: Vec<Multiaddr> = Vec::new();
-        if let Some(ip) = self.ip4() {
-            if let Some(udp) = self.udp4() {
-                let mut multiaddr: Multiaddr = ip.into();
-                multiaddr.push(Protocol::Udp(udp));
-                multiaddr.push(Protocol::P2p(peer_id.into()));
-                multiaddrs.push(multiaddr);
-            }
-
-            if let Some(tcp) = self.tcp4() {
-                let mut multiaddr: Multiaddr = ip.into();
-                multiaddr.push(Protocol::Tcp(tcp));
-                multiaddr.push(Protocol::P2p(peer_id.into()));
-                multiaddrs.push(multiaddr);
-            }
-        }
-        if let Some(ip6) = self.ip6() {
-            if let Some(udp6) = self.udp6() {
-                let mut multiaddr: Multiaddr = ip6.into();
-                multiaddr.push(Protocol::Udp(udp6));
-                multiaddr.push(Protocol::P2p(peer_id.into()));
-                multiaddrs.push(multiaddr);
-            }
-
-            if let Some(tcp6) = self.tcp6() {
-                let mut multiaddr: Multiaddr = ip6.into();
-                multiaddr.push(Protocol::Tcp(tcp6));
+        self.multiaddr()
+            .into_iter()
+            .map(|mut multiaddr| {
                 multiaddr.push(Protocol::P2p(peer_id.into()));
-                multiaddrs.push(multiaddr);
-            }
-        }
-        multiaddrs
+                multiaddr
+            })
+            .collect()
     }
 
     /// Returns a list of multiaddrs if the ENR has an `ip` and a `tcp` key **or** an `ip6` and a `tcp6`.
@@ -169,7 +165,22 @@ impl EnrExt for Enr {
         multiaddrs
     }
 
-    /// Returns a list of multiaddrs if the ENR has an `ip` and either a `tcp` or `udp` key **or** an `ip6` and either a `tcp6` or `udp6`.
+    /// Returns a list of multiaddrs if the ENR has an `ip` and a `quic` key **or** an `ip6` and a `quic6`.
+    /// The vector remains empty if these fields are not defined.
+    ///
+    /// This also prepends the `PeerId` into each multiaddr with the `P2p` protocol.
+    fn multiaddr_p2p_quic(&self) -> Vec<Multiaddr> {
+        let peer_id = self.peer_id();
+        self.multiaddr_quic()
+            .into_iter()
+            .map(|mut multiaddr| {
+                multiaddr.push(Protocol::P2p(peer_id.into()));
+                multiaddr
+            })
+            .collect()
+    }
+
+    /// Returns a list of multiaddrs if the ENR has an `ip` and a `tcp` key **or** an `ip6` and a `tcp6`.
     /// The vector remains empty if these fields are not defined.
     fn multiaddr_tcp(&self) -> Vec<Multiaddr> {
         let mut multiaddrs: Vec<Multiaddr> = Vec::new();
@@ -189,6 +200,29 @@ impl EnrExt for Enr {
         }
         multiaddrs
     }
+
+    /// Returns a list of multiaddrs if the ENR has an `ip` and a `quic` key **or** an `ip6` and a `quic6`.
+    /// The vector remains empty if these fields are not defined.
+    fn multiaddr_quic(&self) -> Vec<Multiaddr> {
+        let mut multiaddrs: Vec<Multiaddr> = Vec::new();
+        if let Some(ip) = self.ip4() {
+            if let Some(quic) = self.quic4() {
+                let mut multiaddr: Multiaddr = ip.into();
+                multiaddr.push(Protocol::Udp(quic));
+                multiaddr.push(Protocol::Quic);
+                multiaddrs.push(multiaddr);
+            }
+        }
+        if let Some(ip6) = self.ip6() {
+            if let Some(quic6) = self.quic6() {
+                let mut multiaddr: Multiaddr = ip6.into();
+                multiaddr.push(Protocol::Udp(quic6));
+                multiaddr.push(Protocol::Quic);
+                multiaddrs.push(multiaddr);
+            }
+        }
+        multiaddrs
+    }
 }
 
 impl CombinedKeyPublicExt for CombinedPublicKey {
@@ -276,6 +310,7 @@ pub fn peer_id_to_node_id(peer_id: &PeerId) -> Result<discv5::enr::NodeId, Strin
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::discovery::enr::QUIC_ENR_KEY;
 
     #[test]
     fn test_secp256k1_peer_id_conversion() {
@@ -314,4 +349,29 @@ mod tests {
 
         assert_eq!(enr.node_id(), node_id);
     }
+
+    #[test]
+    fn test_quic_multiaddrs() {
+        let key = discv5::enr::CombinedKey::generate_secp256k1();
+        let enr = discv5::enr::EnrBuilder::new("v4")
+            .ip4("10.0.0.1".parse().unwrap())
+            .tcp4(9000)
+            .add_value(QUIC_ENR_KEY, &9001u16.to_be_bytes().to_vec())
+            .build(&key)
+            .unwrap();
+
+        assert_eq!(enr.quic4(), Some(9001));
+        assert_eq!(enr.quic6(), None);
+        assert_eq!(
+            enr.multiaddr_quic(),
+            vec!["/ip4/10.0.0.1/udp/9001/quic".parse::<Multiaddr>().unwrap()]
+        );
+        assert_eq!(
+            enr.multiaddr(),
+            vec![
+                "/ip4/10.0.0.1/tcp/9000".parse::<Multiaddr>().unwrap(),
+                "/ip4/10.0.0.1/udp/9001/quic".parse::<Multiaddr>().unwrap(),
+            ]
+        );
+    }
 }
@@ -184,6 +184,9 @@ pub struct Discovery<TSpec: EthSpec> {
     /// second indicates tcp6.
     update_tcp_port: (bool, bool),
 
+    /// Indicates if we are able to dial peers over QUIC.
+    quic_enabled: bool,
+
     /// Logger for the discovery behaviour.
     log: slog::Logger,
 }
@@ -322,6 +325,7 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
             event_stream,
             started: !config.disable_discovery,
             update_tcp_port,
+            quic_enabled: config.listen_addrs().quic_enabled(),
             log,
             enr_dir,
         })
@@ -597,6 +601,16 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         }
     }
 
+    /// Bans an IP address in discovery.
+    pub fn ban_peer_ip(&mut self, ip_address: IpAddr) {
+        self.discv5.ban_ip(ip_address, None);
+    }
+
+    /// Unbans an IP address in discovery.
+    pub fn unban_peer_ip(&mut self, ip_address: &IpAddr) {
+        self.discv5.ban_ip_remove(ip_address);
+    }
+
     ///  Marks node as disconnected in the DHT, freeing up space for other nodes, this also removes
     ///  nodes from the cached ENR list.
     pub fn disconnect_peer(&mut self, peer_id: &PeerId) {
@@ -964,7 +978,16 @@ impl<TSpec: EthSpec> NetworkBehaviour for Discovery<TSpec> {
             // ENR's may have multiple Multiaddrs. The multi-addr associated with the UDP
             // port is removed, which is assumed to be associated with the discv5 protocol (and
             // therefore irrelevant for other libp2p components).
-            enr.multiaddr_tcp()
+            //
+            // If both peers support QUIC, the QUIC multi-addrs are returned first so that they
+            // are dialed ahead of TCP, which remains as a fallback.
+            let mut multiaddrs = if self.quic_enabled {
+                enr.multiaddr_quic()
+            } else {
+                Vec::new()
+            };
+            multiaddrs.extend(enr.multiaddr_tcp());
+            multiaddrs
         } else {
             // PeerId is not known
             Vec::new()
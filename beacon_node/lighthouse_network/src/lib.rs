@@ -81,8 +81,8 @@ pub use libp2p::{multiaddr, Multiaddr};
 pub use metrics::scrape_discovery_metrics;
 pub use peer_manager::{
     peerdb::client::Client,
-    peerdb::score::{PeerAction, ReportSource},
-    peerdb::PeerDB,
+    peerdb::score::{PeerAction, ReportSource, MAX_BAN_DURATION},
+    peerdb::{PeerDB, PersistedIpBan, PersistedPeer},
     ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo, SyncStatus,
 };
 // pub use service::{load_private_key, Context, Libp2pEvent, Service, NETWORK_KEY_FILENAME};
@@ -3,12 +3,15 @@ use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use libp2p::{multiaddr::Protocol, Multiaddr};
 use serde::{Deserialize, Serialize};
 
-/// A listening address composed by an Ip, an UDP port and a TCP port.
+/// A listening address composed by an Ip, an UDP port, a TCP port and an optional QUIC port.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ListenAddr<Ip> {
     pub addr: Ip,
     pub udp_port: u16,
     pub tcp_port: u16,
+    /// The UDP port used by the QUIC transport. QUIC is disabled if this is `None`.
+    #[serde(default)]
+    pub quic_port: Option<u16>,
 }
 
 impl<Ip: Into<IpAddr> + Clone> ListenAddr<Ip> {
@@ -19,6 +22,11 @@ impl<Ip: Into<IpAddr> + Clone> ListenAddr<Ip> {
     pub fn tcp_socket_addr(&self) -> SocketAddr {
         (self.addr.clone().into(), self.tcp_port).into()
     }
+
+    pub fn quic_socket_addr(&self) -> Option<SocketAddr> {
+        self.quic_port
+            .map(|quic_port| (self.addr.clone().into(), quic_port).into())
+    }
 }
 
 /// Types of listening addresses Lighthouse can accept.
@@ -57,12 +65,41 @@ impl ListenAddress {
         v4_multiaddr.into_iter().chain(v6_multiaddr)
     }
 
+    /// Returns the QUIC addresses, if QUIC is enabled.
+    pub fn quic_addresses(&self) -> impl Iterator<Item = Multiaddr> + '_ {
+        let v4_multiaddr = self.v4().and_then(|v4_addr| {
+            v4_addr.quic_port.map(|quic_port| {
+                Multiaddr::from(v4_addr.addr)
+                    .with(Protocol::Udp(quic_port))
+                    .with(Protocol::Quic)
+            })
+        });
+        let v6_multiaddr = self.v6().and_then(|v6_addr| {
+            v6_addr.quic_port.map(|quic_port| {
+                Multiaddr::from(v6_addr.addr)
+                    .with(Protocol::Udp(quic_port))
+                    .with(Protocol::Quic)
+            })
+        });
+        v4_multiaddr.into_iter().chain(v6_multiaddr)
+    }
+
+    /// Returns `true` if QUIC is enabled on any of the listening addresses.
+    pub fn quic_enabled(&self) -> bool {
+        self.v4()
+            .map_or(false, |v4_addr| v4_addr.quic_port.is_some())
+            || self
+                .v6()
+                .map_or(false, |v6_addr| v6_addr.quic_port.is_some())
+    }
+
     #[cfg(test)]
     pub fn unused_v4_ports() -> Self {
         ListenAddress::V4(ListenAddr {
             addr: Ipv4Addr::UNSPECIFIED,
             udp_port: unused_port::unused_udp4_port().unwrap(),
             tcp_port: unused_port::unused_tcp4_port().unwrap(),
+            quic_port: None,
         })
     }
 
@@ -72,6 +109,7 @@ impl ListenAddress {
             addr: Ipv6Addr::UNSPECIFIED,
             udp_port: unused_port::unused_udp6_port().unwrap(),
             tcp_port: unused_port::unused_tcp6_port().unwrap(),
+            quic_port: None,
         })
     }
 }
@@ -86,11 +124,17 @@ impl slog::KV for ListenAddress {
             serializer.emit_arguments("ip4_address", &format_args!("{}", v4_addr.addr))?;
             serializer.emit_u16("udp4_port", v4_addr.udp_port)?;
             serializer.emit_u16("tcp4_port", v4_addr.tcp_port)?;
+            if let Some(quic_port) = v4_addr.quic_port {
+                serializer.emit_u16("quic4_port", quic_port)?;
+            }
         }
         if let Some(v6_addr) = self.v6() {
             serializer.emit_arguments("ip6_address", &format_args!("{}", v6_addr.addr))?;
             serializer.emit_u16("udp6_port", v6_addr.udp_port)?;
             serializer.emit_u16("tcp6_port", v6_addr.tcp_port)?;
+            if let Some(quic_port) = v6_addr.quic_port {
+                serializer.emit_u16("quic6_port", quic_port)?;
+            }
         }
         slog::Result::Ok(())
     }
@@ -1,5 +1,7 @@
 pub use lighthouse_metrics::*;
 
+use libp2p::{multiaddr::Protocol, Multiaddr};
+
 lazy_static! {
     pub static ref NAT_OPEN: Result<IntCounter> = try_create_int_counter(
         "nat_open",
@@ -136,6 +138,20 @@ lazy_static! {
     pub static ref NETWORK_OUTBOUND_PEERS: Result<IntGauge> =
         try_create_int_gauge("network_outbound_peers","The number of peers that are currently connected that we dialed.");
 
+    /*
+     * Transports
+     */
+    pub static ref CONNECTIONS_PER_TRANSPORT: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "libp2p_connections_per_transport",
+        "The number of open libp2p connections per transport",
+        &["transport"]
+    );
+    pub static ref DIAL_LATENCY_PER_TRANSPORT: Result<HistogramVec> = try_create_histogram_vec(
+        "libp2p_dial_latency_seconds",
+        "Time taken to establish a connection to a dialed peer, per transport",
+        &["transport"]
+    );
+
     /*
      * Peer Reporting
      */
@@ -166,6 +182,15 @@ pub fn check_nat() {
     }
 }
 
+/// Returns the name of the transport used by a connection to `address`, for use as a metric label.
+pub fn transport_name(address: &Multiaddr) -> &'static str {
+    if address.iter().any(|proto| matches!(proto, Protocol::Quic)) {
+        "quic"
+    } else {
+        "tcp"
+    }
+}
+
 pub fn scrape_discovery_metrics() {
     let metrics =
         discv5::metrics::Metrics::from(discv5::Discv5::<discv5::DefaultProtocolId>::raw_metrics());
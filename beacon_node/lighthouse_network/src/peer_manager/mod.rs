@@ -9,7 +9,9 @@ use delay_map::HashSetDelay;
 use discv5::Enr;
 use libp2p::identify::Info as IdentifyInfo;
 use lru_cache::LRUTimeCache;
-use peerdb::{client::ClientKind, BanOperation, BanResult, ScoreUpdateResult};
+use peerdb::{
+    client::ClientKind, BanOperation, BanResult, PersistedIpBan, PersistedPeer, ScoreUpdateResult,
+};
 use rand::seq::SliceRandom;
 use slog::{debug, error, trace, warn};
 use smallvec::SmallVec;
@@ -128,6 +130,10 @@ pub enum PeerManagerEvent {
     Banned(PeerId, Vec<IpAddr>),
     /// The peer should be unbanned with the associated ip addresses.
     UnBanned(PeerId, Vec<IpAddr>),
+    /// Inform the behaviour to ban an ip address, independently of any peer.
+    BannedIp(IpAddr),
+    /// The ip address should be unbanned.
+    UnBannedIp(IpAddr),
     /// Request the behaviour to discover more peers and the amount of peers to discover.
     DiscoverPeers(usize),
     /// Request the behaviour to discover peers on subnets.
@@ -343,6 +349,82 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         to_dial_peers
     }
 
+    /// Restores the scores of peers persisted by a previous run, which stopped `offline` ago.
+    ///
+    /// Peers whose ban has not yet expired are banned again at the swarm level.
+    pub fn restore_persisted_peers(&mut self, peers: Vec<PersistedPeer>, offline: Duration) {
+        let persisted = peers.len();
+        let mut banned = 0;
+        for persisted_peer in peers {
+            let peer_id = persisted_peer.peer_id;
+            let ban_operation = self
+                .network_globals
+                .peers
+                .write()
+                .restore_peer(persisted_peer, offline);
+            if let Some(ban_operation) = ban_operation {
+                banned += 1;
+                self.handle_ban_operation(&peer_id, ban_operation, None);
+            }
+        }
+
+        debug!(self.log, "Restored persisted peer scores"; "persisted" => persisted, "banned" => banned);
+    }
+
+    /// Restores the IP addresses banned by request in a previous run, which stopped `offline` ago.
+    pub fn restore_persisted_ip_bans(&mut self, ip_bans: Vec<PersistedIpBan>, offline: Duration) {
+        for PersistedIpBan { ip, ban_remaining } in ip_bans {
+            if let Some(ban_remaining) = ban_remaining
+                .checked_sub(offline)
+                .filter(|remaining| !remaining.is_zero())
+            {
+                self.ban_ip_for(ip, ban_remaining);
+            }
+        }
+    }
+
+    /// Bans a peer by request for `duration`, disconnecting it if it is connected.
+    pub fn ban_peer_for(&mut self, peer_id: &PeerId, duration: Duration) {
+        let action = self
+            .network_globals
+            .peers
+            .write()
+            .ban_peer_for(peer_id, duration);
+        self.handle_score_action(peer_id, action, Some(GoodbyeReason::Banned));
+    }
+
+    /// Lifts any ban on a peer, resetting its score.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        let action = self.network_globals.peers.write().unban_peer(peer_id);
+        self.handle_score_action(peer_id, action, None);
+    }
+
+    /// Bans an ip address by request for `duration`, disconnecting any peers connected from it.
+    pub fn ban_ip_for(&mut self, ip: IpAddr, duration: Duration) {
+        let peers_to_disconnect = self.network_globals.peers.write().ban_ip_for(ip, duration);
+        self.events.push(PeerManagerEvent::BannedIp(ip));
+        for peer_id in peers_to_disconnect {
+            self.disconnect_peer(peer_id, GoodbyeReason::BannedIP);
+        }
+    }
+
+    /// Lifts the ban on an ip address which was banned by request.
+    pub fn unban_ip(&mut self, ip: &IpAddr) {
+        if self.network_globals.peers.write().unban_ip(ip) {
+            self.events.push(PeerManagerEvent::UnBannedIp(*ip));
+        }
+    }
+
+    /// Adds or removes a peer from the set of trusted peers, which are exempt from scoring.
+    pub fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) {
+        let action = self
+            .network_globals
+            .peers
+            .write()
+            .set_trusted(peer_id, trusted);
+        self.handle_score_action(peer_id, action, None);
+    }
+
     /// A STATUS message has been received from a peer. This resets the status timer.
     pub fn peer_statusd(&mut self, peer_id: &PeerId) {
         self.status_peers.insert(*peer_id);
@@ -535,6 +617,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRange => PeerAction::MidToleranceError,
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => PeerAction::LowToleranceError,
+                    Protocol::LightClientUpdatesByRange => PeerAction::LowToleranceError,
                     Protocol::Goodbye => PeerAction::LowToleranceError,
                     Protocol::MetaData => PeerAction::LowToleranceError,
                     Protocol::Status => PeerAction::LowToleranceError,
@@ -552,6 +635,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRoot => return,
                     Protocol::Goodbye => return,
                     Protocol::LightClientBootstrap => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::MetaData => PeerAction::LowToleranceError,
                     Protocol::Status => PeerAction::LowToleranceError,
                 }
@@ -567,6 +651,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRange => PeerAction::MidToleranceError,
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::MetaData => return,
                     Protocol::Status => return,
@@ -828,7 +913,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     }
 
     // Gracefully disconnects a peer without banning them.
-    fn disconnect_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
+    pub fn disconnect_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
         self.events
             .push(PeerManagerEvent::DisconnectPeer(peer_id, reason));
         self.network_globals
@@ -1186,6 +1271,12 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         // Unban any peers that have served their temporary ban timeout
         self.unban_temporary_banned_peers();
 
+        // Unban any ip addresses whose ban by request has expired
+        let unbanned_ips = self.network_globals.peers.write().remove_expired_ip_bans();
+        for ip in unbanned_ips {
+            self.events.push(PeerManagerEvent::UnBannedIp(ip));
+        }
+
         // Maintains memory by shrinking mappings
         self.shrink_mappings();
     }
@@ -15,7 +15,7 @@ use crate::rpc::GoodbyeReason;
 use crate::types::SyncState;
 
 use super::peerdb::BanResult;
-use super::{ConnectingType, PeerManager, PeerManagerEvent, ReportSource};
+use super::{ConnectingType, PeerConnectionStatus, PeerManager, PeerManagerEvent, ReportSource};
 
 impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
     type ConnectionHandler = ConnectionHandler;
@@ -113,9 +113,10 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
             }) => self.on_connection_established(peer_id, endpoint, other_established),
             FromSwarm::ConnectionClosed(ConnectionClosed {
                 peer_id,
+                endpoint,
                 remaining_established,
                 ..
-            }) => self.on_connection_closed(peer_id, remaining_established),
+            }) => self.on_connection_closed(peer_id, endpoint, remaining_established),
             FromSwarm::DialFailure(DialFailure { peer_id, .. }) => self.on_dial_failure(peer_id),
             FromSwarm::AddressChange(_)
             | FromSwarm::ListenFailure(_)
@@ -145,6 +146,24 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             self.events.push(PeerManagerEvent::MetaData(peer_id));
         }
 
+        // Track the connection per transport, along with the time taken to dial the peer.
+        let transport = metrics::transport_name(endpoint.get_remote_address());
+        metrics::inc_gauge_vec(&metrics::CONNECTIONS_PER_TRANSPORT, &[transport]);
+        if endpoint.is_dialer() {
+            if let Some(PeerConnectionStatus::Dialing { since }) = self
+                .network_globals
+                .peers
+                .read()
+                .connection_status(&peer_id)
+            {
+                metrics::observe_timer_vec(
+                    &metrics::DIAL_LATENCY_PER_TRANSPORT,
+                    &[transport],
+                    since.elapsed(),
+                );
+            }
+        }
+
         // Check NAT if metrics are enabled
         if self.network_globals.local_enr.read().udp4().is_some() {
             metrics::check_nat();
@@ -206,7 +225,17 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         metrics::inc_counter(&metrics::PEER_CONNECT_EVENT_COUNT);
     }
 
-    fn on_connection_closed(&mut self, peer_id: PeerId, remaining_established: usize) {
+    fn on_connection_closed(
+        &mut self,
+        peer_id: PeerId,
+        endpoint: &ConnectedPoint,
+        remaining_established: usize,
+    ) {
+        metrics::dec_gauge_vec(
+            &metrics::CONNECTIONS_PER_TRANSPORT,
+            &[metrics::transport_name(endpoint.get_remote_address())],
+        );
+
         if remaining_established > 0 {
             return;
         }
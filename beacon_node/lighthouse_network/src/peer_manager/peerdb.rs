@@ -6,12 +6,12 @@ use crate::{
 };
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
-use score::{PeerAction, ReportSource, Score, ScoreState};
+use score::{ban_expiry, PeerAction, ReportSource, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::net::{IpAddr, SocketAddr};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use sync_status::SyncStatus;
 use types::EthSpec;
 
@@ -32,6 +32,11 @@ const ALLOWED_NEGATIVE_GOSSIPSUB_FACTOR: f32 = 0.1;
 /// The time we allow peers to be in the dialing state in our PeerDb before we revert them to a
 /// disconnected state.
 const DIAL_TIMEOUT: u64 = 15;
+/// The maximum number of peer scores to persist across restarts.
+const MAX_PERSISTED_PEERS: usize = MAX_BANNED_PEERS;
+/// Scores closer to zero than this are neither persisted nor restored, as they have all but
+/// decayed.
+const MIN_PERSISTED_SCORE: f64 = 1.0;
 
 /// Storage of known peers, their reputation and information
 pub struct PeerDB<TSpec: EthSpec> {
@@ -41,6 +46,8 @@ pub struct PeerDB<TSpec: EthSpec> {
     disconnected_peers: usize,
     /// Counts banned peers in total and per ip
     banned_peers_count: BannedPeersCount,
+    /// IP addresses banned by request, along with the time at which their ban expires.
+    manually_banned_ips: HashMap<IpAddr, Instant>,
     /// PeerDB's logger
     log: slog::Logger,
 }
@@ -56,6 +63,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             log: log.clone(),
             disconnected_peers: 0,
             banned_peers_count: BannedPeersCount::default(),
+            manually_banned_ips: HashMap::new(),
             peers,
         }
     }
@@ -162,13 +170,31 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
 
     /// Checks if the peer's known addresses are currently banned.
     fn ip_is_banned(&self, peer: &PeerInfo<TSpec>) -> Option<IpAddr> {
-        peer.seen_ip_addresses()
-            .find(|ip| self.banned_peers_count.ip_is_banned(ip))
+        peer.seen_ip_addresses().find(|ip| self.is_ip_banned(ip))
     }
 
     /// Returns true if the IP is banned.
     pub fn is_ip_banned(&self, ip: &IpAddr) -> bool {
-        self.banned_peers_count.ip_is_banned(ip)
+        self.banned_peers_count.ip_is_banned(ip) || self.manually_banned_ips.contains_key(ip)
+    }
+
+    /// Returns the banned IP addresses, along with the time remaining for those banned by
+    /// request.
+    pub fn banned_ips(&self) -> Vec<(IpAddr, Option<Duration>)> {
+        let now = Instant::now();
+        let mut banned_ips = self
+            .manually_banned_ips
+            .iter()
+            .map(|(ip, until)| (*ip, Some(until.saturating_duration_since(now))))
+            .collect::<Vec<_>>();
+        banned_ips.extend(
+            self.banned_peers_count
+                .banned_ips()
+                .into_iter()
+                .filter(|ip| !self.manually_banned_ips.contains_key(ip))
+                .map(|ip| (ip, None)),
+        );
+        banned_ips
     }
 
     /// Returns true if the Peer is either banned or in the disconnected state.
@@ -465,38 +491,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
 
         for (peer_id, action) in actions {
-            let result = match action {
-                ScoreTransitionResult::Banned => {
-                    // The peer was banned as a result of this action.
-                    self.update_connection_state(&peer_id, NewConnectionState::Banned)
-                        .into()
-                }
-                ScoreTransitionResult::Disconnected => {
-                    // The peer needs to be disconnected
-
-                    // Update the state
-                    self.update_connection_state(
-                        &peer_id,
-                        NewConnectionState::Disconnecting { to_ban: false },
-                    );
-                    ScoreUpdateResult::Disconnect
-                }
-                ScoreTransitionResult::NoAction => ScoreUpdateResult::NoAction,
-                ScoreTransitionResult::Unbanned => {
-                    self.update_connection_state(&peer_id, NewConnectionState::Unbanned);
-                    let seen_ip_addresses = self
-                        .peers
-                        .get(&peer_id)
-                        .map(|info| {
-                            info.seen_ip_addresses()
-                                .filter(|ip| !self.is_ip_banned(ip))
-                                .collect::<Vec<_>>()
-                        })
-                        .unwrap_or_default();
-
-                    ScoreUpdateResult::Unbanned(seen_ip_addresses)
-                }
-            };
+            let result = self.apply_score_transition(&peer_id, action);
 
             // Actions to be handled by the peer manager for each peer id
             if !matches!(result, ScoreUpdateResult::NoAction) {
@@ -582,6 +577,96 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Bans a peer by request for `duration`, after which its score decays as usual.
+    ///
+    /// Trusted peers cannot be banned.
+    #[must_use = "Banned and disconnected peers need to be handled in libp2p"]
+    pub(super) fn ban_peer_for(
+        &mut self,
+        peer_id: &PeerId,
+        duration: Duration,
+    ) -> ScoreUpdateResult {
+        if !self.peers.contains_key(peer_id) {
+            // Peers which are banned before they are seen are treated as disconnected.
+            let mut info = PeerInfo::default();
+            info.set_connection_status(PeerConnectionStatus::Disconnected {
+                since: Instant::now(),
+            });
+            self.peers.insert(*peer_id, info);
+            self.disconnected_peers = self.disconnected_peers.saturating_add(1);
+        }
+        let info = self.peers.entry(*peer_id).or_default();
+        if info.is_trusted() {
+            warn!(self.log, "Ignoring request to ban a trusted peer"; "peer_id" => %peer_id);
+            return ScoreUpdateResult::NoAction;
+        }
+        let previous_state = info.score_state();
+        info.set_score(Score::banned_for(duration));
+        debug!(self.log, "Banning peer by request"; "peer_id" => %peer_id, "duration" => ?duration);
+        let transition = Self::handle_score_transition(previous_state, peer_id, info, &self.log);
+        self.apply_score_transition(peer_id, transition)
+    }
+
+    /// Lifts any ban on a peer by resetting its score.
+    #[must_use = "Unbanned peers need to be handled in libp2p"]
+    pub(super) fn unban_peer(&mut self, peer_id: &PeerId) -> ScoreUpdateResult {
+        match self.peers.get_mut(peer_id) {
+            Some(info) => {
+                let previous_state = info.score_state();
+                info.set_score(Score::default());
+                let transition =
+                    Self::handle_score_transition(previous_state, peer_id, info, &self.log);
+                self.apply_score_transition(peer_id, transition)
+            }
+            None => ScoreUpdateResult::NoAction,
+        }
+    }
+
+    /// Adds or removes a peer from the set of trusted peers, whose score is not tracked.
+    #[must_use = "Unbanned peers need to be handled in libp2p"]
+    pub(super) fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) -> ScoreUpdateResult {
+        if !trusted && !self.peers.contains_key(peer_id) {
+            return ScoreUpdateResult::NoAction;
+        }
+        let info = self.peers.entry(*peer_id).or_default();
+        let previous_state = info.score_state();
+        info.set_trusted(trusted);
+        let transition = Self::handle_score_transition(previous_state, peer_id, info, &self.log);
+        self.apply_score_transition(peer_id, transition)
+    }
+
+    /// Bans an IP address by request for `duration`. Returns the connected peers which have been
+    /// seen on the address, which should be disconnected.
+    pub(super) fn ban_ip_for(&mut self, ip: IpAddr, duration: Duration) -> Vec<PeerId> {
+        self.manually_banned_ips
+            .insert(ip, ban_expiry(Instant::now(), duration));
+        self.peers
+            .iter()
+            .filter(|(_, info)| {
+                info.is_connected_or_dialing() && info.seen_ip_addresses().any(|seen| seen == ip)
+            })
+            .map(|(peer_id, _)| *peer_id)
+            .collect()
+    }
+
+    /// Lifts the ban on an IP address which was banned by request. Returns `true` if the address
+    /// is no longer banned.
+    pub(super) fn unban_ip(&mut self, ip: &IpAddr) -> bool {
+        self.manually_banned_ips.remove(ip).is_some() && !self.is_ip_banned(ip)
+    }
+
+    /// Removes IP addresses whose ban has expired, returning those which are no longer banned.
+    pub(super) fn remove_expired_ip_bans(&mut self) -> Vec<IpAddr> {
+        let now = Instant::now();
+        let expired = self
+            .manually_banned_ips
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(ip, _)| *ip)
+            .collect::<Vec<_>>();
+        expired.into_iter().filter(|ip| self.unban_ip(ip)).collect()
+    }
+
     /// Update min ttl of a peer.
     // VISIBILITY: Only the peer manager can update the min_ttl
     pub(super) fn update_min_ttl(&mut self, peer_id: &PeerId, min_ttl: Instant) {
@@ -1004,6 +1089,84 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         self.update_connection_state(peer_id, NewConnectionState::Disconnecting { to_ban });
     }
 
+    /// Returns the scores of peers to persist across restarts, worst first.
+    ///
+    /// Trusted peers and peers whose score has decayed are excluded, and at most
+    /// `MAX_PERSISTED_PEERS` are returned.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        let now = Instant::now();
+        let mut persisted_peers = self
+            .peers
+            .iter()
+            .filter_map(|(peer_id, info)| {
+                let (score, ban_remaining) = info.score().persisted_state(now)?;
+                (score.abs() >= MIN_PERSISTED_SCORE).then(|| PersistedPeer {
+                    peer_id: *peer_id,
+                    score,
+                    ban_remaining,
+                    seen_addresses: info.seen_addresses().copied().collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+        persisted_peers.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal));
+        persisted_peers.truncate(MAX_PERSISTED_PEERS);
+        persisted_peers
+    }
+
+    /// Returns the IP addresses banned by request to persist across restarts.
+    pub fn persisted_ip_bans(&self) -> Vec<PersistedIpBan> {
+        let now = Instant::now();
+        self.manually_banned_ips
+            .iter()
+            .filter(|(_, until)| **until > now)
+            .map(|(ip, until)| PersistedIpBan {
+                ip: *ip,
+                ban_remaining: until.saturating_duration_since(now),
+            })
+            .collect()
+    }
+
+    /// Restores the score of a peer persisted by a previous run, which stopped `offline` ago. The
+    /// peer is banned if its ban has not yet expired.
+    ///
+    /// Peers which are already known, or whose score has since decayed, are ignored.
+    // VISIBILITY: Only the peer manager can restore peers, as it must process the ban.
+    #[must_use = "Banned peers need to be reported to libp2p."]
+    pub(super) fn restore_peer(
+        &mut self,
+        persisted_peer: PersistedPeer,
+        offline: Duration,
+    ) -> Option<BanOperation> {
+        let PersistedPeer {
+            peer_id,
+            score,
+            ban_remaining,
+            seen_addresses,
+        } = persisted_peer;
+        if self.peers.contains_key(&peer_id) {
+            return None;
+        }
+        let score = Score::restore(score, ban_remaining, offline);
+        if score.score().abs() < MIN_PERSISTED_SCORE {
+            return None;
+        }
+
+        let mut info = PeerInfo::default();
+        info.restore(score, seen_addresses);
+        info.set_connection_status(PeerConnectionStatus::Disconnected {
+            since: Instant::now(),
+        });
+        let banned = matches!(info.score_state(), ScoreState::Banned);
+        self.peers.insert(peer_id, info);
+        self.disconnected_peers = self.disconnected_peers.saturating_add(1);
+
+        if banned {
+            self.update_connection_state(&peer_id, NewConnectionState::Banned)
+        } else {
+            None
+        }
+    }
+
     /// Removes banned and disconnected peers from the DB if we have reached any of our limits.
     /// Drops the peers with the lowest reputation so that the number of disconnected peers is less
     /// than MAX_DC_PEERS
@@ -1074,6 +1237,47 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         unbanned_peers
     }
 
+    /// Updates the connection state of a peer following a score transition, returning the action
+    /// the peer manager must take.
+    fn apply_score_transition(
+        &mut self,
+        peer_id: &PeerId,
+        transition: ScoreTransitionResult,
+    ) -> ScoreUpdateResult {
+        match transition {
+            ScoreTransitionResult::Banned => {
+                // The peer was banned as a result of this action.
+                self.update_connection_state(peer_id, NewConnectionState::Banned)
+                    .into()
+            }
+            ScoreTransitionResult::Disconnected => {
+                // The peer needs to be disconnected
+
+                // Update the state
+                self.update_connection_state(
+                    peer_id,
+                    NewConnectionState::Disconnecting { to_ban: false },
+                );
+                ScoreUpdateResult::Disconnect
+            }
+            ScoreTransitionResult::NoAction => ScoreUpdateResult::NoAction,
+            ScoreTransitionResult::Unbanned => {
+                self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+                let seen_ip_addresses = self
+                    .peers
+                    .get(peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| !self.is_ip_banned(ip))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+
+                ScoreUpdateResult::Unbanned(seen_ip_addresses)
+            }
+        }
+    }
+
     /// This handles score transitions between states. It transitions peers states from
     /// disconnected/banned/connected.
     fn handle_score_transition(
@@ -1216,6 +1420,27 @@ impl BanResult {
     }
 }
 
+/// The score of a peer, persisted across restarts so that we do not reconnect to peers we have
+/// recently banned or disconnected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistedPeer {
+    pub peer_id: PeerId,
+    /// The lighthouse component of the peer's score.
+    pub score: f64,
+    /// The time remaining before the score of a banned peer begins to decay.
+    pub ban_remaining: Duration,
+    /// The addresses the peer has been seen on, from which IP bans are derived.
+    pub seen_addresses: Vec<SocketAddr>,
+}
+
+/// An IP address banned by request, persisted across restarts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistedIpBan {
+    pub ip: IpAddr,
+    /// The time remaining before the ban expires.
+    pub ban_remaining: Duration,
+}
+
 #[derive(Default)]
 pub struct BannedPeersCount {
     /// The number of banned peers in the database.
@@ -1994,6 +2219,142 @@ mod tests {
         assert!(pdb.ban_status(&p2).is_banned());
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_restore_persisted_peers() {
+        let mut pdb = get_db();
+        let ip1 = Ipv4Addr::new(1, 2, 3, 4).into();
+        let ip2 = Ipv4Addr::new(1, 2, 3, 5).into();
+
+        let mut banned_peers = Vec::new();
+        for _ in 0..BANNED_PEERS_PER_IP_THRESHOLD + 1 {
+            let peer = connect_peer_with_ips(&mut pdb, vec![ip1]);
+            let _ = pdb.report_peer(&peer, PeerAction::Fatal, ReportSource::PeerManager, "");
+            pdb.inject_disconnect(&peer);
+            banned_peers.push(peer);
+        }
+        let penalized_peer = connect_peer_with_ips(&mut pdb, vec![ip2]);
+        add_score(&mut pdb, &penalized_peer, -10.0);
+        let unscored_peer = connect_peer_with_ips(&mut pdb, vec![ip2]);
+
+        let persisted_peers = pdb.persisted_peers();
+        assert_eq!(persisted_peers.len(), banned_peers.len() + 1);
+        assert!(persisted_peers.iter().all(|p| p.peer_id != unscored_peer));
+
+        let mut restored_pdb = get_db();
+        for persisted_peer in persisted_peers {
+            let _ = restored_pdb.restore_peer(persisted_peer, Duration::ZERO);
+        }
+
+        for peer in &banned_peers {
+            assert!(restored_pdb.ban_status(peer).is_banned());
+        }
+        assert!(!restored_pdb.ban_status(&penalized_peer).is_banned());
+        assert_eq!(
+            restored_pdb.score(&penalized_peer),
+            pdb.score(&penalized_peer)
+        );
+        assert!(restored_pdb.peer_info(&unscored_peer).is_none());
+
+        // The IP of the banned peers is banned again.
+        let new_peer = connect_peer_with_ips(&mut restored_pdb, vec![ip1]);
+        assert!(restored_pdb.ban_status(&new_peer).is_banned());
+    }
+
+    #[test]
+    fn test_ban_peer_by_request() {
+        let mut pdb = get_db();
+        let ip = Ipv4Addr::new(1, 2, 3, 4).into();
+        let connected_peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+        let unknown_peer = PeerId::random();
+
+        // A connected peer must be disconnected before it is banned.
+        assert!(matches!(
+            pdb.ban_peer_for(&connected_peer, Duration::from_secs(60)),
+            ScoreUpdateResult::Ban(BanOperation::DisconnectThePeer)
+        ));
+        pdb.inject_disconnect(&connected_peer);
+        assert!(pdb.ban_status(&connected_peer).is_banned());
+        assert!(pdb.peer_info(&connected_peer).unwrap().is_banned());
+
+        // Unknown peers can be banned pre-emptively.
+        assert!(matches!(
+            pdb.ban_peer_for(&unknown_peer, Duration::from_secs(60)),
+            ScoreUpdateResult::Ban(BanOperation::ReadyToBan(_))
+        ));
+        assert!(pdb.ban_status(&unknown_peer).is_banned());
+
+        assert!(matches!(
+            pdb.unban_peer(&connected_peer),
+            ScoreUpdateResult::Unbanned(_)
+        ));
+        assert!(!pdb.ban_status(&connected_peer).is_banned());
+        assert!(!pdb.peer_info(&connected_peer).unwrap().is_banned());
+    }
+
+    #[test]
+    fn test_ban_ip_by_request() {
+        let mut pdb = get_db();
+        let ip1 = Ipv4Addr::new(1, 2, 3, 4).into();
+        let ip2 = Ipv4Addr::new(1, 2, 3, 5).into();
+        let p1 = connect_peer_with_ips(&mut pdb, vec![ip1]);
+        let p2 = connect_peer_with_ips(&mut pdb, vec![ip2]);
+
+        assert_eq!(pdb.ban_ip_for(ip1, Duration::from_secs(60)), vec![p1]);
+        assert!(pdb.is_ip_banned(&ip1));
+        assert!(pdb.ban_status(&p1).is_banned());
+        assert!(!pdb.ban_status(&p2).is_banned());
+        assert_eq!(pdb.banned_ips().len(), 1);
+
+        // Unexpired bans are kept.
+        assert!(pdb.remove_expired_ip_bans().is_empty());
+        assert!(pdb.unban_ip(&ip1));
+        assert!(!pdb.ban_status(&p1).is_banned());
+        assert!(!pdb.unban_ip(&ip1));
+
+        // Expired bans are removed.
+        let _ = pdb.ban_ip_for(ip2, Duration::ZERO);
+        assert_eq!(pdb.remove_expired_ip_bans(), vec![ip2]);
+        assert!(!pdb.is_ip_banned(&ip2));
+
+        // Overly long bans are limited rather than overflowing.
+        let _ = pdb.ban_ip_for(ip2, Duration::MAX);
+        assert!(pdb.is_ip_banned(&ip2));
+        let persisted_ip_bans = pdb.persisted_ip_bans();
+        assert_eq!(persisted_ip_bans.len(), 1);
+        assert_eq!(persisted_ip_bans[0].ip, ip2);
+        assert!(persisted_ip_bans[0].ban_remaining <= score::MAX_BAN_DURATION);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_set_trusted_peer() {
+        let mut pdb = get_db();
+        let peer = connect_peer_with_ips(&mut pdb, vec![Ipv4Addr::new(1, 2, 3, 4).into()]);
+        let _ = pdb.ban_peer_for(&peer, Duration::from_secs(60));
+        pdb.inject_disconnect(&peer);
+        assert!(pdb.ban_status(&peer).is_banned());
+
+        // Trusting a banned peer lifts its ban.
+        assert!(matches!(
+            pdb.set_trusted(&peer, true),
+            ScoreUpdateResult::Unbanned(_)
+        ));
+        assert!(pdb.peer_info(&peer).unwrap().is_trusted());
+        assert!(!pdb.ban_status(&peer).is_banned());
+
+        // Trusted peers cannot be banned.
+        assert!(matches!(
+            pdb.ban_peer_for(&peer, Duration::from_secs(60)),
+            ScoreUpdateResult::NoAction
+        ));
+        assert!(!pdb.ban_status(&peer).is_banned());
+
+        let _ = pdb.set_trusted(&peer, false);
+        assert!(!pdb.peer_info(&peer).unwrap().is_trusted());
+        assert_eq!(pdb.score(&peer), 0.0);
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn test_trusted_peers_score() {
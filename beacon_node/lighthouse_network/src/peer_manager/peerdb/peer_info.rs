@@ -372,6 +372,35 @@ impl<T: EthSpec> PeerInfo<T> {
         self.subnets.clear()
     }
 
+    /// Restores the score and seen addresses of a peer persisted by a previous run.
+    pub(super) fn restore(
+        &mut self,
+        score: Score,
+        seen_addresses: impl IntoIterator<Item = SocketAddr>,
+    ) {
+        self.score = score;
+        self.seen_addresses.extend(seen_addresses);
+    }
+
+    /// Sets the score of a non-trusted peer.
+    pub(super) fn set_score(&mut self, score: Score) {
+        if !self.is_trusted {
+            self.score = score;
+        }
+    }
+
+    /// Marks the peer as trusted, exempting it from scoring, or returns it to the default score.
+    pub(super) fn set_trusted(&mut self, trusted: bool) {
+        if self.is_trusted != trusted {
+            self.is_trusted = trusted;
+            self.score = if trusted {
+                Score::max_score()
+            } else {
+                Score::default()
+            };
+        }
+    }
+
     /// Applies decay rates to a non-trusted peer's score.
     pub(super) fn score_update(&mut self) {
         if !self.is_trusted {
@@ -32,6 +32,8 @@ const MIN_SCORE: f64 = -100.0;
 const SCORE_HALFLIFE: f64 = 600.0;
 /// The number of seconds we ban a peer for before their score begins to decay.
 const BANNED_BEFORE_DECAY: Duration = Duration::from_secs(12 * 3600); // 12 hours
+/// The longest that a peer or IP address may be banned for by request.
+pub const MAX_BAN_DURATION: Duration = Duration::from_secs(365 * 24 * 3600); // 1 year
 
 /// We weight negative gossipsub scores in such a way that they never result in a disconnect by
 /// themselves. This "solves" the problem of non-decaying gossipsub scores for disconnected peers.
@@ -259,6 +261,58 @@ impl RealScore {
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.gossipsub_score >= 0.0
     }
+
+    /// Restores a score persisted by a previous run, which stopped `offline` ago.
+    ///
+    /// Any ban remaining when the score was persisted continues to elapse whilst offline, after
+    /// which the score decays as usual.
+    fn restore(lighthouse_score: f64, ban_remaining: Duration, offline: Duration) -> Self {
+        let now = Instant::now();
+        let mut score = RealScore {
+            lighthouse_score,
+            last_updated: now,
+            ..Default::default()
+        };
+        match ban_remaining.checked_sub(offline) {
+            // The peer remains banned for the rest of the ban period.
+            Some(ban_remaining) => score.last_updated = ban_expiry(now, ban_remaining),
+            None => {
+                let secs_since_update = (offline - ban_remaining).as_secs();
+                score.lighthouse_score *= (*HALFLIFE_DECAY * secs_since_update as f64).exp();
+            }
+        }
+        // NOTE: `update_state` is not used, as it would extend the ban of a banned peer.
+        score.recompute_score();
+        score
+    }
+
+    /// Returns a score which is banned for `duration`, after which it decays as usual.
+    fn banned_for(duration: Duration) -> Self {
+        let mut score = RealScore {
+            lighthouse_score: MIN_LIGHTHOUSE_SCORE_BEFORE_BAN,
+            last_updated: ban_expiry(Instant::now(), duration),
+            ..Default::default()
+        };
+        score.recompute_score();
+        score
+    }
+
+    /// Returns the lighthouse score along with the time remaining, from `now`, before the score
+    /// of a banned peer begins to decay.
+    fn persisted_state(&self, now: Instant) -> (f64, Duration) {
+        (
+            self.lighthouse_score,
+            self.last_updated.saturating_duration_since(now),
+        )
+    }
+}
+
+/// Returns the time at which a ban of `duration` from `now` expires.
+///
+/// The duration is limited to `MAX_BAN_DURATION`, so that the result cannot overflow.
+pub(crate) fn ban_expiry(now: Instant, duration: Duration) -> Instant {
+    now.checked_add(std::cmp::min(duration, MAX_BAN_DURATION))
+        .unwrap_or(now)
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize)]
@@ -324,6 +378,35 @@ impl Score {
             Self::Real(score) => score.is_good_gossipsub_peer(),
         }
     }
+
+    /// Restores a score persisted by a previous run, which stopped `offline` ago.
+    pub(crate) fn restore(
+        lighthouse_score: f64,
+        ban_remaining: Duration,
+        offline: Duration,
+    ) -> Self {
+        Self::Real(RealScore::restore(lighthouse_score, ban_remaining, offline))
+    }
+
+    /// Returns a score which is banned for `duration`, after which it decays as usual.
+    pub(crate) fn banned_for(duration: Duration) -> Self {
+        Self::Real(RealScore::banned_for(duration))
+    }
+
+    /// Returns the time remaining before the score of a banned peer begins to decay.
+    pub fn ban_remaining(&self) -> Duration {
+        self.persisted_state(Instant::now())
+            .map_or(Duration::ZERO, |(_, ban_remaining)| ban_remaining)
+    }
+
+    /// Returns the lighthouse score and the remaining ban time to persist across restarts, or
+    /// `None` for the scores of trusted peers.
+    pub(crate) fn persisted_state(&self, now: Instant) -> Option<(f64, Duration)> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some(score.persisted_state(now)),
+        }
+    }
 }
 
 impl Eq for Score {}
@@ -397,6 +480,64 @@ mod tests {
         assert!(score.score() > MIN_SCORE_BEFORE_BAN);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_restore() {
+        let mut score = RealScore::default();
+        score.test_add(MIN_SCORE_BEFORE_BAN);
+        let (lighthouse_score, ban_remaining) = score.persisted_state(Instant::now());
+        assert_eq!(lighthouse_score, MIN_SCORE_BEFORE_BAN);
+        assert!(ban_remaining > BANNED_BEFORE_DECAY - Duration::from_secs(1));
+
+        // The ban continues whilst offline.
+        let offline = Duration::from_secs(3600);
+        let restored = RealScore::restore(lighthouse_score, ban_remaining, offline);
+        assert_eq!(restored.score(), MIN_SCORE_BEFORE_BAN);
+        let (_, restored_ban_remaining) = restored.persisted_state(Instant::now());
+        assert!(restored_ban_remaining <= ban_remaining - offline);
+        assert!(restored_ban_remaining > ban_remaining - offline - Duration::from_secs(1));
+
+        // The score decays once the ban has expired.
+        let restored = RealScore::restore(
+            lighthouse_score,
+            ban_remaining,
+            ban_remaining + Duration::from_secs(SCORE_HALFLIFE as u64),
+        );
+        assert!((restored.score() - MIN_SCORE_BEFORE_BAN / 2.0).abs() < 1e-9);
+        assert_eq!(
+            restored.persisted_state(Instant::now()).1,
+            Duration::from_secs(0)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_banned_for() {
+        let now = Instant::now();
+        let duration = Duration::from_secs(3600);
+        let mut score = RealScore::banned_for(duration);
+        assert_eq!(Score::Real(score.clone()).state(), ScoreState::Banned);
+
+        // The score does not decay during the ban.
+        score.update_at(now + duration - Duration::from_secs(1));
+        assert_eq!(score.score(), MIN_LIGHTHOUSE_SCORE_BEFORE_BAN);
+
+        // The ban is lifted shortly after the ban expires.
+        score.update_at(now + duration + Duration::from_secs(SCORE_HALFLIFE as u64 / 3));
+        assert_eq!(Score::Real(score).state(), ScoreState::Disconnected);
+    }
+
+    #[test]
+    fn test_banned_for_too_long() {
+        let score = RealScore::banned_for(Duration::MAX);
+        let (_, ban_remaining) = score.persisted_state(Instant::now());
+        assert!(ban_remaining <= MAX_BAN_DURATION);
+        assert!(ban_remaining > MAX_BAN_DURATION - Duration::from_secs(60));
+
+        let restored = RealScore::restore(MIN_SCORE, Duration::MAX, Duration::ZERO);
+        assert!(restored.persisted_state(Instant::now()).1 <= MAX_BAN_DURATION);
+    }
+
     #[test]
     fn test_very_negative_gossipsub_score() {
         let mut score = Score::default();
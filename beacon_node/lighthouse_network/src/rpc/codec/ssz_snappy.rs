@@ -16,6 +16,7 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder};
 use types::light_client_bootstrap::LightClientBootstrap;
+use types::light_client_update::LightClientUpdate;
 use types::{
     EthSpec, ForkContext, ForkName, Hash256, SignedBeaconBlock, SignedBeaconBlockAltair,
     SignedBeaconBlockBase, SignedBeaconBlockCapella, SignedBeaconBlockMerge,
@@ -72,6 +73,7 @@ impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZSnappyInboundCodec<
                 RPCResponse::BlocksByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
                 RPCResponse::LightClientBootstrap(res) => res.as_ssz_bytes(),
+                RPCResponse::LightClientUpdatesByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
                 RPCResponse::MetaData(res) =>
                 // Encode the correct version of the MetaData response based on the negotiated version.
@@ -233,6 +235,7 @@ impl<TSpec: EthSpec> Encoder<OutboundRequest<TSpec>> for SSZSnappyOutboundCodec<
             OutboundRequest::Ping(req) => req.as_ssz_bytes(),
             OutboundRequest::MetaData(_) => return Ok(()), // no metadata to encode
             OutboundRequest::LightClientBootstrap(req) => req.as_ssz_bytes(),
+            OutboundRequest::LightClientUpdatesByRange(req) => req.as_ssz_bytes(),
         };
         // SSZ encoded bytes should be within `max_packet_size`
         if bytes.len() > self.max_packet_size {
@@ -313,7 +316,11 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyOutboundCodec<TSpec> {
                 let _read_bytes = src.split_to(n as usize);
 
                 match self.protocol.version {
-                    Version::V1 => handle_v1_response(self.protocol.message_name, &decoded_buffer),
+                    Version::V1 => handle_v1_response(
+                        self.protocol.message_name,
+                        &decoded_buffer,
+                        &mut self.fork_name,
+                    ),
                     Version::V2 => handle_v2_response(
                         self.protocol.message_name,
                         &decoded_buffer,
@@ -404,6 +411,16 @@ fn context_bytes<T: EthSpec>(
     // Add the context bytes if required
     if protocol.has_context_bytes() {
         if let RPCCodedResponse::Success(rpc_variant) = resp {
+            // The context of light client data is the fork at the slot of its header.
+            if let RPCResponse::LightClientBootstrap(bootstrap) = rpc_variant {
+                return fork_context
+                    .to_context_bytes(fork_context.fork_name_at_slot::<T>(bootstrap.header.slot));
+            }
+            if let RPCResponse::LightClientUpdatesByRange(update) = rpc_variant {
+                return fork_context.to_context_bytes(
+                    fork_context.fork_name_at_slot::<T>(update.attested_header.slot),
+                );
+            }
             if let RPCResponse::BlocksByRange(ref_box_block)
             | RPCResponse::BlocksByRoot(ref_box_block) = rpc_variant
             {
@@ -484,6 +501,9 @@ fn handle_v1_request<T: EthSpec>(
                 root: Hash256::from_ssz_bytes(decoded_buffer)?,
             },
         ))),
+        Protocol::LightClientUpdatesByRange => Ok(Some(InboundRequest::LightClientUpdatesByRange(
+            LightClientUpdatesByRangeRequest::from_ssz_bytes(decoded_buffer)?,
+        ))),
         // MetaData requests return early from InboundUpgrade and do not reach the decoder.
         // Handle this case just for completeness.
         Protocol::MetaData => {
@@ -531,9 +551,14 @@ fn handle_v2_request<T: EthSpec>(
 /// Decodes a `Version::V1` `RPCResponse` from the byte stream.
 /// `decoded_buffer` should be an ssz-encoded bytestream with
 // length = length-prefix received in the beginning of the stream.
+///
+/// The light client protocols carry context bytes in their `Version::V1` responses. The light
+/// client types are identical across all forks since Altair, so the `ForkName` is only consumed
+/// to make sure the context bytes of the next chunk are read.
 fn handle_v1_response<T: EthSpec>(
     protocol: Protocol,
     decoded_buffer: &[u8],
+    fork_name: &mut Option<ForkName>,
 ) -> Result<Option<RPCResponse<T>>, RPCError> {
     match protocol {
         Protocol::Status => Ok(Some(RPCResponse::Status(StatusMessage::from_ssz_bytes(
@@ -555,9 +580,18 @@ fn handle_v1_response<T: EthSpec>(
         Protocol::MetaData => Ok(Some(RPCResponse::MetaData(MetaData::V1(
             MetaDataV1::from_ssz_bytes(decoded_buffer)?,
         )))),
-        Protocol::LightClientBootstrap => Ok(Some(RPCResponse::LightClientBootstrap(
-            LightClientBootstrap::from_ssz_bytes(decoded_buffer)?,
-        ))),
+        Protocol::LightClientBootstrap => {
+            fork_name.take();
+            Ok(Some(RPCResponse::LightClientBootstrap(
+                LightClientBootstrap::from_ssz_bytes(decoded_buffer)?,
+            )))
+        }
+        Protocol::LightClientUpdatesByRange => {
+            fork_name.take();
+            Ok(Some(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                LightClientUpdate::from_ssz_bytes(decoded_buffer)?,
+            ))))
+        }
     }
 }
 
@@ -660,8 +694,9 @@ mod tests {
     };
     use std::sync::Arc;
     use types::{
-        BeaconBlock, BeaconBlockAltair, BeaconBlockBase, BeaconBlockMerge, EmptyBlock, Epoch,
-        ForkContext, FullPayload, Hash256, Signature, SignedBeaconBlock, Slot,
+        BeaconBlock, BeaconBlockAltair, BeaconBlockBase, BeaconBlockHeader, BeaconBlockMerge,
+        EmptyBlock, Epoch, FixedVector, ForkContext, FullPayload, Hash256, Signature,
+        SignedBeaconBlock, Slot, SyncAggregate, SyncCommittee,
     };
 
     use snap::write::FrameEncoder;
@@ -760,6 +795,33 @@ mod tests {
         Ping { data: 1 }
     }
 
+    fn lc_updates_by_range_request() -> LightClientUpdatesByRangeRequest {
+        LightClientUpdatesByRangeRequest {
+            start_period: 0,
+            count: 10,
+        }
+    }
+
+    /// A light client update attested during Altair, as configured by `fork_context`.
+    fn light_client_update() -> LightClientUpdate<Spec> {
+        let header = BeaconBlockHeader {
+            slot: Epoch::new(1).start_slot(Spec::slots_per_epoch()),
+            proposer_index: 0,
+            parent_root: Hash256::zero(),
+            state_root: Hash256::zero(),
+            body_root: Hash256::zero(),
+        };
+        LightClientUpdate {
+            attested_header: header.clone(),
+            next_sync_committee: Arc::new(SyncCommittee::temporary().unwrap()),
+            next_sync_committee_branch: FixedVector::from_elem(Hash256::zero()),
+            finalized_header: header,
+            finality_branch: FixedVector::from_elem(Hash256::zero()),
+            sync_aggregate: SyncAggregate::empty(),
+            signature_slot: Epoch::new(1).start_slot(Spec::slots_per_epoch()) + 1,
+        }
+    }
+
     fn metadata() -> MetaData<Spec> {
         MetaData::V1(MetaDataV1 {
             seq_number: 1,
@@ -897,6 +959,9 @@ mod tests {
                 OutboundRequest::LightClientBootstrap(bootstrap) => {
                     assert_eq!(decoded, InboundRequest::LightClientBootstrap(bootstrap))
                 }
+                OutboundRequest::LightClientUpdatesByRange(req) => {
+                    assert_eq!(decoded, InboundRequest::LightClientUpdatesByRange(req))
+                }
             }
         }
     }
@@ -1008,6 +1073,109 @@ mod tests {
         );
     }
 
+    // Test encoding/decoding of light client updates, which carry context bytes in V1
+    #[test]
+    fn test_encode_then_decode_light_client_updates() {
+        assert_eq!(
+            encode_then_decode_response(
+                Protocol::LightClientUpdatesByRange,
+                Version::V1,
+                RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                    light_client_update()
+                ))),
+                ForkName::Altair,
+            ),
+            Ok(Some(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                light_client_update()
+            ))))
+        );
+
+        // Every chunk of the stream is prefixed with its own context bytes
+        let fork_context = Arc::new(fork_context(ForkName::Capella));
+        let max_packet_size = max_rpc_size(&fork_context);
+        let protocol = ProtocolId::new(
+            Protocol::LightClientUpdatesByRange,
+            Version::V1,
+            Encoding::SSZSnappy,
+        );
+        let mut inbound_codec = SSZSnappyInboundCodec::<Spec>::new(
+            protocol.clone(),
+            max_packet_size,
+            fork_context.clone(),
+        );
+        let mut buf = BytesMut::new();
+        for _ in 0..2 {
+            inbound_codec
+                .encode(
+                    RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                        light_client_update(),
+                    ))),
+                    &mut buf,
+                )
+                .unwrap();
+        }
+
+        let mut outbound_codec =
+            SSZSnappyOutboundCodec::<Spec>::new(protocol, max_packet_size, fork_context);
+        for _ in 0..2 {
+            assert_eq!(
+                outbound_codec.decode(&mut buf),
+                Ok(Some(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                    light_client_update()
+                ))))
+            );
+        }
+        assert!(buf.is_empty());
+    }
+
+    // Test that light client data is sent with the context bytes of the fork of its header,
+    // rather than those of the current fork
+    #[test]
+    fn test_light_client_context_bytes() {
+        let fork_context = fork_context(ForkName::Capella);
+        let altair_context_bytes = fork_context.to_context_bytes(ForkName::Altair);
+        assert_ne!(
+            altair_context_bytes,
+            fork_context.to_context_bytes(fork_context.current_fork())
+        );
+
+        let update = light_client_update();
+        let protocol = ProtocolId::new(
+            Protocol::LightClientUpdatesByRange,
+            Version::V1,
+            Encoding::SSZSnappy,
+        );
+        assert_eq!(
+            context_bytes(
+                &protocol,
+                &fork_context,
+                &RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                    update.clone()
+                ))),
+            ),
+            altair_context_bytes
+        );
+
+        let bootstrap = LightClientBootstrap {
+            header: update.attested_header,
+            current_sync_committee: update.next_sync_committee,
+            current_sync_committee_branch: update.next_sync_committee_branch,
+        };
+        let protocol = ProtocolId::new(
+            Protocol::LightClientBootstrap,
+            Version::V1,
+            Encoding::SSZSnappy,
+        );
+        assert_eq!(
+            context_bytes(
+                &protocol,
+                &fork_context,
+                &RPCCodedResponse::Success(RPCResponse::LightClientBootstrap(bootstrap)),
+            ),
+            altair_context_bytes
+        );
+    }
+
     // Test RPCResponse encoding/decoding for V1 messages
     #[test]
     fn test_encode_then_decode_v2() {
@@ -1373,6 +1541,7 @@ mod tests {
             OutboundRequest::BlocksByRange(bbrange_request()),
             OutboundRequest::BlocksByRoot(bbroot_request()),
             OutboundRequest::MetaData(PhantomData::<Spec>),
+            OutboundRequest::LightClientUpdatesByRange(lc_updates_by_range_request()),
         ];
         for req in requests.iter() {
             for fork_name in ForkName::list_all() {
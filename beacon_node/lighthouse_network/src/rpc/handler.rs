@@ -936,6 +936,9 @@ where
                             let termination = match protocol {
                                 Protocol::BlocksByRange => Some(ResponseTermination::BlocksByRange),
                                 Protocol::BlocksByRoot => Some(ResponseTermination::BlocksByRoot),
+                                Protocol::LightClientUpdatesByRange => {
+                                    Some(ResponseTermination::LightClientUpdatesByRange)
+                                }
                                 _ => None, // all other protocols are do not have multiple responses and we do not inform the user, we simply drop the stream.
                             };
 
@@ -21,9 +21,9 @@ use tokio_util::{
     compat::{Compat, FuturesAsyncReadCompatExt},
 };
 use types::{
-    BeaconBlock, BeaconBlockAltair, BeaconBlockBase, BeaconBlockCapella, BeaconBlockMerge,
-    EmptyBlock, EthSpec, ForkContext, ForkName, Hash256, MainnetEthSpec, Signature,
-    SignedBeaconBlock,
+    light_client_update::LightClientUpdate, BeaconBlock, BeaconBlockAltair, BeaconBlockBase,
+    BeaconBlockCapella, BeaconBlockMerge, EmptyBlock, EthSpec, ForkContext, ForkName, Hash256,
+    MainnetEthSpec, Signature, SignedBeaconBlock,
 };
 
 lazy_static! {
@@ -177,6 +177,9 @@ pub enum Protocol {
     /// The `LightClientBootstrap` protocol name.
     #[strum(serialize = "light_client_bootstrap")]
     LightClientBootstrap,
+    /// The `LightClientUpdatesByRange` protocol name.
+    #[strum(serialize = "light_client_updates_by_range")]
+    LightClientUpdatesByRange,
 }
 
 /// RPC Versions
@@ -245,6 +248,11 @@ impl<TSpec: EthSpec> UpgradeInfo for RPCProtocol<TSpec> {
                 Version::V1,
                 Encoding::SSZSnappy,
             ));
+            supported_protocols.push(ProtocolId::new(
+                Protocol::LightClientUpdatesByRange,
+                Version::V1,
+                Encoding::SSZSnappy,
+            ));
         }
         supported_protocols
     }
@@ -312,6 +320,10 @@ impl ProtocolId {
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
             ),
+            Protocol::LightClientUpdatesByRange => RpcLimits::new(
+                <LightClientUpdatesByRangeRequest as Encode>::ssz_fixed_len(),
+                <LightClientUpdatesByRangeRequest as Encode>::ssz_fixed_len(),
+            ),
             Protocol::MetaData => RpcLimits::new(0, 0), // Metadata requests are empty
         }
     }
@@ -338,6 +350,10 @@ impl ProtocolId {
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
             ),
+            Protocol::LightClientUpdatesByRange => RpcLimits::new(
+                <LightClientUpdate<T> as Encode>::ssz_fixed_len(),
+                <LightClientUpdate<T> as Encode>::ssz_fixed_len(),
+            ),
         }
     }
 
@@ -349,9 +365,11 @@ impl ProtocolId {
                 Version::V2 => true,
                 Version::V1 => false,
             },
-            Protocol::LightClientBootstrap => match self.version {
-                Version::V2 | Version::V1 => true,
-            },
+            Protocol::LightClientBootstrap | Protocol::LightClientUpdatesByRange => {
+                match self.version {
+                    Version::V2 | Version::V1 => true,
+                }
+            }
             Protocol::Goodbye | Protocol::Ping | Protocol::Status | Protocol::MetaData => false,
         }
     }
@@ -447,6 +465,7 @@ pub enum InboundRequest<TSpec: EthSpec> {
     BlocksByRange(OldBlocksByRangeRequest),
     BlocksByRoot(BlocksByRootRequest),
     LightClientBootstrap(LightClientBootstrapRequest),
+    LightClientUpdatesByRange(LightClientUpdatesByRangeRequest),
     Ping(Ping),
     MetaData(PhantomData<TSpec>),
 }
@@ -465,6 +484,7 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::Ping(_) => 1,
             InboundRequest::MetaData(_) => 1,
             InboundRequest::LightClientBootstrap(_) => 1,
+            InboundRequest::LightClientUpdatesByRange(req) => req.count,
         }
     }
 
@@ -478,6 +498,7 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::Ping(_) => Protocol::Ping,
             InboundRequest::MetaData(_) => Protocol::MetaData,
             InboundRequest::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
+            InboundRequest::LightClientUpdatesByRange(_) => Protocol::LightClientUpdatesByRange,
         }
     }
 
@@ -489,6 +510,9 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             // variants that have `multiple_responses()` can have values.
             InboundRequest::BlocksByRange(_) => ResponseTermination::BlocksByRange,
             InboundRequest::BlocksByRoot(_) => ResponseTermination::BlocksByRoot,
+            InboundRequest::LightClientUpdatesByRange(_) => {
+                ResponseTermination::LightClientUpdatesByRange
+            }
             InboundRequest::Status(_) => unreachable!(),
             InboundRequest::Goodbye(_) => unreachable!(),
             InboundRequest::Ping(_) => unreachable!(),
@@ -600,6 +624,9 @@ impl<TSpec: EthSpec> std::fmt::Display for InboundRequest<TSpec> {
             InboundRequest::LightClientBootstrap(bootstrap) => {
                 write!(f, "LightClientBootstrap: {}", bootstrap.root)
             }
+            InboundRequest::LightClientUpdatesByRange(req) => {
+                write!(f, "LightClientUpdatesByRange: {}", req)
+            }
         }
     }
 }
@@ -95,6 +95,8 @@ pub struct RPCRateLimiter {
     bbroots_rl: Limiter<PeerId>,
     /// LightClientBootstrap rate limiter.
     lcbootstrap_rl: Limiter<PeerId>,
+    /// LightClientUpdatesByRange rate limiter.
+    lcupdates_rl: Limiter<PeerId>,
 }
 
 /// Error type for non conformant requests
@@ -123,6 +125,8 @@ pub struct RPCRateLimiterBuilder {
     bbroots_quota: Option<Quota>,
     /// Quota for the LightClientBootstrap protocol.
     lcbootstrap_quota: Option<Quota>,
+    /// Quota for the LightClientUpdatesByRange protocol.
+    lcupdates_quota: Option<Quota>,
 }
 
 impl RPCRateLimiterBuilder {
@@ -137,6 +141,7 @@ impl RPCRateLimiterBuilder {
             Protocol::BlocksByRange => self.bbrange_quota = q,
             Protocol::BlocksByRoot => self.bbroots_quota = q,
             Protocol::LightClientBootstrap => self.lcbootstrap_quota = q,
+            Protocol::LightClientUpdatesByRange => self.lcupdates_quota = q,
         }
         self
     }
@@ -179,6 +184,9 @@ impl RPCRateLimiterBuilder {
         let lcbootstrap_quote = self
             .lcbootstrap_quota
             .ok_or("LightClientBootstrap quota not specified")?;
+        let lcupdates_quota = self
+            .lcupdates_quota
+            .ok_or("LightClientUpdatesByRange quota not specified")?;
 
         // create the rate limiters
         let ping_rl = Limiter::from_quota(ping_quota)?;
@@ -188,6 +196,7 @@ impl RPCRateLimiterBuilder {
         let bbroots_rl = Limiter::from_quota(bbroots_quota)?;
         let bbrange_rl = Limiter::from_quota(bbrange_quota)?;
         let lcbootstrap_rl = Limiter::from_quota(lcbootstrap_quote)?;
+        let lcupdates_rl = Limiter::from_quota(lcupdates_quota)?;
 
         // check for peers to prune every 30 seconds, starting in 30 seconds
         let prune_every = tokio::time::Duration::from_secs(30);
@@ -202,6 +211,7 @@ impl RPCRateLimiterBuilder {
             bbroots_rl,
             bbrange_rl,
             lcbootstrap_rl,
+            lcupdates_rl,
             init_time: Instant::now(),
         })
     }
@@ -255,6 +265,7 @@ impl RPCRateLimiter {
             Protocol::BlocksByRange => &mut self.bbrange_rl,
             Protocol::BlocksByRoot => &mut self.bbroots_rl,
             Protocol::LightClientBootstrap => &mut self.lcbootstrap_rl,
+            Protocol::LightClientUpdatesByRange => &mut self.lcupdates_rl,
         };
         check(limiter)
     }
@@ -267,6 +278,8 @@ impl RPCRateLimiter {
         self.goodbye_rl.prune(time_since_start);
         self.bbrange_rl.prune(time_since_start);
         self.bbroots_rl.prune(time_since_start);
+        self.lcbootstrap_rl.prune(time_since_start);
+        self.lcupdates_rl.prune(time_since_start);
     }
 }
 
@@ -2,12 +2,14 @@ use std::sync::Arc;
 
 use libp2p::core::connection::ConnectionId;
 use types::light_client_bootstrap::LightClientBootstrap;
+use types::light_client_update::LightClientUpdate;
 use types::{EthSpec, SignedBeaconBlock};
 
 use crate::rpc::{
     methods::{
         BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
-        OldBlocksByRangeRequest, RPCCodedResponse, RPCResponse, ResponseTermination, StatusMessage,
+        LightClientUpdatesByRangeRequest, OldBlocksByRangeRequest, RPCCodedResponse, RPCResponse,
+        ResponseTermination, StatusMessage,
     },
     OutboundRequest, SubstreamId,
 };
@@ -37,6 +39,8 @@ pub enum Request {
     BlocksByRoot(BlocksByRootRequest),
     // light client bootstrap request
     LightClientBootstrap(LightClientBootstrapRequest),
+    // light client updates by range request
+    LightClientUpdatesByRange(LightClientUpdatesByRangeRequest),
 }
 
 impl<TSpec: EthSpec> std::convert::From<Request> for OutboundRequest<TSpec> {
@@ -51,6 +55,7 @@ impl<TSpec: EthSpec> std::convert::From<Request> for OutboundRequest<TSpec> {
                 })
             }
             Request::LightClientBootstrap(b) => OutboundRequest::LightClientBootstrap(b),
+            Request::LightClientUpdatesByRange(r) => OutboundRequest::LightClientUpdatesByRange(r),
             Request::Status(s) => OutboundRequest::Status(s),
         }
     }
@@ -72,6 +77,9 @@ pub enum Response<TSpec: EthSpec> {
     BlocksByRoot(Option<Arc<SignedBeaconBlock<TSpec>>>),
     /// A response to a LightClientUpdate request.
     LightClientBootstrap(LightClientBootstrap<TSpec>),
+    /// A response to a LIGHT_CLIENT_UPDATES_BY_RANGE request. A None response signals the end of
+    /// the batch.
+    LightClientUpdatesByRange(Option<Arc<LightClientUpdate<TSpec>>>),
 }
 
 impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TSpec> {
@@ -89,6 +97,12 @@ impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TS
             Response::LightClientBootstrap(b) => {
                 RPCCodedResponse::Success(RPCResponse::LightClientBootstrap(b))
             }
+            Response::LightClientUpdatesByRange(r) => match r {
+                Some(u) => RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(u)),
+                None => RPCCodedResponse::StreamTermination(
+                    ResponseTermination::LightClientUpdatesByRange,
+                ),
+            },
         }
     }
 }
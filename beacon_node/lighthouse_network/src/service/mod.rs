@@ -5,7 +5,10 @@ use crate::discovery::{
     subnet_predicate, DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS,
 };
 use crate::peer_manager::{
-    config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
+    config::Config as PeerManagerCfg,
+    peerdb::score::PeerAction,
+    peerdb::score::ReportSource,
+    peerdb::{PersistedIpBan, PersistedPeer},
     ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
@@ -38,8 +41,10 @@ use std::path::PathBuf;
 use std::pin::Pin;
 use std::{
     marker::PhantomData,
+    net::IpAddr,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use types::ForkName;
 use types::{
@@ -185,10 +190,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         let score_settings = PeerScoreSettings::new(ctx.chain_spec, &config.gs_config);
 
         let gossip_cache = {
-            let slot_duration = std::time::Duration::from_secs(ctx.chain_spec.seconds_per_slot);
-            let half_epoch = std::time::Duration::from_secs(
-                ctx.chain_spec.seconds_per_slot * TSpec::slots_per_epoch() / 2,
-            );
+            let slot_duration = Duration::from_secs(ctx.chain_spec.seconds_per_slot);
+            let half_epoch =
+                Duration::from_secs(ctx.chain_spec.seconds_per_slot * TSpec::slots_per_epoch() / 2);
 
             GossipCache::builder()
                 .beacon_block_timeout(slot_duration)
@@ -314,9 +318,10 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         };
 
         let (swarm, bandwidth) = {
-            // Set up the transport - tcp/ws with noise and mplex
-            let (transport, bandwidth) = build_transport(local_keypair.clone())
-                .map_err(|e| format!("Failed to build transport: {:?}", e))?;
+            // Set up the transport - tcp/ws with noise and mplex, and optionally quic
+            let (transport, bandwidth) =
+                build_transport(local_keypair.clone(), config.listen_addrs().quic_enabled())
+                    .map_err(|e| format!("Failed to build transport: {:?}", e))?;
 
             // use the executor for libp2p
             struct Executor(task_executor::TaskExecutor);
@@ -390,7 +395,11 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         info!(self.log, "Libp2p Starting"; "peer_id" => %enr.peer_id(), "bandwidth_config" => format!("{}-{}", config.network_load, NetworkLoad::from(config.network_load).name));
         debug!(self.log, "Attempting to open listening ports"; config.listen_addrs(), "discovery_enabled" => !config.disable_discovery);
 
-        for listen_multiaddr in config.listen_addrs().tcp_addresses() {
+        let listen_addrs = config.listen_addrs();
+        for listen_multiaddr in listen_addrs
+            .tcp_addresses()
+            .chain(listen_addrs.quic_addresses())
+        {
             match self.swarm.listen_on(listen_multiaddr.clone()) {
                 Ok(_) => {
                     let mut log_address = listen_multiaddr;
@@ -409,6 +418,8 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             };
         }
 
+        let quic_enabled = listen_addrs.quic_enabled();
+
         // helper closure for dialing peers
         let mut dial = |mut multiaddr: Multiaddr| {
             // strip the p2p protocol if it exists
@@ -432,10 +443,12 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
 
         for bootnode_enr in boot_nodes {
             for multiaddr in &bootnode_enr.multiaddr() {
-                // ignore udp multiaddr if it exists
+                // ignore udp multiaddr if it exists, unless it is a quic multiaddr we can dial
                 let components = multiaddr.iter().collect::<Vec<_>>();
                 if let MProtocol::Udp(_) = components[1] {
-                    continue;
+                    if !(quic_enabled && components.get(2) == Some(&MProtocol::Quic)) {
+                        continue;
+                    }
                 }
 
                 if !self
@@ -450,11 +463,12 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         }
 
         for multiaddr in &config.boot_nodes_multiaddr {
-            // check TCP support for dialing
-            if multiaddr
-                .iter()
-                .any(|proto| matches!(proto, MProtocol::Tcp(_)))
-            {
+            // check TCP or QUIC support for dialing
+            if multiaddr.iter().any(|proto| match proto {
+                MProtocol::Tcp(_) => true,
+                MProtocol::Quic => quic_enabled,
+                _ => false,
+            }) {
                 dial(multiaddr.clone());
             }
         }
@@ -801,6 +815,77 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             .goodbye_peer(peer_id, reason, source);
     }
 
+    /// Dials a peer at the given multiaddr.
+    pub fn dial_address(&mut self, addr: Multiaddr) -> Result<(), libp2p::swarm::DialError> {
+        self.swarm.dial(addr)
+    }
+
+    /// Adds the ENR of a peer to the routing table and dials it.
+    pub fn dial_enr(&mut self, enr: Enr) {
+        let peer_id = enr.peer_id();
+        self.add_enr(enr.clone());
+        self.peer_manager_mut().dial_peer(&peer_id, Some(enr));
+    }
+
+    /// Requests the `MetaData` of a connected peer, which is recorded in its `PeerInfo`.
+    pub fn request_meta_data(&mut self, peer_id: PeerId) {
+        self.send_meta_data_request(peer_id);
+    }
+
+    /// Gracefully disconnects a peer without banning it.
+    pub fn disconnect_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
+        if self.peer_manager().is_connected(&peer_id) {
+            self.peer_manager_mut().disconnect_peer(peer_id, reason);
+        }
+    }
+
+    /// Bans a peer for `duration`, after which its score decays as usual.
+    pub fn ban_peer_for(&mut self, peer_id: &PeerId, duration: Duration) {
+        self.peer_manager_mut().ban_peer_for(peer_id, duration);
+    }
+
+    /// Lifts any ban on a peer.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        self.peer_manager_mut().unban_peer(peer_id);
+    }
+
+    /// Bans an IP address for `duration`.
+    pub fn ban_ip_for(&mut self, ip: IpAddr, duration: Duration) {
+        self.peer_manager_mut().ban_ip_for(ip, duration);
+    }
+
+    /// Lifts the ban on an IP address.
+    pub fn unban_ip(&mut self, ip: &IpAddr) {
+        self.peer_manager_mut().unban_ip(ip);
+    }
+
+    /// Adds or removes a trusted peer.
+    pub fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) {
+        self.peer_manager_mut().set_trusted(peer_id, trusted);
+    }
+
+    /// Returns the peer scores to persist across restarts.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.network_globals.peers.read().persisted_peers()
+    }
+
+    /// Restores the peer scores persisted by a previous run, which stopped `offline` ago.
+    pub fn restore_persisted_peers(&mut self, peers: Vec<PersistedPeer>, offline: Duration) {
+        self.peer_manager_mut()
+            .restore_persisted_peers(peers, offline);
+    }
+
+    /// Returns the IP addresses banned by request to persist across restarts.
+    pub fn persisted_ip_bans(&self) -> Vec<PersistedIpBan> {
+        self.network_globals.peers.read().persisted_ip_bans()
+    }
+
+    /// Restores the IP addresses banned by request in a previous run, which stopped `offline` ago.
+    pub fn restore_persisted_ip_bans(&mut self, ip_bans: Vec<PersistedIpBan>, offline: Duration) {
+        self.peer_manager_mut()
+            .restore_persisted_ip_bans(ip_bans, offline);
+    }
+
     /// Returns an iterator over all enr entries in the DHT.
     pub fn enr_entries(&self) -> Vec<Enr> {
         self.discovery().table_entries_enr()
@@ -990,6 +1075,10 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             Request::LightClientBootstrap(_) => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["light_client_bootstrap"])
             }
+            Request::LightClientUpdatesByRange(_) => metrics::inc_counter_vec(
+                &metrics::TOTAL_RPC_REQUESTS,
+                &["light_client_updates_by_range"],
+            ),
             Request::BlocksByRange { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blocks_by_range"])
             }
@@ -1267,6 +1356,14 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                         );
                         Some(event)
                     }
+                    InboundRequest::LightClientUpdatesByRange(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::LightClientUpdatesByRange(req),
+                        );
+                        Some(event)
+                    }
                 }
             }
             Ok(RPCReceived::Response(id, resp)) => {
@@ -1298,12 +1395,21 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     RPCResponse::LightClientBootstrap(bootstrap) => {
                         self.build_response(id, peer_id, Response::LightClientBootstrap(bootstrap))
                     }
+                    // Should never be reached
+                    RPCResponse::LightClientUpdatesByRange(update) => self.build_response(
+                        id,
+                        peer_id,
+                        Response::LightClientUpdatesByRange(Some(update)),
+                    ),
                 }
             }
             Ok(RPCReceived::EndOfStream(id, termination)) => {
                 let response = match termination {
                     ResponseTermination::BlocksByRange => Response::BlocksByRange(None),
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
+                    ResponseTermination::LightClientUpdatesByRange => {
+                        Response::LightClientUpdatesByRange(None)
+                    }
                 };
                 self.build_response(id, peer_id, response)
             }
@@ -1375,6 +1481,14 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 self.discovery_mut().unban_peer(&peer_id, associated_ips);
                 Some(NetworkEvent::PeerUnbanned(peer_id))
             }
+            PeerManagerEvent::BannedIp(ip) => {
+                self.discovery_mut().ban_peer_ip(ip);
+                None
+            }
+            PeerManagerEvent::UnBannedIp(ip) => {
+                self.discovery_mut().unban_peer_ip(&ip);
+                None
+            }
             PeerManagerEvent::Status(peer_id) => {
                 // it's time to status. We don't keep a beacon chain reference here, so we inform
                 // the network to send a status to this peer
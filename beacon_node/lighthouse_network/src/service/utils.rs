@@ -6,7 +6,8 @@ use crate::types::{
 use crate::{GossipTopic, NetworkConfig};
 use libp2p::bandwidth::{BandwidthLogging, BandwidthSinks};
 use libp2p::core::{
-    identity::Keypair, multiaddr::Multiaddr, muxing::StreamMuxerBox, transport::Boxed,
+    either::EitherOutput, identity::Keypair, multiaddr::Multiaddr, muxing::StreamMuxerBox,
+    transport::Boxed,
 };
 use libp2p::gossipsub::subscription_filter::WhitelistSubscriptionFilter;
 use libp2p::gossipsub::IdentTopic as Topic;
@@ -40,9 +41,13 @@ pub struct Context<'a> {
 type BoxedTransport = Boxed<(PeerId, StreamMuxerBox)>;
 
 /// The implementation supports TCP/IP, WebSockets over TCP/IP, noise as the encryption layer, and
-/// mplex as the multiplexing layer.
+/// mplex as the multiplexing layer. If `quic_support` is set, QUIC is also supported, which
+/// provides its own encryption and multiplexing.
+///
+/// NOTE: The returned `BandwidthSinks` only account for TCP connections.
 pub fn build_transport(
     local_private_key: Keypair,
+    quic_support: bool,
 ) -> std::io::Result<(BoxedTransport, Arc<BandwidthSinks>)> {
     let tcp = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true));
     let transport = libp2p::dns::TokioDnsConfig::system(tcp)?;
@@ -64,18 +69,32 @@ pub fn build_transport(
     yamux_config.set_window_update_mode(libp2p::yamux::WindowUpdateMode::on_read());
 
     // Authentication
-    Ok((
-        transport
-            .upgrade(core::upgrade::Version::V1)
-            .authenticate(generate_noise_config(&local_private_key))
-            .multiplex(core::upgrade::SelectUpgrade::new(
-                yamux_config,
-                mplex_config,
-            ))
-            .timeout(Duration::from_secs(10))
-            .boxed(),
-        bandwidth,
-    ))
+    let transport = transport
+        .upgrade(core::upgrade::Version::V1)
+        .authenticate(generate_noise_config(&local_private_key))
+        .multiplex(core::upgrade::SelectUpgrade::new(
+            yamux_config,
+            mplex_config,
+        ))
+        .timeout(Duration::from_secs(10));
+
+    if !quic_support {
+        return Ok((transport.boxed(), bandwidth));
+    }
+
+    // QUIC handles authentication and multiplexing itself
+    let quic = libp2p::quic::tokio::Transport::new(libp2p::quic::Config::new(&local_private_key));
+    let transport = transport
+        .or_transport(quic)
+        .map(|output, _| match output {
+            EitherOutput::First((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+            EitherOutput::Second((peer_id, connection)) => {
+                (peer_id, StreamMuxerBox::new(connection))
+            }
+        })
+        .boxed();
+
+    Ok((transport, bandwidth))
 }
 
 // Useful helper functions for debugging. Currently not used in the client.
@@ -1,11 +1,13 @@
 #![cfg(test)]
 use libp2p::gossipsub::GossipsubConfigBuilder;
+use lighthouse_network::multiaddr::Protocol;
 use lighthouse_network::service::Network as LibP2PService;
 use lighthouse_network::Enr;
 use lighthouse_network::EnrExt;
 use lighthouse_network::Multiaddr;
-use lighthouse_network::{NetworkConfig, NetworkEvent};
+use lighthouse_network::{ListenAddr, ListenAddress, NetworkConfig, NetworkEvent};
 use slog::{debug, error, o, Drain};
+use std::net::Ipv4Addr;
 use std::sync::Arc;
 use std::sync::Weak;
 use std::time::Duration;
@@ -13,7 +15,7 @@ use tokio::runtime::Runtime;
 use types::{
     ChainSpec, EnrForkId, Epoch, EthSpec, ForkContext, ForkName, Hash256, MinimalEthSpec, Slot,
 };
-use unused_port::unused_tcp4_port;
+use unused_port::{unused_tcp4_port, unused_udp4_port};
 
 type E = MinimalEthSpec;
 type ReqId = usize;
@@ -97,6 +99,15 @@ pub async fn build_libp2p_instance(
 ) -> Libp2pInstance {
     let port = unused_tcp4_port().unwrap();
     let config = build_config(port, boot_nodes);
+    build_libp2p_instance_from_config(rt, config, log, fork_name).await
+}
+
+pub async fn build_libp2p_instance_from_config(
+    rt: Weak<Runtime>,
+    config: NetworkConfig,
+    log: slog::Logger,
+    fork_name: ForkName,
+) -> Libp2pInstance {
     // launch libp2p service
 
     let (signal, exit) = exit_future::signal();
@@ -203,6 +214,72 @@ pub async fn build_node_pair(
     (sender, receiver)
 }
 
+// Constructs a pair of nodes which listen over QUIC as well as TCP. The sender dials the
+// receiver's QUIC address, so that they can only connect over QUIC.
+// This returns a (sender, receiver) pair.
+#[allow(dead_code)]
+pub async fn build_quic_node_pair(
+    rt: Weak<Runtime>,
+    log: &slog::Logger,
+    fork_name: ForkName,
+) -> (Libp2pInstance, Libp2pInstance) {
+    let build_quic_config = || {
+        let port = unused_tcp4_port().unwrap();
+        let quic_port = unused_udp4_port().unwrap();
+        let mut config = build_config(port, vec![]);
+        config.set_listening_addr(ListenAddress::V4(ListenAddr {
+            addr: Ipv4Addr::UNSPECIFIED,
+            udp_port: port,
+            tcp_port: port,
+            quic_port: Some(quic_port),
+        }));
+        (config, quic_port)
+    };
+
+    let (sender_config, _) = build_quic_config();
+    let (receiver_config, receiver_quic_port) = build_quic_config();
+    let mut sender = build_libp2p_instance_from_config(
+        rt.clone(),
+        sender_config,
+        log.new(o!("who" => "sender")),
+        fork_name,
+    )
+    .await;
+    let mut receiver = build_libp2p_instance_from_config(
+        rt,
+        receiver_config,
+        log.new(o!("who" => "receiver")),
+        fork_name,
+    )
+    .await;
+
+    // wait for the receiver to listen over QUIC, or a timeout
+    let receiver_fut = async {
+        loop {
+            if let NetworkEvent::NewListenAddr(addr) = receiver.next_event().await {
+                if addr.iter().any(|protocol| protocol == Protocol::Quic) {
+                    return;
+                }
+            }
+        }
+    };
+    tokio::select! {
+        _  = tokio::time::sleep(Duration::from_millis(500)) => {}
+        _ = receiver_fut => {}
+    }
+
+    let receiver_multiaddr = Multiaddr::from(Ipv4Addr::LOCALHOST)
+        .with(Protocol::Udp(receiver_quic_port))
+        .with(Protocol::Quic);
+    match sender.testing_dial(receiver_multiaddr.clone()) {
+        Ok(()) => {
+            debug!(log, "Sender dialed receiver"; "address" => format!("{:?}", receiver_multiaddr))
+        }
+        Err(_) => error!(log, "Dialing failed"),
+    };
+    (sender, receiver)
+}
+
 // Returns `n` peers in a linear topology
 #[allow(dead_code)]
 pub async fn build_linear(
@@ -135,6 +135,82 @@ fn test_status_rpc() {
     })
 }
 
+// Tests that two nodes can connect and exchange a STATUS RPC message over QUIC
+#[test]
+#[allow(clippy::single_match)]
+fn test_status_rpc_over_quic() {
+    // set up the logging. The level and enabled logging or not
+    let log_level = Level::Debug;
+    let enable_logging = false;
+
+    let rt = Arc::new(Runtime::new().unwrap());
+
+    let log = common::build_log(log_level, enable_logging);
+
+    rt.block_on(async {
+        // get sender/receiver, connected over QUIC
+        let (mut sender, mut receiver) =
+            common::build_quic_node_pair(Arc::downgrade(&rt), &log, ForkName::Base).await;
+
+        let status = StatusMessage {
+            fork_digest: [0; 4],
+            finalized_root: Hash256::from_low_u64_be(0),
+            finalized_epoch: Epoch::new(1),
+            head_root: Hash256::from_low_u64_be(0),
+            head_slot: Slot::new(1),
+        };
+        let rpc_request = Request::Status(status.clone());
+        let rpc_response = Response::Status(status);
+
+        // build the sender future
+        let sender_future = async {
+            loop {
+                match sender.next_event().await {
+                    NetworkEvent::PeerConnectedOutgoing(peer_id) => {
+                        debug!(log, "Sending RPC over QUIC");
+                        sender.send_request(peer_id, 10, rpc_request.clone());
+                    }
+                    NetworkEvent::ResponseReceived {
+                        peer_id: _,
+                        id: 10,
+                        response,
+                    } => {
+                        assert_eq!(response, rpc_response.clone());
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+        };
+
+        // build the receiver future
+        let receiver_future = async {
+            loop {
+                match receiver.next_event().await {
+                    NetworkEvent::RequestReceived {
+                        peer_id,
+                        id,
+                        request,
+                    } => {
+                        if request == rpc_request {
+                            receiver.send_response(peer_id, id, rpc_response.clone());
+                        }
+                    }
+                    _ => {} // Ignore other events
+                }
+            }
+        };
+
+        tokio::select! {
+            _ = sender_future => {}
+            _ = receiver_future => {}
+            _ = sleep(Duration::from_secs(30)) => {
+                panic!("Future timed out");
+            }
+        }
+    })
+}
+
 // Tests a streamed BlocksByRange RPC Message
 #[test]
 #[allow(clippy::single_match)]
@@ -9,9 +9,10 @@ use lighthouse_network::rpc::*;
 use lighthouse_network::{PeerId, PeerRequestId, ReportSource, Response, SyncInfo};
 use slog::{debug, error, warn};
 use slot_clock::SlotClock;
+use std::sync::Arc;
 use task_executor::TaskExecutor;
 use tokio_stream::StreamExt;
-use types::{light_client_bootstrap::LightClientBootstrap, Epoch, EthSpec, Hash256, Slot};
+use types::{Epoch, EthSpec, Hash256, Slot};
 
 use super::Worker;
 
@@ -215,21 +216,9 @@ impl<T: BeaconChainTypes> Worker<T> {
         request_id: PeerRequestId,
         request: LightClientBootstrapRequest,
     ) {
-        let block_root = request.root;
-        let state_root = match self.chain.get_blinded_block(&block_root) {
-            Ok(signed_block) => match signed_block {
-                Some(signed_block) => signed_block.state_root(),
-                None => {
-                    self.send_error_response(
-                        peer_id,
-                        RPCResponseErrorCode::ResourceUnavailable,
-                        "Bootstrap not avaiable".into(),
-                        request_id,
-                    );
-                    return;
-                }
-            },
-            Err(_) => {
+        let bootstrap = match self.chain.get_light_client_bootstrap(&request.root) {
+            Ok(Some(bootstrap)) => bootstrap,
+            Ok(None) | Err(_) => {
                 self.send_error_response(
                     peer_id,
                     RPCResponseErrorCode::ResourceUnavailable,
@@ -239,46 +228,72 @@ impl<T: BeaconChainTypes> Worker<T> {
                 return;
             }
         };
-        let mut beacon_state = match self.chain.get_state(&state_root, None) {
-            Ok(beacon_state) => match beacon_state {
-                Some(state) => state,
-                None => {
-                    self.send_error_response(
-                        peer_id,
-                        RPCResponseErrorCode::ResourceUnavailable,
-                        "Bootstrap not avaiable".into(),
-                        request_id,
-                    );
-                    return;
-                }
-            },
-            Err(_) => {
-                self.send_error_response(
-                    peer_id,
-                    RPCResponseErrorCode::ResourceUnavailable,
-                    "Bootstrap not avaiable".into(),
-                    request_id,
+        self.send_response(
+            peer_id,
+            Response::LightClientBootstrap(bootstrap),
+            request_id,
+        )
+    }
+
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    pub fn handle_light_client_updates_by_range(
+        self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        mut req: LightClientUpdatesByRangeRequest,
+    ) {
+        debug!(self.log, "Received LightClientUpdatesByRange Request";
+            "peer_id" => %peer_id,
+            "count" => req.count,
+            "start_period" => req.start_period,
+        );
+
+        // Should not send more than max request updates
+        if req.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+            req.count = MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+        }
+
+        let updates = match self
+            .chain
+            .get_light_client_updates(req.start_period, req.count)
+        {
+            Ok(updates) => updates,
+            Err(e) => {
+                error!(self.log, "Unable to load light client updates";
+                    "peer_id" => %peer_id,
+                    "start_period" => req.start_period,
+                    "error" => ?e,
                 );
-                return;
-            }
-        };
-        let bootstrap = match LightClientBootstrap::from_beacon_state(&mut beacon_state) {
-            Ok(bootstrap) => bootstrap,
-            Err(_) => {
                 self.send_error_response(
                     peer_id,
-                    RPCResponseErrorCode::ResourceUnavailable,
-                    "Bootstrap not avaiable".into(),
+                    RPCResponseErrorCode::ServerError,
+                    "Database error".into(),
                     request_id,
                 );
                 return;
             }
         };
+
+        debug!(self.log, "LightClientUpdatesByRange Response processed";
+            "peer_id" => %peer_id,
+            "requested" => req.count,
+            "returned" => updates.len(),
+        );
+
+        for update in updates {
+            self.send_response(
+                peer_id,
+                Response::LightClientUpdatesByRange(Some(Arc::new(update))),
+                request_id,
+            );
+        }
+
+        // send the stream terminator
         self.send_response(
             peer_id,
-            Response::LightClientBootstrap(bootstrap),
+            Response::LightClientUpdatesByRange(None),
             request_id,
-        )
+        );
     }
 
     /// Handle a `BlocksByRange` request from the peer.
@@ -312,11 +327,20 @@ impl<T: BeaconChainTypes> Worker<T> {
                     oldest_block_slot,
                 },
             )) => {
-                debug!(self.log, "Range request failed during backfill"; "requested_slot" => slot, "oldest_known_slot" => oldest_block_slot);
+                // Blocks prior to the oldest block are either yet to be backfilled, or have been
+                // pruned and will never be served.
+                let history_pruned = slot < self.chain.store.get_history_retention_slot();
+                let reason = if history_pruned {
+                    debug!(self.log, "Range request failed for pruned history"; "requested_slot" => slot, "oldest_known_slot" => oldest_block_slot);
+                    "Pruned"
+                } else {
+                    debug!(self.log, "Range request failed during backfill"; "requested_slot" => slot, "oldest_known_slot" => oldest_block_slot);
+                    "Backfilling"
+                };
                 return self.send_error_response(
                     peer_id,
                     RPCResponseErrorCode::ResourceUnavailable,
-                    "Backfilling".into(),
+                    reason.into(),
                     request_id,
                 );
             }
@@ -0,0 +1,320 @@
+//! Downloads the block for a checkpoint state from the network, before the `BeaconChain` exists.
+//!
+//! This allows a node to checkpoint sync from nothing but a finalized `BeaconState`, by requesting
+//! the block committed to by the state's `latest_block_header` from peers with `BlocksByRoot`.
+use crate::NetworkConfig;
+use lighthouse_network::rpc::{BlocksByRootRequest, RPCResponseErrorCode, StatusMessage};
+use lighthouse_network::service::Network;
+use lighthouse_network::{Context, NetworkEvent, PeerAction, ReportSource, Request, Response};
+use slog::{debug, info, o, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use types::{ChainSpec, EthSpec, ForkContext, Hash256, SignedBeaconBlock, Slot};
+
+/// The interval at which we log while waiting for a peer to serve the checkpoint block.
+const STATUS_LOG_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Starts a temporary libp2p service and requests the block with `block_root` from every peer it
+/// connects to, until one of them returns it.
+///
+/// The block is expected to be the finalized block at `block_slot`, which is advertised to peers as
+/// both our head and finalized checkpoint. The temporary service is shut down before this function
+/// returns, so that the main network service can bind to the same ports afterwards.
+#[allow(clippy::too_many_arguments)]
+pub async fn fetch_checkpoint_block<E: EthSpec>(
+    block_root: Hash256,
+    block_slot: Slot,
+    current_slot: Slot,
+    genesis_validators_root: Hash256,
+    config: &NetworkConfig,
+    executor: task_executor::TaskExecutor,
+    spec: &ChainSpec,
+    timeout: Duration,
+) -> Result<SignedBeaconBlock<E>, String> {
+    let log = executor.log().new(o!("service" => "checkpoint_block"));
+
+    let fork_context = Arc::new(ForkContext::new::<E>(
+        current_slot,
+        genesis_validators_root,
+        spec,
+    ));
+    let enr_fork_id = spec.enr_fork_id::<E>(current_slot, genesis_validators_root);
+
+    // Alias the genesis checkpoint root to `0x00`, as in `status_message`.
+    let finalized_epoch = block_slot.epoch(E::slots_per_epoch());
+    let finalized_root = if finalized_epoch == spec.genesis_slot.epoch(E::slots_per_epoch()) {
+        Hash256::zero()
+    } else {
+        block_root
+    };
+    let status = StatusMessage {
+        fork_digest: enr_fork_id.fork_digest,
+        finalized_root,
+        finalized_epoch,
+        head_root: block_root,
+        head_slot: block_slot,
+    };
+
+    let service_context = Context {
+        config,
+        enr_fork_id,
+        fork_context,
+        chain_spec: spec,
+        gossipsub_registry: None,
+    };
+
+    let (mut libp2p, _network_globals) =
+        Network::<(), E>::new(executor.clone(), service_context, &log)
+            .await
+            .map_err(|e| format!("Unable to start network to fetch checkpoint block: {:?}", e))?;
+
+    info!(
+        log,
+        "Requesting checkpoint block from peers";
+        "block_root" => ?block_root,
+    );
+
+    let fetch = async {
+        let mut requested_peers = HashSet::new();
+        let mut status_log = tokio::time::interval(STATUS_LOG_INTERVAL);
+
+        loop {
+            let event = tokio::select! {
+                event = libp2p.next_event() => event,
+                _ = status_log.tick() => {
+                    debug!(
+                        log,
+                        "Waiting for checkpoint block";
+                        "block_root" => ?block_root,
+                        "peers_requested" => requested_peers.len(),
+                    );
+                    continue;
+                }
+            };
+
+            match event {
+                NetworkEvent::PeerConnectedOutgoing(peer_id)
+                | NetworkEvent::PeerConnectedIncoming(peer_id)
+                | NetworkEvent::StatusPeer(peer_id) => {
+                    libp2p.send_request(peer_id, (), Request::Status(status.clone()));
+                    if requested_peers.insert(peer_id) {
+                        debug!(
+                            log,
+                            "Requesting checkpoint block";
+                            "peer_id" => %peer_id,
+                        );
+                        libp2p.send_request(
+                            peer_id,
+                            (),
+                            Request::BlocksByRoot(BlocksByRootRequest {
+                                block_roots: vec![block_root].into(),
+                            }),
+                        );
+                    }
+                }
+                NetworkEvent::RequestReceived {
+                    peer_id,
+                    id,
+                    request,
+                } => match request {
+                    Request::Status(_) => {
+                        libp2p.send_response(peer_id, id, Response::Status(status.clone()))
+                    }
+                    _ => libp2p.send_error_reponse(
+                        peer_id,
+                        id,
+                        RPCResponseErrorCode::ResourceUnavailable,
+                        "Node is starting".into(),
+                    ),
+                },
+                NetworkEvent::ResponseReceived {
+                    peer_id,
+                    response: Response::BlocksByRoot(Some(block)),
+                    ..
+                } => {
+                    if block.canonical_root() == block_root {
+                        info!(
+                            log,
+                            "Downloaded checkpoint block";
+                            "block_root" => ?block_root,
+                            "slot" => block.slot(),
+                            "peer_id" => %peer_id,
+                        );
+                        return (*block).clone();
+                    }
+                    warn!(
+                        log,
+                        "Peer sent incorrect checkpoint block";
+                        "expected" => ?block_root,
+                        "received" => ?block.canonical_root(),
+                        "peer_id" => %peer_id,
+                    );
+                    libp2p.report_peer(
+                        &peer_id,
+                        PeerAction::LowToleranceError,
+                        ReportSource::RPC,
+                        "incorrect_checkpoint_block",
+                    );
+                }
+                NetworkEvent::RPCFailed { peer_id, .. } => {
+                    // Allow the request to be retried when the peer is next re-statused.
+                    requested_peers.remove(&peer_id);
+                    debug!(
+                        log,
+                        "Checkpoint block request failed";
+                        "peer_id" => %peer_id,
+                    );
+                }
+                _ => {}
+            }
+        }
+    };
+
+    let result = tokio::time::timeout(timeout, fetch).await.map_err(|_| {
+        format!(
+            "Timed out fetching checkpoint block {:?} from peers. Provide the block with \
+             --checkpoint-block instead",
+            block_root
+        )
+    });
+
+    // Dropping the service closes its listeners and shuts down discovery.
+    drop(libp2p);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::multiaddr::Protocol;
+    use lighthouse_network::Multiaddr;
+    use std::net::Ipv4Addr;
+    use task_executor::test_utils::TestRuntime;
+    use tempfile::{tempdir, TempDir};
+    use types::{BeaconBlock, MinimalEthSpec, Signature};
+    use unused_port::{unused_tcp4_port, unused_udp4_port};
+
+    type E = MinimalEthSpec;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    fn network_config(network_dir: &TempDir) -> (NetworkConfig, u16) {
+        let tcp_port = unused_tcp4_port().unwrap();
+        let mut config = NetworkConfig::default();
+        config.set_ipv4_listening_address(
+            Ipv4Addr::LOCALHOST,
+            tcp_port,
+            unused_udp4_port().unwrap(),
+        );
+        config.enr_address = (Some(Ipv4Addr::LOCALHOST), None);
+        config.disable_discovery = true;
+        config.upnp_enabled = false;
+        config.network_dir = network_dir.path().into();
+        (config, tcp_port)
+    }
+
+    /// Starts a peer which answers every `BlocksByRoot` request with `block`, returning its address.
+    async fn serve_block(
+        block: SignedBeaconBlock<E>,
+        network_dir: &TempDir,
+        executor: task_executor::TaskExecutor,
+        spec: &ChainSpec,
+    ) -> Multiaddr {
+        let (config, tcp_port) = network_config(network_dir);
+        let context = Context {
+            config: &config,
+            enr_fork_id: spec.enr_fork_id::<E>(Slot::new(0), Hash256::zero()),
+            fork_context: Arc::new(ForkContext::new::<E>(Slot::new(0), Hash256::zero(), spec)),
+            chain_spec: spec,
+            gossipsub_registry: None,
+        };
+        let log = executor.log().clone();
+        let (mut libp2p, _network_globals) = Network::<(), E>::new(executor, context, &log)
+            .await
+            .unwrap();
+
+        let block = Arc::new(block);
+        tokio::spawn(async move {
+            loop {
+                if let NetworkEvent::RequestReceived {
+                    peer_id,
+                    id,
+                    request: Request::BlocksByRoot(_),
+                } = libp2p.next_event().await
+                {
+                    libp2p.send_response(peer_id, id, Response::BlocksByRoot(Some(block.clone())));
+                    libp2p.send_response(peer_id, id, Response::BlocksByRoot(None));
+                }
+            }
+        });
+
+        Multiaddr::from(Ipv4Addr::LOCALHOST).with(Protocol::Tcp(tcp_port))
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn fetch_from_peer() {
+        let runtime = TestRuntime::default();
+        let spec = E::default_spec();
+        let block = SignedBeaconBlock::from_block(BeaconBlock::empty(&spec), Signature::empty());
+        let block_root = block.canonical_root();
+
+        let server_dir = tempdir().unwrap();
+        let server_address = serve_block(
+            block.clone(),
+            &server_dir,
+            runtime.task_executor.clone(),
+            &spec,
+        )
+        .await;
+
+        let client_dir = tempdir().unwrap();
+        let (mut config, _) = network_config(&client_dir);
+        config.libp2p_nodes = vec![server_address];
+
+        let fetched = fetch_checkpoint_block::<E>(
+            block_root,
+            Slot::new(0),
+            Slot::new(0),
+            Hash256::zero(),
+            &config,
+            runtime.task_executor.clone(),
+            &spec,
+            TIMEOUT,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fetched, block);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn fetch_incorrect_block_times_out() {
+        let runtime = TestRuntime::default();
+        let spec = E::default_spec();
+        let block = SignedBeaconBlock::from_block(BeaconBlock::empty(&spec), Signature::empty());
+
+        let server_dir = tempdir().unwrap();
+        let server_address =
+            serve_block(block, &server_dir, runtime.task_executor.clone(), &spec).await;
+
+        let client_dir = tempdir().unwrap();
+        let (mut config, _) = network_config(&client_dir);
+        config.libp2p_nodes = vec![server_address];
+
+        // The peer only has a different block, which must not be accepted.
+        let error = fetch_checkpoint_block::<E>(
+            Hash256::repeat_byte(1),
+            Slot::new(0),
+            Slot::new(0),
+            Hash256::zero(),
+            &config,
+            runtime.task_executor.clone(),
+            &spec,
+            Duration::from_secs(3),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("--checkpoint-block"), "{}", error);
+    }
+}
@@ -2,6 +2,7 @@
 extern crate lazy_static;
 
 /// This crate provides the network server for Lighthouse.
+pub mod checkpoint_block;
 pub mod error;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 pub mod service;
@@ -11,12 +12,14 @@ mod beacon_processor;
 mod metrics;
 mod nat;
 mod persisted_dht;
+mod persisted_peers;
 mod router;
 mod status;
 mod subnet_service;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 mod sync;
 
+pub use checkpoint_block::fetch_checkpoint_block;
 pub use lighthouse_network::NetworkConfig;
 pub use service::{
     NetworkMessage, NetworkReceivers, NetworkSenders, NetworkService, ValidatorSubscriptionMessage,
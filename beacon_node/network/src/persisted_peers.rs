@@ -0,0 +1,208 @@
+use lighthouse_network::{PeerId, PersistedIpBan, PersistedPeer};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeerScores`. All zero because they have their own
+/// column.
+pub const PEER_SCORES_DB_KEY: Hash256 = Hash256::zero();
+
+/// The peer scores and IP bans persisted by a previous run.
+#[derive(Debug, PartialEq)]
+pub struct LoadedPeerScores {
+    pub peers: Vec<PersistedPeer>,
+    pub ip_bans: Vec<PersistedIpBan>,
+    /// The time elapsed since the scores were persisted.
+    pub offline: Duration,
+}
+
+/// Loads the peer scores and IP bans persisted by a previous run.
+pub fn load_peer_scores<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Option<LoadedPeerScores> {
+    let persisted: PersistedPeerScores = store.get_item(&PEER_SCORES_DB_KEY).ok()??;
+    let offline = unix_time()
+        .checked_sub(Duration::from_secs(persisted.saved_at))
+        .unwrap_or_default();
+    let peers = persisted
+        .peers
+        .into_iter()
+        .filter_map(SszPersistedPeer::into_persisted_peer)
+        .collect();
+    let ip_bans = persisted
+        .ip_bans
+        .into_iter()
+        .filter_map(SszPersistedIpBan::into_persisted_ip_ban)
+        .collect();
+    Some(LoadedPeerScores {
+        peers,
+        ip_bans,
+        offline,
+    })
+}
+
+/// Attempt to persist the scores of peers and the IP addresses banned by request to `store`.
+pub fn persist_peer_scores<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    peers: Vec<PersistedPeer>,
+    ip_bans: Vec<PersistedIpBan>,
+) -> Result<(), store::Error> {
+    let persisted = PersistedPeerScores {
+        saved_at: unix_time().as_secs(),
+        peers: peers.iter().map(SszPersistedPeer::from).collect(),
+        ip_bans: ip_bans.iter().map(SszPersistedIpBan::from).collect(),
+    };
+    store.put_item(&PEER_SCORES_DB_KEY, &persisted)
+}
+
+fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Wrapper around the scores of peers for persistence to disk.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedPeerScores {
+    /// The unix timestamp, in seconds, at which the scores were persisted.
+    saved_at: u64,
+    peers: Vec<SszPersistedPeer>,
+    ip_bans: Vec<SszPersistedIpBan>,
+}
+
+/// An SSZ encoding of a `PersistedPeer`.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct SszPersistedPeer {
+    peer_id: Vec<u8>,
+    /// The bits of the `f64` score.
+    score: u64,
+    /// The remaining ban, in milliseconds.
+    ban_remaining: u64,
+    /// Socket addresses in their string representation.
+    seen_addresses: Vec<Vec<u8>>,
+}
+
+impl From<&PersistedPeer> for SszPersistedPeer {
+    fn from(peer: &PersistedPeer) -> Self {
+        Self {
+            peer_id: peer.peer_id.to_bytes(),
+            score: peer.score.to_bits(),
+            ban_remaining: peer.ban_remaining.as_millis() as u64,
+            seen_addresses: peer
+                .seen_addresses
+                .iter()
+                .map(|address| address.to_string().into_bytes())
+                .collect(),
+        }
+    }
+}
+
+impl SszPersistedPeer {
+    /// Returns `None` if the peer id is invalid.
+    fn into_persisted_peer(self) -> Option<PersistedPeer> {
+        Some(PersistedPeer {
+            peer_id: PeerId::from_bytes(&self.peer_id).ok()?,
+            score: f64::from_bits(self.score),
+            ban_remaining: Duration::from_millis(self.ban_remaining),
+            seen_addresses: self
+                .seen_addresses
+                .iter()
+                .filter_map(|address| std::str::from_utf8(address).ok()?.parse().ok())
+                .collect::<Vec<SocketAddr>>(),
+        })
+    }
+}
+
+/// An SSZ encoding of a `PersistedIpBan`.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct SszPersistedIpBan {
+    /// The IP address in its string representation.
+    ip: Vec<u8>,
+    /// The remaining ban, in milliseconds.
+    ban_remaining: u64,
+}
+
+impl From<&PersistedIpBan> for SszPersistedIpBan {
+    fn from(ip_ban: &PersistedIpBan) -> Self {
+        Self {
+            ip: ip_ban.ip.to_string().into_bytes(),
+            ban_remaining: ip_ban.ban_remaining.as_millis() as u64,
+        }
+    }
+}
+
+impl SszPersistedIpBan {
+    /// Returns `None` if the IP address is invalid.
+    fn into_persisted_ip_ban(self) -> Option<PersistedIpBan> {
+        Some(PersistedIpBan {
+            ip: std::str::from_utf8(&self.ip).ok()?.parse::<IpAddr>().ok()?,
+            ban_remaining: Duration::from_millis(self.ban_remaining),
+        })
+    }
+}
+
+impl StoreItem for PersistedPeerScores {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerScores
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::{HotColdDB, MemoryStore};
+    use types::{ChainSpec, MinimalEthSpec};
+    #[test]
+    fn test_persisted_peer_scores() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+        let peers = vec![
+            PersistedPeer {
+                peer_id: PeerId::random(),
+                score: -100.0,
+                ban_remaining: Duration::from_millis(1_800_000),
+                seen_addresses: vec!["10.0.0.1:9000".parse().unwrap()],
+            },
+            PersistedPeer {
+                peer_id: PeerId::random(),
+                score: -25.5,
+                ban_remaining: Duration::ZERO,
+                seen_addresses: vec!["[::1]:9000".parse().unwrap()],
+            },
+        ];
+        let ip_bans = vec![
+            PersistedIpBan {
+                ip: "10.0.0.2".parse().unwrap(),
+                ban_remaining: Duration::from_millis(3_600_000),
+            },
+            PersistedIpBan {
+                ip: "::2".parse().unwrap(),
+                ban_remaining: Duration::from_millis(1),
+            },
+        ];
+        persist_peer_scores(store.clone(), peers.clone(), ip_bans.clone()).unwrap();
+        let loaded = load_peer_scores(store).unwrap();
+        assert_eq!(loaded.peers, peers);
+        assert_eq!(loaded.ip_bans, ip_bans);
+        assert!(loaded.offline < Duration::from_secs(60));
+    }
+}
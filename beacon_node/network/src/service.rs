@@ -1,5 +1,6 @@
 use super::sync::manager::RequestId as SyncId;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_peers::{load_peer_scores, persist_peer_scores};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -20,10 +21,15 @@ use lighthouse_network::{
 };
 use lighthouse_network::{
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
-    MessageId, NetworkEvent, NetworkGlobals, PeerId,
+    Enr, MessageId, Multiaddr, NetworkEvent, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
-use std::{net::SocketAddr, pin::Pin, sync::Arc, time::Duration};
+use std::{
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
@@ -38,6 +44,8 @@ mod tests;
 
 /// The interval (in seconds) that various network metrics will update.
 const METRIC_UPDATE_INTERVAL: u64 = 5;
+/// The interval (in seconds) at which peer scores are persisted to the store.
+const PEER_SCORES_PERSIST_INTERVAL: u64 = 300;
 /// Number of slots before the fork when we should subscribe to the new fork topics.
 const SUBSCRIBE_DELAY_SLOTS: u64 = 2;
 /// Delay after a fork where we unsubscribe from pre-fork topics.
@@ -110,6 +118,22 @@ pub enum NetworkMessage<T: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Dial a peer at a multiaddr.
+    DialAddress(Multiaddr),
+    /// Add a peer's ENR to the routing table and dial it.
+    DialEnr(Enr),
+    /// Disconnect a peer without banning it.
+    DisconnectPeer(PeerId),
+    /// Ban a peer for a duration, disconnecting it if required.
+    BanPeer { peer_id: PeerId, duration: Duration },
+    /// Lift any ban on a peer.
+    UnbanPeer(PeerId),
+    /// Ban an IP address for a duration, disconnecting any peers connected from it.
+    BanIp { ip: IpAddr, duration: Duration },
+    /// Lift the ban on an IP address.
+    UnbanIp(IpAddr),
+    /// Add or remove a peer from the set of trusted peers.
+    SetTrustedPeer { peer_id: PeerId, trusted: bool },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -208,6 +232,8 @@ pub struct NetworkService<T: BeaconChainTypes> {
     metrics_update: tokio::time::Interval,
     /// gossipsub_parameter_update timer
     gossipsub_parameter_update: tokio::time::Interval,
+    /// A timer for persisting peer scores, so that bans survive an unclean shutdown.
+    peer_scores_persist: tokio::time::Interval,
     /// enable_light_client_server indicator
     enable_light_client_server: bool,
     /// The logger for the network service.
@@ -294,6 +320,15 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the scores of peers, re-banning any peers and IP addresses whose bans have not
+        // yet expired.
+        if let Some(loaded) =
+            load_peer_scores::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone())
+        {
+            libp2p.restore_persisted_peers(loaded.peers, loaded.offline);
+            libp2p.restore_persisted_ip_bans(loaded.ip_bans, loaded.offline);
+        }
+
         // launch derived network services
 
         // router task
@@ -323,6 +358,13 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // create a timer for updating gossipsub parameters
         let gossipsub_parameter_update = tokio::time::interval(Duration::from_secs(60));
 
+        // create a timer for persisting peer scores
+        let peer_scores_persist_interval = Duration::from_secs(PEER_SCORES_PERSIST_INTERVAL);
+        let peer_scores_persist = tokio::time::interval_at(
+            tokio::time::Instant::now() + peer_scores_persist_interval,
+            peer_scores_persist_interval,
+        );
+
         let NetworkReceivers {
             network_recv,
             validator_subscription_recv,
@@ -350,6 +392,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             metrics_enabled: config.metrics_enabled,
             metrics_update,
             gossipsub_parameter_update,
+            peer_scores_persist,
             fork_context,
             log: network_log,
             enable_light_client_server: config.enable_light_client_server,
@@ -397,6 +440,27 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         result
     }
 
+    /// Persists the scores of peers and the IP addresses banned by request to the store.
+    fn persist_peer_scores(&self) {
+        let peers = self.libp2p.persisted_peers();
+        let ip_bans = self.libp2p.persisted_ip_bans();
+        let num_peers = peers.len();
+        let num_ip_bans = ip_bans.len();
+        match persist_peer_scores::<T::EthSpec, T::HotStore, T::ColdStore>(
+            self.store.clone(),
+            peers,
+            ip_bans,
+        ) {
+            Err(e) => error!(self.log, "Failed to persist peer scores"; "error" => ?e),
+            Ok(_) => debug!(
+                self.log,
+                "Persisted peer scores";
+                "peers" => num_peers,
+                "ip_bans" => num_ip_bans,
+            ),
+        }
+    }
+
     fn send_to_router(&mut self, msg: RouterMessage<T::EthSpec>) {
         if let Err(mpsc::error::SendError(msg)) = self.router_send.send(msg) {
             debug!(self.log, "Failed to send msg to router"; "msg" => ?msg);
@@ -422,6 +486,8 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
                     _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
 
+                    _ = self.peer_scores_persist.tick() => self.persist_peer_scores(),
+
                     // handle a message sent to the network
                     Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, &mut shutdown_sender).await,
 
@@ -670,6 +736,24 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::DialAddress(addr) => {
+                if let Err(e) = self.libp2p.dial_address(addr.clone()) {
+                    warn!(self.log, "Failed to dial address"; "address" => %addr, "error" => %e);
+                }
+            }
+            NetworkMessage::DialEnr(enr) => self.libp2p.dial_enr(enr),
+            NetworkMessage::DisconnectPeer(peer_id) => {
+                self.libp2p.disconnect_peer(peer_id, GoodbyeReason::Unknown)
+            }
+            NetworkMessage::BanPeer { peer_id, duration } => {
+                self.libp2p.ban_peer_for(&peer_id, duration)
+            }
+            NetworkMessage::UnbanPeer(peer_id) => self.libp2p.unban_peer(&peer_id),
+            NetworkMessage::BanIp { ip, duration } => self.libp2p.ban_ip_for(ip, duration),
+            NetworkMessage::UnbanIp(ip) => self.libp2p.unban_ip(&ip),
+            NetworkMessage::SetTrustedPeer { peer_id, trusted } => {
+                self.libp2p.set_trusted(&peer_id, trusted)
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.shutdown_after_sync {
                     if let Err(e) = shutdown_sender
@@ -962,6 +1046,8 @@ impl<T: BeaconChainTypes> Drop for NetworkService<T> {
             ),
         }
 
+        self.persist_peer_scores();
+
         // attempt to remove port mappings
         crate::nat::remove_mappings(self.upnp_mappings.0, self.upnp_mappings.1, &self.log);
 
@@ -24,7 +24,7 @@ use std::collections::{
     HashMap, HashSet,
 };
 use std::sync::Arc;
-use types::{Epoch, EthSpec, SignedBeaconBlock};
+use types::{Epoch, EthSpec, SignedBeaconBlock, Slot};
 
 /// Blocks are downloaded in batches from peers. This constant specifies how many epochs worth of
 /// blocks per batch are requested _at most_. A batch may request less blocks to account for
@@ -101,7 +101,8 @@ pub enum BackFillError {
 
 pub struct BackFillSync<T: BeaconChainTypes> {
     /// Keeps track of the current progress of the backfill.
-    /// This only gets refreshed from the beacon chain if we enter a failed state.
+    /// This only gets refreshed from the beacon chain if we enter a failed state, or once an era
+    /// import has finished.
     current_start: BatchId,
 
     /// Starting epoch of the batch that needs to be processed next.
@@ -136,6 +137,10 @@ pub struct BackFillSync<T: BeaconChainTypes> {
     /// This signifies that we are able to attempt to restart a failed chain.
     restart_failed_sync: bool,
 
+    /// Whether the beacon chain was importing era files when backfill sync was created. Backfill
+    /// sync doesn't start until the import has finished, and then resumes from its oldest block.
+    era_import_pending: bool,
+
     /// Reference to the beacon chain to obtain initial starting points for the backfill sync.
     beacon_chain: Arc<BeaconChain<T>>,
 
@@ -161,7 +166,8 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
 
         let (state, current_start) = if let Some(anchor_info) = beacon_chain.store.get_anchor_info()
         {
-            if anchor_info.block_backfill_complete() {
+            if anchor_info.block_backfill_complete(beacon_chain.store.get_history_retention_slot())
+            {
                 (BackFillState::Completed, Epoch::new(0))
             } else {
                 (
@@ -187,6 +193,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             validated_batches: 0,
             participating_peers: HashSet::new(),
             restart_failed_sync: false,
+            era_import_pending: beacon_chain.is_importing_era_files(),
             beacon_chain,
             log,
         };
@@ -215,6 +222,30 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         match self.state() {
             BackFillState::Syncing => {} // already syncing ignore.
             BackFillState::Paused => {
+                if self.era_import_pending {
+                    if self.beacon_chain.is_importing_era_files() {
+                        return Ok(SyncStart::NotSyncing);
+                    }
+                    self.era_import_pending = false;
+
+                    // Nothing has been downloaded yet, so continue from the oldest imported block.
+                    match self.reset_start_epoch() {
+                        Err(ResetEpochError::SyncCompleted) => {
+                            info!(self.log, "Backfill sync completed by era import");
+                            self.set_state(BackFillState::Completed);
+                            return Ok(SyncStart::NotSyncing);
+                        }
+                        Err(ResetEpochError::NotRequired) => {
+                            self.set_state(BackFillState::NotRequired);
+                            return Ok(SyncStart::NotSyncing);
+                        }
+                        Ok(()) => {
+                            self.processing_target = self.current_start;
+                            self.to_be_downloaded = self.current_start;
+                        }
+                    }
+                }
+
                 if self
                     .network_globals
                     .peers
@@ -1068,7 +1099,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
     /// Creates the next required batch from the chain. If there are no more batches required,
     /// `false` is returned.
     fn include_next_batch(&mut self) -> Option<BatchId> {
-        // don't request batches beyond genesis;
+        // don't request batches beyond the target;
         if self.last_batch_downloaded {
             return None;
         }
@@ -1097,7 +1128,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         match self.batches.entry(batch_id) {
             Entry::Occupied(_) => {
                 // this batch doesn't need downloading, let this same function decide the next batch
-                if batch_id == 0 {
+                if self.would_complete(batch_id) {
                     self.last_batch_downloaded = true;
                 }
 
@@ -1108,7 +1139,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             }
             Entry::Vacant(entry) => {
                 entry.insert(BatchInfo::new(&batch_id, BACKFILL_EPOCHS_PER_BATCH));
-                if batch_id == 0 {
+                if self.would_complete(batch_id) {
                     self.last_batch_downloaded = true;
                 }
                 self.to_be_downloaded = self
@@ -1125,7 +1156,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
     /// not required.
     fn reset_start_epoch(&mut self) -> Result<(), ResetEpochError> {
         if let Some(anchor_info) = self.beacon_chain.store.get_anchor_info() {
-            if anchor_info.block_backfill_complete() {
+            if anchor_info.block_backfill_complete(self.target_slot()) {
                 Err(ResetEpochError::SyncCompleted)
             } else {
                 self.current_start = anchor_info
@@ -1140,21 +1171,29 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
 
     /// Checks with the beacon chain if backfill sync has completed.
     fn check_completed(&mut self) -> bool {
-        if self.current_start == 0 {
-            // Check that the beacon chain agrees
-
-            if let Some(anchor_info) = self.beacon_chain.store.get_anchor_info() {
-                // Conditions that we have completed a backfill sync
-                if anchor_info.block_backfill_complete() {
-                    return true;
-                } else {
-                    error!(self.log, "Backfill out of sync with beacon chain");
-                }
+        if let Some(anchor_info) = self.beacon_chain.store.get_anchor_info() {
+            // Conditions that we have completed a backfill sync
+            if anchor_info.block_backfill_complete(self.target_slot()) {
+                return true;
+            } else if self.would_complete(self.current_start) {
+                // The beacon chain should agree that we have reached the target
+                error!(self.log, "Backfill out of sync with beacon chain");
             }
         }
         false
     }
 
+    /// The slot that backfill must reach. Blocks prior to the retained history are never
+    /// downloaded.
+    fn target_slot(&self) -> Slot {
+        self.beacon_chain.store.get_history_retention_slot()
+    }
+
+    /// Checks if a batch starting at `start_epoch` is the last one required to reach the target.
+    fn would_complete(&self, start_epoch: Epoch) -> bool {
+        start_epoch <= self.target_slot().epoch(T::EthSpec::slots_per_epoch())
+    }
+
     /// Updates the global network state indicating the current state of a backfill sync.
     fn set_state(&self, state: BackFillState) {
         *self.network_globals.backfill_state.write() = state;
@@ -119,6 +119,25 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .hidden(true) // TODO: implement dual stack via two sockets in discv5.
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("quic-port")
+                .long("quic-port")
+                .value_name("PORT")
+                .help("Enables the QUIC transport, listening on the given UDP port. This port \
+                      must differ from the discovery port. If listening over both Ipv4 and Ipv6 \
+                      the --quic-port flag will apply to the Ipv4 address and --quic-port6 to \
+                      the Ipv6 address.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("quic-port6")
+                .long("quic-port6")
+                .value_name("PORT")
+                .help("Enables the QUIC transport over IpV6 when listening over both Ipv4 and \
+                      Ipv6, listening on the given UDP port. This port must differ from the \
+                      discovery port.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("target-peers")
                 .long("target-peers")
@@ -189,6 +208,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                       used if this is not set.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("enr-quic-port")
+                .long("enr-quic-port")
+                .value_name("PORT")
+                .help("The QUIC4 port of the local ENR. Set this only if you are sure other nodes \
+                      can connect to your local node on this port over IpV4. The --quic-port flag \
+                      is used if this is not set. Has no effect unless QUIC is enabled.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("enr-quic6-port")
+                .long("enr-quic6-port")
+                .value_name("PORT")
+                .help("The QUIC6 port of the local ENR. Set this only if you are sure other nodes \
+                      can connect to your local node on this port over IpV6. The --quic-port6 \
+                      flag is used if this is not set. Has no effect unless QUIC is enabled.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("enr-address")
                 .long("enr-address")
@@ -337,6 +374,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     stalled. This is useful for very small testnets. TESTING ONLY. DO NOT USE ON \
                     MAINNET.")
         )
+        .arg(
+            Arg::with_name("http-admin-token-file")
+                .long("http-admin-token-file")
+                .value_name("PATH")
+                .help("Path to a file containing a secret token which enables the peer \
+                    administration endpoints of the HTTP API. Requests to these endpoints must \
+                    provide the token in an `Authorization: Bearer <token>` header.")
+                .takes_value(true)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::with_name("metrics")
@@ -495,6 +541,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Specifies how many blocks the database should cache in memory [default: 5]")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("db-backend")
+                .long("db-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend to be used by the beacon node. The backend is \
+                       recorded in the database, and an existing database can only be opened \
+                       with the backend that created it. [default: leveldb]")
+                .takes_value(true)
+                .possible_values(store::config::DatabaseBackend::VARIANTS)
+        )
         /*
          * Execution Layer Integration
          */
@@ -512,8 +568,10 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("EXECUTION-ENDPOINT")
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
-                       JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       JSON-RPC connection. Multiple comma-separated endpoints may be \
+                       provided, in which case fork choice updates and payloads are sent \
+                       to all of them and payloads are produced by the healthiest. Uses \
+                       the first endpoint to populate the deposit cache.")
                 .takes_value(true)
         )
         .arg(
@@ -522,7 +580,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. If \
+                       multiple endpoints are provided, either one comma-separated path \
+                       per endpoint or a single path shared by all endpoints may be given.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -574,7 +634,20 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. Multiple \
+                       relays may be provided as a comma-separated list, in which case headers \
+                       are requested from all of them and the most valuable bid is used.")
+                .requires("execution-endpoint")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("execution-quorum")
+                .long("execution-quorum")
+                .value_name("NUM")
+                .help("The number of execution endpoints that must agree that a payload is \
+                       valid or invalid before the beacon node acts on it. Until the quorum \
+                       is reached the payload is imported optimistically. Defaults to 1, \
+                       in which case the first valid response is used.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -616,6 +689,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .default_value("true")
         )
+        .arg(
+            Arg::with_name("history-retention-epochs")
+                .long("history-retention-epochs")
+                .value_name("EPOCHS")
+                .help("Prune finalized blocks, execution payloads and historic states which are \
+                       older than this number of epochs. History within the weak subjectivity \
+                       period is always retained. Pruned blocks cannot be served to syncing \
+                       peers. By default all history is retained.")
+                .takes_value(true)
+                .conflicts_with("reconstruct-historic-states")
+        )
 
         /*
          * Misc.
@@ -767,10 +851,11 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("checkpoint-state")
                 .long("checkpoint-state")
                 .help("Set a checkpoint state to start syncing from. Must be aligned and match \
-                       --checkpoint-block. Using --checkpoint-sync-url instead is recommended.")
+                       --checkpoint-block if provided. If --checkpoint-block is omitted, the \
+                       block is downloaded from peers during startup. Using \
+                       --checkpoint-sync-url instead is recommended.")
                 .value_name("STATE_SSZ")
                 .takes_value(true)
-                .requires("checkpoint-block")
         )
         .arg(
             Arg::with_name("checkpoint-block")
@@ -803,6 +888,14 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("After a checkpoint sync, reconstruct historic states in the database.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("import-era-dir")
+                .long("import-era-dir")
+                .value_name("DIR")
+                .help("After a checkpoint sync, import historic blocks from the era files in this \
+                       directory on startup, rather than backfilling them from the network.")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("validator-monitor-auto")
                 .long("validator-monitor-auto")
@@ -1005,7 +1098,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("light-client-server")
                 .long("light-client-server")
                 .help("Act as a full node supporting light clients on the p2p network \
-                       [experimental]")
+                       and HTTP API [experimental]")
                 .takes_value(false)
         )
         .arg(
@@ -79,6 +79,10 @@ pub fn get_config<E: EthSpec>(
 
     set_network_config(&mut client_config.network, cli_args, &data_dir_ref, log)?;
 
+    // The chain computes light client data whenever the network serves it.
+    client_config.chain.enable_light_client_server =
+        client_config.network.enable_light_client_server;
+
     /*
      * Staking flag
      * Note: the config values set here can be overwritten by other more specific cli params
@@ -148,6 +152,9 @@ pub fn get_config<E: EthSpec>(
         client_config.http_api.allow_sync_stalled = true;
     }
 
+    client_config.http_api.admin_token_path =
+        clap_utils::parse_optional(cli_args, "http-admin-token-file")?;
+
     if let Some(cache_size) = clap_utils::parse_optional(cli_args, "shuffling-cache-size")? {
         client_config.chain.shuffling_cache_size = cache_size;
     }
@@ -291,25 +298,33 @@ pub fn get_config<E: EthSpec>(
         // stakers. The merge is already complicated enough.
         client_config.sync_eth1_chain = true;
 
-        // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-        let execution_endpoint =
-            parse_only_one_value(endpoints, SensitiveUrl::parse, "--execution-endpoint", log)?;
+        // Parse one or more comma-separated execution endpoints.
+        let execution_endpoints =
+            parse_multiple_values(endpoints, SensitiveUrl::parse, "--execution-endpoint")?;
 
         // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
         // file_path or directly as string.
 
-        let secret_file: PathBuf;
-        // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
-        if let Some(secret_files) = cli_args.value_of("execution-jwt") {
-            secret_file =
-                parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
+        let secret_files: Vec<PathBuf>;
+        // Parse one JWT secret per endpoint, or a single JWT secret shared by all endpoints.
+        if let Some(secret_files_arg) = cli_args.value_of("execution-jwt") {
+            secret_files =
+                parse_multiple_values(secret_files_arg, PathBuf::from_str, "--execution-jwt")?;
+            if secret_files.len() != 1 && secret_files.len() != execution_endpoints.len() {
+                return Err(format!(
+                    "--execution-jwt must provide either one secret or one secret per execution \
+                     endpoint, got {} secrets for {} endpoints",
+                    secret_files.len(),
+                    execution_endpoints.len()
+                ));
+            }
 
         // Check if the JWT secret key is passed directly via cli flag and persist it to the default
         // file location.
         } else if let Some(jwt_secret_key) = cli_args.value_of("execution-jwt-secret-key") {
             use std::fs::File;
             use std::io::Write;
-            secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
+            let secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
             let mut jwt_secret_key_file = File::create(secret_file.clone())
                 .map_err(|e| format!("Error while creating jwt_secret_key file: {:?}", e))?;
             jwt_secret_key_file
@@ -320,20 +335,21 @@ pub fn get_config<E: EthSpec>(
                         e
                     )
                 })?;
+            secret_files = vec![secret_file];
         } else {
             return Err("Error! Please set either --execution-jwt file_path or --execution-jwt-secret-key directly via cli when using --execution-endpoint".to_string());
         }
 
-        // Parse and set the payload builder, if any.
-        if let Some(endpoint) = cli_args.value_of("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+        // Parse and set the payload builders, if any.
+        if let Some(endpoints) = cli_args.value_of("builder") {
+            el_config.builder_urls =
+                parse_multiple_values(endpoints, SensitiveUrl::parse, "--builder")?;
         }
 
         // Set config values from parse values.
-        el_config.secret_files = vec![secret_file.clone()];
-        el_config.execution_endpoints = vec![execution_endpoint.clone()];
+        el_config.secret_files = secret_files.clone();
+        el_config.execution_endpoints = execution_endpoints.clone();
+        el_config.execution_quorum = clap_utils::parse_optional(cli_args, "execution-quorum")?;
         el_config.suggested_fee_recipient =
             clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
         el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
@@ -355,9 +371,10 @@ pub fn get_config<E: EthSpec>(
                     --eth1-endpoints has been deprecated for post-merge configurations"
             );
         }
+        // The deposit cache is populated from the first execution endpoint.
         client_config.eth1.endpoint = Eth1Endpoint::Auth {
-            endpoint: execution_endpoint,
-            jwt_path: secret_file,
+            endpoint: execution_endpoints[0].clone(),
+            jwt_path: secret_files[0].clone(),
             jwt_id: el_config.jwt_id.clone(),
             jwt_version: el_config.jwt_version.clone(),
         };
@@ -380,6 +397,10 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "block-cache-size is not a valid integer".to_string())?;
     }
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "db-backend")? {
+        client_config.store.backend = backend;
+    }
+
     client_config.store.compact_on_init = cli_args.is_present("compact-db");
     if let Some(compact_on_prune) = cli_args.value_of("auto-compact-db") {
         client_config.store.compact_on_prune = compact_on_prune
@@ -391,6 +412,9 @@ pub fn get_config<E: EthSpec>(
         client_config.store.prune_payloads = prune_payloads;
     }
 
+    client_config.store.history_retention_epochs =
+        clap_utils::parse_optional(cli_args, "history-retention-epochs")?;
+
     /*
      * Zero-ports
      *
@@ -447,10 +471,7 @@ pub fn get_config<E: EthSpec>(
         eth2_network_config.genesis_state_bytes.clone()
     {
         // Set up weak subjectivity sync, or start from the hardcoded genesis state.
-        if let (Some(initial_state_path), Some(initial_block_path)) = (
-            cli_args.value_of("checkpoint-state"),
-            cli_args.value_of("checkpoint-block"),
-        ) {
+        if let Some(initial_state_path) = cli_args.value_of("checkpoint-state") {
             let read = |path: &str| {
                 use std::fs::File;
                 use std::io::Read;
@@ -464,12 +485,21 @@ pub fn get_config<E: EthSpec>(
             };
 
             let anchor_state_bytes = read(initial_state_path)?;
-            let anchor_block_bytes = read(initial_block_path)?;
 
-            ClientGenesis::WeakSubjSszBytes {
-                genesis_state_bytes,
-                anchor_state_bytes,
-                anchor_block_bytes,
+            if let Some(initial_block_path) = cli_args.value_of("checkpoint-block") {
+                let anchor_block_bytes = read(initial_block_path)?;
+
+                ClientGenesis::WeakSubjSszBytes {
+                    genesis_state_bytes,
+                    anchor_state_bytes,
+                    anchor_block_bytes,
+                }
+            } else {
+                // The block will be downloaded from peers during startup.
+                ClientGenesis::WeakSubjSszState {
+                    genesis_state_bytes,
+                    anchor_state_bytes,
+                }
             }
         } else if let Some(remote_bn_url) = cli_args.value_of("checkpoint-sync-url") {
             let url = SensitiveUrl::parse(remote_bn_url)
@@ -501,6 +531,8 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.reconstruct_historic_states = true;
     }
 
+    client_config.chain.era_import_dir = clap_utils::parse_optional(cli_args, "import-era-dir")?;
+
     let raw_graffiti = if let Some(graffiti) = cli_args.value_of("graffiti") {
         if graffiti.len() > GRAFFITI_BYTES_LEN {
             return Err(format!(
@@ -832,6 +864,22 @@ pub fn parse_listening_addresses(
             format!("Failed to parse --discovery-port6 as an integer: {parse_error}")
         })?;
 
+    // parse the possible quic ports. QUIC is disabled unless a port is given.
+    let maybe_quic_port = cli_args
+        .value_of("quic-port")
+        .map(str::parse::<u16>)
+        .transpose()
+        .map_err(|parse_error| {
+            format!("Failed to parse --quic-port as an integer: {parse_error}")
+        })?;
+    let maybe_quic6_port = cli_args
+        .value_of("quic-port6")
+        .map(str::parse::<u16>)
+        .transpose()
+        .map_err(|parse_error| {
+            format!("Failed to parse --quic-port6 as an integer: {parse_error}")
+        })?;
+
     // Now put everything together
     let listening_addresses = match (maybe_ipv4, maybe_ipv6) {
         (None, None) => {
@@ -861,10 +909,20 @@ pub fn parse_listening_addresses(
                 .or(maybe_udp_port)
                 .unwrap_or(port);
 
+            if maybe_quic6_port.is_some() {
+                warn!(log, "When listening only over IpV6, use the --quic-port flag. The value of --quic-port6 will be ignored.")
+            }
+            // use zero ports if required and QUIC is enabled.
+            let quic_port = match maybe_quic_port {
+                Some(_) if use_zero_ports => Some(unused_port::unused_udp6_port()?),
+                quic_port => quic_port,
+            };
+
             ListenAddress::V6(lighthouse_network::ListenAddr {
                 addr: ipv6,
                 udp_port,
                 tcp_port,
+                quic_port,
             })
         }
         (Some(ipv4), None) => {
@@ -882,10 +940,16 @@ pub fn parse_listening_addresses(
                 .transpose()?
                 .or(maybe_udp_port)
                 .unwrap_or(port);
+            // use zero ports if required and QUIC is enabled.
+            let quic_port = match maybe_quic_port {
+                Some(_) if use_zero_ports => Some(unused_port::unused_udp4_port()?),
+                quic_port => quic_port,
+            };
             ListenAddress::V4(lighthouse_network::ListenAddr {
                 addr: ipv4,
                 udp_port,
                 tcp_port,
+                quic_port,
             })
         }
         (Some(ipv4), Some(ipv6)) => {
@@ -909,21 +973,55 @@ pub fn parse_listening_addresses(
                 .transpose()?
                 .or(maybe_udp6_port)
                 .unwrap_or(ipv6_tcp_port);
+
+            // QUIC is enabled independently for each ip version.
+            let ipv4_quic_port = match maybe_quic_port {
+                Some(_) if use_zero_ports => Some(unused_port::unused_udp4_port()?),
+                quic_port => quic_port,
+            };
+            let ipv6_quic_port = match maybe_quic6_port {
+                Some(_) if use_zero_ports => Some(unused_port::unused_udp6_port()?),
+                quic_port => quic_port,
+            };
             ListenAddress::DualStack(
                 lighthouse_network::ListenAddr {
                     addr: ipv4,
                     udp_port: ipv4_udp_port,
                     tcp_port: ipv4_tcp_port,
+                    quic_port: ipv4_quic_port,
                 },
                 lighthouse_network::ListenAddr {
                     addr: ipv6,
                     udp_port: ipv6_udp_port,
                     tcp_port: ipv6_tcp_port,
+                    quic_port: ipv6_quic_port,
                 },
             )
         }
     };
 
+    // QUIC and discovery both listen over UDP, so they can't share a port. Zero ports are picked
+    // independently of each other, so they aren't checked.
+    if !use_zero_ports {
+        let ports = listening_addresses
+            .v4()
+            .map(|addr| (addr.quic_port, addr.udp_port))
+            .into_iter()
+            .chain(
+                listening_addresses
+                    .v6()
+                    .map(|addr| (addr.quic_port, addr.udp_port)),
+            );
+        for (quic_port, udp_port) in ports {
+            if quic_port == Some(udp_port) {
+                return Err(format!(
+                    "The QUIC port ({udp_port}) must differ from the discovery port, as both \
+                    listen over UDP"
+                ));
+            }
+        }
+    }
+
     Ok(listening_addresses)
 }
 
@@ -1047,6 +1145,22 @@ pub fn set_network_config(
         );
     }
 
+    if let Some(enr_quic_port_str) = cli_args.value_of("enr-quic-port") {
+        config.enr_quic4_port = Some(
+            enr_quic_port_str
+                .parse::<u16>()
+                .map_err(|_| format!("Invalid ENR QUIC port: {}", enr_quic_port_str))?,
+        );
+    }
+
+    if let Some(enr_quic_port_str) = cli_args.value_of("enr-quic6-port") {
+        config.enr_quic6_port = Some(
+            enr_quic_port_str
+                .parse::<u16>()
+                .map_err(|_| format!("Invalid ENR QUIC port: {}", enr_quic_port_str))?,
+        );
+    }
+
     if cli_args.is_present("enr-match") {
         // Match the Ip and UDP port in the enr.
 
@@ -1232,6 +1346,29 @@ pub fn get_slots_per_restore_point<E: EthSpec>(
     }
 }
 
+/// Parses a comma-separated list of values for `flag_name`, requiring at least one value.
+pub fn parse_multiple_values<F, T, E>(
+    cli_value: &str,
+    parser: F,
+    flag_name: &str,
+) -> Result<Vec<T>, String>
+where
+    F: Fn(&str) -> Result<T, E>,
+    E: Debug,
+{
+    let values = cli_value
+        .split(',')
+        .map(parser)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))?;
+
+    if values.is_empty() {
+        return Err(format!("Must provide at least one value to {}", flag_name));
+    }
+
+    Ok(values)
+}
+
 /// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
 ///
 /// If there is more than one value, log a warning. If there are no values, return an error.
@@ -0,0 +1,136 @@
+//! An on-disk key-value store whose backend is chosen at runtime by `StoreConfig::backend`.
+use crate::config::DatabaseBackend;
+#[cfg(feature = "rocksdb")]
+use crate::rocksdb_store::RocksDB;
+use crate::{
+    ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp, LevelDB,
+    RawEntryIter,
+};
+use parking_lot::MutexGuard;
+use std::path::Path;
+use types::EthSpec;
+
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    #[cfg(feature = "rocksdb")]
+    RocksDb(RocksDB<E>),
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open a database at `path` with the given `backend`, creating it if it does not exist.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(BeaconNodeBackend::LevelDb),
+            #[cfg(feature = "rocksdb")]
+            DatabaseBackend::RocksDb => RocksDB::open(path).map(BeaconNodeBackend::RocksDb),
+        }
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            BeaconNodeBackend::LevelDb(_) => DatabaseBackend::LevelDb,
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(_) => DatabaseBackend::RocksDb,
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.get_bytes(column, key),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.get_bytes(column, key),
+        }
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.put_bytes(column, key, value),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.put_bytes(column, key, value),
+        }
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.put_bytes_sync(column, key, value),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.put_bytes_sync(column, key, value),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.sync(),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.sync(),
+        }
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.key_exists(column, key),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.key_exists(column, key),
+        }
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.key_delete(column, key),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.key_delete(column, key),
+        }
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.do_atomically(batch),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.do_atomically(batch),
+        }
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.begin_rw_transaction(),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.begin_rw_transaction(),
+        }
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.compact(),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.compact(),
+        }
+    }
+
+    fn iter_column(&self, column: DBColumn) -> ColumnIter {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.iter_column(column),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.iter_column(column),
+        }
+    }
+
+    fn iter_column_keys(&self, column: DBColumn) -> ColumnKeyIter {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.iter_column_keys(column),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.iter_column_keys(column),
+        }
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn) -> RawEntryIter {
+        match self {
+            BeaconNodeBackend::LevelDb(db) => db.iter_raw_entries(column),
+            #[cfg(feature = "rocksdb")]
+            BeaconNodeBackend::RocksDb(db) => db.iter_raw_entries(column),
+        }
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
@@ -2,11 +2,14 @@ use crate::{DBColumn, Error, StoreItem};
 use serde_derive::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
+use std::str::FromStr;
+use strum::{Display, EnumString, EnumVariantNames};
 use types::{EthSpec, MinimalEthSpec};
 
 pub const PREV_DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
 pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 8192;
 pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 5;
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::LevelDb;
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,6 +26,12 @@ pub struct StoreConfig {
     pub compact_on_prune: bool,
     /// Whether to prune payloads on initialization and finalization.
     pub prune_payloads: bool,
+    /// Number of epochs of finalized blocks and states to retain, or `None` to retain all history.
+    ///
+    /// History within the weak subjectivity period is always retained.
+    pub history_retention_epochs: Option<u64>,
+    /// Key-value database backend to use for the hot and cold databases.
+    pub backend: DatabaseBackend,
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -33,7 +42,24 @@ pub struct OnDiskStoreConfig {
 
 #[derive(Debug, Clone)]
 pub enum StoreConfigError {
-    MismatchedSlotsPerRestorePoint { config: u64, on_disk: u64 },
+    MismatchedSlotsPerRestorePoint {
+        config: u64,
+        on_disk: u64,
+    },
+    MismatchedDatabaseBackend {
+        config: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString, EnumVariantNames,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    LevelDb,
+    #[cfg(feature = "rocksdb")]
+    RocksDb,
 }
 
 impl Default for StoreConfig {
@@ -46,6 +72,8 @@ impl Default for StoreConfig {
             compact_on_init: false,
             compact_on_prune: true,
             prune_payloads: true,
+            history_retention_epochs: None,
+            backend: DEFAULT_BACKEND,
         }
     }
 }
@@ -69,6 +97,20 @@ impl StoreConfig {
         }
         Ok(())
     }
+
+    /// Check that the backend recorded in an existing database matches the configured backend.
+    pub fn check_backend_compatibility(
+        &self,
+        on_disk_backend: DatabaseBackend,
+    ) -> Result<(), StoreConfigError> {
+        if self.backend != on_disk_backend {
+            return Err(StoreConfigError::MismatchedDatabaseBackend {
+                config: self.backend,
+                on_disk: on_disk_backend,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl StoreItem for OnDiskStoreConfig {
@@ -84,3 +126,20 @@ impl StoreItem for OnDiskStoreConfig {
         Ok(Self::from_ssz_bytes(bytes)?)
     }
 }
+
+/// The backend is recorded by name, so that a database written by a backend which this build does
+/// not support can still be identified.
+impl StoreItem for DatabaseBackend {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let name = String::from_utf8_lossy(bytes);
+        DatabaseBackend::from_str(&name).map_err(|_| Error::UnsupportedDatabaseBackend(name.into()))
+    }
+}
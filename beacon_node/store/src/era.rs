@@ -0,0 +1,573 @@
+//! Reading and writing of era files, and export of finalized history from the freezer database.
+//!
+//! An era file is an e2store file holding the blocks of one period of `SLOTS_PER_HISTORICAL_ROOT`
+//! slots, followed by the state at the end of the period. Blocks and states are stored as
+//! snappy-framed SSZ, and slot indices at the end of the file give the offsets of each record:
+//!
+//! `Version | CompressedSignedBeaconBlock* | CompressedBeaconState | SlotIndex(block)? |
+//! SlotIndex(state)`
+//!
+//! See https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md
+use crate::hot_cold_store::HotColdDB;
+use crate::{Error as StoreError, ItemStore};
+use ssz::{Decode, Encode};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use tree_hash::TreeHash;
+use types::{BeaconState, ChainSpec, EthSpec, SignedBeaconBlock, Slot};
+
+/// Record type of the version record which starts every e2store file.
+pub const VERSION: [u8; 2] = [0x65, 0x32];
+/// Record type of a snappy-framed SSZ `SignedBeaconBlock`.
+pub const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+/// Record type of a snappy-framed SSZ `BeaconState`.
+pub const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+/// Record type of an index from slots to the offsets of records.
+pub const SLOT_INDEX: [u8; 2] = [0x69, 0x32];
+
+/// Length of the header of each e2store record.
+const HEADER_LEN: u64 = 8;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    SszDecodeError(ssz::DecodeError),
+    /// The file does not start with an e2store version record.
+    MissingVersion,
+    /// A record is too large to be stored in an e2store file.
+    RecordTooLarge(usize),
+    /// Blocks must be written in slot order, with at most one per slot.
+    BlockOutOfOrder {
+        slot: Slot,
+    },
+    /// A block does not belong to the era being written.
+    BlockOutsideEra {
+        slot: Slot,
+        start_slot: Slot,
+    },
+    /// The state of an era must be written exactly once, after all of its blocks.
+    UnexpectedState,
+    /// An era was finished without its state.
+    MissingState,
+    /// The chain spec has no `CONFIG_NAME` with which to name era files.
+    MissingConfigName,
+    /// A slot index is malformed, or does not point to a record of the expected type.
+    InvalidSlotIndex,
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ssz::DecodeError> for Error {
+    fn from(e: ssz::DecodeError) -> Self {
+        Error::SszDecodeError(e)
+    }
+}
+
+/// A block or state read from an era file.
+pub enum EraEntry<E: EthSpec> {
+    Block(Box<SignedBeaconBlock<E>>),
+    State(Box<BeaconState<E>>),
+}
+
+/// Writes the records of a single era to `writer`.
+pub struct EraWriter<E: EthSpec, W: Write> {
+    writer: W,
+    /// Number of bytes written so far.
+    position: u64,
+    /// First slot of the blocks of the era.
+    start_slot: Slot,
+    /// Position of the block at each slot of the era, if any.
+    block_positions: Vec<Option<u64>>,
+    /// Slot of the last block written.
+    last_block_slot: Option<Slot>,
+    /// Position of the state at the end of the era.
+    state_position: Option<u64>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec, W: Write> EraWriter<E, W> {
+    /// Start writing era number `era` to `writer`.
+    pub fn new(writer: W, era: u64) -> Result<Self, Error> {
+        let slots_per_era = E::slots_per_historical_root() as u64;
+        let mut era_writer = Self {
+            writer,
+            position: 0,
+            start_slot: Slot::new(era.saturating_sub(1) * slots_per_era),
+            // The genesis era has no blocks.
+            block_positions: if era == 0 {
+                vec![]
+            } else {
+                vec![None; slots_per_era as usize]
+            },
+            last_block_slot: None,
+            state_position: None,
+            _phantom: PhantomData,
+        };
+        era_writer.write_record(VERSION, &[])?;
+        Ok(era_writer)
+    }
+
+    /// Write a block of the era. Blocks must be written in slot order, before the state.
+    pub fn write_block(&mut self, block: &SignedBeaconBlock<E>) -> Result<(), Error> {
+        let slot = block.slot();
+        if self.state_position.is_some()
+            || self
+                .last_block_slot
+                .map_or(false, |last_slot| slot <= last_slot)
+        {
+            return Err(Error::BlockOutOfOrder { slot });
+        }
+
+        let index = slot
+            .as_u64()
+            .checked_sub(self.start_slot.as_u64())
+            .filter(|index| *index < self.block_positions.len() as u64)
+            .ok_or(Error::BlockOutsideEra {
+                slot,
+                start_slot: self.start_slot,
+            })?;
+
+        self.block_positions[index as usize] = Some(self.position);
+        self.last_block_slot = Some(slot);
+        let data = compress(&block.as_ssz_bytes())?;
+        self.write_record(COMPRESSED_SIGNED_BEACON_BLOCK, &data)
+    }
+
+    /// Write the state at the end of the era.
+    pub fn write_state(&mut self, state: &BeaconState<E>) -> Result<(), Error> {
+        if self.state_position.is_some() {
+            return Err(Error::UnexpectedState);
+        }
+        self.state_position = Some(self.position);
+        let data = compress(&state.as_ssz_bytes())?;
+        self.write_record(COMPRESSED_BEACON_STATE, &data)
+    }
+
+    /// Write the slot indices and return the underlying writer.
+    pub fn finish(mut self) -> Result<W, Error> {
+        let state_position = self.state_position.ok_or(Error::MissingState)?;
+        let state_slot = self.start_slot + self.block_positions.len() as u64;
+
+        if !self.block_positions.is_empty() {
+            let block_positions = std::mem::take(&mut self.block_positions);
+            self.write_slot_index(self.start_slot, &block_positions)?;
+        }
+        self.write_slot_index(state_slot, &[Some(state_position)])?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    /// Write a slot index, whose offsets are relative to the start of the index record.
+    fn write_slot_index(
+        &mut self,
+        start_slot: Slot,
+        positions: &[Option<u64>],
+    ) -> Result<(), Error> {
+        let index_position = self.position as i64;
+        let mut data = Vec::with_capacity((positions.len() + 2) * 8);
+        data.extend_from_slice(&(start_slot.as_u64() as i64).to_le_bytes());
+        for position in positions {
+            let offset = position.map_or(0, |position| position as i64 - index_position);
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(&(positions.len() as i64).to_le_bytes());
+        self.write_record(SLOT_INDEX, &data)
+    }
+
+    fn write_record(&mut self, record_type: [u8; 2], data: &[u8]) -> Result<(), Error> {
+        let length = u32::try_from(data.len()).map_err(|_| Error::RecordTooLarge(data.len()))?;
+        self.writer.write_all(&record_type)?;
+        self.writer.write_all(&length.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+        self.writer.write_all(data)?;
+        self.position += HEADER_LEN + data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Reads the blocks and state of an era file, in the order in which they were written.
+pub struct EraReader<E: EthSpec, R: Read> {
+    reader: R,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec, R: Read> EraReader<E, R> {
+    pub fn new(reader: R) -> Result<Self, Error> {
+        let mut era_reader = Self {
+            reader,
+            _phantom: PhantomData,
+        };
+        match era_reader.read_record()? {
+            Some((VERSION, _)) => Ok(era_reader),
+            _ => Err(Error::MissingVersion),
+        }
+    }
+
+    /// Read the next block or state, skipping any other records.
+    pub fn next_entry(&mut self, spec: &ChainSpec) -> Result<Option<EraEntry<E>>, Error> {
+        while let Some((record_type, data)) = self.read_record()? {
+            match record_type {
+                COMPRESSED_SIGNED_BEACON_BLOCK => {
+                    let block = SignedBeaconBlock::from_ssz_bytes(&decompress(&data)?, spec)?;
+                    return Ok(Some(EraEntry::Block(Box::new(block))));
+                }
+                COMPRESSED_BEACON_STATE => {
+                    let state = BeaconState::from_ssz_bytes(&decompress(&data)?, spec)?;
+                    return Ok(Some(EraEntry::State(Box::new(state))));
+                }
+                _ => continue,
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_record(&mut self) -> Result<Option<([u8; 2], Vec<u8>)>, Error> {
+        let mut header = [0; HEADER_LEN as usize];
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => (),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        let record_type = [header[0], header[1]];
+        let length = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+
+        let mut data = vec![0; length as usize];
+        self.reader.read_exact(&mut data)?;
+        Ok(Some((record_type, data)))
+    }
+}
+
+impl<E: EthSpec, R: Read + Seek> EraReader<E, R> {
+    /// Read the slot indices at the end of the file, returning the slot and position of each
+    /// block of the era in slot order.
+    ///
+    /// Together with `read_block_at` this allows the blocks to be read in any order without
+    /// holding the whole era in memory.
+    pub fn block_positions(&mut self) -> Result<Vec<(Slot, u64)>, Error> {
+        // The state index is the last record, and holds a single offset.
+        let file_len = self.reader.seek(SeekFrom::End(0))?;
+        let state_index_position = file_len
+            .checked_sub(HEADER_LEN + 3 * 8)
+            .ok_or(Error::InvalidSlotIndex)?;
+        let (state_slot, _) = self.read_slot_index_at(state_index_position)?;
+
+        // The genesis era has no blocks, and so no block index.
+        if state_slot == Slot::new(0) {
+            return Ok(vec![]);
+        }
+
+        // Otherwise the block index precedes the state index, with an offset for every slot.
+        let slots_per_era = E::slots_per_historical_root() as u64;
+        let block_index_position = state_index_position
+            .checked_sub(HEADER_LEN + (slots_per_era + 2) * 8)
+            .ok_or(Error::InvalidSlotIndex)?;
+        let (start_slot, offsets) = self.read_slot_index_at(block_index_position)?;
+        if offsets.len() as u64 != slots_per_era {
+            return Err(Error::InvalidSlotIndex);
+        }
+
+        offsets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, offset)| *offset != 0)
+            .map(|(index, offset)| {
+                let position = (block_index_position as i64)
+                    .checked_add(offset)
+                    .and_then(|position| u64::try_from(position).ok())
+                    .ok_or(Error::InvalidSlotIndex)?;
+                Ok((start_slot + index as u64, position))
+            })
+            .collect()
+    }
+
+    /// Read the block whose record starts at `position`.
+    pub fn read_block_at(
+        &mut self,
+        position: u64,
+        spec: &ChainSpec,
+    ) -> Result<SignedBeaconBlock<E>, Error> {
+        self.reader.seek(SeekFrom::Start(position))?;
+        match self.read_record()? {
+            Some((COMPRESSED_SIGNED_BEACON_BLOCK, data)) => Ok(SignedBeaconBlock::from_ssz_bytes(
+                &decompress(&data)?,
+                spec,
+            )?),
+            _ => Err(Error::InvalidSlotIndex),
+        }
+    }
+
+    /// Read the slot index whose record starts at `position`, returning its start slot and offsets.
+    fn read_slot_index_at(&mut self, position: u64) -> Result<(Slot, Vec<i64>), Error> {
+        self.reader.seek(SeekFrom::Start(position))?;
+        let data = match self.read_record()? {
+            Some((SLOT_INDEX, data)) if data.len() >= 16 && data.len() % 8 == 0 => data,
+            _ => return Err(Error::InvalidSlotIndex),
+        };
+
+        let mut values = data.chunks_exact(8).map(|chunk| {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(chunk);
+            i64::from_le_bytes(bytes)
+        });
+        let start_slot = values.next().ok_or(Error::InvalidSlotIndex)?;
+        let mut offsets = values.collect::<Vec<_>>();
+        let count = offsets.pop().ok_or(Error::InvalidSlotIndex)?;
+        if start_slot < 0 || count != offsets.len() as i64 {
+            return Err(Error::InvalidSlotIndex);
+        }
+
+        Ok((Slot::new(start_slot as u64), offsets))
+    }
+}
+
+fn compress(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut encoder = snap::write::FrameEncoder::new(vec![]);
+    encoder.write_all(bytes)?;
+    encoder.into_inner().map_err(|e| e.into_error().into())
+}
+
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut decompressed = vec![];
+    snap::read::FrameDecoder::new(bytes).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Return the name of the file for `era`, given the state at the end of the era.
+///
+/// Files are named `<config-name>-<era-number>-<short-historical-root>.era`, where the short
+/// historical root is the first 4 bytes of the latest historical root of the state, or of the
+/// genesis validators root for the genesis era.
+pub fn era_file_name<E: EthSpec>(config_name: &str, era: u64, state: &BeaconState<E>) -> String {
+    let historical_root = state
+        .historical_summaries()
+        .ok()
+        .and_then(|summaries| summaries.last().map(TreeHash::tree_hash_root))
+        .or_else(|| state.historical_roots().last().copied())
+        .filter(|_| era > 0)
+        .unwrap_or_else(|| state.genesis_validators_root());
+    let short_root = historical_root.as_bytes()[..4]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    format!("{}-{:05}-{}.era", config_name, era, short_root)
+}
+
+/// Parse the era number from the name of an era file.
+pub fn parse_era_file_name(file_name: &str) -> Option<u64> {
+    let stem = file_name.strip_suffix(".era")?;
+    let mut parts = stem.rsplitn(3, '-');
+    let _short_root = parts.next()?;
+    let era = parts.next()?;
+    let _config_name = parts.next()?;
+    era.parse().ok()
+}
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    /// Return the eras which can be exported from the freezer database, in ascending order.
+    ///
+    /// An era can be exported if all of its blocks, and the state at its end, are available.
+    pub fn exportable_eras(&self) -> Vec<u64> {
+        let slots_per_era = E::slots_per_historical_root() as u64;
+        let split_slot = self.get_split_slot();
+        let (lower_limit, upper_limit) = self.get_historic_state_limits();
+        let oldest_block_slot = self.get_oldest_block_slot();
+
+        // The state of the last era must be older than the split, so that it is frozen.
+        let end_era = (split_slot.as_u64() + slots_per_era - 1) / slots_per_era;
+
+        (0..end_era)
+            .filter(|era| {
+                let state_slot = Slot::new(era * slots_per_era);
+                let start_slot = Slot::new(era.saturating_sub(1) * slots_per_era);
+                (state_slot <= lower_limit || state_slot >= upper_limit)
+                    && (*era == 0 || start_slot >= oldest_block_slot)
+            })
+            .collect()
+    }
+
+    /// Export `era` from the freezer database to a file in `dir`, returning the file's path.
+    ///
+    /// Blocks are exported with their execution payloads, so the payloads of the era must not
+    /// have been pruned.
+    pub fn export_era(&self, era: u64, dir: &Path) -> Result<PathBuf, StoreError> {
+        if !self.exportable_eras().contains(&era) {
+            return Err(StoreError::HistoryUnavailable);
+        }
+        let config_name = self
+            .spec
+            .config_name
+            .as_deref()
+            .ok_or(Error::MissingConfigName)?;
+
+        let slots_per_era = E::slots_per_historical_root() as u64;
+        let state_slot = Slot::new(era * slots_per_era);
+        let state = self
+            .load_cold_state_by_slot(state_slot)?
+            .ok_or(StoreError::HistoryUnavailable)?;
+
+        let path = dir.join(era_file_name(config_name, era, &state));
+        let temp_path = path.with_extension("era.tmp");
+        let file = File::create(&temp_path).map_err(Error::Io)?;
+        let mut writer = EraWriter::<E, _>::new(BufWriter::new(file), era)?;
+
+        if era > 0 {
+            // The state at the end of the era holds the block roots of every slot of the era.
+            let start_slot = state_slot - slots_per_era;
+            let mut last_block_root = None;
+            for slot in start_slot.as_u64()..state_slot.as_u64() {
+                let block_root = *state.get_block_root(Slot::new(slot))?;
+                if Some(block_root) == last_block_root {
+                    continue;
+                }
+                last_block_root = Some(block_root);
+
+                let block = self
+                    .get_full_block(&block_root)?
+                    .ok_or(StoreError::BlockNotFound(block_root))?;
+                // The first slots of the era may be skipped, repeating the root of an earlier
+                // block.
+                if block.slot() >= start_slot {
+                    writer.write_block(&block)?;
+                }
+            }
+        }
+
+        writer.write_state(&state)?;
+        writer.finish()?;
+        fs::rename(&temp_path, &path).map_err(Error::Io)?;
+
+        Ok(path)
+    }
+}
+
+/// Return the paths of the era files in `dir`, ordered by era number.
+pub fn list_era_files(dir: &Path) -> Result<Vec<(u64, PathBuf)>, Error> {
+    let mut era_files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some(era) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_era_file_name)
+        {
+            era_files.push((era, path));
+        }
+    }
+    era_files.sort();
+    Ok(era_files)
+}
+
+/// Open the era file at `path` for reading.
+pub fn open_era_file<E: EthSpec>(path: &Path) -> Result<EraReader<E, BufReader<File>>, Error> {
+    EraReader::new(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, Eth1Data, MainnetEthSpec, Signature};
+
+    type E = MainnetEthSpec;
+
+    fn block_at_slot(slot: u64, spec: &ChainSpec) -> SignedBeaconBlock<E> {
+        let mut block = BeaconBlock::empty(spec);
+        *block.slot_mut() = Slot::new(slot);
+        SignedBeaconBlock::from_block(block, Signature::empty())
+    }
+
+    #[test]
+    fn era_round_trip() {
+        let spec = E::default_spec();
+        let slots_per_era = E::slots_per_historical_root() as u64;
+        let era = 2;
+        let blocks = [slots_per_era, slots_per_era + 1, 2 * slots_per_era - 1]
+            .iter()
+            .map(|slot| block_at_slot(*slot, &spec))
+            .collect::<Vec<_>>();
+        let state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+
+        let mut writer = EraWriter::<E, _>::new(vec![], era).unwrap();
+        for block in &blocks {
+            writer.write_block(block).unwrap();
+        }
+        assert!(matches!(
+            writer.write_block(&blocks[0]),
+            Err(Error::BlockOutOfOrder { .. })
+        ));
+        writer.write_state(&state).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = EraReader::<E, _>::new(&bytes[..]).unwrap();
+        for block in &blocks {
+            match reader.next_entry(&spec).unwrap() {
+                Some(EraEntry::Block(read_block)) => assert_eq!(*read_block, *block),
+                _ => panic!("expected block"),
+            }
+        }
+        match reader.next_entry(&spec).unwrap() {
+            Some(EraEntry::State(read_state)) => assert_eq!(*read_state, state),
+            _ => panic!("expected state"),
+        }
+        assert!(reader.next_entry(&spec).unwrap().is_none());
+
+        // The blocks can also be read individually, via the slot index.
+        let mut reader = EraReader::<E, _>::new(io::Cursor::new(&bytes)).unwrap();
+        let positions = reader.block_positions().unwrap();
+        assert_eq!(
+            positions.iter().map(|(slot, _)| *slot).collect::<Vec<_>>(),
+            blocks.iter().map(|block| block.slot()).collect::<Vec<_>>()
+        );
+        for ((_, position), block) in positions.iter().rev().zip(blocks.iter().rev()) {
+            assert_eq!(reader.read_block_at(*position, &spec).unwrap(), *block);
+        }
+    }
+
+    #[test]
+    fn genesis_era_block_positions() {
+        let spec = E::default_spec();
+        let state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        let mut writer = EraWriter::<E, _>::new(vec![], 0).unwrap();
+        writer.write_state(&state).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = EraReader::<E, _>::new(io::Cursor::new(&bytes)).unwrap();
+        assert!(reader.block_positions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn block_outside_era() {
+        let spec = E::default_spec();
+        let slots_per_era = E::slots_per_historical_root() as u64;
+        let mut writer = EraWriter::<E, _>::new(vec![], 1).unwrap();
+        assert!(matches!(
+            writer.write_block(&block_at_slot(slots_per_era, &spec)),
+            Err(Error::BlockOutsideEra { .. })
+        ));
+    }
+
+    #[test]
+    fn file_names() {
+        let spec = E::default_spec();
+        let state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        let name = era_file_name("mainnet", 0, &state);
+        assert_eq!(name, "mainnet-00000-00000000.era");
+        assert_eq!(parse_era_file_name(&name), Some(0));
+        assert_eq!(
+            parse_era_file_name("my-testnet-01234-abcdef01.era"),
+            Some(1234)
+        );
+        assert_eq!(parse_era_file_name("mainnet-00000-00000000.era.tmp"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::era::Error as EraError;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -45,6 +46,20 @@ pub enum Error {
     SlotClockUnavailableForMigration,
     UnableToDowngrade,
     InconsistentFork(InconsistentFork),
+    /// The database was written by a backend that this build does not support.
+    UnsupportedDatabaseBackend(String),
+    /// A different change of `slots_per_restore_point` is already in progress.
+    RestorePointMigrationMismatch {
+        from: u64,
+        to: u64,
+    },
+    /// A change of `slots_per_restore_point` was interrupted and must be completed by re-running
+    /// `lighthouse db change_sprp` before the database can be used.
+    RestorePointMigrationInProgress {
+        from: u64,
+        to: u64,
+    },
+    EraError(EraError),
 }
 
 pub trait HandleUnavailable<T> {
@@ -61,6 +76,12 @@ impl<T> HandleUnavailable<T> for Result<T> {
     }
 }
 
+impl From<EraError> for Error {
+    fn from(e: EraError) -> Error {
+        Error::EraError(e)
+    }
+}
+
 impl From<DecodeError> for Error {
     fn from(e: DecodeError) -> Error {
         Error::SszDecodeError(e)
@@ -1,20 +1,21 @@
+use crate::beacon_node_backend::BeaconNodeBackend;
 use crate::chunked_vector::{
     store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
 };
 use crate::config::{
-    OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
+    DatabaseBackend, OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
-    AnchorInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
-    COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION, PRUNING_CHECKPOINT_KEY,
-    SCHEMA_VERSION_KEY, SPLIT_KEY,
+    AnchorInfo, CompactionTimestamp, HistoryRetention, PruningCheckpoint, RestorePointMigration,
+    SchemaVersion, ANCHOR_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
+    DATABASE_BACKEND_KEY, HISTORY_RETENTION_KEY, PRUNING_CHECKPOINT_KEY,
+    RESTORE_POINT_MIGRATION_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
 };
 use crate::metrics;
 use crate::{
@@ -22,7 +23,6 @@ use crate::{
     PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde_derive::{Deserialize, Serialize};
@@ -32,14 +32,18 @@ use ssz_derive::{Decode, Encode};
 use state_processing::{
     BlockProcessingError, BlockReplayer, SlotProcessingError, StateRootStrategy,
 };
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::convert::TryInto;
 use std::marker::PhantomData;
+use std::mem;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use types::*;
 
+/// Maximum number of blocks to delete in a single batch while pruning history.
+const HISTORY_PRUNING_BATCH_SIZE: usize = 1024;
+
 /// On-disk database that stores finalized states efficiently.
 ///
 /// Stores vector fields like the `block_roots` and `state_roots` separately, and only stores
@@ -53,6 +57,8 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     pub(crate) split: RwLock<Split>,
     /// The starting slots for the range of blocks & states stored in the database.
     anchor_info: RwLock<Option<AnchorInfo>>,
+    /// The range of finalized history retained after pruning, if history has been pruned.
+    history_retention: RwLock<Option<HistoryRetention>>,
     pub(crate) config: StoreConfig,
     /// Cold database containing compact historical data.
     pub cold_db: Cold,
@@ -99,6 +105,7 @@ pub enum HotColdDBError {
     BlockReplaySlotError(SlotProcessingError),
     BlockReplayBlockError(BlockProcessingError),
     MissingLowerLimitState(Slot),
+    MissingHistoricBlockRoot(Slot),
     InvalidSlotsPerRestorePoint {
         slots_per_restore_point: u64,
         slots_per_historical_root: u64,
@@ -126,6 +133,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
         let db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
+            history_retention: RwLock::new(None),
             cold_db: MemoryStore::open(),
             hot_db: MemoryStore::open(),
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
@@ -139,7 +147,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `slots_per_restore_point` parameter must be a divisor of `SLOTS_PER_HISTORICAL_ROOT`.
@@ -153,14 +161,50 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         config: StoreConfig,
         spec: ChainSpec,
         log: Logger,
+    ) -> Result<Arc<Self>, Error> {
+        Self::open_inner(
+            hot_path,
+            cold_path,
+            migrate_schema,
+            config,
+            spec,
+            log,
+            false,
+        )
+    }
+
+    /// Open a new or existing database in order to change its `slots_per_restore_point`.
+    ///
+    /// Unlike `Self::open`, this succeeds if a change of `slots_per_restore_point` is already in
+    /// progress, so that it can be resumed.
+    pub fn open_for_restore_point_migration(
+        hot_path: &Path,
+        cold_path: &Path,
+        migrate_schema: impl FnOnce(Arc<Self>, SchemaVersion, SchemaVersion) -> Result<(), Error>,
+        config: StoreConfig,
+        spec: ChainSpec,
+        log: Logger,
+    ) -> Result<Arc<Self>, Error> {
+        Self::open_inner(hot_path, cold_path, migrate_schema, config, spec, log, true)
+    }
+
+    fn open_inner(
+        hot_path: &Path,
+        cold_path: &Path,
+        migrate_schema: impl FnOnce(Arc<Self>, SchemaVersion, SchemaVersion) -> Result<(), Error>,
+        config: StoreConfig,
+        spec: ChainSpec,
+        log: Logger,
+        allow_restore_point_migration: bool,
     ) -> Result<Arc<Self>, Error> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
 
         let mut db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
-            cold_db: LevelDB::open(cold_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            history_retention: RwLock::new(None),
+            cold_db: BeaconNodeBackend::open(config.backend, cold_path)?,
+            hot_db: BeaconNodeBackend::open(config.backend, hot_path)?,
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
             config,
             spec,
@@ -188,12 +232,22 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             }
         }
 
+        // Ensure that both databases were created with the configured backend. Databases which
+        // pre-date the backend being recorded are assumed to use the current backend.
+        for kv_store in [&db.hot_db, &db.cold_db] {
+            match kv_store.get::<DatabaseBackend>(&DATABASE_BACKEND_KEY)? {
+                Some(on_disk_backend) => db.config.check_backend_compatibility(on_disk_backend)?,
+                None => kv_store.put(&DATABASE_BACKEND_KEY, &db.config.backend)?,
+            }
+        }
+
         // Load the previous split slot from the database (if any). This ensures we can
         // stop and restart correctly. This needs to occur *before* running any migrations
         // because some migrations load states and depend on the split.
         if let Some(split) = db.load_split()? {
             *db.split.write() = split;
             *db.anchor_info.write() = db.load_anchor_info()?;
+            *db.history_retention.write() = db.hot_db.get(&HISTORY_RETENTION_KEY)?;
 
             info!(
                 db.log,
@@ -203,6 +257,17 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             );
         }
 
+        // Historic states may be unavailable or inconsistent until an interrupted change of
+        // slots-per-restore-point is completed by `lighthouse db change_sprp`.
+        if let Some(migration) = db.load_restore_point_migration()? {
+            if !allow_restore_point_migration {
+                return Err(Error::RestorePointMigrationInProgress {
+                    from: migration.from,
+                    to: migration.to,
+                });
+            }
+        }
+
         // Ensure that the schema version of the on-disk database matches the software.
         // If the version is mismatched, an automatic migration will be attempted.
         let db = Arc::new(db);
@@ -239,23 +304,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db.iter_column_keys(DBColumn::BeaconStateTemporary)
     }
 }
 
@@ -469,6 +518,23 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .map(|payload| payload.is_some())
     }
 
+    /// Load the best light client update for a sync committee period from disk.
+    pub fn get_light_client_update(
+        &self,
+        sync_committee_period: u64,
+    ) -> Result<Option<LightClientUpdate<E>>, Error> {
+        self.get_item(&light_client_update_key(sync_committee_period))
+    }
+
+    /// Store the best light client update for a sync committee period.
+    pub fn put_light_client_update(
+        &self,
+        sync_committee_period: u64,
+        update: &LightClientUpdate<E>,
+    ) -> Result<(), Error> {
+        self.put_item(&light_client_update_key(sync_committee_period), update)
+    }
+
     /// Determine whether a block exists in the database.
     pub fn block_exists(&self, block_root: &Hash256) -> Result<bool, Error> {
         self.hot_db
@@ -895,6 +961,23 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             "state_root" => format!("{:?}", state_root)
         );
 
+        // 1. Store the state and its updated vector entries.
+        self.store_restore_point_state(state_root, state, ops)?;
+
+        // 2. Store restore point.
+        let restore_point_index = state.slot().as_u64() / self.config.slots_per_restore_point;
+        self.store_restore_point_hash(restore_point_index, *state_root, ops);
+
+        Ok(())
+    }
+
+    /// Store the state of a restore point in the freezer database, without indexing it.
+    pub(crate) fn store_restore_point_state(
+        &self,
+        state_root: &Hash256,
+        state: &BeaconState<E>,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
         // 1. Convert to PartialBeaconState and store that in the DB.
         let partial_state = PartialBeaconState::from_state_forgetful(state);
         let op = partial_state.as_kv_store_op(*state_root);
@@ -908,10 +991,6 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         store_updated_vector(RandaoMixes, db, state, &self.spec, ops)?;
         store_updated_vector(HistoricalSummaries, db, state, &self.spec, ops)?;
 
-        // 3. Store restore point.
-        let restore_point_index = state.slot().as_u64() / self.config.slots_per_restore_point;
-        self.store_restore_point_hash(restore_point_index, *state_root, ops);
-
         Ok(())
     }
 
@@ -967,7 +1046,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load a restore point state by its `restore_point_index`.
-    fn load_restore_point_by_index(
+    pub(crate) fn load_restore_point_by_index(
         &self,
         restore_point_index: u64,
     ) -> Result<BeaconState<E>, Error> {
@@ -1034,7 +1113,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// Get a suitable block root for backtracking from `high_restore_point` to the state at `slot`.
     ///
     /// Defaults to the block root for `slot`, which *should* be in range.
-    fn get_high_restore_point_block_root(
+    pub(crate) fn get_high_restore_point_block_root(
         &self,
         high_restore_point: &BeaconState<E>,
         slot: Slot,
@@ -1090,7 +1169,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ///
     /// Will skip slots as necessary. The returned state is not guaranteed
     /// to have any caches built, beyond those immediately required by block processing.
-    fn replay_blocks(
+    pub(crate) fn replay_blocks(
         &self,
         state: BeaconState<E>,
         blocks: Vec<SignedBeaconBlock<E, BlindedPayload<E>>>,
@@ -1153,6 +1232,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             * self.config.slots_per_restore_point
     }
 
+    /// Load the progress of an in-progress change of `slots_per_restore_point` from disk.
+    pub fn load_restore_point_migration(&self) -> Result<Option<RestorePointMigration>, Error> {
+        self.hot_db.get(&RESTORE_POINT_MIGRATION_KEY)
+    }
+
     /// Load the database schema version from disk.
     fn load_schema_version(&self) -> Result<Option<SchemaVersion>, Error> {
         self.hot_db.get(&SCHEMA_VERSION_KEY)
@@ -1292,6 +1376,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         // function will return the current split slot as the upper limit. Once slot 4096 is reached
         // a new restore point will be created at that slot, making all states from 4096 onwards
         // permanently available.
+        //
+        // If history has been pruned then only the genesis state remains below the oldest
+        // retained slot.
+        let (lower_limit, upper_limit) = self.get_anchor_state_limits();
+        match self.get_history_retention() {
+            Some(retention) => (
+                self.spec.genesis_slot,
+                max(upper_limit, retention.oldest_retained_slot),
+            ),
+            None => (lower_limit, upper_limit),
+        }
+    }
+
+    /// As for `get_historic_state_limits`, but ignoring any pruning of history.
+    fn get_anchor_state_limits(&self) -> (Slot, Slot) {
         let split_slot = self.get_split_slot();
         self.anchor_info
             .read_recursive()
@@ -1303,10 +1402,35 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Return the minimum slot such that blocks are available for all subsequent slots.
     pub fn get_oldest_block_slot(&self) -> Slot {
-        self.anchor_info
+        let anchor_oldest_block_slot = self
+            .anchor_info
             .read_recursive()
             .as_ref()
-            .map_or(self.spec.genesis_slot, |anchor| anchor.oldest_block_slot)
+            .map_or(self.spec.genesis_slot, |anchor| anchor.oldest_block_slot);
+        max(anchor_oldest_block_slot, self.get_history_retention_slot())
+    }
+
+    /// Return the range of finalized history retained after pruning, if history has been pruned.
+    pub fn get_history_retention(&self) -> Option<HistoryRetention> {
+        *self.history_retention.read_recursive()
+    }
+
+    /// Return the slot from which finalized history is retained.
+    ///
+    /// This is the slot that block backfill must reach: it is genesis, unless older history has
+    /// been pruned.
+    pub fn get_history_retention_slot(&self) -> Slot {
+        self.get_history_retention()
+            .map_or(self.spec.genesis_slot, |retention| {
+                retention.oldest_retained_slot
+            })
+    }
+
+    /// Store `retention` to disk, and update the in-memory copy.
+    pub(crate) fn set_history_retention(&self, retention: HistoryRetention) -> Result<(), Error> {
+        self.hot_db.put(&HISTORY_RETENTION_KEY, &retention)?;
+        *self.history_retention.write() = Some(retention);
+        Ok(())
     }
 
     /// Return the in-memory configuration used by the database.
@@ -1344,7 +1468,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Store the state root of a restore point.
-    fn store_restore_point_hash(
+    pub(crate) fn store_restore_point_hash(
         &self,
         restore_point_index: u64,
         state_root: Hash256,
@@ -1356,7 +1480,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Convert a `restore_point_index` into a database key.
-    fn restore_point_key(restore_point_index: u64) -> Hash256 {
+    pub(crate) fn restore_point_key(restore_point_index: u64) -> Hash256 {
         Hash256::from_low_u64_be(restore_point_index)
     }
 
@@ -1400,7 +1524,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ///
     /// (2) ensures that restore points align with hot state summaries, making it
     /// quick to migrate hot to cold.
-    fn verify_slots_per_restore_point(slots_per_restore_point: u64) -> Result<(), HotColdDBError> {
+    pub(crate) fn verify_slots_per_restore_point(
+        slots_per_restore_point: u64,
+    ) -> Result<(), HotColdDBError> {
         let slots_per_historical_root = E::SlotsPerHistoricalRoot::to_u64();
         let slots_per_epoch = E::slots_per_epoch();
         if slots_per_restore_point > 0
@@ -1569,6 +1695,196 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         );
         Ok(())
     }
+
+    /// Delete the finalized blocks, execution payloads and freezer states prior to `prune_slot`.
+    ///
+    /// The slot is rounded down to a restore point, so that every state from the oldest retained
+    /// slot can still be reconstructed from the blocks that remain. The genesis block and state
+    /// are always kept.
+    ///
+    /// The new boundary is recorded in the `HistoryRetention` before anything is deleted, so that
+    /// pruned history is never loaded and is never backfilled. Blocks are deleted in batches
+    /// alongside an update to the `pruned_slot`, so that an interrupted run is resumed by the next
+    /// call. The anchor is left untouched.
+    pub fn try_prune_history(&self, prune_slot: Slot) -> Result<(), Error> {
+        let split = self.get_split_info();
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let prune_slot =
+            min(prune_slot, split.slot) / slots_per_restore_point * slots_per_restore_point;
+
+        let retention = self.get_history_retention().unwrap_or(HistoryRetention {
+            oldest_retained_slot: self.spec.genesis_slot,
+            pruned_slot: self.spec.genesis_slot,
+        });
+        // Complete any interrupted run, even if the boundary has not advanced.
+        let prune_slot = max(prune_slot, retention.oldest_retained_slot);
+
+        if prune_slot <= self.spec.genesis_slot || retention.pruned_slot >= prune_slot {
+            return Ok(());
+        }
+
+        // Only history which is present in the database needs deleting. States between the
+        // anchor's limits and blocks prior to its oldest block were never stored.
+        let start_slot = max(retention.pruned_slot, self.spec.genesis_slot + 1);
+        let (lower_limit, upper_limit) = self.get_anchor_state_limits();
+        let state_ranges = if lower_limit >= upper_limit {
+            vec![(start_slot, prune_slot)]
+        } else {
+            vec![
+                (start_slot, min(lower_limit + 1, prune_slot)),
+                (max(upper_limit, start_slot), prune_slot),
+            ]
+        }
+        .into_iter()
+        .filter(|(start_slot, end_slot)| start_slot < end_slot)
+        .collect::<Vec<_>>();
+
+        let anchor_oldest_block_slot = self
+            .get_anchor_info()
+            .map_or(self.spec.genesis_slot, |a| a.oldest_block_slot);
+        let block_start_slot = max(start_slot, anchor_oldest_block_slot);
+
+        info!(
+            self.log,
+            "Pruning finalized history";
+            "prune_slot" => prune_slot,
+        );
+
+        let mut retention = HistoryRetention {
+            oldest_retained_slot: prune_slot,
+            ..retention
+        };
+        self.set_history_retention(retention)?;
+
+        let split_state = self.get_state(&split.state_root, Some(split.slot))?.ok_or(
+            HotColdDBError::MissingSplitState(split.state_root, split.slot),
+        )?;
+
+        let mut cold_ops = vec![];
+        for (start_slot, end_slot) in state_ranges {
+            let state_roots = self.forwards_state_roots_iterator_until(
+                start_slot,
+                end_slot,
+                || (split_state.clone(), split.state_root),
+                &self.spec,
+            )?;
+
+            for res in state_roots {
+                let (state_root, slot) = res?;
+                if slot >= end_slot {
+                    break;
+                }
+
+                cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconStateSummary.into(),
+                    state_root.as_bytes(),
+                )));
+                if slot % slots_per_restore_point == 0 {
+                    cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                        DBColumn::BeaconState.into(),
+                        state_root.as_bytes(),
+                    )));
+                    cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                        DBColumn::BeaconRestorePoint.into(),
+                        Self::restore_point_key(slot.as_u64() / slots_per_restore_point).as_bytes(),
+                    )));
+                }
+            }
+        }
+        let states_pruned = cold_ops.len();
+        self.cold_db.do_atomically(cold_ops)?;
+        debug!(
+            self.log,
+            "Pruned historic states";
+            "keys_deleted" => states_pruned,
+        );
+
+        let mut ops = vec![];
+        let mut blocks_pruned = 0;
+
+        if block_start_slot < prune_slot {
+            // The block at `prune_slot` may have been proposed at an earlier slot, in which case
+            // it must be kept.
+            let retained_block_root = self
+                .forwards_block_roots_iterator_until(
+                    prune_slot,
+                    prune_slot,
+                    || (split_state.clone(), split.state_root),
+                    &self.spec,
+                )?
+                .next()
+                .ok_or(HotColdDBError::MissingHistoricBlockRoot(prune_slot))??
+                .0;
+            let mut kept_block_roots = vec![retained_block_root];
+
+            // Include the genesis slot so that the genesis block is recognised and kept.
+            let iter_start_slot = if block_start_slot == self.spec.genesis_slot + 1 {
+                self.spec.genesis_slot
+            } else {
+                block_start_slot
+            };
+            let block_roots = self.forwards_block_roots_iterator_until(
+                iter_start_slot,
+                prune_slot - 1,
+                || (split_state.clone(), split.state_root),
+                &self.spec,
+            )?;
+
+            let mut last_block_root = None;
+
+            for res in block_roots {
+                let (block_root, slot) = res?;
+                if slot == self.spec.genesis_slot {
+                    kept_block_roots.push(block_root);
+                }
+                if Some(block_root) == last_block_root || kept_block_roots.contains(&block_root) {
+                    continue;
+                }
+                last_block_root = Some(block_root);
+
+                // Every block deleted so far has a slot prior to this one.
+                if ops.len() / 2 >= HISTORY_PRUNING_BATCH_SIZE {
+                    blocks_pruned += ops.len() / 2;
+                    retention.pruned_slot = slot;
+                    self.prune_history_batch(retention, mem::take(&mut ops))?;
+                }
+
+                ops.push(StoreOp::DeleteBlock(block_root));
+                ops.push(StoreOp::DeleteExecutionPayload(block_root));
+            }
+        }
+        blocks_pruned += ops.len() / 2;
+        retention.pruned_slot = prune_slot;
+        self.prune_history_batch(retention, ops)?;
+
+        debug!(
+            self.log,
+            "Pruned historic blocks";
+            "blocks_pruned" => blocks_pruned,
+        );
+
+        info!(
+            self.log,
+            "History pruning complete";
+            "oldest_block_slot" => self.get_oldest_block_slot(),
+        );
+
+        Ok(())
+    }
+
+    /// Delete a batch of blocks, recording the progress made in `retention`.
+    fn prune_history_batch(
+        &self,
+        retention: HistoryRetention,
+        mut ops: Vec<StoreOp<E>>,
+    ) -> Result<(), Error> {
+        ops.push(StoreOp::KeyValueOp(
+            retention.as_kv_store_op(HISTORY_RETENTION_KEY),
+        ));
+        self.do_atomically(ops)?;
+        *self.history_retention.write() = Some(retention);
+        Ok(())
+    }
 }
 
 /// Advance the split point of the store, moving new finalized states to the freezer.
@@ -1731,6 +2047,11 @@ impl StoreItem for Split {
     }
 }
 
+/// Light client updates are keyed by their sync committee period.
+fn light_client_update_key(sync_committee_period: u64) -> Hash256 {
+    Hash256::from_low_u64_be(sync_committee_period)
+}
+
 /// Type hint.
 fn no_state_root_iter() -> Option<std::iter::Empty<Result<(Hash256, Slot), Error>>> {
     None
@@ -219,6 +219,22 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
                 }),
         )
     }
+
+    /// Iterate through all keys and values in a particular column, without interpreting the keys.
+    fn iter_raw_entries(&self, column: DBColumn) -> RawEntryIter {
+        let start_key = BytesKey::from_vec(column.as_bytes().to_vec());
+
+        let iter = self.db.iter(self.read_options());
+        iter.seek(&start_key);
+
+        Box::new(
+            iter.take_while(move |(key, _)| key.matches_column(column))
+                .map(move |(bytes_key, value)| {
+                    let key = bytes_key.key[column.as_bytes().len()..].to_vec();
+                    Ok((key, value))
+                }),
+        )
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
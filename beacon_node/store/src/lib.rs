@@ -2,7 +2,7 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or rocksdb. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
@@ -10,10 +10,12 @@
 #[macro_use]
 extern crate lazy_static;
 
+mod beacon_node_backend;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
 pub mod config;
+pub mod era;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
@@ -25,26 +27,33 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+pub mod restore_point_migration;
+#[cfg(feature = "rocksdb")]
+mod rocksdb_store;
 
 pub mod iter;
 
+pub use self::beacon_node_backend::BeaconNodeBackend;
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, StoreConfig};
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
+#[cfg(feature = "rocksdb")]
+pub use self::rocksdb_store::RocksDB;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
 pub use metadata::AnchorInfo;
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
 use std::sync::Arc;
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoEnumIterator, IntoStaticStr};
 pub use types::*;
 
 pub type ColumnIter<'a> = Box<dyn Iterator<Item = Result<(Hash256, Vec<u8>), Error>> + 'a>;
 pub type ColumnKeyIter<'a> = Box<dyn Iterator<Item = Result<Hash256, Error>> + 'a>;
+pub type RawEntryIter<'a> = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), Error>> + 'a>;
 
 pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
     /// Retrieve some bytes in `column` with `key`.
@@ -90,6 +99,15 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
         // Default impl for non LevelDB databases
         Box::new(std::iter::empty())
     }
+
+    /// Iterate through all keys and values in a particular column, without interpreting the keys.
+    ///
+    /// Unlike `iter_column` this includes keys which are not 32 bytes long, such as the chunk
+    /// indices of the freezer's vector columns.
+    fn iter_raw_entries(&self, _column: DBColumn) -> RawEntryIter {
+        // Default impl for non LevelDB databases
+        Box::new(std::iter::empty())
+    }
 }
 
 pub fn get_key_for_col(column: &str, key: &[u8]) -> Vec<u8> {
@@ -165,7 +183,7 @@ pub enum StoreOp<'a, E: EthSpec> {
 }
 
 /// A unique column identifier.
-#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString, EnumIter)]
 pub enum DBColumn {
     /// For data related to the database itself.
     #[strum(serialize = "bma")]
@@ -214,6 +232,12 @@ pub enum DBColumn {
     OptimisticTransitionBlock,
     #[strum(serialize = "bhs")]
     BeaconHistoricalSummaries,
+    /// For the best `LightClientUpdate` of each sync committee period.
+    #[strum(serialize = "lcu")]
+    LightClientUpdate,
+    /// For the scores and bans of peers, persisted across restarts.
+    #[strum(serialize = "psc")]
+    PeerScores,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -230,6 +254,15 @@ impl DBColumn {
     pub fn as_bytes(self) -> &'static [u8] {
         self.as_str().as_bytes()
     }
+
+    /// Split a key created by `get_key_for_col` into its column and the key within that column.
+    pub fn split_key(column_key: &[u8]) -> Option<(DBColumn, &[u8])> {
+        DBColumn::iter().find_map(|column| {
+            column_key
+                .strip_prefix(column.as_bytes())
+                .map(|key| (column, key))
+        })
+    }
 }
 
 /// An item that may stored in a `Store` by serializing and deserializing from bytes.
@@ -307,6 +340,50 @@ mod tests {
         test_impl(store);
     }
 
+    #[cfg(feature = "rocksdb")]
+    #[test]
+    fn rocksdb() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = RocksDB::open(path).unwrap();
+
+        test_impl(store);
+    }
+
+    #[test]
+    fn iter_raw_entries() {
+        let dir = tempdir().unwrap();
+        let store = LevelDB::<MinimalEthSpec>::open(dir.path()).unwrap();
+        let column = DBColumn::BeaconBlockRoots;
+
+        store.put_bytes(column.as_str(), &[0; 8], &[1]).unwrap();
+        store.put_bytes(column.as_str(), &[0xff; 32], &[2]).unwrap();
+        store
+            .put_bytes(DBColumn::BeaconStateRoots.as_str(), &[0; 8], &[3])
+            .unwrap();
+
+        let entries = store
+            .iter_raw_entries(column)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![(vec![0; 8], vec![1]), (vec![0xff; 32], vec![2])]
+        );
+    }
+
+    #[test]
+    fn split_key() {
+        let key = Hash256::random();
+        let column_key = get_key_for_col(DBColumn::BeaconState.into(), key.as_bytes());
+
+        assert_eq!(
+            DBColumn::split_key(&column_key),
+            Some((DBColumn::BeaconState, key.as_bytes()))
+        );
+        assert_eq!(DBColumn::split_key(b"zz"), None);
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
@@ -15,6 +15,9 @@ pub const SPLIT_KEY: Hash256 = Hash256::repeat_byte(2);
 pub const PRUNING_CHECKPOINT_KEY: Hash256 = Hash256::repeat_byte(3);
 pub const COMPACTION_TIMESTAMP_KEY: Hash256 = Hash256::repeat_byte(4);
 pub const ANCHOR_INFO_KEY: Hash256 = Hash256::repeat_byte(5);
+pub const DATABASE_BACKEND_KEY: Hash256 = Hash256::repeat_byte(6);
+pub const RESTORE_POINT_MIGRATION_KEY: Hash256 = Hash256::repeat_byte(7);
+pub const HISTORY_RETENTION_KEY: Hash256 = Hash256::repeat_byte(8);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SchemaVersion(pub u64);
@@ -98,9 +101,11 @@ pub struct AnchorInfo {
 }
 
 impl AnchorInfo {
-    /// Returns true if the block backfill has completed.
-    pub fn block_backfill_complete(&self) -> bool {
-        self.oldest_block_slot == 0
+    /// Returns true if the block backfill has reached `target_slot`.
+    ///
+    /// The target is genesis, unless older history has been pruned.
+    pub fn block_backfill_complete(&self, target_slot: Slot) -> bool {
+        self.oldest_block_slot <= target_slot
     }
 }
 
@@ -117,3 +122,60 @@ impl StoreItem for AnchorInfo {
         Ok(Self::from_ssz_bytes(bytes)?)
     }
 }
+
+/// Progress of a change to the `slots_per_restore_point` of the freezer database.
+///
+/// Present only while a change is in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct RestorePointMigration {
+    /// The `slots_per_restore_point` that the database was created with.
+    pub from: u64,
+    /// The `slots_per_restore_point` being changed to.
+    pub to: u64,
+    /// All new restore points prior to this slot have been stored.
+    pub next_slot: Slot,
+    /// Whether the restore point index of the freezer database may have already been rewritten.
+    pub finalizing: bool,
+}
+
+impl StoreItem for RestorePointMigration {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+/// The finalized history retained by the database once older history has been pruned.
+///
+/// Absent if history has never been pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct HistoryRetention {
+    /// Blocks and states prior to this slot, other than those at genesis, are not retained and are
+    /// never backfilled.
+    pub oldest_retained_slot: Slot,
+    /// Blocks and states prior to this slot have been deleted.
+    ///
+    /// Lags `oldest_retained_slot` until pruning has completed.
+    pub pruned_slot: Slot,
+}
+
+impl StoreItem for HistoryRetention {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
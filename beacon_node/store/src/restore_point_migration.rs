@@ -0,0 +1,299 @@
+//! Implementation of changing the `slots_per_restore_point` of an existing freezer database.
+//!
+//! When the restore points become denser the new restore points are created by replaying blocks
+//! forwards from the existing ones. Progress is recorded in the hot database every batch, so that
+//! an interrupted change can be resumed. Once all of the new restore points exist, the restore
+//! point index is rewritten and restore points which are no longer needed are deleted.
+use crate::config::OnDiskStoreConfig;
+use crate::hot_cold_store::{HotColdDB, HotColdDBError};
+use crate::metadata::{
+    HistoryRetention, RestorePointMigration, CONFIG_KEY, RESTORE_POINT_MIGRATION_KEY,
+};
+use crate::{get_key_for_col, DBColumn, Error, ItemStore, KeyValueStoreOp, StoreItem};
+use slog::info;
+use state_processing::StateRootStrategy;
+use std::cmp::{max, min};
+use std::sync::Arc;
+use types::{EthSpec, Hash256, Slot};
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    /// Change the `slots_per_restore_point` of the freezer database to `new_sprp`.
+    ///
+    /// The database must have been opened with its current `slots_per_restore_point`. Progress is
+    /// written to disk every `batch_size` new restore points, and an interrupted change is resumed
+    /// by calling this function again with the same arguments.
+    pub fn migrate_slots_per_restore_point(
+        self: &Arc<Self>,
+        new_sprp: u64,
+        batch_size: usize,
+    ) -> Result<(), Error> {
+        Self::verify_slots_per_restore_point(new_sprp)?;
+        let old_sprp = self.config.slots_per_restore_point;
+
+        let mut migration = match self.load_restore_point_migration()? {
+            Some(migration) if migration.from == old_sprp && migration.to == new_sprp => {
+                info!(
+                    self.log,
+                    "Resuming change of slots-per-restore-point";
+                    "from" => old_sprp,
+                    "to" => new_sprp,
+                    "next_slot" => migration.next_slot,
+                );
+                migration
+            }
+            Some(migration) => {
+                return Err(Error::RestorePointMigrationMismatch {
+                    from: migration.from,
+                    to: migration.to,
+                })
+            }
+            None if old_sprp == new_sprp => return Ok(()),
+            None => {
+                info!(
+                    self.log,
+                    "Changing slots-per-restore-point";
+                    "from" => old_sprp,
+                    "to" => new_sprp,
+                );
+                RestorePointMigration {
+                    from: old_sprp,
+                    to: new_sprp,
+                    next_slot: self.spec.genesis_slot,
+                    finalizing: false,
+                }
+            }
+        };
+
+        if !migration.finalizing {
+            // Restore points only need creating if they are becoming denser. As both values divide
+            // `SLOTS_PER_HISTORICAL_ROOT` every existing restore point remains a restore point.
+            if new_sprp < old_sprp {
+                self.create_restore_points(&mut migration, batch_size)?;
+            }
+            migration.finalizing = true;
+            self.hot_db.put(&RESTORE_POINT_MIGRATION_KEY, &migration)?;
+        }
+
+        self.finalize_restore_point_migration(&migration)?;
+
+        info!(
+            self.log,
+            "Changed slots-per-restore-point";
+            "from" => old_sprp,
+            "to" => new_sprp,
+        );
+
+        Ok(())
+    }
+
+    /// Return the `[start, end)` ranges of slots for which the freezer database holds states.
+    fn frozen_state_ranges(&self) -> Vec<(Slot, Slot)> {
+        let split_slot = self.get_split_slot();
+        let (lower_limit, upper_limit) = self.get_historic_state_limits();
+
+        if lower_limit >= upper_limit {
+            vec![(self.spec.genesis_slot, split_slot)]
+        } else {
+            vec![
+                (self.spec.genesis_slot, lower_limit + 1),
+                (upper_limit, split_slot),
+            ]
+        }
+    }
+
+    /// Store a restore point for every slot of the new restore points which is not already one.
+    fn create_restore_points(
+        &self,
+        migration: &mut RestorePointMigration,
+        batch_size: usize,
+    ) -> Result<(), Error> {
+        let old_sprp = migration.from;
+        let new_sprp = migration.to;
+        let split = self.get_split_info();
+        let mut restore_points_since_commit = 0;
+
+        for (start_slot, end_slot) in self.frozen_state_ranges() {
+            // Work through the intervals between existing restore points, as the states within an
+            // interval are computed by replaying blocks from the restore point at its start.
+            let mut low_slot = max(start_slot, migration.next_slot) / old_sprp * old_sprp;
+
+            while low_slot < end_slot {
+                let high_slot = low_slot + old_sprp;
+                let new_slots = (low_slot.as_u64() + new_sprp..min(high_slot, end_slot).as_u64())
+                    .step_by(new_sprp as usize)
+                    .map(Slot::new)
+                    .filter(|slot| *slot >= migration.next_slot)
+                    .collect::<Vec<_>>();
+
+                if new_slots.is_empty() {
+                    low_slot = high_slot;
+                    continue;
+                }
+
+                let mut state = self.load_restore_point_by_index(low_slot.as_u64() / old_sprp)?;
+                let high_restore_point =
+                    self.get_restore_point(high_slot.as_u64() / old_sprp, &split)?;
+
+                for slot in new_slots {
+                    let blocks = self.load_blocks_to_replay(
+                        state.slot(),
+                        slot,
+                        self.get_high_restore_point_block_root(&high_restore_point, slot)?,
+                    )?;
+                    // The state root of the high restore point should never be used, so is safely
+                    // set to 0.
+                    let state_root_iter = self.forwards_state_roots_iterator_until(
+                        state.slot(),
+                        slot,
+                        || (high_restore_point.clone(), Hash256::zero()),
+                        &self.spec,
+                    )?;
+                    state = self.replay_blocks(
+                        state,
+                        blocks,
+                        slot,
+                        Some(state_root_iter),
+                        StateRootStrategy::Accurate,
+                    )?;
+                    let state_root = *high_restore_point
+                        .get_state_root(slot)
+                        .map_err(HotColdDBError::RestorePointBlockHashError)?;
+
+                    // Each restore point is written separately, as the vectors stored for a
+                    // restore point depend on those stored for the previous one.
+                    let mut ops = vec![];
+                    self.store_restore_point_state(&state_root, &state, &mut ops)?;
+                    self.cold_db.do_atomically(ops)?;
+
+                    migration.next_slot = slot + 1;
+                    restore_points_since_commit += 1;
+
+                    if restore_points_since_commit >= batch_size {
+                        self.cold_db.sync()?;
+                        self.hot_db.put(&RESTORE_POINT_MIGRATION_KEY, migration)?;
+                        restore_points_since_commit = 0;
+
+                        info!(
+                            self.log,
+                            "Restore point creation in progress";
+                            "slot" => slot,
+                            "remaining" => end_slot - slot,
+                        );
+                    }
+                }
+
+                low_slot = high_slot;
+            }
+        }
+
+        self.cold_db.sync()
+    }
+
+    /// Rewrite the restore point index for the new `slots_per_restore_point`, delete the states of
+    /// restore points which are no longer needed and update the on-disk config.
+    ///
+    /// This only reads the state roots of the freezer database, so it may safely be repeated if
+    /// it is interrupted after the restore point index has been rewritten.
+    fn finalize_restore_point_migration(
+        &self,
+        migration: &RestorePointMigration,
+    ) -> Result<(), Error> {
+        let old_sprp = migration.from;
+        let new_sprp = migration.to;
+        let split = self.get_split_info();
+        let split_state = self.get_state(&split.state_root, Some(split.slot))?.ok_or(
+            HotColdDBError::MissingSplitState(split.state_root, split.slot),
+        )?;
+
+        let mut cold_ops = vec![];
+
+        // The restore point at genesis has the same index for any `slots_per_restore_point`.
+        let genesis_key = Self::restore_point_key(0);
+        for key in self.cold_db.iter_column_keys(DBColumn::BeaconRestorePoint) {
+            let key = key?;
+            if key != genesis_key {
+                cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconRestorePoint.into(),
+                    key.as_bytes(),
+                )));
+            }
+        }
+
+        for (start_slot, end_slot) in self.frozen_state_ranges() {
+            if start_slot >= end_slot {
+                continue;
+            }
+
+            let state_roots = self.forwards_state_roots_iterator_until(
+                start_slot,
+                end_slot,
+                || (split_state.clone(), split.state_root),
+                &self.spec,
+            )?;
+
+            for res in state_roots {
+                let (state_root, slot) = res?;
+                if slot >= end_slot {
+                    break;
+                }
+                if slot == self.spec.genesis_slot {
+                    continue;
+                }
+
+                if slot % new_sprp == 0 {
+                    self.store_restore_point_hash(
+                        slot.as_u64() / new_sprp,
+                        state_root,
+                        &mut cold_ops,
+                    );
+                } else if slot % old_sprp == 0 {
+                    cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                        DBColumn::BeaconState.into(),
+                        state_root.as_bytes(),
+                    )));
+                }
+            }
+        }
+
+        self.cold_db.do_atomically(cold_ops)?;
+        self.cold_db.sync()?;
+
+        let mut hot_ops = vec![OnDiskStoreConfig {
+            slots_per_restore_point: new_sprp,
+        }
+        .as_kv_store_op(CONFIG_KEY)];
+
+        // States between the limits and the nearest new restore points can no longer be loaded.
+        if let Some(anchor) = self.get_anchor_info() {
+            let mut new_anchor = anchor.clone();
+            new_anchor.state_lower_limit = anchor.state_lower_limit / new_sprp * new_sprp;
+            if anchor.state_upper_limit % new_sprp != 0 {
+                new_anchor.state_upper_limit = (anchor.state_upper_limit / new_sprp + 1) * new_sprp;
+            }
+            if new_anchor != anchor {
+                hot_ops.push(self.compare_and_set_anchor_info(Some(anchor), Some(new_anchor))?);
+            }
+        }
+        if let Some(retention) = self.get_history_retention() {
+            if retention.oldest_retained_slot % new_sprp != 0 {
+                self.set_history_retention(HistoryRetention {
+                    oldest_retained_slot: (retention.oldest_retained_slot / new_sprp + 1)
+                        * new_sprp,
+                    ..retention
+                })?;
+            }
+        }
+
+        hot_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+            DBColumn::BeaconMeta.into(),
+            RESTORE_POINT_MIGRATION_KEY.as_bytes(),
+        )));
+
+        self.hot_db.do_atomically(hot_ops)
+    }
+}
@@ -0,0 +1,267 @@
+use super::*;
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use crate::metrics;
+use parking_lot::{Mutex, MutexGuard};
+use rocksdb::{
+    ColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, WriteOptions, DB,
+};
+use std::marker::PhantomData;
+use std::path::Path;
+use strum::IntoEnumIterator;
+
+/// A wrapped RocksDB database.
+///
+/// Each `DBColumn` is stored in its own column family, so keys are stored without the column
+/// prefix used by `LevelDB`.
+pub struct RocksDB<E: EthSpec> {
+    db: DB,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> RocksDB<E> {
+    /// Open a database at `path`, creating a new database if one does not already exist.
+    ///
+    /// An existing database must have been created by `RocksDB`, so that a `LevelDB` database is
+    /// never mistaken for an empty one.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let mut options = Options::default();
+        options.create_if_missing = true;
+        options.create_missing_column_families(true);
+
+        if path.join("CURRENT").exists() {
+            let column_families = DB::list_cf(&options, path)?;
+            if !column_families
+                .iter()
+                .any(|cf| cf == DBColumn::BeaconMeta.as_str())
+            {
+                return Err(Error::DBError {
+                    message: format!(
+                        "database at {} was not created by the RocksDB backend",
+                        path.display()
+                    ),
+                });
+            }
+        }
+
+        let descriptors = DBColumn::iter()
+            .map(|column| ColumnFamilyDescriptor::new(column.as_str(), Options::default()));
+        let db = DB::open_cf_descriptors(&options, path, descriptors)?;
+
+        Ok(Self {
+            db,
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    fn write_options(&self) -> WriteOptions {
+        WriteOptions::default()
+    }
+
+    fn write_options_sync(&self) -> WriteOptions {
+        let mut opts = WriteOptions::default();
+        opts.set_sync(true);
+        opts
+    }
+
+    fn cf_handle(&self, col: &str) -> Result<&ColumnFamily, Error> {
+        self.db.cf_handle(col).ok_or_else(|| Error::DBError {
+            message: format!("unknown column family: {}", col),
+        })
+    }
+
+    fn put_bytes_with_options(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        opts: WriteOptions,
+    ) -> Result<(), Error> {
+        let cf = self.cf_handle(col)?;
+
+        metrics::inc_counter(&metrics::DISK_DB_WRITE_COUNT);
+        metrics::inc_counter_by(&metrics::DISK_DB_WRITE_BYTES, val.len() as u64);
+        let timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        self.db
+            .put_cf_opt(cf, key, val, &opts)
+            .map_err(Into::into)
+            .map(|()| {
+                metrics::stop_timer(timer);
+            })
+    }
+
+    /// Split a key created by `get_key_for_col` into its column family and key.
+    fn split_column_key<'a>(
+        &self,
+        column_key: &'a [u8],
+    ) -> Result<(&ColumnFamily, &'a [u8]), Error> {
+        let (column, key) = DBColumn::split_key(column_key).ok_or_else(|| Error::DBError {
+            message: format!("key has no known column: {:?}", column_key),
+        })?;
+        Ok((self.cf_handle(column.as_str())?, key))
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for RocksDB<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_options(col, key, val, self.write_options())
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_options(col, key, val, self.write_options_sync())
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        self.db.flush_wal(true).map_err(Into::into)
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let cf = self.cf_handle(col)?;
+
+        metrics::inc_counter(&metrics::DISK_DB_READ_COUNT);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        self.db.get_cf(cf, key).map_err(Into::into).map(|opt| {
+            opt.map(|bytes| {
+                metrics::inc_counter_by(&metrics::DISK_DB_READ_BYTES, bytes.len() as u64);
+                metrics::stop_timer(timer);
+                bytes
+            })
+        })
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let cf = self.cf_handle(col)?;
+
+        metrics::inc_counter(&metrics::DISK_DB_EXISTS_COUNT);
+
+        self.db
+            .get_pinned_cf(cf, key)
+            .map_err(Into::into)
+            .map(|val| val.is_some())
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let cf = self.cf_handle(col)?;
+
+        metrics::inc_counter(&metrics::DISK_DB_DELETE_COUNT);
+
+        self.db
+            .delete_cf_opt(cf, key, &self.write_options())
+            .map_err(Into::into)
+    }
+
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let mut rocksdb_batch = WriteBatch::default();
+        for op in ops_batch {
+            match op {
+                KeyValueStoreOp::PutKeyValue(column_key, value) => {
+                    let (cf, key) = self.split_column_key(&column_key)?;
+                    rocksdb_batch.put_cf(cf, key, &value);
+                }
+
+                KeyValueStoreOp::DeleteKey(column_key) => {
+                    let (cf, key) = self.split_column_key(&column_key)?;
+                    rocksdb_batch.delete_cf(cf, key);
+                }
+            }
+        }
+        self.db.write_opt(rocksdb_batch, &self.write_options())?;
+        Ok(())
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    /// Compact all values in the states and states flag columns.
+    fn compact(&self) -> Result<(), Error> {
+        for column in [DBColumn::BeaconStateTemporary, DBColumn::BeaconState] {
+            let cf = self.cf_handle(column.as_str())?;
+            self.db.compact_range_cf(cf, None::<&[u8]>, None::<&[u8]>);
+        }
+        Ok(())
+    }
+
+    /// Iterate through all keys and values in a particular column.
+    fn iter_column(&self, column: DBColumn) -> ColumnIter {
+        let cf = match self.cf_handle(column.as_str()) {
+            Ok(cf) => cf,
+            Err(e) => return Box::new(std::iter::once(Err(e))),
+        };
+
+        Box::new(
+            self.db
+                .iterator_cf(cf, IteratorMode::Start)
+                .map(move |result| {
+                    let (key, value) = result?;
+                    let key = hash_key(&key)?;
+                    Ok((key, value.into_vec()))
+                }),
+        )
+    }
+
+    /// Iterate through all keys in a particular column.
+    fn iter_column_keys(&self, column: DBColumn) -> ColumnKeyIter {
+        let cf = match self.cf_handle(column.as_str()) {
+            Ok(cf) => cf,
+            Err(e) => return Box::new(std::iter::once(Err(e))),
+        };
+
+        Box::new(
+            self.db
+                .iterator_cf(cf, IteratorMode::Start)
+                .map(move |result| {
+                    let (key, _) = result?;
+                    hash_key(&key)
+                }),
+        )
+    }
+
+    /// Iterate through all keys and values in a particular column, without interpreting the keys.
+    fn iter_raw_entries(&self, column: DBColumn) -> RawEntryIter {
+        let cf = match self.cf_handle(column.as_str()) {
+            Ok(cf) => cf,
+            Err(e) => return Box::new(std::iter::once(Err(e))),
+        };
+
+        Box::new(
+            self.db
+                .iterator_cf(cf, IteratorMode::Start)
+                .map(move |result| {
+                    let (key, value) = result?;
+                    Ok((key.into_vec(), value.into_vec()))
+                }),
+        )
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for RocksDB<E> {}
+
+/// Interpret a key read from a column family as a `Hash256`.
+fn hash_key(key: &[u8]) -> Result<Hash256, Error> {
+    if key.len() == 32 {
+        Ok(Hash256::from_slice(key))
+    } else {
+        Err(HotColdDBError::IterationError {
+            unexpected_key: BytesKey::from_vec(key.to_vec()),
+        }
+        .into())
+    }
+}
+
+impl From<rocksdb::Error> for Error {
+    fn from(e: rocksdb::Error) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
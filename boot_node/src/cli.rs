@@ -80,4 +80,27 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("The directory which contains the enr and it's assoicated private key")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("http")
+                .long("http")
+                .help("Enable the HTTP server, which serves metrics, the routing table and the \
+                ENRs known per fork digest.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-address")
+                .long("http-address")
+                .value_name("ADDRESS")
+                .help("Set the listen address for the HTTP server.")
+                .default_value("127.0.0.1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-port")
+                .long("http-port")
+                .value_name("PORT")
+                .help("Set the listen TCP port for the HTTP server.")
+                .default_value("5058")
+                .takes_value(true),
+        )
 }
@@ -1,3 +1,4 @@
+use crate::persisted_dht::DHT_FILENAME;
 use beacon_node::{get_data_dir, set_network_config};
 use clap::ArgMatches;
 use eth2_network_config::Eth2NetworkConfig;
@@ -10,7 +11,7 @@ use lighthouse_network::{
 };
 use serde_derive::{Deserialize, Serialize};
 use ssz::Encode;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::{marker::PhantomData, path::PathBuf};
 use types::EthSpec;
 
@@ -22,6 +23,10 @@ pub struct BootNodeConfig<T: EthSpec> {
     pub local_enr: Enr,
     pub local_key: CombinedKey,
     pub discv5_config: Discv5Config,
+    /// The socket to serve the HTTP API on, if it is enabled.
+    pub http_listen_socket: Option<SocketAddr>,
+    /// The file the routing table is persisted to.
+    pub dht_path: PathBuf,
     phantom: PhantomData<T>,
 }
 
@@ -106,6 +111,16 @@ impl<T: EthSpec> BootNodeConfig<T> {
             }
         };
 
+        let http_listen_socket = if matches.is_present("http") {
+            let address: IpAddr = clap_utils::parse_required(matches, "http-address")?;
+            let port: u16 = clap_utils::parse_required(matches, "http-port")?;
+            Some(SocketAddr::new(address, port))
+        } else {
+            None
+        };
+
+        let dht_path = network_config.network_dir.join(DHT_FILENAME);
+
         let private_key = load_private_key(&network_config, &logger);
         let local_key = CombinedKey::from_libp2p(&private_key)?;
 
@@ -160,6 +175,8 @@ impl<T: EthSpec> BootNodeConfig<T> {
             local_enr,
             local_key,
             discv5_config: network_config.discv5_config,
+            http_listen_socket,
+            dht_path,
             phantom: PhantomData,
         })
     }
@@ -176,6 +193,8 @@ pub struct BootNodeConfigSerialization {
     pub local_enr: Enr,
     pub disable_packet_filter: bool,
     pub enable_enr_auto_update: bool,
+    pub http_listen_socket: Option<SocketAddr>,
+    pub dht_path: PathBuf,
 }
 
 impl BootNodeConfigSerialization {
@@ -188,6 +207,8 @@ impl BootNodeConfigSerialization {
             local_enr,
             local_key: _,
             discv5_config,
+            http_listen_socket,
+            dht_path,
             phantom: _,
         } = config;
 
@@ -197,6 +218,8 @@ impl BootNodeConfigSerialization {
             local_enr: local_enr.clone(),
             disable_packet_filter: !discv5_config.enable_packet_filter,
             enable_enr_auto_update: discv5_config.enr_update,
+            http_listen_socket: *http_listen_socket,
+            dht_path: dht_path.clone(),
         }
     }
 }
@@ -0,0 +1,231 @@
+//! A small HTTP API for inspecting the boot node, serving:
+//!
+//! - `/metrics`: Prometheus metrics.
+//! - `/status`: the discv5 request rates and a summary of the routing table.
+//! - `/routing_table`: every entry in the routing table.
+//! - `/enrs`: the ENRs of connected nodes, grouped by fork digest.
+
+use crate::metrics;
+use lighthouse_metrics::{Encoder, TextEncoder};
+use lighthouse_network::discv5::Discv5;
+use lighthouse_network::EnrExt;
+use lighthouse_version::version_with_platform;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use warp::{http::Response, Filter};
+
+#[derive(Debug, Serialize)]
+pub struct Status {
+    pub connected_peers: usize,
+    pub active_sessions: usize,
+    pub unsolicited_requests_per_second: f64,
+    pub sent_bytes: usize,
+    pub recv_bytes: usize,
+    pub table: metrics::TableSummary,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableEntry {
+    pub node_id: String,
+    pub peer_id: String,
+    pub connected: bool,
+    pub enr: String,
+}
+
+/// Creates a server that will serve requests using information from `discv5`.
+///
+/// Returns the address that the server will listen on and the server future, which must be
+/// awaited.
+pub fn serve(
+    listen_socket: SocketAddr,
+    discv5: Arc<Discv5>,
+) -> Result<(SocketAddr, impl Future<Output = ()>), String> {
+    warp::serve(routes(discv5))
+        .try_bind_ephemeral(listen_socket)
+        .map_err(|e| format!("Unable to start HTTP server: {}", e))
+}
+
+/// The routes served by the HTTP server.
+fn routes(
+    discv5: Arc<Discv5>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let discv5_filter = warp::any().map(move || discv5.clone());
+
+    // GET metrics
+    let get_metrics = warp::path("metrics")
+        .and(warp::path::end())
+        .and(discv5_filter.clone())
+        .map(|discv5: Arc<Discv5>| {
+            metrics::scrape_for_metrics(&discv5);
+            let mut buffer = vec![];
+            let response = TextEncoder::new()
+                .encode(&lighthouse_metrics::gather(), &mut buffer)
+                .map_err(|e| format!("{:?}", e))
+                .and_then(|()| String::from_utf8(buffer).map_err(|e| format!("{:?}", e)));
+            match response {
+                Ok(body) => Response::builder()
+                    .status(200)
+                    .header("Content-Type", "text/plain")
+                    .body(body)
+                    .unwrap(),
+                Err(e) => Response::builder()
+                    .status(500)
+                    .header("Content-Type", "text/plain")
+                    .body(format!("Unable to gather metrics: {}", e))
+                    .unwrap(),
+            }
+        });
+
+    // GET status
+    let get_status = warp::path("status")
+        .and(warp::path::end())
+        .and(discv5_filter.clone())
+        .map(|discv5: Arc<Discv5>| {
+            let discv5_metrics = discv5.metrics();
+            warp::reply::json(&Status {
+                connected_peers: discv5.connected_peers(),
+                active_sessions: discv5_metrics.active_sessions,
+                unsolicited_requests_per_second: discv5_metrics.unsolicited_requests_per_second,
+                sent_bytes: discv5_metrics.bytes_sent,
+                recv_bytes: discv5_metrics.bytes_recv,
+                table: metrics::summarise_table(&discv5),
+            })
+        });
+
+    // GET routing_table
+    let get_routing_table = warp::path("routing_table")
+        .and(warp::path::end())
+        .and(discv5_filter.clone())
+        .map(|discv5: Arc<Discv5>| {
+            let table = discv5.kbuckets();
+            let entries = table
+                .iter_ref()
+                .map(|entry| TableEntry {
+                    node_id: entry.node.key.preimage().to_string(),
+                    peer_id: entry.node.value.peer_id().to_string(),
+                    connected: entry.status.is_connected(),
+                    enr: entry.node.value.to_base64(),
+                })
+                .collect::<Vec<_>>();
+            warp::reply::json(&entries)
+        });
+
+    // GET enrs
+    let get_enrs = warp::path("enrs")
+        .and(warp::path::end())
+        .and(discv5_filter)
+        .map(|discv5: Arc<Discv5>| {
+            let table = discv5.kbuckets();
+            let mut enrs: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for enr in table
+                .iter_ref()
+                .filter_map(|entry| entry.status.is_connected().then_some(entry.node.value))
+            {
+                enrs.entry(metrics::fork_digest(enr))
+                    .or_default()
+                    .push(enr.to_base64());
+            }
+            warp::reply::json(&enrs)
+        });
+
+    warp::get()
+        .and(
+            get_metrics
+                .or(get_status)
+                .or(get_routing_table)
+                .or(get_enrs),
+        )
+        // Add a `Server` header.
+        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::discv5::enr::{CombinedKey, EnrBuilder};
+    use lighthouse_network::discv5::Discv5ConfigBuilder;
+    use lighthouse_network::Enr;
+    use serde_json::Value;
+    use std::net::Ipv4Addr;
+
+    fn enr(port: u16) -> (Enr, CombinedKey) {
+        let key = CombinedKey::generate_secp256k1();
+        let enr = EnrBuilder::new("v4")
+            .ip4(Ipv4Addr::LOCALHOST)
+            .udp4(port)
+            .build(&key)
+            .unwrap();
+        (enr, key)
+    }
+
+    /// A discv5 server whose routing table holds `num_nodes` disconnected nodes.
+    fn discv5(num_nodes: u16) -> (Arc<Discv5>, Vec<Enr>) {
+        let (local_enr, local_key) = enr(9000);
+        let discv5 = Discv5::new(local_enr, local_key, Discv5ConfigBuilder::new().build()).unwrap();
+        let enrs = (0..num_nodes).map(|i| enr(9001 + i).0).collect::<Vec<_>>();
+        for enr in &enrs {
+            discv5.add_enr(enr.clone()).unwrap();
+        }
+        (Arc::new(discv5), enrs)
+    }
+
+    async fn get_json(discv5: Arc<Discv5>, path: &str) -> Value {
+        let response = warp::test::request()
+            .path(path)
+            .reply(&routes(discv5))
+            .await;
+        assert_eq!(response.status(), 200);
+        assert!(response.headers().contains_key("Server"));
+        serde_json::from_slice(response.body()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn status() {
+        let (discv5, _) = discv5(2);
+        let status = get_json(discv5, "/status").await;
+        assert_eq!(status["connected_peers"], 0);
+        assert_eq!(status["table"]["connected"], 0);
+        assert_eq!(
+            status["table"]["fork_digests"],
+            Value::Object(Default::default())
+        );
+    }
+
+    #[tokio::test]
+    async fn routing_table() {
+        let (discv5, enrs) = discv5(2);
+        let entries = get_json(discv5, "/routing_table").await;
+        let entries = entries.as_array().unwrap();
+        assert_eq!(entries.len(), enrs.len());
+        for enr in &enrs {
+            let entry = entries
+                .iter()
+                .find(|entry| entry["node_id"] == enr.node_id().to_string())
+                .unwrap();
+            assert_eq!(entry["peer_id"], enr.peer_id().to_string());
+            assert_eq!(entry["connected"], false);
+            assert_eq!(entry["enr"], enr.to_base64());
+        }
+    }
+
+    #[tokio::test]
+    async fn enrs() {
+        // Only connected nodes are served, so disconnected nodes are omitted.
+        let (discv5, _) = discv5(2);
+        let enrs = get_json(discv5, "/enrs").await;
+        assert_eq!(enrs, Value::Object(Default::default()));
+    }
+
+    #[tokio::test]
+    async fn unknown_route() {
+        let (discv5, _) = discv5(0);
+        let response = warp::test::request()
+            .path("/unknown")
+            .reply(&routes(discv5))
+            .await;
+        assert_eq!(response.status(), 404);
+    }
+}
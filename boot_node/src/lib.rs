@@ -3,8 +3,14 @@ use clap::ArgMatches;
 use slog::{o, Drain, Level, Logger};
 
 use eth2_network_config::Eth2NetworkConfig;
+#[macro_use]
+extern crate lazy_static;
+
 mod cli;
 pub mod config;
+mod http;
+mod metrics;
+mod persisted_dht;
 mod server;
 pub use cli::cli_app;
 use config::{BootNodeConfig, BootNodeConfigSerialization};
@@ -0,0 +1,112 @@
+pub use lighthouse_metrics::*;
+use lighthouse_network::discv5::Discv5;
+use lighthouse_network::{Enr, Eth2Enr};
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+lazy_static! {
+    pub static ref CONNECTED_PEERS: Result<IntGauge> = try_create_int_gauge(
+        "boot_node_connected_peers",
+        "The number of connected nodes in the routing table"
+    );
+    pub static ref ACTIVE_SESSIONS: Result<IntGauge> = try_create_int_gauge(
+        "boot_node_active_sessions",
+        "The number of active discv5 sessions"
+    );
+    pub static ref UNSOLICITED_REQUESTS_PER_SECOND: Result<Gauge> = try_create_float_gauge(
+        "boot_node_unsolicited_requests_per_second",
+        "The rate of unsolicited discv5 requests received"
+    );
+    pub static ref SENT_BYTES: Result<IntGauge> = try_create_int_gauge(
+        "boot_node_sent_bytes",
+        "The total number of bytes sent by discv5"
+    );
+    pub static ref RECV_BYTES: Result<IntGauge> = try_create_int_gauge(
+        "boot_node_recv_bytes",
+        "The total number of bytes received by discv5"
+    );
+    pub static ref TABLE_NODES_BY_IP_VERSION: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "boot_node_table_nodes_by_ip_version",
+        "The number of connected nodes in the routing table by the IP versions in their ENR",
+        &["ip_version"]
+    );
+    pub static ref TABLE_NODES_BY_FORK_DIGEST: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "boot_node_table_nodes_by_fork_digest",
+        "The number of connected nodes in the routing table by the fork digest in their ENR",
+        &["fork_digest"]
+    );
+}
+
+/// Counts of the connected nodes in the routing table.
+#[derive(Debug, Default, Serialize)]
+pub struct TableSummary {
+    pub connected: usize,
+    pub ipv4_only: usize,
+    pub ipv6_only: usize,
+    pub ipv4_and_ipv6: usize,
+    pub unreachable: usize,
+    pub fork_digests: BTreeMap<String, usize>,
+}
+
+/// Summarises the connected nodes in the routing table of `discv5`.
+pub fn summarise_table(discv5: &Discv5) -> TableSummary {
+    let mut summary = TableSummary::default();
+    let table = discv5.kbuckets();
+    for enr in table
+        .iter_ref()
+        .filter_map(|entry| entry.status.is_connected().then_some(entry.node.value))
+    {
+        summary.connected += 1;
+        match (enr.udp4_socket().is_some(), enr.udp6_socket().is_some()) {
+            (true, true) => summary.ipv4_and_ipv6 += 1,
+            (true, false) => summary.ipv4_only += 1,
+            (false, true) => summary.ipv6_only += 1,
+            (false, false) => summary.unreachable += 1,
+        }
+        *summary.fork_digests.entry(fork_digest(enr)).or_default() += 1;
+    }
+    summary
+}
+
+/// Returns the hex-encoded fork digest of `enr`, or `none` if it does not have an `eth2` field.
+pub fn fork_digest(enr: &Enr) -> String {
+    enr.eth2().map_or_else(
+        |_| "none".to_string(),
+        |fork_id| format!("0x{}", hex::encode(fork_id.fork_digest)),
+    )
+}
+
+/// Update the global metrics `DEFAULT_REGISTRY` with info from `discv5`.
+pub fn scrape_for_metrics(discv5: &Discv5) {
+    let metrics = discv5.metrics();
+    set_gauge(&CONNECTED_PEERS, discv5.connected_peers() as i64);
+    set_gauge(&ACTIVE_SESSIONS, metrics.active_sessions as i64);
+    set_float_gauge(
+        &UNSOLICITED_REQUESTS_PER_SECOND,
+        metrics.unsolicited_requests_per_second,
+    );
+    set_gauge(&SENT_BYTES, metrics.bytes_sent as i64);
+    set_gauge(&RECV_BYTES, metrics.bytes_recv as i64);
+
+    let summary = summarise_table(discv5);
+    for (ip_version, count) in [
+        ("ipv4", summary.ipv4_only),
+        ("ipv6", summary.ipv6_only),
+        ("ipv4_and_ipv6", summary.ipv4_and_ipv6),
+        ("none", summary.unreachable),
+    ] {
+        set_gauge_vec(&TABLE_NODES_BY_IP_VERSION, &[ip_version], count as i64);
+    }
+
+    // Remove the fork digests of nodes which have left the table.
+    if let Ok(gauge_vec) = TABLE_NODES_BY_FORK_DIGEST.as_ref() {
+        gauge_vec.reset();
+    }
+    for (fork_digest, count) in &summary.fork_digests {
+        set_gauge_vec(
+            &TABLE_NODES_BY_FORK_DIGEST,
+            &[fork_digest.as_str()],
+            *count as i64,
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Persists the ENRs in the discv5 routing table to disk, so that a restarted boot node can serve
+//! them immediately rather than re-learning the network from its own boot nodes.
+
+use lighthouse_network::Enr;
+use std::fs;
+use std::path::Path;
+
+/// The file in the network directory which holds the persisted routing table.
+pub const DHT_FILENAME: &str = "dht.rlp";
+
+/// Loads the ENRs persisted by a previous run, if any.
+pub fn load_dht(path: &Path) -> Result<Vec<Enr>, String> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let bytes = fs::read(path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+    rlp::Rlp::new(&bytes)
+        .as_list()
+        .map_err(|e| format!("Unable to decode {:?}: {}", path, e))
+}
+
+/// Persists `enrs` to `path`, replacing any previously persisted ENRs.
+pub fn persist_dht(path: &Path, enrs: &[Enr]) -> Result<(), String> {
+    // Write to a temporary file first so that a crash cannot leave a partially written table.
+    let temp_path = path.with_extension("rlp.tmp");
+    fs::write(&temp_path, rlp::encode_list::<Enr, _>(enrs))
+        .map_err(|e| format!("Unable to write {:?}: {:?}", temp_path, e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::discv5::enr::{CombinedKey, EnrBuilder};
+    use std::net::Ipv4Addr;
+    use tempfile::tempdir;
+
+    fn enr(port: u16) -> Enr {
+        EnrBuilder::new("v4")
+            .ip4(Ipv4Addr::LOCALHOST)
+            .udp4(port)
+            .build(&CombinedKey::generate_secp256k1())
+            .unwrap()
+    }
+
+    #[test]
+    fn persist_and_load() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(DHT_FILENAME);
+        assert!(load_dht(&path).unwrap().is_empty());
+
+        let enrs = vec![enr(9000), enr(9001)];
+        persist_dht(&path, &enrs).unwrap();
+        assert_eq!(load_dht(&path).unwrap(), enrs);
+
+        // Persisting again replaces the previous table, without leaving the temporary file.
+        persist_dht(&path, &enrs[1..]).unwrap();
+        assert_eq!(load_dht(&path).unwrap(), enrs[1..]);
+        assert!(!path.with_extension("rlp.tmp").exists());
+    }
+
+    #[test]
+    fn corrupt_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(DHT_FILENAME);
+        fs::write(&path, b"not rlp").unwrap();
+        assert!(load_dht(&path).is_err());
+    }
+}
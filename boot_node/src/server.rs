@@ -1,13 +1,20 @@
 //! The main bootnode server execution.
 
 use super::BootNodeConfig;
+use crate::{http, metrics, persisted_dht};
 use lighthouse_network::{
     discv5::{enr::NodeId, Discv5, Discv5Event},
     EnrExt, Eth2Enr,
 };
 use slog::info;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use types::EthSpec;
 
+/// The interval at which the routing table is persisted to disk.
+const DHT_PERSIST_INTERVAL: Duration = Duration::from_secs(300);
+
 pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
     let BootNodeConfig {
         listen_socket,
@@ -15,6 +22,8 @@ pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
         local_enr,
         local_key,
         discv5_config,
+        http_listen_socket,
+        dht_path,
         ..
     } = config;
 
@@ -63,11 +72,39 @@ pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
         }
     }
 
+    // Add the nodes known before the last shutdown to the routing table
+    match persisted_dht::load_dht(&dht_path) {
+        Ok(enrs) => {
+            let count = enrs.len();
+            for enr in enrs.into_iter().filter(|enr| *enr != local_enr) {
+                let _ = discv5.add_enr(enr);
+            }
+            if count > 0 {
+                info!(log, "Loaded persisted routing table"; "entries" => count);
+            }
+        }
+        Err(e) => slog::warn!(log, "Failed to load persisted routing table"; "error" => e),
+    }
+
     // start the server
     if let Err(e) = discv5.start(listen_socket).await {
         slog::crit!(log, "Could not start discv5 server"; "error" => %e);
         return;
     }
+    let discv5 = Arc::new(discv5);
+
+    if let Some(http_listen_socket) = http_listen_socket {
+        match http::serve(http_listen_socket, discv5.clone()) {
+            Ok((listening_socket, server)) => {
+                info!(log, "HTTP server started"; "listen_address" => %listening_socket);
+                tokio::spawn(server);
+            }
+            Err(e) => {
+                slog::crit!(log, "Could not start HTTP server"; "error" => e);
+                return;
+            }
+        }
+    }
 
     // if there are peers in the local routing table, establish a session by running a query
     if !discv5.table_entries_id().is_empty() {
@@ -77,6 +114,12 @@ pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
 
     // respond with metrics every 10 seconds
     let mut metric_interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
+    let mut persist_interval = tokio::time::interval(DHT_PERSIST_INTERVAL);
+    // The first tick completes immediately, before any new nodes can have been found.
+    persist_interval.tick().await;
+
+    let shutdown = shutdown_signal(&log);
+    tokio::pin!(shutdown);
 
     // get an event stream
     let mut event_stream = match discv5.event_stream().await {
@@ -92,20 +135,7 @@ pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
         tokio::select! {
             _ = metric_interval.tick() => {
                 // Get some ipv4/ipv6 stats to add in the metrics.
-                let mut ipv4_only_reachable: usize = 0;
-                let mut ipv6_only_reachable: usize= 0;
-                let mut ipv4_ipv6_reachable: usize = 0;
-                let mut unreachable_nodes: usize = 0;
-                for enr in discv5.kbuckets().iter_ref().filter_map(|entry| entry.status.is_connected().then_some(entry.node.value)) {
-                    let declares_ipv4 = enr.udp4_socket().is_some();
-                    let declares_ipv6 = enr.udp6_socket().is_some();
-                    match (declares_ipv4, declares_ipv6) {
-                        (true, true) => ipv4_ipv6_reachable += 1,
-                        (true, false) => ipv4_only_reachable += 1,
-                        (false, true) => ipv6_only_reachable += 1,
-                        (false, false) => unreachable_nodes += 1,
-                    }
-                }
+                let table = metrics::summarise_table(&discv5);
 
                 // display server metrics
                 let metrics = discv5.metrics();
@@ -114,13 +144,21 @@ pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
                     "connected_peers" => discv5.connected_peers(),
                     "active_sessions" => metrics.active_sessions,
                     "requests/s" => format_args!("{:.2}", metrics.unsolicited_requests_per_second),
-                    "ipv4_nodes" => ipv4_only_reachable,
-                    "ipv6_nodes" => ipv6_only_reachable,
-                    "ipv6_and_ipv4_nodes" => ipv4_ipv6_reachable,
-                    "unreachable_nodes" => unreachable_nodes,
+                    "ipv4_nodes" => table.ipv4_only,
+                    "ipv6_nodes" => table.ipv6_only,
+                    "ipv6_and_ipv4_nodes" => table.ipv4_and_ipv6,
+                    "unreachable_nodes" => table.unreachable,
                 );
 
             }
+            _ = persist_interval.tick() => {
+                persist_dht(&discv5, &dht_path, &log);
+            }
+            _ = &mut shutdown => {
+                info!(log, "Shutting down");
+                persist_dht(&discv5, &dht_path, &log);
+                return;
+            }
             Some(event) = event_stream.recv() => {
                 match event {
                     Discv5Event::Discovered(_enr) => {
@@ -139,3 +177,41 @@ pub async fn run<T: EthSpec>(config: BootNodeConfig<T>, log: slog::Logger) {
         }
     }
 }
+
+/// Resolves once the process is asked to shut down, by SIGINT or (on Unix) SIGTERM.
+async fn shutdown_signal(log: &slog::Logger) {
+    #[cfg(target_family = "unix")]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => slog::error!(log, "Could not register SIGTERM handler"; "error" => %e),
+        }
+    }
+
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        slog::error!(log, "Could not register SIGINT handler"; "error" => %e);
+        // Never resolve, rather than shutting down immediately.
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Persists the routing table of `discv5` to `path`, unless it is empty.
+fn persist_dht(discv5: &Discv5, path: &Path, log: &slog::Logger) {
+    let enrs = discv5.table_entries_enr();
+    // Avoid replacing a useful table if the boot node has lost connectivity.
+    if enrs.is_empty() {
+        return;
+    }
+    match persisted_dht::persist_dht(path, &enrs) {
+        Ok(()) => slog::debug!(log, "Persisted routing table"; "entries" => enrs.len()),
+        Err(e) => slog::warn!(log, "Failed to persist routing table"; "error" => e),
+    }
+}
@@ -68,6 +68,55 @@ pub struct Web3SignerDefinition {
     pub client_identity_password: Option<String>,
 }
 
+/// An external source of the password for a voting keystore, so that the password need not be
+/// stored on the local filesystem.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PasswordSource {
+    /// Run `command` with `args` and use its standard output as the password.
+    Command {
+        command: PathBuf,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+    /// Request the password from an agent listening on the Unix socket at `socket_path`.
+    ///
+    /// The agent receives `key` (or the validator's 0x-prefixed voting public key, if `key` is
+    /// omitted) followed by a newline and must respond with the password, then close the
+    /// connection.
+    UnixSocket {
+        socket_path: PathBuf,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        key: Option<String>,
+    },
+    /// Read the password from a HashiCorp Vault compatible KV secrets engine (version 1 or 2).
+    Vault {
+        /// The full URL of the secret, e.g. `https://vault:8200/v1/secret/data/validator-1`.
+        url: String,
+        /// Path to a file containing the Vault token. The `VAULT_TOKEN` environment variable is
+        /// used if this is omitted.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        token_path: Option<PathBuf>,
+        /// The field of the secret which holds the password. Defaults to `password`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+    },
+}
+
+/// A single share of a distributed validator key.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyShareDefinition {
+    /// The non-zero point at which the dealer's polynomial was evaluated to produce this share.
+    pub index: u64,
+    /// The public key of this share, which is distinct from the validator's voting public key.
+    pub public_key: PublicKey,
+    /// How to obtain signatures from this share.
+    ///
+    /// Only the `local_keystore` and `web3signer` types are valid for a key share.
+    #[serde(flatten)]
+    pub signing_definition: SigningDefinition,
+}
+
 /// Defines how the validator client should attempt to sign messages for this validator.
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -80,12 +129,23 @@ pub enum SigningDefinition {
         voting_keystore_password_path: Option<PathBuf>,
         #[serde(skip_serializing_if = "Option::is_none")]
         voting_keystore_password: Option<ZeroizeString>,
+        /// Fetches the password when neither `voting_keystore_password` nor
+        /// `voting_keystore_password_path` is supplied.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        voting_keystore_password_source: Option<PasswordSource>,
     },
     /// A validator that defers to a Web3Signer HTTP server for signing.
     ///
     /// https://github.com/ConsenSys/web3signer
     #[serde(rename = "web3signer")]
     Web3Signer(Web3SignerDefinition),
+    /// A validator whose key has been split into shares held by several signers, any `threshold`
+    /// of which can jointly produce a signature.
+    #[serde(rename = "distributed")]
+    Distributed {
+        threshold: usize,
+        shares: Vec<KeyShareDefinition>,
+    },
 }
 
 impl SigningDefinition {
@@ -152,6 +212,7 @@ impl ValidatorDefinition {
                 voting_keystore_path,
                 voting_keystore_password_path: None,
                 voting_keystore_password,
+                voting_keystore_password_source: None,
             },
         })
     }
@@ -219,13 +280,24 @@ impl ValidatorDefinitions {
         let known_paths: HashSet<&PathBuf> = self
             .0
             .iter()
-            .filter_map(|def| match &def.signing_definition {
+            .flat_map(|def| match &def.signing_definition {
                 SigningDefinition::LocalKeystore {
                     voting_keystore_path,
                     ..
-                } => Some(voting_keystore_path),
+                } => vec![voting_keystore_path],
                 // A Web3Signer validator does not use a local keystore file.
-                SigningDefinition::Web3Signer { .. } => None,
+                SigningDefinition::Web3Signer { .. } => vec![],
+                // Key shares in local keystores must not be discovered as standalone validators.
+                SigningDefinition::Distributed { shares, .. } => shares
+                    .iter()
+                    .filter_map(|share| match &share.signing_definition {
+                        SigningDefinition::LocalKeystore {
+                            voting_keystore_path,
+                            ..
+                        } => Some(voting_keystore_path),
+                        _ => None,
+                    })
+                    .collect(),
             })
             .collect();
 
@@ -300,6 +372,7 @@ impl ValidatorDefinitions {
                         voting_keystore_path,
                         voting_keystore_password_path,
                         voting_keystore_password: None,
+                        voting_keystore_password_source: None,
                     },
                 })
             })
@@ -618,4 +691,89 @@ mod tests {
         let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_proposals).unwrap();
         assert_eq!(def.builder_proposals, Some(true));
     }
+
+    #[test]
+    fn distributed_checks() {
+        let distributed = r#"---
+        description: ""
+        enabled: true
+        type: distributed
+        threshold: 2
+        shares:
+          - index: 1
+            public_key: "0xa99a76ed7796f7be22d5b7e85deeb7c5677e88e511e0b337618f8c4eb61349b4bf2d153f649f7b53359fe8b94a38e44c"
+            type: local_keystore
+            voting_keystore_path: "share_1.json"
+          - index: 2
+            public_key: "0xb89bebc699769726a318c8e9971bd3171297c61aea4a6578a7a4f94b547dcba5bac16a89108b6b6a1fe3695d1a874a0b"
+            type: web3signer
+            url: "http://localhost:9000"
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+
+        let def: ValidatorDefinition = serde_yaml::from_str(distributed).unwrap();
+        match def.signing_definition {
+            SigningDefinition::Distributed { threshold, shares } => {
+                assert_eq!(threshold, 2);
+                assert_eq!(shares.len(), 2);
+                assert_eq!(shares[0].index, 1);
+                assert!(shares[0].signing_definition.is_local_keystore());
+                assert_eq!(shares[1].index, 2);
+                assert!(matches!(
+                    shares[1].signing_definition,
+                    SigningDefinition::Web3Signer(_)
+                ));
+            }
+            _ => panic!("expected a distributed signing definition"),
+        }
+    }
+
+    #[test]
+    fn password_source_checks() {
+        let password_source = |source: &str| {
+            let yaml = format!(
+                r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        voting_keystore_password_source:
+          {}
+        "#,
+                source
+            );
+            let def: ValidatorDefinition = serde_yaml::from_str(&yaml).unwrap();
+            match def.signing_definition {
+                SigningDefinition::LocalKeystore {
+                    voting_keystore_password_source,
+                    ..
+                } => voting_keystore_password_source,
+                _ => panic!("expected a local keystore signing definition"),
+            }
+        };
+
+        assert_eq!(
+            password_source("{type: command, command: pass, args: [show, validator-1]}"),
+            Some(PasswordSource::Command {
+                command: PathBuf::from("pass"),
+                args: vec!["show".to_string(), "validator-1".to_string()],
+            })
+        );
+        assert_eq!(
+            password_source("{type: unix_socket, socket_path: /run/agent.sock}"),
+            Some(PasswordSource::UnixSocket {
+                socket_path: PathBuf::from("/run/agent.sock"),
+                key: None,
+            })
+        );
+        assert_eq!(
+            password_source("{type: vault, url: \"https://vault:8200/v1/secret/data/v1\"}"),
+            Some(PasswordSource::Vault {
+                url: "https://vault:8200/v1/secret/data/v1".to_string(),
+                token_path: None,
+                field: None,
+            })
+        );
+    }
 }
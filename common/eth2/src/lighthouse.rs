@@ -20,6 +20,7 @@ use reqwest::IntoUrl;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::net::IpAddr;
 use store::{AnchorInfo, Split, StoreConfig};
 
 pub use attestation_performance::{
@@ -50,6 +51,51 @@ pub struct Peer<T: EthSpec> {
     pub peer_info: PeerInfo<T>,
 }
 
+/// A request to dial a peer, by either its multiaddr or its `enr:`-prefixed ENR.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialPeerRequest {
+    pub address: String,
+}
+
+/// A request to ban either a peer or an IP address for `duration_secs` seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BanRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+    pub duration_secs: u64,
+}
+
+/// A request to add a trusted peer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrustedPeerRequest {
+    pub peer_id: String,
+}
+
+/// The peers and IP addresses which are currently banned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerBans {
+    pub peers: Vec<BannedPeer>,
+    pub ips: Vec<BannedIp>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BannedPeer {
+    pub peer_id: String,
+    pub score: f64,
+    /// The number of seconds before the peer's score begins to decay, lifting the ban.
+    pub ban_remaining_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BannedIp {
+    pub ip: IpAddr,
+    /// The number of seconds remaining for IP addresses banned by request, or `None` for
+    /// addresses banned because too many peers using them are banned.
+    pub ban_remaining_secs: Option<u64>,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -306,6 +352,19 @@ pub struct Eth1SyncStatusData {
     pub lighthouse_is_cached_and_ready: bool,
 }
 
+/// The name and version of an execution client, as returned by `engine_getClientVersionV1`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ExecutionClientVersion {
+    /// The two letter client code, e.g. `GE` for Geth.
+    pub code: String,
+    /// The human-readable name of the client, e.g. `Geth`.
+    pub name: String,
+    /// The version of the client, e.g. `v1.0.0`.
+    pub version: String,
+    /// The first four bytes of the client's git commit, hex-encoded.
+    pub commit: String,
+}
+
 /// A fully parsed eth1 deposit contract log.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct DepositLog {
@@ -529,6 +588,20 @@ impl BeaconNodeHttpClient {
             .transpose()
     }
 
+    /// `GET lighthouse/execution_client_version`
+    pub async fn get_lighthouse_execution_client_version(
+        &self,
+    ) -> Result<GenericResponse<Vec<ExecutionClientVersion>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution_client_version");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/staking`
     pub async fn get_lighthouse_staking(&self) -> Result<bool, Error> {
         let mut path = self.server.full.clone();
@@ -397,6 +397,20 @@ impl ValidatorClientHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET lighthouse/doppelganger`
+    pub async fn get_lighthouse_doppelganger(
+        &self,
+    ) -> Result<GenericResponse<Vec<DoppelgangerData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("doppelganger");
+
+        self.get(path).await
+    }
+
     /// `POST lighthouse/validators`
     pub async fn post_lighthouse_validators(
         &self,
@@ -565,6 +579,20 @@ impl ValidatorClientHttpClient {
         self.delete_with_unsigned_response(url, req).await
     }
 
+    /// `POST lighthouse/keystores/export`
+    pub async fn post_lighthouse_keystores_export(
+        &self,
+        req: &ExportKeystoresRequest,
+    ) -> Result<ExportKeystoresResponse, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("keystores")
+            .push("export");
+        self.post(url, req).await
+    }
+
     /// `GET eth/v1/remotekeys`
     pub async fn get_remotekeys(&self) -> Result<ListRemotekeysResponse, Error> {
         let url = self.make_remotekeys_url()?;
@@ -642,6 +670,29 @@ impl ValidatorClientHttpClient {
         let url = self.make_gas_limit_url(pubkey)?;
         self.delete_with_raw_response(url, &()).await
     }
+
+    /// `POST /eth/v1/validator/{pubkey}/voluntary_exit`
+    pub async fn post_validator_voluntary_exit(
+        &self,
+        pubkey: &PublicKeyBytes,
+        epoch: Option<Epoch>,
+    ) -> Result<GenericResponse<SignedVoluntaryExit>, Error> {
+        let mut url = self.server.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("voluntary_exit");
+
+        if let Some(epoch) = epoch {
+            url.query_pairs_mut()
+                .append_pair("epoch", &epoch.to_string());
+        }
+
+        self.post(url, &()).await
+    }
 }
 
 /// Returns `Ok(response)` if the response is a `200 OK` response or a
@@ -2,6 +2,7 @@ use account_utils::ZeroizeString;
 use eth2_keystore::Keystore;
 use graffiti::GraffitiString;
 use serde::{Deserialize, Serialize};
+use slashing_protection::interchange::Interchange;
 use std::path::PathBuf;
 
 pub use crate::lighthouse::Health;
@@ -144,3 +145,72 @@ pub struct UpdateGasLimitRequest {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
     pub gas_limit: u64,
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VoluntaryExitQuery {
+    pub epoch: Option<Epoch>,
+}
+
+/// The request body of `POST /lighthouse/keystores/export`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ExportKeystoresRequest {
+    pub pubkeys: Vec<PublicKeyBytes>,
+}
+
+/// The response to `POST /lighthouse/keystores/export`.
+///
+/// Contains each exported keystore and its password, along with the slashing protection data of
+/// the requested validators, so that they may be imported into another validator client.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExportKeystoresResponse {
+    pub data: Vec<SingleExportKeystoresResponse>,
+    #[serde(with = "eth2_serde_utils::json_str")]
+    pub slashing_protection: Interchange,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SingleExportKeystoresResponse {
+    pub status: Status<ExportKeystoreStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validating_keystore: Option<KeystoreJsonStr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validating_keystore_password: Option<ZeroizeString>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKeystoreStatus {
+    Exported,
+    NotFound,
+    Error,
+}
+
+/// How doppelganger protection treats a validator that signed messages shortly before it was
+/// registered with the doppelganger service (e.g., prior to a restart of the validator client).
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoppelgangerDetection {
+    /// There is no record of recent signing, so the full detection period applies.
+    Full,
+    /// Signing was recorded by this validator client in the current or previous epoch and liveness
+    /// data is awaited to prove that it has continued without interruption.
+    AwaitingLiveness,
+    /// The liveness data showed the validator to be live in the last signed epoch and not during
+    /// the whole of the following epoch, so detection was skipped.
+    Skipped,
+    /// The liveness data did not prove that signing continued without interruption, so detection
+    /// began from the epoch following the last signed epoch.
+    Shortened,
+}
+
+/// The doppelganger protection state of a single validator.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DoppelgangerData {
+    pub pubkey: PublicKeyBytes,
+    pub signing_enabled: bool,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub remaining_epochs: u64,
+    pub next_check_epoch: Epoch,
+    pub last_signed_epoch: Option<Epoch>,
+    pub detection: DoppelgangerDetection,
+}
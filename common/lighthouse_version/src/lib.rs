@@ -21,6 +21,24 @@ pub const VERSION: &str = git_version!(
     fallback = "Lighthouse/v4.0.0"
 );
 
+/// Returns the first eight characters of the git commit of this build of Lighthouse.
+///
+/// Set to all zeros if the sources don't include git information.
+///
+/// ## Example
+///
+/// `67da0320`
+pub const COMMIT_PREFIX: &str = git_version!(
+    args = [
+        "--always",
+        "--abbrev=8",
+        // NOTE: using --match instead of --exclude for compatibility with old Git
+        "--match=thiswillnevermatchlol"
+    ],
+    prefix = "",
+    fallback = "00000000"
+);
+
 /// Returns `VERSION`, but with platform information appended to the end.
 ///
 /// ## Example
@@ -46,4 +64,14 @@ mod test {
             VERSION
         );
     }
+
+    #[test]
+    fn commit_prefix_formatting() {
+        let re = Regex::new(r"^[[:xdigit:]]{8,}$").unwrap();
+        assert!(
+            re.is_match(COMMIT_PREFIX),
+            "commit prefix doesn't match regex: {}",
+            COMMIT_PREFIX
+        );
+    }
 }
@@ -0,0 +1,176 @@
+//! Provides `StandardDepositDataJson`, the `deposit_data-*.json` format produced by the
+//! `staking-deposit-cli` and consumed by the Ethereum staking launchpad.
+use serde::{Deserialize, Serialize};
+use tree_hash::TreeHash;
+use types::{ChainSpec, DepositData, Hash256, PublicKeyBytes, SignatureBytes, SignedRoot};
+
+/// The `staking-deposit-cli` version that our output is compatible with.
+///
+/// The launchpad refuses files which do not declare a version, so we claim compatibility with
+/// the release whose format we reproduce.
+pub const DEPOSIT_CLI_VERSION: &str = "2.3.0";
+
+/// Networks which the `staking-deposit-cli` and launchpad call by a name other than their
+/// `CONFIG_NAME`, as `(config_name, launchpad_name)` pairs.
+const LAUNCHPAD_NETWORK_NAMES: &[(&str, &str)] = &[("prater", "goerli")];
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    ForkVersionMismatch { expected: [u8; 4], found: [u8; 4] },
+    NetworkNameMismatch { expected: String, found: String },
+    InvalidAmount(u64),
+    InvalidDepositMessageRoot { expected: Hash256, found: Hash256 },
+    InvalidDepositDataRoot { expected: Hash256, found: Hash256 },
+    InvalidPubkey,
+    InvalidSignature,
+}
+
+/// A single entry in a `deposit_data-*.json` file.
+///
+/// Byte fields are hex-encoded *without* a `0x` prefix, to match the `staking-deposit-cli`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StandardDepositDataJson {
+    #[serde(with = "hex_without_0x_prefix")]
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "hex_without_0x_prefix")]
+    pub withdrawal_credentials: Hash256,
+    pub amount: u64,
+    #[serde(with = "hex_without_0x_prefix")]
+    pub signature: SignatureBytes,
+    #[serde(with = "hex_without_0x_prefix")]
+    pub deposit_message_root: Hash256,
+    #[serde(with = "hex_without_0x_prefix")]
+    pub deposit_data_root: Hash256,
+    #[serde(with = "hex_without_0x_prefix")]
+    pub fork_version: [u8; 4],
+    pub network_name: String,
+    pub deposit_cli_version: String,
+}
+
+impl StandardDepositDataJson {
+    /// Build a JSON entry from some (signed) `deposit_data` for the network described by `spec`.
+    pub fn new(deposit_data: &DepositData, spec: &ChainSpec) -> Self {
+        Self {
+            pubkey: deposit_data.pubkey,
+            withdrawal_credentials: deposit_data.withdrawal_credentials,
+            amount: deposit_data.amount,
+            signature: deposit_data.signature.clone(),
+            deposit_message_root: deposit_data.as_deposit_message().tree_hash_root(),
+            deposit_data_root: deposit_data.tree_hash_root(),
+            fork_version: spec.genesis_fork_version,
+            network_name: network_name(spec),
+            deposit_cli_version: DEPOSIT_CLI_VERSION.to_string(),
+        }
+    }
+
+    /// Returns the `DepositData` described by `self`, without any verification.
+    pub fn deposit_data(&self) -> DepositData {
+        DepositData {
+            pubkey: self.pubkey,
+            withdrawal_credentials: self.withdrawal_credentials,
+            amount: self.amount,
+            signature: self.signature.clone(),
+        }
+    }
+
+    /// Checks that `self` is a valid deposit for the network described by `spec`.
+    ///
+    /// This performs the same checks as the `staking-deposit-cli` and launchpad: the fork version
+    /// and network name must match, the amount must be within the deposit bounds, both roots must
+    /// be correct and the signature must be valid. The network name may be either our
+    /// `CONFIG_NAME` or the launchpad's name for the network (e.g. `goerli` for `prater`).
+    pub fn verify(&self, spec: &ChainSpec) -> Result<DepositData, Error> {
+        if self.fork_version != spec.genesis_fork_version {
+            return Err(Error::ForkVersionMismatch {
+                expected: spec.genesis_fork_version,
+                found: self.fork_version,
+            });
+        }
+
+        if let Some(config_name) = spec.config_name.as_deref() {
+            let launchpad_name = launchpad_network_name(config_name);
+            if self.network_name != config_name && self.network_name != launchpad_name {
+                return Err(Error::NetworkNameMismatch {
+                    expected: launchpad_name.to_string(),
+                    found: self.network_name.clone(),
+                });
+            }
+        }
+
+        if self.amount < spec.min_deposit_amount || self.amount > spec.max_effective_balance {
+            return Err(Error::InvalidAmount(self.amount));
+        }
+
+        let deposit_data = self.deposit_data();
+        let deposit_message = deposit_data.as_deposit_message();
+
+        let deposit_message_root = deposit_message.tree_hash_root();
+        if self.deposit_message_root != deposit_message_root {
+            return Err(Error::InvalidDepositMessageRoot {
+                expected: deposit_message_root,
+                found: self.deposit_message_root,
+            });
+        }
+
+        let deposit_data_root = deposit_data.tree_hash_root();
+        if self.deposit_data_root != deposit_data_root {
+            return Err(Error::InvalidDepositDataRoot {
+                expected: deposit_data_root,
+                found: self.deposit_data_root,
+            });
+        }
+
+        let pubkey = self.pubkey.decompress().map_err(|_| Error::InvalidPubkey)?;
+        let signature = self
+            .signature
+            .decompress()
+            .map_err(|_| Error::InvalidSignature)?;
+        let signing_root = deposit_message.signing_root(spec.get_deposit_domain());
+        if !signature.verify(&pubkey, signing_root) {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(deposit_data)
+    }
+}
+
+/// The network name expected by the launchpad, falling back to the fork version for networks
+/// without a `CONFIG_NAME`.
+fn network_name(spec: &ChainSpec) -> String {
+    spec.config_name.as_deref().map_or_else(
+        || hex::encode(spec.genesis_fork_version),
+        |config_name| launchpad_network_name(config_name).to_string(),
+    )
+}
+
+fn launchpad_network_name(config_name: &str) -> &str {
+    LAUNCHPAD_NETWORK_NAMES
+        .iter()
+        .find(|(name, _)| *name == config_name)
+        .map_or(config_name, |&(_, launchpad_name)| launchpad_name)
+}
+
+mod hex_without_0x_prefix {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use ssz::{Decode, Encode};
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Encode,
+    {
+        serializer.serialize_str(&hex::encode(value.as_ssz_bytes()))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Decode,
+    {
+        let string = String::deserialize(deserializer)?;
+        let bytes = hex::decode(string.strip_prefix("0x").unwrap_or(&string))
+            .map_err(|e| D::Error::custom(format!("invalid hex: {:?}", e)))?;
+        T::from_ssz_bytes(&bytes).map_err(|e| D::Error::custom(format!("invalid bytes: {:?}", e)))
+    }
+}
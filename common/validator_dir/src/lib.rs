@@ -2,11 +2,13 @@
 //!
 //! - `ValidatorDir`: manages a directory containing validator keypairs, deposit info and other
 //! things.
+//! - `StandardDepositDataJson`: the `deposit_data-*.json` format used by the staking launchpad.
 //!
 //! This crate is intended to be used by the account manager to create validators and the validator
 //! client to load those validators.
 
 mod builder;
+pub mod deposit_data_json;
 pub mod insecure_keys;
 mod validator_dir;
 
@@ -18,3 +20,4 @@ pub use builder::{
     Builder, Error as BuilderError, ETH1_DEPOSIT_DATA_FILE, VOTING_KEYSTORE_FILE,
     WITHDRAWAL_KEYSTORE_FILE,
 };
+pub use deposit_data_json::StandardDepositDataJson;
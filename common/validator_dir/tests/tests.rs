@@ -4,9 +4,12 @@ use eth2_keystore::{Keystore, KeystoreBuilder, PlainText};
 use std::fs::{self, File};
 use std::path::Path;
 use tempfile::{tempdir, TempDir};
-use types::{test_utils::generate_deterministic_keypair, EthSpec, Keypair, MainnetEthSpec};
+use types::{
+    test_utils::generate_deterministic_keypair, ChainSpec, EthSpec, Keypair, MainnetEthSpec,
+};
 use validator_dir::{
-    Builder, BuilderError, ValidatorDir, ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
+    deposit_data_json::Error as DepositDataJsonError, Builder, BuilderError,
+    StandardDepositDataJson, ValidatorDir, ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
     VOTING_KEYSTORE_FILE, WITHDRAWAL_KEYSTORE_FILE,
 };
 
@@ -375,3 +378,76 @@ fn store_withdrawal_keystore_with_eth1_data() {
 
     harness.create_and_test(&config);
 }
+
+#[test]
+fn deposit_data_json() {
+    let harness = Harness::new();
+    let spec = MainnetEthSpec::default_spec();
+
+    let config = BuildConfig {
+        deposit_amount: Some(32000000000),
+        ..BuildConfig::default()
+    };
+
+    let validator = harness.create_and_test(&config);
+    let deposit_data = validator.eth1_deposit_data().unwrap().unwrap();
+
+    let json = StandardDepositDataJson::new(&deposit_data.deposit_data, &spec);
+    assert_eq!(json.deposit_data_root, deposit_data.root);
+    assert_eq!(json.fork_version, spec.genesis_fork_version);
+    assert_eq!(json.network_name, "mainnet");
+    assert_eq!(json.verify(&spec).unwrap(), deposit_data.deposit_data);
+
+    // Byte fields must be hex without a `0x` prefix, like the `staking-deposit-cli`.
+    let string = serde_json::to_string(&json).unwrap();
+    assert!(!string.contains("0x"), "{}", string);
+    let decoded: StandardDepositDataJson = serde_json::from_str(&string).unwrap();
+    assert_eq!(decoded, json);
+
+    let mut wrong_amount = json.clone();
+    wrong_amount.amount -= 1;
+    assert!(matches!(
+        wrong_amount.verify(&spec),
+        Err(DepositDataJsonError::InvalidDepositMessageRoot { .. })
+    ));
+
+    let mut wrong_root = json.clone();
+    wrong_root.deposit_data_root = Default::default();
+    assert!(matches!(
+        wrong_root.verify(&spec),
+        Err(DepositDataJsonError::InvalidDepositDataRoot { .. })
+    ));
+
+    let mut wrong_fork = json.clone();
+    wrong_fork.fork_version = [1, 2, 3, 4];
+    assert!(matches!(
+        wrong_fork.verify(&spec),
+        Err(DepositDataJsonError::ForkVersionMismatch { .. })
+    ));
+
+    let mut wrong_network = json;
+    wrong_network.network_name = "prater".into();
+    assert!(matches!(
+        wrong_network.verify(&spec),
+        Err(DepositDataJsonError::NetworkNameMismatch { .. })
+    ));
+
+    // The launchpad knows Prater as Goerli, so either name is accepted.
+    let prater_spec = ChainSpec {
+        config_name: Some("prater".to_string()),
+        ..spec
+    };
+    let mut prater_json = StandardDepositDataJson::new(&deposit_data.deposit_data, &prater_spec);
+    assert_eq!(prater_json.network_name, "goerli");
+    assert!(prater_json.verify(&prater_spec).is_ok());
+    prater_json.network_name = "prater".into();
+    assert!(prater_json.verify(&prater_spec).is_ok());
+    prater_json.network_name = "mainnet".into();
+    assert_eq!(
+        prater_json.verify(&prater_spec),
+        Err(DepositDataJsonError::NetworkNameMismatch {
+            expected: "goerli".to_string(),
+            found: "mainnet".to_string(),
+        })
+    );
+}
@@ -0,0 +1,70 @@
+use safe_arith::{ArithError, SafeArith};
+use std::cmp::max;
+use types::{BeaconState, BeaconStateError as Error, ChainSpec, Epoch, EthSpec, Unsigned};
+
+/// The maximum tolerated loss of safety of the weak subjectivity period, as a percentage.
+pub const SAFETY_DECAY: u64 = 10;
+
+const ETH_TO_GWEI: u64 = 1_000_000_000;
+
+/// Compute the weak subjectivity period of `state`, as per the spec.
+///
+/// The churn limit and total active balance are computed from the validator registry, so that no
+/// caches need to be built on `state`.
+pub fn compute_weak_subjectivity_period<E: EthSpec>(
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Epoch, Error> {
+    let mut ws_period = spec.min_validator_withdrawability_delay;
+
+    let current_epoch = state.current_epoch();
+    let (n, total_active_balance) = state
+        .validators()
+        .iter()
+        .filter(|validator| validator.is_active_at(current_epoch))
+        .try_fold((0u64, 0u64), |(count, balance), validator| {
+            Ok::<_, ArithError>((
+                count.safe_add(1)?,
+                balance.safe_add(validator.effective_balance)?,
+            ))
+        })?;
+    if n == 0 {
+        return Ok(ws_period);
+    }
+
+    let t = total_active_balance.safe_div(n)?.safe_div(ETH_TO_GWEI)?;
+    let max_t = spec.max_effective_balance.safe_div(ETH_TO_GWEI)?;
+    let delta = max(
+        spec.min_per_epoch_churn_limit,
+        n.safe_div(spec.churn_limit_quotient)?,
+    );
+    let max_delta = E::MaxDeposits::to_u64().safe_mul(E::slots_per_epoch())?;
+    let d = SAFETY_DECAY;
+
+    let low = max_t.safe_mul(d.safe_mul(3)?.safe_add(200)?)?;
+    let high = t.safe_mul(d.safe_mul(12)?.safe_add(200)?)?;
+
+    if low < high {
+        let epochs_for_validator_set_churn = n.safe_mul(high.safe_sub(low)?)?.safe_div(
+            600u64
+                .safe_mul(delta)?
+                .safe_mul(t.safe_mul(2)?.safe_add(max_t)?)?,
+        )?;
+        let epochs_for_balance_top_ups = n
+            .safe_mul(d.safe_mul(3)?.safe_add(200)?)?
+            .safe_div(600u64.safe_mul(max_delta)?)?;
+        ws_period.safe_add_assign(max(
+            epochs_for_validator_set_churn,
+            epochs_for_balance_top_ups,
+        ))?;
+    } else {
+        ws_period.safe_add_assign(
+            n.safe_mul(d)?
+                .safe_mul(t)?
+                .safe_mul(3)?
+                .safe_div(max_delta.safe_mul(max_t.safe_sub(t)?)?.safe_mul(200)?)?,
+        )?;
+    }
+
+    Ok(ws_period)
+}
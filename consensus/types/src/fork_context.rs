@@ -1,6 +1,6 @@
 use parking_lot::RwLock;
 
-use crate::{ChainSpec, EthSpec, ForkName, Hash256, Slot};
+use crate::{ChainSpec, Epoch, EthSpec, ForkName, Hash256, Slot};
 use std::collections::HashMap;
 
 /// Provides fork specific info like the current fork name and the fork digests corresponding to every valid fork.
@@ -9,6 +9,8 @@ pub struct ForkContext {
     current_fork: RwLock<ForkName>,
     fork_to_digest: HashMap<ForkName, [u8; 4]>,
     digest_to_fork: HashMap<[u8; 4], ForkName>,
+    /// The activation epoch of every enabled fork, in fork order.
+    fork_epochs: Vec<(ForkName, Epoch)>,
 }
 
 impl ForkContext {
@@ -62,10 +64,16 @@ impl ForkContext {
             .map(|(k, v)| (v, k))
             .collect();
 
+        let fork_epochs = ForkName::list_all()
+            .into_iter()
+            .filter_map(|fork_name| Some((fork_name, spec.fork_epoch(fork_name)?)))
+            .collect();
+
         Self {
             current_fork: RwLock::new(spec.fork_name_at_slot::<T>(current_slot)),
             fork_to_digest,
             digest_to_fork,
+            fork_epochs,
         }
     }
 
@@ -79,6 +87,17 @@ impl ForkContext {
         *self.current_fork.read()
     }
 
+    /// Returns the fork which is active at `slot`.
+    pub fn fork_name_at_slot<T: EthSpec>(&self, slot: Slot) -> ForkName {
+        let epoch = slot.epoch(T::slots_per_epoch());
+        self.fork_epochs
+            .iter()
+            .filter(|(_, fork_epoch)| *fork_epoch <= epoch)
+            .map(|(fork_name, _)| *fork_name)
+            .last()
+            .unwrap_or(ForkName::Base)
+    }
+
     /// Updates the `current_fork` field to a new fork.
     pub fn update_current_fork(&self, new_fork: ForkName) {
         *self.current_fork.write() = new_fork;
@@ -1,7 +1,12 @@
 use super::{BeaconBlockHeader, EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
-use crate::{beacon_state, test_utils::TestRandom, BeaconBlock, BeaconState, ChainSpec};
+use crate::{
+    beacon_state, test_utils::TestRandom, BeaconState, ChainSpec, ForkName, ForkVersionDeserialize,
+    SignedBeaconBlock, SignedBlindedBeaconBlock,
+};
 use safe_arith::ArithError;
+use serde::Deserializer;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
 use ssz_derive::{Decode, Encode};
 use ssz_types::typenum::{U5, U6};
 use std::sync::Arc;
@@ -83,12 +88,15 @@ pub struct LightClientUpdate<T: EthSpec> {
 }
 
 impl<T: EthSpec> LightClientUpdate<T> {
+    /// Build the update attested to by the sync aggregate of `block`.
+    ///
+    /// The `attested_state` must be the post-state of the parent of `block`, and `finalized_block`
+    /// the block at its finalized checkpoint.
     pub fn new(
-        chain_spec: ChainSpec,
-        beacon_state: BeaconState<T>,
-        block: BeaconBlock<T>,
+        chain_spec: &ChainSpec,
+        block: &SignedBeaconBlock<T>,
         attested_state: &mut BeaconState<T>,
-        finalized_block: BeaconBlock<T>,
+        finalized_block: &SignedBlindedBeaconBlock<T>,
     ) -> Result<Self, Error> {
         let altair_fork_epoch = chain_spec
             .altair_fork_epoch
@@ -97,31 +105,28 @@ impl<T: EthSpec> LightClientUpdate<T> {
             return Err(Error::AltairForkNotActive);
         }
 
-        let sync_aggregate = block.body().sync_aggregate()?;
+        let sync_aggregate = block.message().body().sync_aggregate()?;
         if sync_aggregate.num_set_bits() < chain_spec.min_sync_committee_participants as usize {
             return Err(Error::NotEnoughSyncCommitteeParticipants);
         }
 
-        let signature_period = block.epoch().sync_committee_period(&chain_spec)?;
+        let signature_period = block
+            .slot()
+            .epoch(T::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
         // Compute and validate attested header.
         let mut attested_header = attested_state.latest_block_header().clone();
-        attested_header.state_root = attested_state.tree_hash_root();
+        attested_header.state_root = attested_state.update_tree_hash_cache()?;
         let attested_period = attested_header
             .slot
             .epoch(T::slots_per_epoch())
-            .sync_committee_period(&chain_spec)?;
+            .sync_committee_period(chain_spec)?;
         if attested_period != signature_period {
             return Err(Error::MismatchingPeriods);
         }
         // Build finalized header from finalized block
-        let finalized_header = BeaconBlockHeader {
-            slot: finalized_block.slot(),
-            proposer_index: finalized_block.proposer_index(),
-            parent_root: finalized_block.parent_root(),
-            state_root: finalized_block.state_root(),
-            body_root: finalized_block.body_root(),
-        };
-        if finalized_header.tree_hash_root() != beacon_state.finalized_checkpoint().root {
+        let finalized_header = finalized_block.message().block_header();
+        if finalized_header.tree_hash_root() != attested_state.finalized_checkpoint().root {
             return Err(Error::InvalidFinalizedBlock);
         }
         let next_sync_committee_branch =
@@ -137,16 +142,149 @@ impl<T: EthSpec> LightClientUpdate<T> {
             signature_slot: block.slot(),
         })
     }
+
+    /// Returns the sync committee period of the attested header, which is the period this update
+    /// is stored and served under.
+    pub fn attested_period(&self, chain_spec: &ChainSpec) -> Result<u64, Error> {
+        Ok(self
+            .attested_header
+            .slot
+            .epoch(T::slots_per_epoch())
+            .sync_committee_period(chain_spec)?)
+    }
+
+    /// Returns `true` if `self` should be preferred over `other` as the best update of a period.
+    ///
+    /// Implements `is_better_update` from the light client sync protocol, restricted to the
+    /// updates we produce, which always carry a next sync committee and a finalized header.
+    pub fn is_better_than(&self, other: &Self, chain_spec: &ChainSpec) -> Result<bool, Error> {
+        let max_active_participants = T::sync_committee_size();
+        let new_active_participants = self.sync_aggregate.num_set_bits();
+        let old_active_participants = other.sync_aggregate.num_set_bits();
+
+        // Compare supermajority (> 2/3) sync committee participation.
+        let new_has_supermajority = new_active_participants * 3 >= max_active_participants * 2;
+        let old_has_supermajority = old_active_participants * 3 >= max_active_participants * 2;
+        if new_has_supermajority != old_has_supermajority {
+            return Ok(new_has_supermajority);
+        }
+        if !new_has_supermajority && new_active_participants != old_active_participants {
+            return Ok(new_active_participants > old_active_participants);
+        }
+
+        // Compare sync committee finality.
+        let new_has_sync_committee_finality = self
+            .finalized_header
+            .slot
+            .epoch(T::slots_per_epoch())
+            .sync_committee_period(chain_spec)?
+            == self.attested_period(chain_spec)?;
+        let old_has_sync_committee_finality = other
+            .finalized_header
+            .slot
+            .epoch(T::slots_per_epoch())
+            .sync_committee_period(chain_spec)?
+            == other.attested_period(chain_spec)?;
+        if new_has_sync_committee_finality != old_has_sync_committee_finality {
+            return Ok(new_has_sync_committee_finality);
+        }
+
+        // Tiebreaker 1: Sync committee participation beyond supermajority.
+        if new_active_participants != old_active_participants {
+            return Ok(new_active_participants > old_active_participants);
+        }
+
+        // Tiebreaker 2: Prefer older data (fewer changes to best).
+        if self.attested_header.slot != other.attested_header.slot {
+            return Ok(self.attested_header.slot < other.attested_header.slot);
+        }
+        Ok(self.signature_slot < other.signature_slot)
+    }
+}
+
+impl<T: EthSpec> ForkVersionDeserialize for LightClientUpdate<T> {
+    fn deserialize_by_fork<'de, D: Deserializer<'de>>(
+        value: Value,
+        fork_name: ForkName,
+    ) -> Result<Self, D::Error> {
+        match fork_name {
+            ForkName::Base => Err(serde::de::Error::custom(format!(
+                "LightClientUpdate failed to deserialize: unsupported fork '{}'",
+                fork_name
+            ))),
+            _ => serde_json::from_value(value).map_err(|e| {
+                serde::de::Error::custom(format!(
+                    "LightClientUpdate failed to deserialize: {:?}",
+                    e
+                ))
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
     use crate::MainnetEthSpec;
     use ssz_types::typenum::Unsigned;
 
     ssz_tests!(LightClientUpdate<MainnetEthSpec>);
 
+    fn update_with(
+        participants: usize,
+        attested_slot: u64,
+        finalized_slot: u64,
+    ) -> LightClientUpdate<MainnetEthSpec> {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut update = LightClientUpdate::<MainnetEthSpec>::random_for_test(&mut rng);
+        update.sync_aggregate = SyncAggregate::new();
+        for i in 0..participants {
+            update
+                .sync_aggregate
+                .sync_committee_bits
+                .set(i, true)
+                .unwrap();
+        }
+        update.attested_header.slot = Slot::new(attested_slot);
+        update.finalized_header.slot = Slot::new(finalized_slot);
+        update.signature_slot = Slot::new(attested_slot + 1);
+        update
+    }
+
+    #[test]
+    fn better_update_prefers_supermajority() {
+        let spec = MainnetEthSpec::default_spec();
+        let supermajority = update_with(400, 100, 64);
+        let minority = update_with(300, 90, 64);
+        assert!(supermajority.is_better_than(&minority, &spec).unwrap());
+        assert!(!minority.is_better_than(&supermajority, &spec).unwrap());
+    }
+
+    #[test]
+    fn better_update_prefers_sync_committee_finality() {
+        let spec = MainnetEthSpec::default_spec();
+        let slots_per_period =
+            spec.epochs_per_sync_committee_period.as_u64() * MainnetEthSpec::slots_per_epoch();
+        let finalized_in_period = update_with(400, slots_per_period + 100, slots_per_period + 64);
+        let finalized_before_period = update_with(500, slots_per_period + 90, 64);
+        assert!(finalized_in_period
+            .is_better_than(&finalized_before_period, &spec)
+            .unwrap());
+    }
+
+    #[test]
+    fn better_update_tiebreaks_on_participation_then_age() {
+        let spec = MainnetEthSpec::default_spec();
+        let more = update_with(450, 100, 64);
+        let fewer = update_with(400, 90, 64);
+        assert!(more.is_better_than(&fewer, &spec).unwrap());
+
+        let older = update_with(450, 90, 64);
+        assert!(older.is_better_than(&more, &spec).unwrap());
+        assert!(!more.is_better_than(&more, &spec).unwrap());
+    }
+
     #[test]
     fn finalized_root_params() {
         assert!(2usize.pow(FINALIZED_ROOT_PROOF_LEN as u32) <= FINALIZED_ROOT_INDEX);
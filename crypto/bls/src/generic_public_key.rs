@@ -4,6 +4,7 @@ use eth2_serde_utils::hex::encode as hex_encode;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use ssz::{Decode, Encode};
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use tree_hash::TreeHash;
@@ -25,6 +26,12 @@ pub trait TPublicKey: Sized + Clone {
 
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Combine public key shares into the public key of the shared secret key by Lagrange
+    /// interpolation at zero.
+    ///
+    /// Each share is paired with its index, which is guaranteed to be non-zero and unique.
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS public key that is generic across some BLS point (`Pub`).
@@ -75,6 +82,32 @@ where
             })
         }
     }
+
+    /// Combine the public keys of `m` or more shares of an `m`-of-`n` threshold secret key into
+    /// the public key of that secret key.
+    ///
+    /// Each public key is paired with the index of its key share. Providing fewer than `m` shares
+    /// results in some other public key.
+    pub fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        if shares.is_empty() {
+            return Err(Error::EmptyPublicKeyShares);
+        }
+
+        let mut seen_indices = HashSet::with_capacity(shares.len());
+        let points = shares
+            .iter()
+            .map(|(index, pubkey)| {
+                if *index == 0 || !seen_indices.insert(*index) {
+                    return Err(Error::InvalidPublicKeyShareIndex(*index));
+                }
+                Ok((*index, pubkey.point()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            point: Pub::combine_shares(&points)?,
+        })
+    }
 }
 
 impl<Pub: TPublicKey> Eq for GenericPublicKey<Pub> {}
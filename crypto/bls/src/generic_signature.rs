@@ -6,6 +6,7 @@ use eth2_serde_utils::hex::encode as hex_encode;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use ssz::{Decode, Encode};
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
@@ -36,6 +37,12 @@ pub trait TSignature<GenericPublicKey>: Sized + Clone {
 
     /// Returns `true` if `self` is a signature across `msg` by `pubkey`.
     fn verify(&self, pubkey: &GenericPublicKey, msg: Hash256) -> bool;
+
+    /// Combine signature shares into the signature of the shared secret key by Lagrange
+    /// interpolation at zero.
+    ///
+    /// Each share is paired with its index, which is guaranteed to be non-zero and unique.
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS signature that is generic across:
@@ -129,6 +136,37 @@ where
             _phantom: PhantomData,
         })
     }
+
+    /// Combine the signatures produced by `m` or more shares of an `m`-of-`n` threshold secret
+    /// key into the signature of that secret key.
+    ///
+    /// Each signature is paired with the index of the key share that produced it (i.e., the
+    /// non-zero point at which the dealer's polynomial was evaluated). Providing fewer than `m`
+    /// shares results in a signature that will not verify.
+    pub fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        if shares.is_empty() {
+            return Err(Error::EmptySignatureShares);
+        }
+
+        let mut seen_indices = HashSet::with_capacity(shares.len());
+        let points = shares
+            .iter()
+            .map(|(index, signature)| {
+                if *index == 0 || !seen_indices.insert(*index) {
+                    return Err(Error::InvalidSignatureShareIndex(*index));
+                }
+                let point = signature
+                    .point()
+                    .ok_or(Error::EmptySignatureShare { index: *index })?;
+                Ok((*index, point))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let point = Sig::combine_shares(&points)?;
+        let is_infinity = point.serialize() == INFINITY_SIGNATURE;
+
+        Ok(Self::from_point(point, is_infinity))
+    }
 }
 
 impl<Pub, Sig> GenericSignature<Pub, Sig>
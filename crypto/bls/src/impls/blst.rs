@@ -7,7 +7,7 @@ use crate::{
     Error, Hash256, ZeroizeHash, INFINITY_SIGNATURE,
 };
 pub use blst::min_pk as blst_core;
-use blst::{blst_scalar, BLST_ERROR};
+use blst::{blst_fr, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, blst_scalar, BLST_ERROR};
 use rand::Rng;
 use std::iter::ExactSizeIterator;
 
@@ -134,6 +134,38 @@ impl TPublicKey for blst_core::PublicKey {
         }
         Self::key_validate(bytes).map_err(Into::into)
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let indices = shares.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        let coefficients = lagrange_coefficients_at_zero(&indices);
+        let mut combined = blst_p1::default();
+
+        // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+        //
+        // https://github.com/sigp/lighthouse/issues/1720
+        unsafe {
+            for ((_, share), coefficient) in shares.iter().zip(coefficients.iter()) {
+                // Shares have already been checked for subgroup and infinity upon
+                // deserialization.
+                let mut affine = blst_p1_affine::default();
+                let err = blst::blst_p1_uncompress(&mut affine, share.compress().as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                let mut point = blst_p1::default();
+                blst::blst_p1_from_affine(&mut point, &affine);
+                let mut term = blst_p1::default();
+                blst::blst_p1_mult(&mut term, &point, coefficient.b.as_ptr(), 255);
+
+                let combined_ptr: *mut blst_p1 = &mut combined;
+                blst::blst_p1_add_or_double(combined_ptr, combined_ptr, &term);
+            }
+
+            let mut bytes = [0; PUBLIC_KEY_BYTES_LEN];
+            blst::blst_p1_compress(bytes.as_mut_ptr(), &combined);
+            <Self as TPublicKey>::deserialize(&bytes)
+        }
+    }
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -181,6 +213,92 @@ impl TSignature<blst_core::PublicKey> for blst_core::Signature {
         // Check Signature inside function for subgroup
         self.verify(true, msg.as_bytes(), DST, &[], pubkey, false) == BLST_ERROR::BLST_SUCCESS
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let indices = shares.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        let coefficients = lagrange_coefficients_at_zero(&indices);
+        let mut combined = blst_p2::default();
+
+        // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+        //
+        // https://github.com/sigp/lighthouse/issues/1720
+        unsafe {
+            for ((_, share), coefficient) in shares.iter().zip(coefficients.iter()) {
+                // Shares have already been checked for subgroup upon deserialization.
+                let mut affine = blst_p2_affine::default();
+                let err = blst::blst_p2_uncompress(&mut affine, share.to_bytes().as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                let mut point = blst_p2::default();
+                blst::blst_p2_from_affine(&mut point, &affine);
+                let mut term = blst_p2::default();
+                blst::blst_p2_mult(&mut term, &point, coefficient.b.as_ptr(), 255);
+
+                let combined_ptr: *mut blst_p2 = &mut combined;
+                blst::blst_p2_add_or_double(combined_ptr, combined_ptr, &term);
+            }
+
+            let mut bytes = [0; SIGNATURE_BYTES_LEN];
+            blst::blst_p2_compress(bytes.as_mut_ptr(), &combined);
+            Self::from_bytes(&bytes).map_err(Into::into)
+        }
+    }
+}
+
+/// Returns the Lagrange basis polynomial for each of the `indices`, evaluated at zero:
+///
+/// l_i(0) = prod_{j != i} x_j / (x_j - x_i)
+///
+/// The `indices` must be non-zero and unique.
+fn lagrange_coefficients_at_zero(indices: &[u64]) -> Vec<blst_scalar> {
+    let indices = indices
+        .iter()
+        .map(|index| fr_from_u64(*index))
+        .collect::<Vec<_>>();
+
+    // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+    //
+    // https://github.com/sigp/lighthouse/issues/1720
+    unsafe {
+        indices
+            .iter()
+            .enumerate()
+            .map(|(i, x_i)| {
+                let mut numerator = fr_from_u64(1);
+                let mut denominator = fr_from_u64(1);
+                let mut difference = blst_fr::default();
+                for (j, x_j) in indices.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    let numerator_ptr: *mut blst_fr = &mut numerator;
+                    blst::blst_fr_mul(numerator_ptr, numerator_ptr, x_j);
+                    blst::blst_fr_sub(&mut difference, x_j, x_i);
+                    let denominator_ptr: *mut blst_fr = &mut denominator;
+                    blst::blst_fr_mul(denominator_ptr, denominator_ptr, &difference);
+                }
+
+                let mut coefficient = blst_fr::default();
+                blst::blst_fr_inverse(&mut coefficient, &denominator);
+                let coefficient_ptr: *mut blst_fr = &mut coefficient;
+                blst::blst_fr_mul(coefficient_ptr, coefficient_ptr, &numerator);
+                let mut scalar = blst_scalar::default();
+                blst::blst_scalar_from_fr(&mut scalar, &coefficient);
+                scalar
+            })
+            .collect()
+    }
+}
+
+/// Converts `value` into an element of the BLS12-381 scalar field.
+fn fr_from_u64(value: u64) -> blst_fr {
+    let mut fr = blst_fr::default();
+    // The field modulus exceeds `u64::MAX`, so `value` is always in range.
+    unsafe {
+        blst::blst_fr_from_uint64(&mut fr, [value, 0, 0, 0].as_ptr());
+    }
+    fr
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
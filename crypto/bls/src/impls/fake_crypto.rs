@@ -51,6 +51,13 @@ impl TPublicKey for PublicKey {
         pubkey.0[..].copy_from_slice(&bytes[0..PUBLIC_KEY_BYTES_LEN]);
         Ok(pubkey)
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        shares
+            .first()
+            .map(|(_, pubkey)| (*pubkey).clone())
+            .ok_or(Error::EmptyPublicKeyShares)
+    }
 }
 
 impl Eq for PublicKey {}
@@ -105,6 +112,13 @@ impl TSignature<PublicKey> for Signature {
     fn verify(&self, _pubkey: &PublicKey, _msg: Hash256) -> bool {
         true
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        shares
+            .first()
+            .map(|(_, signature)| (*signature).clone())
+            .ok_or(Error::EmptySignatureShares)
+    }
 }
 
 impl PartialEq for Signature {
@@ -84,6 +84,11 @@ impl TPublicKey for milagro::PublicKey {
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
+
+    fn combine_shares(_shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        // The Milagro library does not expose the scalar and point arithmetic required.
+        Err(Error::PublicKeySharesNotSupported)
+    }
 }
 
 impl TAggregatePublicKey<milagro::PublicKey> for milagro::AggregatePublicKey {
@@ -115,6 +120,11 @@ impl TSignature<milagro::PublicKey> for milagro::Signature {
     fn verify(&self, pubkey: &milagro::PublicKey, msg: Hash256) -> bool {
         self.verify(msg.as_bytes(), pubkey)
     }
+
+    fn combine_shares(_shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        // The Milagro library does not expose the scalar and point arithmetic required.
+        Err(Error::SignatureSharesNotSupported)
+    }
 }
 
 impl TAggregateSignature<milagro::PublicKey, milagro::AggregatePublicKey, milagro::Signature>
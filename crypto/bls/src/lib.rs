@@ -64,6 +64,20 @@ pub enum Error {
     InvalidInfinityPublicKey,
     /// The secret key is all zero bytes, which is invalid.
     InvalidZeroSecretKey,
+    /// No signature shares were provided to combine.
+    EmptySignatureShares,
+    /// A signature share was the "empty" signature.
+    EmptySignatureShare { index: u64 },
+    /// A signature share index was zero or was used by more than one share.
+    InvalidSignatureShareIndex(u64),
+    /// The BLS backend in use cannot combine signature shares.
+    SignatureSharesNotSupported,
+    /// No public key shares were provided to combine.
+    EmptyPublicKeyShares,
+    /// A public key share index was zero or was used by more than one share.
+    InvalidPublicKeyShareIndex(u64),
+    /// The BLS backend in use cannot combine public key shares.
+    PublicKeySharesNotSupported,
 }
 
 #[cfg(feature = "milagro")]
@@ -508,6 +508,124 @@ macro_rules! test_suite {
 
 mod blst {
     test_suite!(blst_implementations);
+
+    /// Produces the signature of the key share at `index` for the secret sharing polynomial
+    /// `f(x) = a_0 + a_1 * x + a_2 * x^2`, using only the signatures of the coefficients.
+    fn share_signature(index: u64, coefficient_signatures: &[Signature; 3]) -> Signature {
+        let mut aggregate = AggregateSignature::infinity();
+        aggregate.add_assign(&coefficient_signatures[0]);
+        for _ in 0..index {
+            aggregate.add_assign(&coefficient_signatures[1]);
+        }
+        for _ in 0..index * index {
+            aggregate.add_assign(&coefficient_signatures[2]);
+        }
+        Signature::deserialize(&aggregate.serialize()).unwrap()
+    }
+
+    #[test]
+    fn combine_threshold_signature_shares() {
+        let msg = Hash256::from_low_u64_be(42);
+        let secret = secret_from_u64(7);
+        let coefficient_signatures = [
+            secret.sign(msg),
+            secret_from_u64(8).sign(msg),
+            secret_from_u64(9).sign(msg),
+        ];
+        let shares = (1..=5)
+            .map(|index| (index, share_signature(index, &coefficient_signatures)))
+            .collect::<Vec<_>>();
+        let share_refs = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|i| (shares[*i].0, &shares[*i].1))
+                .collect::<Vec<_>>()
+        };
+
+        for indices in [
+            vec![0, 1, 2],
+            vec![4, 2, 0],
+            vec![1, 3, 4],
+            vec![0, 1, 2, 3, 4],
+        ] {
+            let combined = Signature::combine_shares(&share_refs(&indices)).unwrap();
+            assert_eq!(combined, coefficient_signatures[0]);
+            assert!(combined.verify(&secret.public_key(), msg));
+        }
+
+        // Fewer than the threshold of shares cannot reproduce the signature.
+        let combined = Signature::combine_shares(&share_refs(&[0, 1])).unwrap();
+        assert!(!combined.verify(&secret.public_key(), msg));
+    }
+
+    #[test]
+    fn combine_invalid_threshold_signature_shares() {
+        let signature = secret_from_u64(0).sign(Hash256::zero());
+        let empty = Signature::empty();
+
+        assert_eq!(
+            Signature::combine_shares(&[]),
+            Err(bls::Error::EmptySignatureShares)
+        );
+        assert_eq!(
+            Signature::combine_shares(&[(0, &signature)]),
+            Err(bls::Error::InvalidSignatureShareIndex(0))
+        );
+        assert_eq!(
+            Signature::combine_shares(&[(1, &signature), (1, &signature)]),
+            Err(bls::Error::InvalidSignatureShareIndex(1))
+        );
+        assert_eq!(
+            Signature::combine_shares(&[(1, &signature), (2, &empty)]),
+            Err(bls::Error::EmptySignatureShare { index: 2 })
+        );
+    }
+
+    #[test]
+    fn combine_threshold_public_key_shares() {
+        // The secret keys of `secret_from_u64` are offset by one, so the shares of the secret
+        // sharing polynomial `f(x) = 8 + 9 * x + 10 * x^2` are known directly.
+        let secret = secret_from_u64(7);
+        let shares = (1..=5)
+            .map(|index: u64| {
+                let share_secret = secret_from_u64(8 + 9 * index + 10 * index * index - 1);
+                (index, share_secret.public_key())
+            })
+            .collect::<Vec<_>>();
+        let share_refs = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|i| (shares[*i].0, &shares[*i].1))
+                .collect::<Vec<_>>()
+        };
+
+        for indices in [vec![0, 1, 2], vec![4, 2, 0], vec![0, 1, 2, 3, 4]] {
+            let combined = PublicKey::combine_shares(&share_refs(&indices)).unwrap();
+            assert_eq!(combined, secret.public_key());
+        }
+
+        // Fewer than the threshold of shares cannot reproduce the public key.
+        let combined = PublicKey::combine_shares(&share_refs(&[0, 1])).unwrap();
+        assert_ne!(combined, secret.public_key());
+    }
+
+    #[test]
+    fn combine_invalid_threshold_public_key_shares() {
+        let pubkey = secret_from_u64(0).public_key();
+
+        assert_eq!(
+            PublicKey::combine_shares(&[]),
+            Err(bls::Error::EmptyPublicKeyShares)
+        );
+        assert_eq!(
+            PublicKey::combine_shares(&[(0, &pubkey)]),
+            Err(bls::Error::InvalidPublicKeyShareIndex(0))
+        );
+        assert_eq!(
+            PublicKey::combine_shares(&[(1, &pubkey), (1, &pubkey)]),
+            Err(bls::Error::InvalidPublicKeyShareIndex(1))
+        );
+    }
 }
 
 #[cfg(all(feature = "milagro", not(debug_assertions)))]
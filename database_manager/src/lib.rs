@@ -4,18 +4,28 @@ use beacon_chain::{
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
 use clap::{App, Arg, ArgMatches};
+use directory::DEFAULT_BEACON_NODE_DIR;
 use environment::{Environment, RuntimeContext};
 use slog::{info, Logger};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
 use store::{
+    config::DatabaseBackend,
+    era,
     errors::Error,
-    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    get_key_for_col,
+    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, DATABASE_BACKEND_KEY},
+    BeaconNodeBackend, DBColumn, HotColdDB, ItemStore, KeyValueStore, KeyValueStoreOp,
 };
-use strum::{EnumString, EnumVariantNames, VariantNames};
+use strum::{EnumString, EnumVariantNames, IntoEnumIterator, VariantNames};
 use types::EthSpec;
 
 pub const CMD: &str = "database_manager";
 
+/// The approximate number of bytes written to the destination database in each batch by `convert`.
+const CONVERT_BATCH_SIZE_BYTES: usize = 64 * 1024 * 1024;
+
 pub fn version_cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new("version")
         .visible_aliases(&["v"])
@@ -65,6 +75,111 @@ pub fn prune_payloads_app<'a, 'b>() -> App<'a, 'b> {
         .about("Prune finalized execution payloads")
 }
 
+pub fn change_sprp_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("change_sprp")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Change the slots-per-restore-point of the freezer database. The current value must \
+             be provided with --slots-per-restore-point.",
+        )
+        .arg(
+            Arg::with_name("to")
+                .long("to")
+                .value_name("SLOT_COUNT")
+                .help("The new slots-per-restore-point")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("batch-size")
+                .long("batch-size")
+                .value_name("N")
+                .help(
+                    "Number of restore points to create between each save of progress. An \
+                     interrupted change resumes from the last save when run again.",
+                )
+                .default_value("64")
+                .takes_value(true),
+        )
+}
+
+pub fn export_era_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("export_era")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about("Export finalized blocks and states from the freezer database as era files")
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help(
+                    "Directory to write the era files to. Eras which already have a file in the \
+                     directory are skipped.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("start-era")
+                .long("start-era")
+                .value_name("ERA")
+                .help("First era to export. Defaults to the oldest era available.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("end-era")
+                .long("end-era")
+                .value_name("ERA")
+                .help("Last era to export. Defaults to the newest finalized era.")
+                .takes_value(true),
+        )
+}
+
+pub fn convert_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("convert")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about("Copy the database into another directory and/or database backend")
+        .arg(
+            Arg::with_name("output-datadir")
+                .long("output-datadir")
+                .value_name("DIR")
+                .help(
+                    "Data directory to write the converted database to, laid out in the same way \
+                     as --datadir. Must not already contain a database.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("output-freezer-dir")
+                .long("output-freezer-dir")
+                .value_name("DIR")
+                .help("Data directory for the converted freezer database.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output-db-backend")
+                .long("output-db-backend")
+                .value_name("DATABASE")
+                .help(
+                    "Database backend to convert to. Defaults to the backend of the source \
+                     database.",
+                )
+                .takes_value(true)
+                .possible_values(DatabaseBackend::VARIANTS),
+        )
+        .arg(
+            Arg::with_name("sample-interval")
+                .long("sample-interval")
+                .value_name("N")
+                .help(
+                    "After copying, compare the value of every Nth key of each column against \
+                     the source database.",
+                )
+                .default_value("128")
+                .takes_value(true),
+        )
+}
+
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
         .visible_aliases(&["db"])
@@ -88,10 +203,21 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Data directory for the freezer database.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("db-backend")
+                .long("db-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend that the database was created with.")
+                .takes_value(true)
+                .possible_values(store::config::DatabaseBackend::VARIANTS),
+        )
         .subcommand(migrate_cli_app())
         .subcommand(version_cli_app())
         .subcommand(inspect_cli_app())
         .subcommand(prune_payloads_app())
+        .subcommand(convert_cli_app())
+        .subcommand(change_sprp_cli_app())
+        .subcommand(export_era_cli_app())
 }
 
 fn parse_client_config<E: EthSpec>(
@@ -110,6 +236,10 @@ fn parse_client_config<E: EthSpec>(
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "db-backend")? {
+        client_config.store.backend = backend;
+    }
+
     Ok(client_config)
 }
 
@@ -123,7 +253,7 @@ pub fn display_db_version<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, from, _| {
@@ -178,7 +308,7 @@ pub fn inspect_db<E: EthSpec>(
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, _, _| Ok(()),
@@ -234,7 +364,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, db_initial_version, _| {
@@ -272,7 +402,7 @@ pub fn prune_payloads<E: EthSpec>(
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, _, _| Ok(()),
@@ -287,6 +417,341 @@ pub fn prune_payloads<E: EthSpec>(
     db.try_prune_execution_payloads(force)
 }
 
+pub struct ConvertConfig {
+    output_client_config: ClientConfig,
+    sample_interval: usize,
+}
+
+fn parse_convert_config(
+    cli_args: &ArgMatches,
+    client_config: &ClientConfig,
+) -> Result<ConvertConfig, String> {
+    let output_datadir: PathBuf = clap_utils::parse_required(cli_args, "output-datadir")?;
+
+    let mut output_client_config = client_config.clone();
+    output_client_config.set_data_dir(output_datadir.join(DEFAULT_BEACON_NODE_DIR));
+    output_client_config.freezer_db_path =
+        clap_utils::parse_optional(cli_args, "output-freezer-dir")?;
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "output-db-backend")? {
+        output_client_config.store.backend = backend;
+    }
+
+    for path in [
+        output_client_config.get_db_path(),
+        output_client_config.get_freezer_db_path(),
+    ] {
+        if path.exists() {
+            return Err(format!(
+                "Output database directory {} already exists",
+                path.display()
+            ));
+        }
+    }
+
+    let sample_interval = clap_utils::parse_required(cli_args, "sample-interval")?;
+    if sample_interval == 0 {
+        return Err("sample-interval must be greater than zero".into());
+    }
+
+    Ok(ConvertConfig {
+        output_client_config,
+        sample_interval,
+    })
+}
+
+/// Copy every entry of `column` from `source` into `destination`, returning the number copied.
+///
+/// The database backend recorded in the metadata column is replaced by that of `destination`.
+fn copy_column<E: EthSpec>(
+    source: &BeaconNodeBackend<E>,
+    destination: &BeaconNodeBackend<E>,
+    column: DBColumn,
+) -> Result<usize, Error> {
+    let mut count = 0;
+    let mut batch = vec![];
+    let mut batch_size = 0;
+
+    for res in source.iter_raw_entries(column) {
+        let (key, value) = res?;
+        count += 1;
+
+        if column == DBColumn::BeaconMeta && key == DATABASE_BACKEND_KEY.as_bytes() {
+            continue;
+        }
+
+        batch_size += key.len() + value.len();
+        batch.push(KeyValueStoreOp::PutKeyValue(
+            get_key_for_col(column.as_str(), &key),
+            value,
+        ));
+
+        if batch_size >= CONVERT_BATCH_SIZE_BYTES {
+            destination.do_atomically(std::mem::take(&mut batch))?;
+            batch_size = 0;
+        }
+    }
+    destination.do_atomically(batch)?;
+
+    if column == DBColumn::BeaconMeta {
+        destination.put(&DATABASE_BACKEND_KEY, &destination.backend())?;
+    }
+
+    Ok(count)
+}
+
+/// Check that `destination` holds `expected_count` entries in `column`, and that every
+/// `sample_interval`th of them has the same value in `source`.
+fn verify_column<E: EthSpec>(
+    source: &BeaconNodeBackend<E>,
+    destination: &BeaconNodeBackend<E>,
+    column: DBColumn,
+    expected_count: usize,
+    sample_interval: usize,
+) -> Result<(), Error> {
+    let mut count = 0;
+
+    for res in destination.iter_raw_entries(column) {
+        let (key, value) = res?;
+        count += 1;
+
+        if (count - 1) % sample_interval != 0
+            || (column == DBColumn::BeaconMeta && key == DATABASE_BACKEND_KEY.as_bytes())
+        {
+            continue;
+        }
+
+        if source.get_bytes(column.as_str(), &key)?.as_ref() != Some(&value) {
+            return Err(Error::DBError {
+                message: format!("value mismatch in column {:?} for key {:?}", column, key),
+            });
+        }
+    }
+
+    if count != expected_count {
+        return Err(Error::DBError {
+            message: format!(
+                "column {:?} has {} entries after conversion, expected {}",
+                column, count, expected_count
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn convert_db<E: EthSpec>(
+    convert_config: ConvertConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = runtime_context.eth2_config.spec.clone();
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+
+    let ConvertConfig {
+        output_client_config,
+        sample_interval,
+    } = convert_config;
+    let output_hot_path = output_client_config.get_db_path();
+    let output_cold_path = output_client_config.get_freezer_db_path();
+
+    let mut version = CURRENT_SCHEMA_VERSION;
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        |_, from, _| {
+            version = from;
+            Ok(())
+        },
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    info!(
+        log,
+        "Converting database";
+        "schema_version" => version.as_u64(),
+        "from_backend" => %db.hot_db.backend(),
+        "to_backend" => %output_client_config.store.backend,
+        "hot_path" => ?output_hot_path,
+        "freezer_path" => ?output_cold_path,
+    );
+
+    let output_hot_db =
+        BeaconNodeBackend::open(output_client_config.store.backend, &output_hot_path)?;
+    let output_cold_db =
+        BeaconNodeBackend::open(output_client_config.store.backend, &output_cold_path)?;
+
+    for (source, destination, name) in [
+        (&db.hot_db, &output_hot_db, "hot"),
+        (&db.cold_db, &output_cold_db, "freezer"),
+    ] {
+        for column in DBColumn::iter() {
+            let count = copy_column(source, destination, column)?;
+            verify_column(source, destination, column, count, sample_interval)?;
+
+            if count > 0 {
+                info!(
+                    log,
+                    "Copied column";
+                    "database" => name,
+                    "column" => column.as_str(),
+                    "entries" => count,
+                );
+            }
+        }
+        destination.sync()?;
+    }
+    drop(output_hot_db);
+    drop(output_cold_db);
+
+    // Open the converted database to check that its schema version and on-disk config were
+    // carried over.
+    let mut output_version = CURRENT_SCHEMA_VERSION;
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &output_hot_path,
+        &output_cold_path,
+        |_, from, _| {
+            output_version = from;
+            Ok(())
+        },
+        output_client_config.store,
+        spec,
+        log.clone(),
+    )?;
+
+    if output_version != version {
+        return Err(Error::DBError {
+            message: format!(
+                "converted database has schema version {}, expected {}",
+                output_version.as_u64(),
+                version.as_u64()
+            ),
+        });
+    }
+
+    info!(log, "Database conversion complete");
+
+    Ok(())
+}
+
+pub struct ChangeSprpConfig {
+    to: u64,
+    batch_size: usize,
+}
+
+fn parse_change_sprp_config(cli_args: &ArgMatches) -> Result<ChangeSprpConfig, String> {
+    let to = clap_utils::parse_required(cli_args, "to")?;
+    let batch_size = clap_utils::parse_required(cli_args, "batch-size")?;
+    if batch_size == 0 {
+        return Err("batch-size must be greater than zero".into());
+    }
+
+    Ok(ChangeSprpConfig { to, batch_size })
+}
+
+pub fn change_sprp<E: EthSpec>(
+    change_sprp_config: ChangeSprpConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = runtime_context.eth2_config.spec.clone();
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+
+    let db =
+        HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open_for_restore_point_migration(
+            &hot_path,
+            &cold_path,
+            |_, _, _| Ok(()),
+            client_config.store,
+            spec,
+            log,
+        )?;
+
+    db.migrate_slots_per_restore_point(change_sprp_config.to, change_sprp_config.batch_size)
+}
+
+pub struct ExportEraConfig {
+    output_dir: PathBuf,
+    start_era: Option<u64>,
+    end_era: Option<u64>,
+}
+
+fn parse_export_era_config(cli_args: &ArgMatches) -> Result<ExportEraConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    let start_era = clap_utils::parse_optional(cli_args, "start-era")?;
+    let end_era = clap_utils::parse_optional(cli_args, "end-era")?;
+
+    Ok(ExportEraConfig {
+        output_dir,
+        start_era,
+        end_era,
+    })
+}
+
+pub fn export_era<E: EthSpec>(
+    export_era_config: ExportEraConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = runtime_context.eth2_config.spec.clone();
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec,
+        log.clone(),
+    )?;
+
+    let output_dir = &export_era_config.output_dir;
+    fs::create_dir_all(output_dir).map_err(era::Error::Io)?;
+    let existing_eras = era::list_era_files(output_dir)?
+        .into_iter()
+        .map(|(era, _)| era)
+        .collect::<HashSet<_>>();
+
+    let eras = db
+        .exportable_eras()
+        .into_iter()
+        .filter(|era| {
+            export_era_config
+                .start_era
+                .map_or(true, |start_era| *era >= start_era)
+                && export_era_config
+                    .end_era
+                    .map_or(true, |end_era| *era <= end_era)
+        })
+        .collect::<Vec<_>>();
+
+    info!(
+        log,
+        "Exporting era files";
+        "first_era" => ?eras.first(),
+        "last_era" => ?eras.last(),
+        "output_dir" => ?output_dir,
+    );
+
+    for era in eras {
+        if existing_eras.contains(&era) {
+            info!(log, "Skipping existing era file"; "era" => era);
+            continue;
+        }
+        let path = db.export_era(era, output_dir)?;
+        info!(log, "Exported era file"; "era" => era, "path" => ?path);
+    }
+
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
     let client_config = parse_client_config(cli_args, &env)?;
@@ -304,6 +769,18 @@ pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, env: Environment<T>) -> Result
             inspect_db(inspect_config, client_config, &context, log)
         }
         ("prune_payloads", Some(_)) => prune_payloads(client_config, &context, log),
+        ("change_sprp", Some(cli_args)) => {
+            let change_sprp_config = parse_change_sprp_config(cli_args)?;
+            change_sprp(change_sprp_config, client_config, &context, log)
+        }
+        ("export_era", Some(cli_args)) => {
+            let export_era_config = parse_export_era_config(cli_args)?;
+            export_era(export_era_config, client_config, &context, log)
+        }
+        ("convert", Some(cli_args)) => {
+            let convert_config = parse_convert_config(cli_args, &client_config)?;
+            convert_db(convert_config, client_config, &context, log)
+        }
         _ => {
             return Err("Unknown subcommand, for help `lighthouse database_manager --help`".into())
         }
@@ -0,0 +1,36 @@
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use eth2_network_config::Eth2NetworkConfig;
+use std::fs::File;
+use std::path::PathBuf;
+use types::EthSpec;
+use validator_dir::StandardDepositDataJson;
+
+pub fn run<T: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(), String> {
+    let path: PathBuf = parse_required(matches, "deposit-data-json")?;
+
+    let eth2_network_config = match matches.value_of("network") {
+        Some(network) => Eth2NetworkConfig::constant(network)?
+            .ok_or_else(|| format!("Unknown network {}", network))?,
+        None => Eth2NetworkConfig::load(testnet_dir)?,
+    };
+    let spec = eth2_network_config.chain_spec::<T>()?;
+
+    let file = File::open(&path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+    let deposits: Vec<StandardDepositDataJson> = serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse deposit data JSON: {:?}", e))?;
+
+    if deposits.is_empty() {
+        return Err("The deposit data JSON contains no deposits".to_string());
+    }
+
+    for (i, deposit) in deposits.iter().enumerate() {
+        deposit
+            .verify(&spec)
+            .map_err(|e| format!("Deposit {} ({}) is invalid: {:?}", i, deposit.pubkey, e))?;
+    }
+
+    info!("All {} deposits are valid", deposits.len());
+
+    Ok(())
+}
@@ -0,0 +1,937 @@
+//! # Crawl Network
+//!
+//! Walks the discv5 DHT to take a census of the nodes on the network, optionally dialing them to
+//! collect their libp2p agent strings and `MetaData`.
+//!
+//! The report describes the fork digests of all discovered nodes, along with the IPv4/IPv6
+//! reachability, client diversity and subnet coverage of the nodes on the selected network. A
+//! node's subnets are taken from its `MetaData` if it was dialed successfully, otherwise from its
+//! ENR.
+//!
+//! Reachability is always reported for the IP addresses that nodes declare in their ENRs. When
+//! nodes are dialed, it is also reported for the addresses over which they accepted a connection.
+//!
+//! ## Examples
+//!
+//! Crawl mainnet for ten minutes, dialing nodes and writing a JSON report.
+//!
+//! ```ignore
+//! lcli crawl-network \
+//!     --network mainnet \
+//!     --duration 600 \
+//!     --dial \
+//!     --output /tmp/mainnet-census.json
+//! ```
+//!
+//! Write one CSV row per node discovered on a custom network.
+//!
+//! ```ignore
+//! lcli crawl-network \
+//!     --testnet-dir ~/my-testnet \
+//!     --format csv \
+//!     --output /tmp/census.csv
+//! ```
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2_network_config::Eth2NetworkConfig;
+use futures::stream::{FuturesUnordered, StreamExt};
+use lighthouse_network::discv5::{
+    enr::{CombinedKey, EnrBuilder, NodeId},
+    Discv5, Discv5ConfigBuilder, Discv5Event, IpMode,
+};
+use lighthouse_network::rpc::GoodbyeReason;
+use lighthouse_network::service::Network;
+use lighthouse_network::{
+    Context, Enr, EnrExt, Eth2Enr, NetworkConfig, NetworkEvent, NetworkGlobals, PeerId,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::File;
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use types::{BitVector, ChainSpec, Epoch, EthSpec, ForkContext, Hash256, Slot, Unsigned};
+
+/// The number of random lookups which are run concurrently whilst walking the DHT.
+const CONCURRENT_LOOKUPS: usize = 8;
+
+/// The crawl ends early once this many consecutive lookups have not discovered any new nodes.
+const MAX_IDLE_LOOKUPS: usize = 64;
+
+/// The time allowed for a dialed peer to identify itself and respond with its `MetaData`.
+const DIAL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The interval at which dialed peers are checked for completion.
+const DIAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The output format of the report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!("Unknown report format {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CrawlReport {
+    /// The fork digest of the network described by the summary, if known.
+    fork_digest: Option<String>,
+    summary: Summary,
+    nodes: Vec<CrawledNode>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Summary {
+    discovered_nodes: usize,
+    /// The number of discovered nodes per fork digest, including those without an `eth2` field.
+    fork_digests: BTreeMap<String, usize>,
+    /// The IP address families declared in the ENRs of nodes.
+    declared_reachability: Reachability,
+    dialed_nodes: usize,
+    connected_nodes: usize,
+    /// The IP address families over which dialed nodes accepted a connection, `None` if no nodes
+    /// were dialed.
+    dialed_reachability: Option<Reachability>,
+    /// The number of connected nodes per client.
+    clients: BTreeMap<String, usize>,
+    /// The number of nodes subscribed to each attestation subnet.
+    attnets: Vec<usize>,
+    /// The number of nodes subscribed to each sync committee subnet.
+    syncnets: Vec<usize>,
+}
+
+/// Counts of nodes by IP address family.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Reachability {
+    ipv4_only: usize,
+    ipv6_only: usize,
+    ipv4_and_ipv6: usize,
+    unreachable: usize,
+}
+
+impl Reachability {
+    fn count(&mut self, ipv4: bool, ipv6: bool) {
+        match (ipv4, ipv6) {
+            (true, true) => self.ipv4_and_ipv6 += 1,
+            (true, false) => self.ipv4_only += 1,
+            (false, true) => self.ipv6_only += 1,
+            (false, false) => self.unreachable += 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CrawledNode {
+    node_id: String,
+    peer_id: String,
+    seq: u64,
+    ip4: Option<Ipv4Addr>,
+    udp4: Option<u16>,
+    tcp4: Option<u16>,
+    ip6: Option<Ipv6Addr>,
+    udp6: Option<u16>,
+    tcp6: Option<u16>,
+    fork_digest: Option<String>,
+    next_fork_version: Option<String>,
+    next_fork_epoch: Option<Epoch>,
+    attnets: Vec<usize>,
+    syncnets: Vec<usize>,
+    /// `None` if the node was not dialed.
+    connected: Option<bool>,
+    client: Option<String>,
+    agent_string: Option<String>,
+}
+
+/// The information collected by dialing a node.
+#[derive(Debug, Default)]
+struct DialResult {
+    connected: bool,
+    /// Whether the node accepted a connection over IPv4.
+    connected_ipv4: bool,
+    /// Whether the node accepted a connection over IPv6.
+    connected_ipv6: bool,
+    client: Option<String>,
+    agent_string: Option<String>,
+    attnets: Option<Vec<usize>>,
+    syncnets: Option<Vec<usize>>,
+}
+
+pub fn run<T: EthSpec>(
+    env: Environment<T>,
+    testnet_dir: PathBuf,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let listen_address: IpAddr = parse_required(matches, "listen-address")?;
+    let port: u16 = parse_required(matches, "port")?;
+    let duration = Duration::from_secs(parse_required(matches, "duration")?);
+    let dial = matches.is_present("dial");
+    let dial_concurrency: usize = parse_required(matches, "dial-concurrency")?;
+    let output: PathBuf = parse_required(matches, "output")?;
+    let format: Format = parse_required(matches, "format")?;
+    let fork_digest: Option<String> = parse_optional(matches, "fork-digest")?;
+
+    let eth2_network_config = match matches.value_of("network") {
+        Some(network) => Eth2NetworkConfig::constant(network)?
+            .ok_or_else(|| format!("Unknown network {}", network))?,
+        None => Eth2NetworkConfig::load(testnet_dir)?,
+    };
+    let spec = eth2_network_config.chain_spec::<T>()?;
+
+    let mut boot_nodes = eth2_network_config.boot_enr.clone().unwrap_or_default();
+    if let Some(nodes) = matches.value_of("boot-nodes") {
+        for enr in nodes.split(',') {
+            boot_nodes.push(enr.parse().map_err(|_| format!("Invalid ENR: {}", enr))?);
+        }
+    }
+    if boot_nodes.is_empty() {
+        return Err("No boot nodes to start the crawl from, see --boot-nodes".to_string());
+    }
+
+    // The current fork digest of the network is only known if its genesis state is known.
+    let genesis = if eth2_network_config.beacon_state_is_known() {
+        let genesis_state = eth2_network_config.beacon_state::<T>()?;
+        Some((
+            current_slot(genesis_state.genesis_time(), &spec),
+            genesis_state.genesis_validators_root(),
+        ))
+    } else {
+        None
+    };
+
+    let fork_digest = match (fork_digest, genesis) {
+        (Some(digest), _) => Some(parse_fork_digest(&digest)?),
+        (None, Some((slot, genesis_validators_root))) => Some(
+            spec.enr_fork_id::<T>(slot, genesis_validators_root)
+                .fork_digest,
+        ),
+        (None, None) => None,
+    };
+    match fork_digest {
+        Some(digest) => info!(
+            "Reporting on nodes with fork digest {}",
+            digest_string(digest)
+        ),
+        None => warn!("Network genesis is unknown, reporting on nodes of all fork digests"),
+    }
+
+    let executor = env.core_context().executor;
+    let log = env.core_context().log().clone();
+    let listen_socket = SocketAddr::new(listen_address, port);
+
+    let report = env.runtime().block_on(async move {
+        let enrs = walk_dht(listen_socket, boot_nodes, duration).await?;
+        info!("Discovered {} nodes", enrs.len());
+
+        let dial_results = if dial {
+            let to_dial = enrs
+                .iter()
+                .filter(|enr| is_on_network(enr, fork_digest))
+                .filter(|enr| enr.tcp4().is_some() || enr.tcp6().is_some())
+                .cloned()
+                .collect::<Vec<_>>();
+            info!("Dialing {} nodes", to_dial.len());
+
+            let (slot, genesis_validators_root) =
+                genesis.unwrap_or((Slot::new(0), Hash256::zero()));
+            dial_nodes::<T>(
+                executor,
+                listen_address,
+                port,
+                to_dial,
+                dial_concurrency,
+                slot,
+                genesis_validators_root,
+                &spec,
+                &log,
+            )
+            .await?
+        } else {
+            HashMap::new()
+        };
+
+        Ok::<_, String>(build_report::<T>(enrs, dial_results, fork_digest))
+    })?;
+
+    info!(
+        "Found {} nodes on the network, connected to {} of {} dialed",
+        report.nodes.len(),
+        report.summary.connected_nodes,
+        report.summary.dialed_nodes
+    );
+
+    let mut file =
+        File::create(&output).map_err(|e| format!("Unable to create {:?}: {:?}", output, e))?;
+    match format {
+        Format::Json => serde_json::to_writer_pretty(&mut file, &report)
+            .map_err(|e| format!("Unable to write report to {:?}: {:?}", output, e)),
+        Format::Csv => write_csv(&mut file, &report.nodes)
+            .map_err(|e| format!("Unable to write report to {:?}: {:?}", output, e)),
+    }
+}
+
+/// Walks the DHT with random lookups, returning the latest ENR of every node discovered.
+async fn walk_dht(
+    listen_socket: SocketAddr,
+    boot_nodes: Vec<Enr>,
+    duration: Duration,
+) -> Result<Vec<Enr>, String> {
+    let local_key = CombinedKey::generate_secp256k1();
+    let local_enr = EnrBuilder::new("v4")
+        .build(&local_key)
+        .map_err(|e| format!("Unable to build local ENR: {:?}", e))?;
+
+    let mut discv5_config = Discv5ConfigBuilder::new().build();
+    if listen_socket.is_ipv6() {
+        discv5_config.ip_mode = IpMode::Ip6 {
+            enable_mapped_addresses: true,
+        };
+    }
+
+    let mut discv5: Discv5 = Discv5::new(local_enr, local_key, discv5_config)
+        .map_err(|e| format!("Unable to create discv5: {}", e))?;
+
+    let mut nodes: HashMap<NodeId, Enr> = HashMap::new();
+    for enr in boot_nodes {
+        if let Err(e) = discv5.add_enr(enr.clone()) {
+            warn!("Unable to add boot node {}: {}", enr, e);
+        }
+        nodes.insert(enr.node_id(), enr);
+    }
+
+    discv5
+        .start(listen_socket)
+        .await
+        .map_err(|e| format!("Unable to start discv5: {}", e))?;
+    let mut event_stream = discv5
+        .event_stream()
+        .await
+        .map_err(|e| format!("Unable to obtain discv5 event stream: {}", e))?;
+
+    let mut lookups = FuturesUnordered::new();
+    let mut idle_lookups = 0;
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+
+    loop {
+        while lookups.len() < CONCURRENT_LOOKUPS {
+            let known_nodes = nodes.len();
+            let lookup = discv5.find_node(NodeId::random());
+            lookups.push(async move { (known_nodes, lookup.await) });
+        }
+
+        tokio::select! {
+            _ = &mut deadline => {
+                info!("Crawl duration elapsed");
+                break;
+            }
+            Some((known_nodes, result)) = lookups.next() => {
+                match result {
+                    Ok(enrs) => {
+                        for enr in enrs {
+                            insert_latest(&mut nodes, enr);
+                        }
+                    }
+                    Err(e) => debug!("Lookup failed: {:?}", e),
+                }
+
+                if nodes.len() > known_nodes {
+                    idle_lookups = 0;
+                } else {
+                    idle_lookups += 1;
+                }
+                if idle_lookups >= MAX_IDLE_LOOKUPS {
+                    info!("No new nodes found in {} lookups, ending crawl", idle_lookups);
+                    break;
+                }
+            }
+            Some(event) = event_stream.recv() => {
+                if let Discv5Event::Discovered(enr) = event {
+                    insert_latest(&mut nodes, enr);
+                }
+            }
+        }
+    }
+
+    discv5.shutdown();
+    Ok(nodes.into_values().collect())
+}
+
+/// Dials `enrs`, collecting the agent strings and `MetaData` of the nodes which accept the
+/// connection.
+#[allow(clippy::too_many_arguments)]
+async fn dial_nodes<T: EthSpec>(
+    executor: task_executor::TaskExecutor,
+    listen_address: IpAddr,
+    port: u16,
+    enrs: Vec<Enr>,
+    concurrency: usize,
+    current_slot: Slot,
+    genesis_validators_root: Hash256,
+    spec: &ChainSpec,
+    log: &slog::Logger,
+) -> Result<HashMap<PeerId, DialResult>, String> {
+    // Use a throwaway network key rather than the key of any local beacon node.
+    let network_dir = tempfile::Builder::new()
+        .prefix("lcli-crawl-network")
+        .tempdir()
+        .map_err(|e| format!("Unable to create network directory: {:?}", e))?;
+
+    let mut config = NetworkConfig {
+        network_dir: network_dir.path().to_path_buf(),
+        disable_discovery: true,
+        upnp_enabled: false,
+        metrics_enabled: false,
+        topics: vec![],
+        target_peers: concurrency,
+        ..NetworkConfig::default()
+    };
+    match listen_address {
+        IpAddr::V4(addr) => config.set_ipv4_listening_address(addr, port, port),
+        IpAddr::V6(addr) => config.set_ipv6_listening_address(addr, port, port),
+    }
+
+    let context = Context {
+        config: &config,
+        enr_fork_id: spec.enr_fork_id::<T>(current_slot, genesis_validators_root),
+        fork_context: Arc::new(ForkContext::new::<T>(
+            current_slot,
+            genesis_validators_root,
+            spec,
+        )),
+        chain_spec: spec,
+        gossipsub_registry: None,
+    };
+    let (mut network, network_globals) = Network::<(), T>::new(executor, context, log)
+        .await
+        .map_err(|e| format!("Unable to start libp2p: {:?}", e))?;
+
+    let mut to_dial = enrs.into_iter().collect::<VecDeque<_>>();
+    let mut dialing: HashMap<PeerId, Instant> = HashMap::new();
+    let mut results: HashMap<PeerId, DialResult> = HashMap::new();
+    let mut poll_interval = tokio::time::interval(DIAL_POLL_INTERVAL);
+
+    loop {
+        while dialing.len() < concurrency {
+            match to_dial.pop_front() {
+                Some(enr) => {
+                    dialing.insert(enr.peer_id(), Instant::now());
+                    network.dial_enr(enr);
+                }
+                None => break,
+            }
+        }
+        if dialing.is_empty() {
+            break;
+        }
+
+        tokio::select! {
+            event = network.next_event() => match event {
+                NetworkEvent::PeerConnectedOutgoing(peer_id) if dialing.contains_key(&peer_id) => {
+                    results.entry(peer_id).or_default().connected = true;
+                    network.request_meta_data(peer_id);
+                }
+                NetworkEvent::PeerDisconnected(peer_id) => {
+                    if dialing.remove(&peer_id).is_some() {
+                        record_peer(&network_globals, peer_id, &mut results);
+                    }
+                }
+                _ => {}
+            },
+            _ = poll_interval.tick() => {
+                let complete = dialing
+                    .iter()
+                    .filter(|(peer_id, dialed_at)| {
+                        dialed_at.elapsed() >= DIAL_TIMEOUT || is_identified(&network_globals, peer_id)
+                    })
+                    .map(|(peer_id, _)| *peer_id)
+                    .collect::<Vec<_>>();
+                for peer_id in complete {
+                    dialing.remove(&peer_id);
+                    record_peer(&network_globals, peer_id, &mut results);
+                    network.disconnect_peer(peer_id, GoodbyeReason::ClientShutdown);
+                }
+                debug!("{} nodes left to dial, {} dialing", to_dial.len(), dialing.len());
+            }
+        }
+    }
+
+    // Shut libp2p down before its directory is removed.
+    drop(network);
+    drop(network_dir);
+
+    Ok(results)
+}
+
+/// Returns `true` if the peer has identified itself and responded with its `MetaData`.
+fn is_identified<T: EthSpec>(network_globals: &NetworkGlobals<T>, peer_id: &PeerId) -> bool {
+    network_globals
+        .peers
+        .read()
+        .peer_info(peer_id)
+        .map_or(false, |info| {
+            info.client().agent_string.is_some() && info.meta_data().is_some()
+        })
+}
+
+fn record_peer<T: EthSpec>(
+    network_globals: &NetworkGlobals<T>,
+    peer_id: PeerId,
+    results: &mut HashMap<PeerId, DialResult>,
+) {
+    let result = results.entry(peer_id).or_default();
+    if let Some(info) = network_globals.peers.read().peer_info(&peer_id) {
+        for ip in info.seen_ip_addresses() {
+            match ip {
+                IpAddr::V4(_) => result.connected_ipv4 = true,
+                IpAddr::V6(_) => result.connected_ipv6 = true,
+            }
+        }
+        let client = info.client();
+        if client.agent_string.is_some() {
+            result.client = Some(client.kind.as_ref().to_string());
+            result.agent_string = client.agent_string.clone();
+        }
+        if let Some(meta_data) = info.meta_data() {
+            result.attnets = Some(set_bits(meta_data.attnets()));
+            result.syncnets = meta_data.syncnets().ok().map(set_bits);
+        }
+    }
+}
+
+fn build_report<T: EthSpec>(
+    enrs: Vec<Enr>,
+    mut dial_results: HashMap<PeerId, DialResult>,
+    fork_digest: Option<[u8; 4]>,
+) -> CrawlReport {
+    let mut summary = Summary {
+        discovered_nodes: enrs.len(),
+        attnets: vec![0; T::SubnetBitfieldLength::to_usize()],
+        syncnets: vec![0; T::SyncCommitteeSubnetCount::to_usize()],
+        ..Summary::default()
+    };
+    let mut nodes = vec![];
+
+    for enr in enrs {
+        let eth2 = enr.eth2().ok();
+        *summary
+            .fork_digests
+            .entry(eth2.as_ref().map_or_else(
+                || "none".to_string(),
+                |fork_id| digest_string(fork_id.fork_digest),
+            ))
+            .or_default() += 1;
+
+        if !is_on_network(&enr, fork_digest) {
+            continue;
+        }
+
+        summary
+            .declared_reachability
+            .count(enr.ip4().is_some(), enr.ip6().is_some());
+
+        let dial_result = dial_results.remove(&enr.peer_id());
+        if let Some(result) = &dial_result {
+            summary.dialed_nodes += 1;
+            if result.connected {
+                summary.connected_nodes += 1;
+            }
+            summary
+                .dialed_reachability
+                .get_or_insert_with(Reachability::default)
+                .count(
+                    result.connected && result.connected_ipv4,
+                    result.connected && result.connected_ipv6,
+                );
+            if let Some(client) = &result.client {
+                *summary.clients.entry(client.clone()).or_default() += 1;
+            }
+        }
+
+        // Prefer the subnets in the `MetaData`, which may be more recent than those in the ENR.
+        let attnets = dial_result
+            .as_ref()
+            .and_then(|result| result.attnets.clone())
+            .or_else(|| enr.attestation_bitfield::<T>().ok().map(|b| set_bits(&b)))
+            .unwrap_or_default();
+        let syncnets = dial_result
+            .as_ref()
+            .and_then(|result| result.syncnets.clone())
+            .or_else(|| {
+                enr.sync_committee_bitfield::<T>()
+                    .ok()
+                    .map(|b| set_bits(&b))
+            })
+            .unwrap_or_default();
+        for &subnet in &attnets {
+            if let Some(count) = summary.attnets.get_mut(subnet) {
+                *count += 1;
+            }
+        }
+        for &subnet in &syncnets {
+            if let Some(count) = summary.syncnets.get_mut(subnet) {
+                *count += 1;
+            }
+        }
+
+        nodes.push(CrawledNode {
+            node_id: hex::encode(enr.node_id().raw()),
+            peer_id: enr.peer_id().to_string(),
+            seq: enr.seq(),
+            ip4: enr.ip4(),
+            udp4: enr.udp4(),
+            tcp4: enr.tcp4(),
+            ip6: enr.ip6(),
+            udp6: enr.udp6(),
+            tcp6: enr.tcp6(),
+            fork_digest: eth2
+                .as_ref()
+                .map(|fork_id| digest_string(fork_id.fork_digest)),
+            next_fork_version: eth2
+                .as_ref()
+                .map(|fork_id| digest_string(fork_id.next_fork_version)),
+            next_fork_epoch: eth2.map(|fork_id| fork_id.next_fork_epoch),
+            attnets,
+            syncnets,
+            connected: dial_result.as_ref().map(|result| result.connected),
+            client: dial_result
+                .as_ref()
+                .and_then(|result| result.client.clone()),
+            agent_string: dial_result.and_then(|result| result.agent_string),
+        });
+    }
+
+    CrawlReport {
+        fork_digest: fork_digest.map(digest_string),
+        summary,
+        nodes,
+    }
+}
+
+/// Writes one row per node, with subnets separated by semicolons.
+fn write_csv<W: Write>(writer: &mut W, nodes: &[CrawledNode]) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "node_id,peer_id,seq,ip4,udp4,tcp4,ip6,udp6,tcp6,fork_digest,next_fork_version,\
+        next_fork_epoch,attnets,syncnets,connected,client,agent_string"
+    )?;
+
+    fn field<T: ToString>(value: &Option<T>) -> String {
+        value.as_ref().map(ToString::to_string).unwrap_or_default()
+    }
+    fn subnets(subnets: &[usize]) -> String {
+        subnets
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    for node in nodes {
+        // Agent strings are chosen by the remote node, so must be quoted.
+        let agent_string = field(&node.agent_string).replace('"', "\"\"");
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},\"{}\"",
+            node.node_id,
+            node.peer_id,
+            node.seq,
+            field(&node.ip4),
+            field(&node.udp4),
+            field(&node.tcp4),
+            field(&node.ip6),
+            field(&node.udp6),
+            field(&node.tcp6),
+            field(&node.fork_digest),
+            field(&node.next_fork_version),
+            field(&node.next_fork_epoch),
+            subnets(&node.attnets),
+            subnets(&node.syncnets),
+            field(&node.connected),
+            field(&node.client),
+            agent_string,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Inserts `enr`, unless a more recent ENR of the same node is already known.
+fn insert_latest(nodes: &mut HashMap<NodeId, Enr>, enr: Enr) {
+    match nodes.get(&enr.node_id()) {
+        Some(known) if known.seq() >= enr.seq() => {}
+        _ => {
+            nodes.insert(enr.node_id(), enr);
+        }
+    }
+}
+
+/// Returns `true` if `enr` has an `eth2` field matching `fork_digest`, or any `eth2` field if
+/// there is no `fork_digest`.
+fn is_on_network(enr: &Enr, fork_digest: Option<[u8; 4]>) -> bool {
+    enr.eth2().map_or(false, |fork_id| {
+        fork_digest.map_or(true, |digest| fork_id.fork_digest == digest)
+    })
+}
+
+fn set_bits<N: Unsigned + Clone>(bitfield: &BitVector<N>) -> Vec<usize> {
+    bitfield
+        .iter()
+        .enumerate()
+        .filter_map(|(i, bit)| bit.then_some(i))
+        .collect()
+}
+
+fn current_slot(genesis_time: u64, spec: &ChainSpec) -> Slot {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Slot::new(now.saturating_sub(genesis_time) / spec.seconds_per_slot)
+}
+
+fn parse_fork_digest(digest: &str) -> Result<[u8; 4], String> {
+    let bytes = hex::decode(digest.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid fork digest {}: {:?}", digest, e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("Fork digest {} is not 4 bytes", digest))
+}
+
+fn digest_string(digest: [u8; 4]) -> String {
+    format!("0x{}", hex::encode(digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssz::Encode;
+    use types::{EnrForkId, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    const FORK_DIGEST: [u8; 4] = [1, 2, 3, 4];
+    const OTHER_FORK_DIGEST: [u8; 4] = [9, 9, 9, 9];
+
+    /// Builds an ENR declaring the given addresses, fork digest and attestation subnets.
+    fn build_enr(
+        ip4: Option<Ipv4Addr>,
+        ip6: Option<Ipv6Addr>,
+        fork_digest: Option<[u8; 4]>,
+        attnets: &[usize],
+    ) -> (CombinedKey, Enr) {
+        let key = CombinedKey::generate_secp256k1();
+        let mut builder = EnrBuilder::new("v4");
+        if let Some(ip) = ip4 {
+            builder.ip4(ip).tcp4(9000);
+        }
+        if let Some(ip) = ip6 {
+            builder.ip6(ip).tcp6(9000);
+        }
+        if let Some(fork_digest) = fork_digest {
+            let fork_id = EnrForkId {
+                fork_digest,
+                next_fork_version: [0; 4],
+                next_fork_epoch: Epoch::new(0),
+            };
+            builder.add_value("eth2", &fork_id.as_ssz_bytes());
+        }
+        if !attnets.is_empty() {
+            let mut bitfield = BitVector::<<E as EthSpec>::SubnetBitfieldLength>::new();
+            for &subnet in attnets {
+                bitfield.set(subnet, true).unwrap();
+            }
+            builder.add_value("attnets", &bitfield.as_ssz_bytes());
+        }
+        let enr = builder.build(&key).unwrap();
+        (key, enr)
+    }
+
+    fn crawled_node(attnets: Vec<usize>, agent_string: Option<&str>) -> CrawledNode {
+        CrawledNode {
+            node_id: "node".to_string(),
+            peer_id: "peer".to_string(),
+            seq: 1,
+            ip4: Some(Ipv4Addr::LOCALHOST),
+            udp4: Some(9000),
+            tcp4: Some(9000),
+            ip6: None,
+            udp6: None,
+            tcp6: None,
+            fork_digest: Some(digest_string(FORK_DIGEST)),
+            next_fork_version: None,
+            next_fork_epoch: None,
+            attnets,
+            syncnets: vec![],
+            connected: agent_string.map(|_| true),
+            client: agent_string.map(|_| "Lighthouse".to_string()),
+            agent_string: agent_string.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn report() {
+        let ip4 = Some(Ipv4Addr::new(1, 2, 3, 4));
+        let ip6 = Some(Ipv6Addr::LOCALHOST);
+        let (_, connected) = build_enr(ip4, None, Some(FORK_DIGEST), &[1, 5]);
+        let (_, unconnected) = build_enr(ip4, ip6, Some(FORK_DIGEST), &[]);
+        let (_, undialed) = build_enr(None, ip6, Some(FORK_DIGEST), &[3]);
+        let (_, other_network) = build_enr(ip4, None, Some(OTHER_FORK_DIGEST), &[]);
+        let (_, no_eth2) = build_enr(ip4, None, None, &[]);
+        let enrs = vec![
+            connected.clone(),
+            unconnected.clone(),
+            undialed.clone(),
+            other_network,
+            no_eth2,
+        ];
+
+        let mut dial_results = HashMap::new();
+        dial_results.insert(
+            connected.peer_id(),
+            DialResult {
+                connected: true,
+                connected_ipv4: true,
+                client: Some("Lighthouse".to_string()),
+                agent_string: Some("Lighthouse/v4.0.0".to_string()),
+                attnets: Some(vec![2]),
+                ..DialResult::default()
+            },
+        );
+        dial_results.insert(unconnected.peer_id(), DialResult::default());
+
+        let report = build_report::<E>(enrs.clone(), dial_results, Some(FORK_DIGEST));
+        let summary = &report.summary;
+        assert_eq!(report.fork_digest, Some("0x01020304".to_string()));
+        assert_eq!(summary.discovered_nodes, 5);
+        assert_eq!(
+            summary.fork_digests.clone().into_iter().collect::<Vec<_>>(),
+            vec![
+                ("0x01020304".to_string(), 3),
+                ("0x09090909".to_string(), 1),
+                ("none".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            summary.declared_reachability,
+            Reachability {
+                ipv4_only: 1,
+                ipv6_only: 1,
+                ipv4_and_ipv6: 1,
+                unreachable: 0,
+            }
+        );
+        assert_eq!(summary.dialed_nodes, 2);
+        assert_eq!(summary.connected_nodes, 1);
+        assert_eq!(
+            summary.dialed_reachability,
+            Some(Reachability {
+                ipv4_only: 1,
+                unreachable: 1,
+                ..Reachability::default()
+            })
+        );
+        assert_eq!(summary.clients.get("Lighthouse"), Some(&1));
+        assert_eq!(summary.clients.len(), 1);
+
+        // The subnets of a connected node are taken from its `MetaData` rather than its ENR.
+        let mut attnets = vec![0; summary.attnets.len()];
+        attnets[2] = 1;
+        attnets[3] = 1;
+        assert_eq!(summary.attnets, attnets);
+
+        assert_eq!(report.nodes.len(), 3);
+        let node = |enr: &Enr| {
+            report
+                .nodes
+                .iter()
+                .find(|node| node.peer_id == enr.peer_id().to_string())
+                .unwrap()
+        };
+        assert_eq!(node(&connected).connected, Some(true));
+        assert_eq!(node(&connected).attnets, vec![2]);
+        assert_eq!(
+            node(&connected).agent_string.as_deref(),
+            Some("Lighthouse/v4.0.0")
+        );
+        assert_eq!(node(&unconnected).connected, Some(false));
+        assert_eq!(node(&undialed).connected, None);
+        assert_eq!(node(&undialed).attnets, vec![3]);
+
+        // Without a fork digest, every node with an `eth2` field is reported.
+        let report = build_report::<E>(enrs, HashMap::new(), None);
+        assert_eq!(report.fork_digest, None);
+        assert_eq!(report.nodes.len(), 4);
+        assert_eq!(report.summary.dialed_nodes, 0);
+        assert_eq!(report.summary.dialed_reachability, None);
+    }
+
+    #[test]
+    fn csv_quoting() {
+        let nodes = vec![
+            crawled_node(vec![1, 5], Some("Lighthouse/v4.0.0 \"quoted\", with comma")),
+            crawled_node(vec![], None),
+        ];
+        let mut csv = vec![];
+        write_csv(&mut csv, &nodes).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("node_id,peer_id,"));
+        assert_eq!(
+            lines[1],
+            "node,peer,1,127.0.0.1,9000,9000,,,,0x01020304,,,1;5,,true,Lighthouse,\
+            \"Lighthouse/v4.0.0 \"\"quoted\"\", with comma\""
+        );
+        assert_eq!(
+            lines[2],
+            "node,peer,1,127.0.0.1,9000,9000,,,,0x01020304,,,,,,,\"\""
+        );
+    }
+
+    #[test]
+    fn insert_latest_keeps_highest_seq() {
+        let (key, enr) = build_enr(Some(Ipv4Addr::LOCALHOST), None, Some(FORK_DIGEST), &[]);
+        let mut updated = enr.clone();
+        updated.set_seq(enr.seq() + 1, &key).unwrap();
+
+        let mut nodes = HashMap::new();
+        insert_latest(&mut nodes, enr.clone());
+        assert_eq!(nodes[&enr.node_id()].seq(), enr.seq());
+        insert_latest(&mut nodes, updated.clone());
+        assert_eq!(nodes[&enr.node_id()].seq(), updated.seq());
+        insert_latest(&mut nodes, enr.clone());
+        assert_eq!(nodes[&enr.node_id()].seq(), updated.seq());
+        assert_eq!(nodes.len(), 1);
+
+        let (_, other) = build_enr(Some(Ipv4Addr::LOCALHOST), None, Some(FORK_DIGEST), &[]);
+        insert_latest(&mut nodes, other);
+        assert_eq!(nodes.len(), 2);
+    }
+
+    #[test]
+    fn fork_digest_parsing() {
+        assert_eq!(parse_fork_digest("0x01020304"), Ok(FORK_DIGEST));
+        assert_eq!(parse_fork_digest("01020304"), Ok(FORK_DIGEST));
+        assert!(parse_fork_digest("0x010203").is_err());
+        assert!(parse_fork_digest("0x0102030405").is_err());
+        assert!(parse_fork_digest("0xzz020304").is_err());
+        assert!(parse_fork_digest("").is_err());
+        assert_eq!(digest_string(FORK_DIGEST), "0x01020304");
+    }
+}
@@ -3,6 +3,8 @@ extern crate log;
 mod block_root;
 mod change_genesis_time;
 mod check_deposit_data;
+mod check_deposit_data_json;
+mod crawl_network;
 mod create_payload_header;
 mod deploy_deposit_contract;
 mod eth1_genesis;
@@ -638,6 +640,33 @@ fn main() {
                         ),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("check-deposit-data-json")
+                .about(
+                    "Checks the integrity of a `deposit_data-*.json` file, as produced by the \
+                    staking-deposit-cli or `lighthouse account validator create`, against the \
+                    selected network.",
+                )
+                .arg(
+                    Arg::with_name("deposit-data-json")
+                        .index(1)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to the deposit data JSON file."),
+                )
+                .arg(
+                    Arg::with_name("network")
+                        .long("network")
+                        .value_name("NETWORK")
+                        .takes_value(true)
+                        .possible_values(eth2_network_config::HARDCODED_NET_NAMES)
+                        .help(
+                            "Check against a built-in network rather than the one in \
+                            --testnet-dir.",
+                        ),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("generate-bootnode-enr")
                 .about("Generates an ENR address to be used as a pre-genesis boot node.")
@@ -767,6 +796,100 @@ fn main() {
                         .help("Number of repeat runs, useful for benchmarking."),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("crawl-network")
+                .about(
+                    "Walks the discv5 DHT and writes a report of the fork digests, IPv4/IPv6 \
+                    reachability, client diversity and subnet coverage of the nodes found.",
+                )
+                .arg(
+                    Arg::with_name("network")
+                        .long("network")
+                        .value_name("NETWORK")
+                        .takes_value(true)
+                        .possible_values(eth2_network_config::HARDCODED_NET_NAMES)
+                        .help("Crawl a built-in network rather than the one in --testnet-dir."),
+                )
+                .arg(
+                    Arg::with_name("boot-nodes")
+                        .long("boot-nodes")
+                        .allow_hyphen_values(true)
+                        .value_name("ENR-LIST")
+                        .takes_value(true)
+                        .help(
+                            "One or more comma-delimited ENRs to start the crawl from, in \
+                            addition to the boot nodes of the network.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("listen-address")
+                        .long("listen-address")
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .default_value("0.0.0.0")
+                        .help("The address to listen on. Use an IPv6 address to crawl both IPv4 \
+                            and IPv6 nodes."),
+                )
+                .arg(
+                    Arg::with_name("port")
+                        .long("port")
+                        .value_name("PORT")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("The UDP port used for discovery and the TCP port used to dial \
+                            nodes. Defaults to any unused port."),
+                )
+                .arg(
+                    Arg::with_name("duration")
+                        .long("duration")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .default_value("300")
+                        .help("The maximum time spent walking the DHT. The walk ends sooner if \
+                            no new nodes are being found."),
+                )
+                .arg(
+                    Arg::with_name("fork-digest")
+                        .long("fork-digest")
+                        .value_name("HEX")
+                        .takes_value(true)
+                        .help("Only report on and dial nodes with this fork digest. Defaults to \
+                            the current fork digest of the network, if its genesis is known."),
+                )
+                .arg(
+                    Arg::with_name("dial")
+                        .long("dial")
+                        .takes_value(false)
+                        .help("Dial the nodes found to collect their agent strings, MetaData and the \
+                            IP address families over which they accept connections."),
+                )
+                .arg(
+                    Arg::with_name("dial-concurrency")
+                        .long("dial-concurrency")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .default_value("50")
+                        .help("The maximum number of nodes dialed at once."),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The file to write the report to."),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .takes_value(true)
+                        .possible_values(&["json", "csv"])
+                        .default_value("json")
+                        .help("The format of the report. A JSON report includes a summary, whilst \
+                            a CSV report has one row per node."),
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -849,6 +972,10 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run new_testnet command: {}", e)),
         ("check-deposit-data", Some(matches)) => check_deposit_data::run::<T>(matches)
             .map_err(|e| format!("Failed to run check-deposit-data command: {}", e)),
+        ("check-deposit-data-json", Some(matches)) => {
+            check_deposit_data_json::run::<T>(testnet_dir, matches)
+                .map_err(|e| format!("Failed to run check-deposit-data-json command: {}", e))
+        }
         ("generate-bootnode-enr", Some(matches)) => generate_bootnode_enr::run::<T>(matches)
             .map_err(|e| format!("Failed to run generate-bootnode-enr command: {}", e)),
         ("insecure-validators", Some(matches)) => insecure_validators::run(matches)
@@ -857,6 +984,8 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run indexed-attestations command: {}", e)),
         ("block-root", Some(matches)) => block_root::run::<T>(env, matches)
             .map_err(|e| format!("Failed to run block-root command: {}", e)),
+        ("crawl-network", Some(matches)) => crawl_network::run::<T>(env, testnet_dir, matches)
+            .map_err(|e| format!("Failed to run crawl-network command: {}", e)),
         (other, _) => Err(format!("Unknown subcommand {}. See --help.", other)),
     }
 }
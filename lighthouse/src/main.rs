@@ -315,6 +315,7 @@ fn main() {
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
         .subcommand(database_manager::cli_app())
+        .subcommand(validator_manager::cli_app())
         .get_matches();
 
     // Configure the allocator early in the process, before it has the chance to use the default values for
@@ -551,6 +552,15 @@ fn run<E: EthSpec>(
         return Ok(());
     }
 
+    if let Some(sub_matches) = matches.subcommand_matches(validator_manager::CMD) {
+        eprintln!("Running validator manager for {} network", network_name);
+        // Pass the entire `environment` to the validator manager so it can run blocking operations.
+        validator_manager::run(sub_matches, environment)?;
+
+        // Exit as soon as validator manager returns control.
+        return Ok(());
+    }
+
     info!(log, "Lighthouse started"; "version" => VERSION);
     info!(
         log,
@@ -1,8 +1,10 @@
 use account_manager::{
     validator::{
+        bls_to_execution_change::{self, CMD as BLS_TO_EXECUTION_CHANGE_CMD},
         create::*,
         import::{self, CMD as IMPORT_CMD},
         modify::{ALL, CMD as MODIFY_CMD, DISABLE, ENABLE, PUBKEY_FLAG},
+        recover::{CMD as RECOVER_CMD, MNEMONIC_FLAG},
         CMD as VALIDATOR_CMD,
     },
     wallet::{
@@ -13,10 +15,13 @@ use account_manager::{
     CMD as ACCOUNT_CMD, WALLETS_DIR_FLAG, *,
 };
 use account_utils::{
-    eth2_keystore::KeystoreBuilder,
+    eth2_keystore::{keypair_from_secret, KeystoreBuilder},
+    eth2_wallet::{bip39::Seed, recover_validator_secret_from_mnemonic, KeyType},
+    random_mnemonic,
     validator_definitions::{SigningDefinition, ValidatorDefinition, ValidatorDefinitions},
     ZeroizeString,
 };
+use eth2_network_config::Eth2NetworkConfig;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 use std::env;
 use std::fs::{self, File};
@@ -25,8 +30,11 @@ use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Output, Stdio};
 use std::str::from_utf8;
 use tempfile::{tempdir, TempDir};
-use types::{Keypair, PublicKey};
-use validator_dir::ValidatorDir;
+use types::{
+    Address, ChainSpec, Domain, Epoch, Hash256, Keypair, PublicKey, SignedBlsToExecutionChange,
+    SignedRoot, Validator,
+};
+use validator_dir::{StandardDepositDataJson, ValidatorDir};
 
 // TODO: create tests for the `lighthouse account validator deposit` command. This involves getting
 // access to an IPC endpoint during testing or adding support for deposit submission via HTTP and
@@ -403,6 +411,108 @@ fn validator_create() {
     assert_eq!(dir_validator_count(validator_dir.path()), 6);
 }
 
+/// Reads and verifies a deposit data JSON file, returning the pubkeys of the deposits.
+fn read_deposit_data_json(path: &Path, amount: u64) -> Vec<String> {
+    let spec = ChainSpec::mainnet();
+    let deposits: Vec<StandardDepositDataJson> =
+        serde_json::from_reader(File::open(path).unwrap()).unwrap();
+
+    deposits
+        .iter()
+        .map(|deposit| {
+            assert_eq!(deposit.amount, amount);
+            assert_eq!(deposit.network_name, "mainnet");
+            deposit.verify(&spec).unwrap();
+            deposit.pubkey.as_hex_string()
+        })
+        .collect()
+}
+
+#[test]
+fn validator_create_deposit_data_json() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let json_path = base_dir.path().join("deposit_data.json");
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let mut cmd = validator_cmd();
+    cmd.arg(format!("--{}", VALIDATOR_DIR_FLAG))
+        .arg(validator_dir.path().as_os_str())
+        .arg(CREATE_CMD)
+        .arg(format!("--{}", WALLETS_DIR_FLAG))
+        .arg(wallet.base_dir().into_os_string())
+        .arg(format!("--{}", WALLET_NAME_FLAG))
+        .arg(&wallet.name)
+        .arg(format!("--{}", WALLET_PASSWORD_FLAG))
+        .arg(wallet.password_path().into_os_string())
+        .arg(format!("--{}", SECRETS_DIR_FLAG))
+        .arg(secrets_dir.path().as_os_str())
+        .arg(format!("--{}", COUNT_FLAG))
+        .arg("2")
+        .arg(format!("--{}", DEPOSIT_DATA_JSON_FLAG))
+        .arg(json_path.as_os_str());
+
+    let output = output_result(&mut cmd).unwrap();
+    let stdout = from_utf8(&output.stdout).unwrap();
+
+    let pubkeys = read_deposit_data_json(&json_path, 32_000_000_000);
+    assert_eq!(pubkeys.len(), 2);
+    for pubkey in pubkeys {
+        assert!(stdout.contains(&pubkey), "{} not in {}", pubkey, stdout);
+    }
+
+    // An existing deposit data file must not be overwritten.
+    output_result(&mut cmd).unwrap_err();
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+}
+
+#[test]
+fn validator_recover_deposit_data_json() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let json_path = base_dir.path().join("deposit_data.json");
+
+    let mnemonic = random_mnemonic();
+    let mnemonic_path = base_dir.path().join("mnemonic");
+    fs::write(&mnemonic_path, mnemonic.phrase()).unwrap();
+
+    output_result(
+        validator_cmd()
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(RECOVER_CMD)
+            .arg(format!("--{}", MNEMONIC_FLAG))
+            .arg(mnemonic_path.as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str())
+            .arg(format!("--{}", COUNT_FLAG))
+            .arg("2")
+            .arg(format!("--{}", DEPOSIT_GWEI_FLAG))
+            .arg("1000000000")
+            .arg(format!("--{}", DEPOSIT_DATA_JSON_FLAG))
+            .arg(json_path.as_os_str()),
+    )
+    .unwrap();
+
+    let seed = Seed::new(&mnemonic, "");
+    let expected = (0..2)
+        .map(|index| {
+            let (secret, _) =
+                recover_validator_secret_from_mnemonic(seed.as_bytes(), index, KeyType::Voting)
+                    .unwrap();
+            keypair_from_secret(secret.as_bytes())
+                .unwrap()
+                .pk
+                .as_hex_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(read_deposit_data_json(&json_path, 1_000_000_000), expected);
+}
+
 #[test]
 fn validator_import_launchpad() {
     const PASSWORD: &str = "cats";
@@ -501,6 +611,7 @@ fn validator_import_launchpad() {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            voting_keystore_password_source: None,
         },
     };
 
@@ -623,6 +734,7 @@ fn validator_import_launchpad_no_password_then_add_password() {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: None,
+            voting_keystore_password_source: None,
         },
     };
 
@@ -649,6 +761,7 @@ fn validator_import_launchpad_no_password_then_add_password() {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            voting_keystore_password_source: None,
         },
     };
 
@@ -750,6 +863,7 @@ fn validator_import_launchpad_password_file() {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            voting_keystore_password_source: None,
         },
     };
 
@@ -769,3 +883,125 @@ fn check_slashing_protection(validator_dir: &TempDir, pubkeys: impl Iterator<Ite
             .unwrap();
     }
 }
+
+#[test]
+fn validator_bls_to_execution_change() {
+    let spec = ChainSpec::mainnet();
+    let dir = tempdir().unwrap();
+    let mnemonic = random_mnemonic();
+    let mnemonic_path = dir.path().join("mnemonic");
+    fs::write(&mnemonic_path, mnemonic.phrase()).unwrap();
+    let seed = Seed::new(&mnemonic, "");
+
+    let derive = |index: u32, key_type: KeyType| -> Keypair {
+        let (secret, _) =
+            recover_validator_secret_from_mnemonic(seed.as_bytes(), index, key_type).unwrap();
+        keypair_from_secret(secret.as_bytes()).unwrap()
+    };
+    let validator_json = |index: u64, key_index: u32, withdrawal_credentials: Hash256| {
+        let validator = Validator {
+            pubkey: derive(key_index, KeyType::Voting).pk.compress(),
+            withdrawal_credentials,
+            effective_balance: spec.max_effective_balance,
+            slashed: false,
+            activation_eligibility_epoch: Epoch::new(0),
+            activation_epoch: Epoch::new(0),
+            exit_epoch: spec.far_future_epoch,
+            withdrawable_epoch: spec.far_future_epoch,
+        };
+        serde_json::json!({
+            "index": index.to_string(),
+            "balance": spec.max_effective_balance.to_string(),
+            "status": "active_ongoing",
+            "validator": validator,
+        })
+    };
+
+    // Key index 0 has BLS credentials, key index 1 has already changed to an execution address
+    // and key index 2 is not in the registry.
+    let withdrawal_keypair = derive(0, KeyType::Withdrawal);
+    let bls_credentials = Hash256::from_slice(&bls::get_withdrawal_credentials(
+        &withdrawal_keypair.pk,
+        spec.bls_withdrawal_prefix_byte,
+    ));
+    let eth1_credentials = Hash256::repeat_byte(0x01);
+    let validators = serde_json::json!({
+        "data": [
+            validator_json(5, 0, bls_credentials),
+            validator_json(6, 1, eth1_credentials),
+        ]
+    });
+    let validators_path = dir.path().join("validators.json");
+    fs::write(&validators_path, validators.to_string()).unwrap();
+
+    let execution_address = Address::repeat_byte(0x42);
+    let output_path = dir.path().join("changes.json");
+    output_result(
+        validator_cmd()
+            .arg(BLS_TO_EXECUTION_CHANGE_CMD)
+            .arg("--mnemonic-path")
+            .arg(mnemonic_path.as_os_str())
+            .arg(format!("--{}", COUNT_FLAG))
+            .arg("3")
+            .arg(format!(
+                "--{}",
+                bls_to_execution_change::EXECUTION_ADDRESS_FLAG
+            ))
+            .arg(format!("{:?}", execution_address))
+            .arg(format!(
+                "--{}",
+                bls_to_execution_change::VALIDATORS_FILE_FLAG
+            ))
+            .arg(validators_path.as_os_str())
+            .arg(format!("--{}", bls_to_execution_change::OUTPUT_PATH_FLAG))
+            .arg(output_path.as_os_str()),
+    )
+    .unwrap();
+
+    let changes: Vec<SignedBlsToExecutionChange> =
+        serde_json::from_reader(File::open(&output_path).unwrap()).unwrap();
+    assert_eq!(changes.len(), 1);
+    let change = &changes[0];
+    assert_eq!(change.message.validator_index, 5);
+    assert_eq!(change.message.to_execution_address, execution_address);
+    assert_eq!(
+        change.message.from_bls_pubkey,
+        withdrawal_keypair.pk.compress()
+    );
+
+    let genesis_validators_root = Eth2NetworkConfig::constant("mainnet")
+        .unwrap()
+        .unwrap()
+        .beacon_state::<types::MainnetEthSpec>()
+        .unwrap()
+        .genesis_validators_root();
+    let domain = spec.compute_domain(
+        Domain::BlsToExecutionChange,
+        spec.genesis_fork_version,
+        genesis_validators_root,
+    );
+    assert!(change
+        .signature
+        .verify(&withdrawal_keypair.pk, change.message.signing_root(domain)));
+
+    // The output file must not be overwritten.
+    output_result(
+        validator_cmd()
+            .arg(BLS_TO_EXECUTION_CHANGE_CMD)
+            .arg("--mnemonic-path")
+            .arg(mnemonic_path.as_os_str())
+            .arg(format!(
+                "--{}",
+                bls_to_execution_change::EXECUTION_ADDRESS_FLAG
+            ))
+            .arg(format!("{:?}", execution_address))
+            .arg(format!(
+                "--{}",
+                bls_to_execution_change::VALIDATORS_FILE_FLAG
+            ))
+            .arg(validators_path.as_os_str())
+            .arg(format!("--{}", bls_to_execution_change::OUTPUT_PATH_FLAG))
+            .arg(output_path.as_os_str()),
+    )
+    .unwrap_err();
+}
@@ -1,9 +1,10 @@
-use beacon_node::ClientConfig as Config;
+use beacon_node::{ClientConfig as Config, ClientGenesis};
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
 };
+use beacon_node::beacon_chain::store::config::DatabaseBackend;
 use eth1::Eth1Endpoint;
 use lighthouse_network::PeerId;
 use std::fs::File;
@@ -168,6 +169,24 @@ fn checkpoint_sync_url_timeout_flag() {
         });
 }
 
+#[test]
+fn checkpoint_state_without_block_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let state_path = dir.path().join("state.ssz");
+    File::create(&state_path)
+        .and_then(|mut file| file.write_all(&[1, 2, 3]))
+        .expect("Unable to write state file");
+    CommandLineTest::new()
+        .flag("checkpoint-state", state_path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::WeakSubjSszState {
+                anchor_state_bytes, ..
+            } => assert_eq!(anchor_state_bytes, &[1, 2, 3]),
+            genesis => panic!("unexpected genesis: {:?}", genesis),
+        });
+}
+
 #[test]
 fn checkpoint_sync_url_timeout_default() {
     CommandLineTest::new()
@@ -429,8 +448,6 @@ fn eth1_cache_follow_distance_manual() {
 fn run_merge_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -460,13 +477,68 @@ fn run_merge_execution_endpoints_flag_test(flag: &str) {
         .run_with_zero_port()
         .with_config(|config| {
             let config = config.execution_layer.as_ref().unwrap();
-            assert_eq!(config.execution_endpoints.len(), 1);
             assert_eq!(
-                config.execution_endpoints[0],
-                SensitiveUrl::parse(&urls[0]).unwrap()
+                config.execution_endpoints,
+                urls.iter()
+                    .map(|url| SensitiveUrl::parse(url).unwrap())
+                    .collect::<Vec<_>>()
             );
-            // Only the first secret file should be used.
-            assert_eq!(config.secret_files, vec![jwts[0].clone()]);
+            // Each endpoint has its own secret file.
+            assert_eq!(config.secret_files, jwts);
+            assert_eq!(config.execution_quorum, None);
+        });
+}
+#[test]
+fn execution_endpoints_shared_jwt_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwt_path = dir.path().join("jwt-file");
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:9551/"),
+        )
+        .flag("execution-jwt", jwt_path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.execution_endpoints.len(), 2);
+            assert_eq!(config.secret_files, vec![jwt_path.clone()]);
+        });
+}
+#[test]
+#[should_panic]
+fn execution_endpoints_mismatched_jwts_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwts_arg = format!(
+        "{},{}",
+        dir.path().join("jwt-0").display(),
+        dir.path().join("jwt-1").display()
+    );
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:9551/,http://localhost:10551/"),
+        )
+        .flag("execution-jwt", Some(&jwts_arg))
+        .run_with_zero_port();
+}
+#[test]
+fn execution_quorum_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:9551/"),
+        )
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag("execution-quorum", Some("2"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.execution_quorum, Some(2));
         });
 }
 #[test]
@@ -610,9 +682,7 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -1137,6 +1207,87 @@ fn private_flag() {
         .with_config(|config| assert!(config.network.private));
 }
 #[test]
+fn network_quic_port_flag_over_ipv4() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.listen_addrs().quic_enabled()));
+
+    let port = unused_udp4_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("quic-port", Some(port.to_string().as_str()))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .network
+                    .listen_addrs()
+                    .v4()
+                    .map(|listen_addr| listen_addr.quic_port),
+                Some(Some(port))
+            );
+        });
+}
+#[test]
+fn network_quic_port_flags_over_ipv4_and_ipv6() {
+    let quic4_port = unused_udp4_port().expect("Unable to find unused port.");
+    let quic6_port = unused_udp6_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("listen-address", Some("127.0.0.1"))
+        .flag("listen-address", Some("::1"))
+        .flag("quic-port", Some(quic4_port.to_string().as_str()))
+        .flag("quic-port6", Some(quic6_port.to_string().as_str()))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .network
+                    .listen_addrs()
+                    .v4()
+                    .map(|listen_addr| listen_addr.quic_port),
+                Some(Some(quic4_port))
+            );
+            assert_eq!(
+                config
+                    .network
+                    .listen_addrs()
+                    .v6()
+                    .map(|listen_addr| listen_addr.quic_port),
+                Some(Some(quic6_port))
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn network_quic_port_same_as_discovery_port() {
+    let port = unused_udp4_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("discovery-port", Some(port.to_string().as_str()))
+        .flag("quic-port", Some(port.to_string().as_str()))
+        .run();
+}
+#[test]
+#[should_panic]
+fn network_quic6_port_same_as_discovery6_port() {
+    let port = unused_udp6_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("listen-address", Some("127.0.0.1"))
+        .flag("listen-address", Some("::1"))
+        .flag("discovery-port6", Some(port.to_string().as_str()))
+        .flag("quic-port6", Some(port.to_string().as_str()))
+        .run();
+}
+#[test]
+fn network_quic_port_same_as_discovery_port_with_zero_ports() {
+    let port = unused_udp4_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("discovery-port", Some(port.to_string().as_str()))
+        .flag("quic-port", Some(port.to_string().as_str()))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.network.listen_addrs().quic_enabled());
+        });
+}
+#[test]
 fn zero_ports_flag() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -1189,6 +1340,22 @@ fn enr_tcp6_port_flag() {
         .with_config(|config| assert_eq!(config.network.enr_tcp6_port, Some(port)));
 }
 #[test]
+fn enr_quic_port_flag() {
+    let port = unused_udp4_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("enr-quic-port", Some(port.to_string().as_str()))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.enr_quic4_port, Some(port)));
+}
+#[test]
+fn enr_quic6_port_flag() {
+    let port = unused_udp6_port().expect("Unable to find unused port.");
+    CommandLineTest::new()
+        .flag("enr-quic6-port", Some(port.to_string().as_str()))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.enr_quic6_port, Some(port)));
+}
+#[test]
 fn enr_match_flag_over_ipv4() {
     let addr = "127.0.0.2".parse::<Ipv4Addr>().unwrap();
     let udp4_port = unused_udp4_port().expect("Unable to find unused port.");
@@ -1427,6 +1594,22 @@ fn http_tls_flags() {
         });
 }
 
+#[test]
+fn http_admin_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("admin-token.txt");
+    CommandLineTest::new()
+        .flag("http-admin-token-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.admin_token_path, Some(path.clone())));
+}
+#[test]
+fn http_admin_token_file_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.admin_token_path, None));
+}
+
 #[test]
 fn http_spec_fork_default() {
     CommandLineTest::new()
@@ -1614,6 +1797,19 @@ fn block_cache_size_flag() {
         .with_config(|config| assert_eq!(config.store.block_cache_size, 4_usize));
 }
 #[test]
+fn db_backend_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::LevelDb));
+}
+#[test]
+fn db_backend_flag() {
+    CommandLineTest::new()
+        .flag("db-backend", Some("leveldb"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::LevelDb));
+}
+#[test]
 fn auto_compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -1642,6 +1838,19 @@ fn prune_payloads_on_startup_false() {
         .with_config(|config| assert!(!config.store.prune_payloads));
 }
 #[test]
+fn history_retention_epochs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.history_retention_epochs, None));
+}
+#[test]
+fn history_retention_epochs_flag() {
+    CommandLineTest::new()
+        .flag("history-retention-epochs", Some("8192"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.history_retention_epochs, Some(8192)));
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
@@ -1654,6 +1863,15 @@ fn no_reconstruct_historic_states_flag() {
         .run_with_zero_port()
         .with_config(|config| assert!(!config.chain.reconstruct_historic_states));
 }
+#[test]
+fn import_era_dir_flag() {
+    CommandLineTest::new()
+        .flag("import-era-dir", Some("/tmp/era"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.era_import_dir, Some(PathBuf::from("/tmp/era")))
+        });
+}
 
 // Tests for Slasher flags.
 #[test]
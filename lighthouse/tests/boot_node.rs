@@ -151,6 +151,28 @@ fn enable_enr_auto_update_flag() {
         });
 }
 
+#[test]
+fn http_disabled_by_default() {
+    CommandLineTest::new()
+        .run_with_ip()
+        .with_config(|config| assert_eq!(config.http_listen_socket, None));
+}
+
+#[test]
+fn http_flags() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-address", Some("127.0.0.2"))
+        .flag("http-port", Some("5059"))
+        .run_with_ip()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_listen_socket,
+                Some("127.0.0.2:5059".parse().unwrap())
+            );
+        });
+}
+
 #[test]
 fn network_dir_flag() {
     // Save enr to temp dir.
@@ -161,7 +183,10 @@ fn network_dir_flag() {
     CommandLineTest::new()
         .flag("network-dir", Some(tmp_dir.path().to_str().unwrap()))
         .run()
-        .with_config(|config| assert_eq!(config.local_enr, enr))
+        .with_config(|config| {
+            assert_eq!(config.local_enr, enr);
+            assert_eq!(config.dht_path, tmp_dir.path().join("dht.rlp"));
+        })
 }
 
 fn save_enr_to_disk(dir: &Path, enr: &Enr) -> Result<(), String> {
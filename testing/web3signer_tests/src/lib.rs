@@ -306,7 +306,7 @@ mod tests {
 
             let validator_store = ValidatorStore::<_, E>::new(
                 initialized_validators,
-                slashing_protection,
+                Arc::new(slashing_protection),
                 Hash256::repeat_byte(42),
                 spec,
                 None,
@@ -367,6 +367,7 @@ mod tests {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
                         voting_keystore_password_path: None,
                         voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
+                        voting_keystore_password_source: None,
                     },
                 };
                 ValidatorStoreRig::new(vec![validator_definition], spec.clone()).await
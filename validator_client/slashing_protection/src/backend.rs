@@ -0,0 +1,230 @@
+use crate::interchange::Interchange;
+use crate::{
+    InterchangeError, InterchangeImportOutcome, NotSafe, Safe, SigningRoot, SlashingDatabase,
+};
+use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKeyBytes, SignedRoot, Slot};
+
+/// A database capable of providing slashing protection for a validator client.
+///
+/// Every `check_and_insert_*` method must check and record its message atomically, such that no
+/// two concurrent callers (possibly in different processes) can both be told a pair of mutually
+/// slashable messages is safe.
+pub trait SlashingProtectionBackend: Send + Sync {
+    /// Register multiple validators with the slashing protection database.
+    ///
+    /// Validators that are already registered but disabled will be re-enabled.
+    fn register_validators(&self, public_keys: &[PublicKeyBytes]) -> Result<(), NotSafe>;
+
+    /// Check that all of the given validators are registered and enabled.
+    fn check_validator_registrations(&self, public_keys: &[PublicKeyBytes]) -> Result<(), NotSafe>;
+
+    /// List the public key of every registered validator, in order of registration.
+    fn registered_validators(&self) -> Result<Vec<PublicKeyBytes>, InterchangeError>;
+
+    /// Count the validators registered with the database, including disabled validators.
+    fn num_validator_rows(&self) -> Result<u32, NotSafe>;
+
+    /// Check a block signing root for slash safety, and if it is safe, record it in the database.
+    fn check_and_insert_block_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe>;
+
+    /// Check an attestation signing root for slash safety, and if it is safe, record it in the
+    /// database.
+    fn check_and_insert_attestation_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe>;
+
+    /// Import slashing protection from another client in the interchange format.
+    ///
+    /// The entire interchange is imported atomically, failing if *any* record cannot be imported.
+    fn import_interchange_info(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError>;
+
+    /// Export the slashing protection data for `selected_pubkeys`, or all validators if `None`.
+    fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+    ) -> Result<Interchange, InterchangeError>;
+
+    /// Disable the given validators and export their slashing protection data, atomically.
+    ///
+    /// Keys unknown to the database are silently omitted from the result.
+    fn disable_and_export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError>;
+
+    /// Prune the signed blocks table for the given public keys.
+    fn prune_all_signed_blocks(
+        &self,
+        public_keys: &[PublicKeyBytes],
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe>;
+
+    /// Prune the signed attestations table for the given public keys.
+    fn prune_all_signed_attestations(
+        &self,
+        public_keys: &[PublicKeyBytes],
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe>;
+
+    /// Register a single validator with the slashing protection database.
+    fn register_validator(&self, validator_pk: PublicKeyBytes) -> Result<(), NotSafe> {
+        self.register_validators(&[validator_pk])
+    }
+
+    /// Check a block proposal for slash safety, and if it is safe, record it in the database.
+    fn check_and_insert_block_proposal(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        block_header: &BeaconBlockHeader,
+        domain: Hash256,
+    ) -> Result<Safe, NotSafe> {
+        self.check_and_insert_block_signing_root(
+            validator_pubkey,
+            block_header.slot,
+            block_header.signing_root(domain).into(),
+        )
+    }
+
+    /// Check an attestation for slash safety, and if it is safe, record it in the database.
+    fn check_and_insert_attestation(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        attestation: &AttestationData,
+        domain: Hash256,
+    ) -> Result<Safe, NotSafe> {
+        self.check_and_insert_attestation_signing_root(
+            validator_pubkey,
+            attestation.source.epoch,
+            attestation.target.epoch,
+            attestation.signing_root(domain).into(),
+        )
+    }
+
+    /// Export the slashing protection data for all registered validators.
+    fn export_all_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+    ) -> Result<Interchange, InterchangeError> {
+        self.export_interchange_info(genesis_validators_root, None)
+    }
+}
+
+impl SlashingProtectionBackend for SlashingDatabase {
+    fn register_validators(&self, public_keys: &[PublicKeyBytes]) -> Result<(), NotSafe> {
+        SlashingDatabase::register_validators(self, public_keys.iter())
+    }
+
+    fn check_validator_registrations(&self, public_keys: &[PublicKeyBytes]) -> Result<(), NotSafe> {
+        SlashingDatabase::check_validator_registrations(self, public_keys.iter())
+    }
+
+    fn registered_validators(&self) -> Result<Vec<PublicKeyBytes>, InterchangeError> {
+        Ok(self
+            .with_transaction(|txn| self.list_all_registered_validators(txn))?
+            .into_iter()
+            .map(|(_, pubkey)| pubkey)
+            .collect())
+    }
+
+    fn num_validator_rows(&self) -> Result<u32, NotSafe> {
+        SlashingDatabase::num_validator_rows(self)
+    }
+
+    fn check_and_insert_block_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        SlashingDatabase::check_and_insert_block_signing_root(
+            self,
+            validator_pubkey,
+            slot,
+            signing_root,
+        )
+    }
+
+    fn check_and_insert_attestation_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        SlashingDatabase::check_and_insert_attestation_signing_root(
+            self,
+            validator_pubkey,
+            att_source_epoch,
+            att_target_epoch,
+            att_signing_root,
+        )
+    }
+
+    fn import_interchange_info(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        SlashingDatabase::import_interchange_info(self, interchange, genesis_validators_root)
+    }
+
+    fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+    ) -> Result<Interchange, InterchangeError> {
+        SlashingDatabase::export_interchange_info(self, genesis_validators_root, selected_pubkeys)
+    }
+
+    fn disable_and_export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError> {
+        self.with_transaction(|txn| {
+            let known_pubkeys = pubkeys
+                .iter()
+                .filter_map(|pubkey| {
+                    let validator_id = self.get_validator_id_ignoring_status(txn, pubkey).ok()?;
+
+                    Some(
+                        self.update_validator_status(txn, validator_id, false)
+                            .map(|()| *pubkey),
+                    )
+                })
+                .collect::<Result<Vec<PublicKeyBytes>, _>>()?;
+            self.export_interchange_info_in_txn(genesis_validators_root, Some(&known_pubkeys), txn)
+        })
+    }
+
+    fn prune_all_signed_blocks(
+        &self,
+        public_keys: &[PublicKeyBytes],
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe> {
+        SlashingDatabase::prune_all_signed_blocks(self, public_keys.iter(), new_min_slot)
+    }
+
+    fn prune_all_signed_attestations(
+        &self,
+        public_keys: &[PublicKeyBytes],
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe> {
+        SlashingDatabase::prune_all_signed_attestations(self, public_keys.iter(), new_min_target)
+    }
+}
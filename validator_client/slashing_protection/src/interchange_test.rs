@@ -1,11 +1,10 @@
 use crate::{
     interchange::{Interchange, SignedAttestation, SignedBlock},
-    test_utils::{pubkey, DEFAULT_GENESIS_VALIDATORS_ROOT},
-    SigningRoot, SlashingDatabase,
+    test_utils::{pubkey, test_backends, TestBackend, DEFAULT_GENESIS_VALIDATORS_ROOT},
+    SigningRoot,
 };
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashSet;
-use tempfile::tempdir;
 use types::{Epoch, Hash256, PublicKeyBytes, Slot};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -64,9 +63,13 @@ impl MultiTestCase {
     }
 
     pub fn run(&self, minify: bool) {
-        let dir = tempdir().unwrap();
-        let slashing_db_file = dir.path().join("slashing_protection.sqlite");
-        let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+        for backend in test_backends() {
+            self.run_with_backend(minify, &backend);
+        }
+    }
+
+    fn run_with_backend(&self, minify: bool, backend: &TestBackend) {
+        let slashing_db = backend.new_database();
 
         // Now that we are using implicit minification on import, we must always allow
         // false positives.
@@ -1,15 +1,21 @@
 mod attestation_tests;
+mod backend;
 mod block_tests;
 mod extra_interchange_tests;
 pub mod interchange;
 pub mod interchange_test;
 mod parallel_tests;
+#[cfg(feature = "postgres")]
+mod postgres_database;
 mod registration_tests;
 mod signed_attestation;
 mod signed_block;
 mod slashing_database;
 pub mod test_utils;
 
+pub use crate::backend::SlashingProtectionBackend;
+#[cfg(feature = "postgres")]
+pub use crate::postgres_database::PostgresSlashingDatabase;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
@@ -122,6 +128,13 @@ impl From<r2d2::Error> for NotSafe {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<postgres::Error> for NotSafe {
+    fn from(error: postgres::Error) -> Self {
+        NotSafe::SQLError(error.to_string())
+    }
+}
+
 impl ToString for NotSafe {
     fn to_string(&self) -> String {
         format!("{:?}", self)
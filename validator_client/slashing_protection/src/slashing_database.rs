@@ -1074,7 +1074,7 @@ pub struct ValidatorSummary {
 }
 
 impl ValidatorSummary {
-    fn check_block_consistency(&self, prev: &Self, imported_blocks: bool) -> bool {
+    pub(crate) fn check_block_consistency(&self, prev: &Self, imported_blocks: bool) -> bool {
         if imported_blocks {
             // Max block slot should be monotonically increasing and non-null.
             // Minimum should match maximum due to pruning.
@@ -1086,7 +1086,11 @@ impl ValidatorSummary {
         }
     }
 
-    fn check_attestation_consistency(&self, prev: &Self, imported_attestations: bool) -> bool {
+    pub(crate) fn check_attestation_consistency(
+        &self,
+        prev: &Self,
+        imported_attestations: bool,
+    ) -> bool {
         if imported_attestations {
             // Max source and target epochs should be monotically increasing and non-null.
             // Minimums should match maximums due to pruning.
@@ -1105,7 +1109,7 @@ impl ValidatorSummary {
 }
 
 /// Take the maximum of `opt_x` and `y`, returning `y` if `opt_x` is `None`.
-fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
+pub(crate) fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
     opt_x.map_or(y, |x| std::cmp::max(x, y))
 }
 
@@ -1166,6 +1170,13 @@ impl From<rusqlite::Error> for InterchangeError {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<postgres::Error> for InterchangeError {
+    fn from(error: postgres::Error) -> Self {
+        Self::SQLError(error.to_string())
+    }
+}
+
 impl From<r2d2::Error> for InterchangeError {
     fn from(error: r2d2::Error) -> Self {
         InterchangeError::SQLPoolError(error)
@@ -1,4 +1,6 @@
 use crate::*;
+use std::cell::Cell;
+use std::ops::Deref;
 use tempfile::{tempdir, TempDir};
 use types::{
     test_utils::generate_deterministic_keypair, AttestationData, BeaconBlockHeader, Hash256,
@@ -9,6 +11,11 @@ pub const DEFAULT_VALIDATOR_INDEX: usize = 0;
 pub const DEFAULT_DOMAIN: Hash256 = Hash256::zero();
 pub const DEFAULT_GENESIS_VALIDATORS_ROOT: Hash256 = Hash256::zero();
 
+/// Environment variable containing the URL of a PostgreSQL database to test against.
+///
+/// The tests create (and drop) their own schemas, so the database may be shared.
+pub const POSTGRES_URL_ENV_VAR: &str = "SLASHING_PROTECTION_POSTGRES_URL";
+
 pub fn pubkey(index: usize) -> PublicKeyBytes {
     generate_deterministic_keypair(index).pk.compress()
 }
@@ -73,17 +80,136 @@ impl<T> Default for StreamTest<T> {
     }
 }
 
+/// A source of fresh, empty databases for one of the backends under test.
+pub enum TestBackend {
+    Sqlite {
+        dir: TempDir,
+        num_databases: Cell<usize>,
+    },
+    #[cfg(feature = "postgres")]
+    Postgres { url: String },
+}
+
+/// Get the backends that the test suites should run against.
+///
+/// SQLite is always tested. PostgreSQL is tested when the `postgres` feature is enabled and
+/// `SLASHING_PROTECTION_POSTGRES_URL` is set.
+pub fn test_backends() -> Vec<TestBackend> {
+    let sqlite = TestBackend::Sqlite {
+        dir: tempdir().unwrap(),
+        num_databases: Cell::new(0),
+    };
+    #[cfg(feature = "postgres")]
+    if let Ok(url) = std::env::var(POSTGRES_URL_ENV_VAR) {
+        return vec![sqlite, TestBackend::Postgres { url }];
+    }
+    vec![sqlite]
+}
+
+impl TestBackend {
+    /// Create a new, empty slashing protection database.
+    pub fn new_database(&self) -> TestDatabase {
+        match self {
+            TestBackend::Sqlite { dir, num_databases } => {
+                let index = num_databases.replace(num_databases.get() + 1);
+                let path = dir
+                    .path()
+                    .join(format!("slashing_protection_{}.sqlite", index));
+                TestDatabase {
+                    db: Box::new(SlashingDatabase::create(&path).unwrap()),
+                    #[cfg(feature = "postgres")]
+                    _schema: None,
+                }
+            }
+            #[cfg(feature = "postgres")]
+            TestBackend::Postgres { url } => {
+                let schema = postgres_schema::TestSchema::new(url);
+                TestDatabase {
+                    db: Box::new(
+                        PostgresSlashingDatabase::open_with_config(schema.config()).unwrap(),
+                    ),
+                    _schema: Some(schema),
+                }
+            }
+        }
+    }
+}
+
+/// A slashing protection database which is cleaned up when dropped.
+pub struct TestDatabase {
+    db: Box<dyn SlashingProtectionBackend>,
+    // Dropped after `db`, so that the database's connections are closed first.
+    #[cfg(feature = "postgres")]
+    _schema: Option<postgres_schema::TestSchema>,
+}
+
+impl Deref for TestDatabase {
+    type Target = dyn SlashingProtectionBackend;
+
+    fn deref(&self) -> &Self::Target {
+        self.db.as_ref()
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres_schema {
+    use postgres::{Client, Config, NoTls};
+    use types::Hash256;
+
+    /// A uniquely named PostgreSQL schema, dropped along with its contents on `Drop`.
+    pub struct TestSchema {
+        url: String,
+        name: String,
+    }
+
+    impl TestSchema {
+        pub fn new(url: &str) -> Self {
+            let name = format!(
+                "slashing_protection_test_{:016x}",
+                Hash256::random().to_low_u64_be()
+            );
+            Client::connect(url, NoTls)
+                .unwrap()
+                .batch_execute(&format!("CREATE SCHEMA {}", name))
+                .unwrap();
+            Self {
+                url: url.to_string(),
+                name,
+            }
+        }
+
+        /// Connection config which resolves unqualified table names to this schema.
+        pub fn config(&self) -> Config {
+            let mut config: Config = self.url.parse().unwrap();
+            config.options(&format!("-c search_path={}", self.name));
+            config
+        }
+    }
+
+    impl Drop for TestSchema {
+        fn drop(&mut self) {
+            if let Ok(mut client) = Client::connect(&self.url, NoTls) {
+                let _ = client.batch_execute(&format!("DROP SCHEMA {} CASCADE", self.name));
+            }
+        }
+    }
+}
+
 impl StreamTest<AttestationData> {
     pub fn run(&self) {
-        let dir = tempdir().unwrap();
-        let slashing_db_file = dir.path().join("slashing_protection.sqlite");
-        let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+        for backend in test_backends() {
+            self.run_with_backend(&backend);
+        }
+    }
+
+    fn run_with_backend(&self, backend: &TestBackend) {
+        let slashing_db = backend.new_database();
 
         for pubkey in &self.registered_validators {
             slashing_db.register_validator(*pubkey).unwrap();
         }
 
-        check_registration_invariants(&slashing_db, &self.registered_validators);
+        check_registration_invariants(&*slashing_db, &self.registered_validators);
 
         for (i, test) in self.cases.iter().enumerate() {
             assert_eq!(
@@ -94,21 +220,29 @@ impl StreamTest<AttestationData> {
             );
         }
 
-        roundtrip_database(&dir, &slashing_db, self.registered_validators.is_empty());
+        roundtrip_database(
+            backend,
+            &*slashing_db,
+            self.registered_validators.is_empty(),
+        );
     }
 }
 
 impl StreamTest<BeaconBlockHeader> {
     pub fn run(&self) {
-        let dir = tempdir().unwrap();
-        let slashing_db_file = dir.path().join("slashing_protection.sqlite");
-        let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+        for backend in test_backends() {
+            self.run_with_backend(&backend);
+        }
+    }
+
+    fn run_with_backend(&self, backend: &TestBackend) {
+        let slashing_db = backend.new_database();
 
         for pubkey in &self.registered_validators {
             slashing_db.register_validator(*pubkey).unwrap();
         }
 
-        check_registration_invariants(&slashing_db, &self.registered_validators);
+        check_registration_invariants(&*slashing_db, &self.registered_validators);
 
         for (i, test) in self.cases.iter().enumerate() {
             assert_eq!(
@@ -119,18 +253,21 @@ impl StreamTest<BeaconBlockHeader> {
             );
         }
 
-        roundtrip_database(&dir, &slashing_db, self.registered_validators.is_empty());
+        roundtrip_database(
+            backend,
+            &*slashing_db,
+            self.registered_validators.is_empty(),
+        );
     }
 }
 
 // This function roundtrips the database, but applies minification in order to be compatible with
 // the implicit minification done on import.
-fn roundtrip_database(dir: &TempDir, db: &SlashingDatabase, is_empty: bool) {
+fn roundtrip_database(backend: &TestBackend, db: &dyn SlashingProtectionBackend, is_empty: bool) {
     let exported = db
         .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
         .unwrap();
-    let new_db =
-        SlashingDatabase::create(&dir.path().join("roundtrip_slashing_protection.sqlite")).unwrap();
+    let new_db = backend.new_database();
     new_db
         .import_interchange_info(exported.clone(), DEFAULT_GENESIS_VALIDATORS_ROOT)
         .unwrap();
@@ -146,17 +283,12 @@ fn roundtrip_database(dir: &TempDir, db: &SlashingDatabase, is_empty: bool) {
 }
 
 fn check_registration_invariants(
-    slashing_db: &SlashingDatabase,
+    slashing_db: &dyn SlashingProtectionBackend,
     registered_validators: &[PublicKeyBytes],
 ) {
     slashing_db
-        .check_validator_registrations(registered_validators.iter())
+        .check_validator_registrations(registered_validators)
         .unwrap();
-    let registered_list = slashing_db
-        .with_transaction(|txn| slashing_db.list_all_registered_validators(txn))
-        .unwrap()
-        .into_iter()
-        .map(|(_, pubkey)| pubkey)
-        .collect::<Vec<_>>();
+    let registered_list = slashing_db.registered_validators().unwrap();
     assert_eq!(registered_validators, registered_list);
 }
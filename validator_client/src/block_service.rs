@@ -3,10 +3,12 @@ use crate::{
     beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
     determine_graffiti,
     graffiti_file::GraffitiFile,
+    graffiti_template::{GraffitiTemplate, GraffitiVariables},
     OfflineOnFailure,
 };
 use crate::{http_metrics::metrics, validator_store::ValidatorStore};
 use environment::RuntimeContext;
+use eth2::BeaconNodeHttpClient;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
 use std::ops::Deref;
@@ -46,7 +48,7 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     slot_clock: Option<Arc<T>>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
-    graffiti: Option<Graffiti>,
+    graffiti: Option<GraffitiTemplate>,
     graffiti_file: Option<GraffitiFile>,
     block_delay: Option<Duration>,
 }
@@ -84,7 +86,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
-    pub fn graffiti(mut self, graffiti: Option<Graffiti>) -> Self {
+    pub fn graffiti(mut self, graffiti: Option<GraffitiTemplate>) -> Self {
         self.graffiti = graffiti;
         self
     }
@@ -128,7 +130,7 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: Arc<T>,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
-    graffiti: Option<Graffiti>,
+    graffiti: Option<GraffitiTemplate>,
     graffiti_file: Option<GraffitiFile>,
     block_delay: Option<Duration>,
 }
@@ -322,14 +324,15 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             })?
             .into();
 
-        let graffiti = determine_graffiti(
+        let graffiti_template = determine_graffiti(
             &validator_pubkey,
             log,
             self.graffiti_file.clone(),
             self.validator_store.graffiti(&validator_pubkey),
-            self.graffiti,
+            self.graffiti.clone(),
         );
 
+        let graffiti_template_ref = &graffiti_template;
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
         let proposer_index = self.validator_store.validator_index(&validator_pubkey);
@@ -347,6 +350,15 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
+                    let graffiti = self_ref
+                        .expand_graffiti(
+                            graffiti_template_ref.as_ref(),
+                            beacon_node,
+                            slot,
+                            proposer_index,
+                        )
+                        .await;
+
                     let block = match Payload::block_type() {
                         BlockType::Full => {
                             let _get_timer = metrics::start_timer_vec(
@@ -472,10 +484,47 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             "block_type" => ?Payload::block_type(),
             "deposits" => signed_block.message().body().deposits().len(),
             "attestations" => signed_block.message().body().attestations().len(),
-            "graffiti" => ?graffiti.map(|g| g.as_utf8_lossy()),
+            "graffiti" => ?signed_block.message().body().graffiti().as_utf8_lossy(),
             "slot" => signed_block.slot().as_u64(),
         );
 
         Ok(())
     }
+
+    /// Expands the graffiti `template` for a block at `slot`.
+    ///
+    /// The version of the execution client is fetched from `beacon_node` if it is used by the
+    /// template.
+    async fn expand_graffiti(
+        &self,
+        template: Option<&GraffitiTemplate>,
+        beacon_node: &BeaconNodeHttpClient,
+        slot: Slot,
+        validator_index: Option<u64>,
+    ) -> Option<Graffiti> {
+        let template = template?;
+
+        let execution_client = if template.requires_execution_client() {
+            match beacon_node.get_lighthouse_execution_client_version().await {
+                Ok(response) => response.data.into_iter().next(),
+                Err(e) => {
+                    warn!(
+                        self.context.log(),
+                        "Unable to fetch execution client version";
+                        "info" => "graffiti will omit the execution client",
+                        "error" => ?e,
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Some(template.expand(&GraffitiVariables {
+            slot,
+            validator_index,
+            execution_client: execution_client.as_ref(),
+        }))
+    }
 }
@@ -1,4 +1,6 @@
 use crate::graffiti_file::GraffitiFile;
+use crate::graffiti_template::GraffitiTemplate;
+use crate::proposer_config::ProposerConfigSource;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, parse_optional, parse_required};
@@ -6,15 +8,15 @@ use directory::{
     get_network_dir, DEFAULT_HARDCODED_NETWORK, DEFAULT_ROOT_DIR, DEFAULT_SECRET_DIR,
     DEFAULT_VALIDATOR_DIR,
 };
-use eth2::types::Graffiti;
 use sensitive_url::SensitiveUrl;
 use serde_derive::{Deserialize, Serialize};
 use slog::{info, warn, Logger};
 use std::fs;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
-use types::{Address, GRAFFITI_BYTES_LEN};
+use types::Address;
 
 pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
 
@@ -36,10 +38,13 @@ pub struct Config {
     pub disable_auto_discover: bool,
     /// If true, re-register existing validators in definitions.yml for slashing protection.
     pub init_slashing_protection: bool,
+    /// The URL of a PostgreSQL slashing protection database, used instead of the SQLite
+    /// database in the `validator_dir`.
+    pub slashing_protection_db_url: Option<SensitiveUrl>,
     /// If true, use longer timeouts for requests made to the beacon node.
     pub use_long_timeouts: bool,
-    /// Graffiti to be inserted everytime we create a block.
-    pub graffiti: Option<Graffiti>,
+    /// Graffiti to be inserted everytime we create a block, which may contain variables.
+    pub graffiti: Option<GraffitiTemplate>,
     /// Graffiti file to load per validator graffitis.
     pub graffiti_file: Option<GraffitiFile>,
     /// Fallback fallback address.
@@ -64,6 +69,8 @@ pub struct Config {
     pub builder_registration_timestamp_override: Option<u64>,
     /// Fallback gas limit.
     pub gas_limit: Option<u64>,
+    /// The file or URL from which per-validator proposer settings are loaded each epoch.
+    pub proposer_config: Option<ProposerConfigSource>,
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
@@ -98,6 +105,7 @@ impl Default for Config {
             allow_unsynced_beacon_node: false,
             disable_auto_discover: false,
             init_slashing_protection: false,
+            slashing_protection_db_url: None,
             use_long_timeouts: false,
             graffiti: None,
             graffiti_file: None,
@@ -112,6 +120,7 @@ impl Default for Config {
             builder_proposals: false,
             builder_registration_timestamp_override: None,
             gas_limit: None,
+            proposer_config: None,
             disable_run_on_all: false,
             enable_latency_measurement_service: true,
         }
@@ -198,6 +207,7 @@ impl Config {
         config.disable_run_on_all = cli_args.is_present("disable-run-on-all");
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
         config.init_slashing_protection = cli_args.is_present("init-slashing-protection");
+        config.slashing_protection_db_url = parse_optional(cli_args, "slashing-protection-db-url")?;
         config.use_long_timeouts = cli_args.is_present("use-long-timeouts");
 
         if let Some(graffiti_file_path) = cli_args.value_of("graffiti-file") {
@@ -210,22 +220,9 @@ impl Config {
         }
 
         if let Some(input_graffiti) = cli_args.value_of("graffiti") {
-            let graffiti_bytes = input_graffiti.as_bytes();
-            if graffiti_bytes.len() > GRAFFITI_BYTES_LEN {
-                return Err(format!(
-                    "Your graffiti is too long! {} bytes maximum!",
-                    GRAFFITI_BYTES_LEN
-                ));
-            } else {
-                let mut graffiti = [0; 32];
-
-                // Copy the provided bytes over.
-                //
-                // Panic-free because `graffiti_bytes.len()` <= `GRAFFITI_BYTES_LEN`.
-                graffiti[..graffiti_bytes.len()].copy_from_slice(graffiti_bytes);
-
-                config.graffiti = Some(graffiti.into());
-            }
+            let graffiti = GraffitiTemplate::from_str(input_graffiti)
+                .map_err(|e| format!("Invalid graffiti: {}", e))?;
+            config.graffiti = Some(graffiti);
         }
 
         if let Some(input_fee_recipient) =
@@ -352,6 +349,8 @@ impl Config {
             );
         }
 
+        config.proposer_config = parse_optional(cli_args, "proposer-config")?;
+
         if cli_args.is_present("strict-fee-recipient") {
             warn!(
                 log,
@@ -15,6 +15,23 @@
 //! prevents a stale-mate where all validators will cease to function for a few epochs and then all
 //! start at the same time.
 //!
+//! ## Restarts
+//!
+//! This validator client keeps a record of the latest epoch in which each validator signed a block
+//! or attestation, which is written to disk when it shuts down and consumed when it next starts
+//! (see `signing_history`). The slashing protection database is *not* used for this purpose, since
+//! it may contain messages signed by another validator client.
+//!
+//! When a validator is registered, if its last signed epoch is the current or previous epoch
+//! (e.g., the validator client has just been restarted) then any liveness *after* that epoch must
+//! belong to a doppelganger. If the liveness data shows the validator to be live in its last signed
+//! epoch, and then shows the whole of the following epoch to pass without it being live, signing is
+//! considered to have continued without interruption and the remaining detection is skipped.
+//! Otherwise detection continues as normal, beginning from the epoch after the last signed epoch.
+//!
+//! Skipping detection offers no protection against a doppelganger started during the restart
+//! which has not yet been seen on the network.
+//!
 //! ## Caveat
 //!
 //! Presently doppelganger protection will never advance if the call at the last slot of each epoch
@@ -33,6 +50,7 @@ use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
 use crate::validator_store::ValidatorStore;
 use crate::OfflineOnFailure;
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{DoppelgangerData, DoppelgangerDetection};
 use eth2::types::LivenessResponseData;
 use parking_lot::RwLock;
 use slog::{crit, error, info, Logger};
@@ -131,6 +149,13 @@ pub struct DoppelgangerState {
     /// The number of epochs that must be checked before this validator is considered
     /// doppelganger-free.
     remaining_epochs: u64,
+    /// The latest epoch in which this validator signed a block or attestation with this validator
+    /// client before it was registered.
+    last_signed_epoch: Option<Epoch>,
+    /// Set once the liveness data has shown this validator to be live in `last_signed_epoch`.
+    live_in_last_signed_epoch: bool,
+    /// How the recent signing history of this validator has affected detection.
+    detection: DoppelgangerDetection,
 }
 
 impl DoppelgangerState {
@@ -370,14 +395,34 @@ impl DoppelgangerService {
             })
     }
 
+    /// Returns the doppelganger protection state of every validator known to the service.
+    pub fn validator_states(&self) -> Vec<DoppelgangerData> {
+        self.doppelganger_states
+            .read()
+            .iter()
+            .map(|(pubkey, state)| DoppelgangerData {
+                pubkey: *pubkey,
+                signing_enabled: !state.requires_further_checks(),
+                remaining_epochs: state.remaining_epochs,
+                next_check_epoch: state.next_check_epoch,
+                last_signed_epoch: state.last_signed_epoch,
+                detection: state.detection,
+            })
+            .collect()
+    }
+
     /// Register a new validator with the doppelganger service.
     ///
     /// Validators added during the genesis epoch will not have doppelganger protection applied to
     /// them.
+    ///
+    /// Validators with a `last_signed_epoch` in the current or previous epoch will have detection
+    /// skipped or shortened, as described in the module-level documentation.
     pub fn register_new_validator<E: EthSpec, T: SlotClock>(
         &self,
         validator: PublicKeyBytes,
         slot_clock: &T,
+        last_signed_epoch: Option<Epoch>,
     ) -> Result<(), String> {
         let current_epoch = slot_clock
             // If registering before genesis, use the genesis slot.
@@ -400,9 +445,30 @@ impl DoppelgangerService {
             DEFAULT_REMAINING_DETECTION_EPOCHS
         };
 
-        let state = DoppelgangerState {
-            next_check_epoch: current_epoch.saturating_add(1_u64),
-            remaining_epochs,
+        let recently_signed_epoch = last_signed_epoch.filter(|epoch| {
+            remaining_epochs > 0
+                && *epoch <= current_epoch
+                && epoch.saturating_add(1_u64) >= current_epoch
+        });
+
+        let state = if let Some(epoch) = recently_signed_epoch {
+            // This validator client has recorded every message it signed, so any liveness after
+            // the last signed epoch must be a doppelganger.
+            DoppelgangerState {
+                next_check_epoch: epoch.saturating_add(1_u64),
+                remaining_epochs,
+                last_signed_epoch,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::AwaitingLiveness,
+            }
+        } else {
+            DoppelgangerState {
+                next_check_epoch: current_epoch.saturating_add(1_u64),
+                remaining_epochs,
+                last_signed_epoch,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
+            }
         };
 
         self.doppelganger_states.write().insert(validator, state);
@@ -569,6 +635,15 @@ impl DoppelgangerService {
             )
         }
 
+        if !violators_exist {
+            self.check_continuity(
+                request_epoch,
+                &previous_epoch_responses,
+                &current_epoch_responses,
+                indices_map,
+            );
+        }
+
         // The concept of "epoch satisfaction" is that for some epoch `e` we are *satisfied* that
         // we've waited long enough such that we don't expect to see any more consensus messages
         // for that epoch.
@@ -666,6 +741,92 @@ impl DoppelgangerService {
 
         Ok(())
     }
+
+    /// Decide whether validators awaiting liveness data have been signing without interruption.
+    ///
+    /// A validator has detection skipped if it was live in its last signed epoch and the whole of
+    /// the following epoch has since been observed without it being live. A validator that was not
+    /// live, or whose following epoch is no longer covered by the liveness data, continues
+    /// detection from the epoch following its last signed epoch.
+    ///
+    /// Liveness after the last signed epoch must have been detected as a doppelganger before
+    /// calling this function.
+    fn check_continuity(
+        &self,
+        request_epoch: Epoch,
+        previous_epoch_responses: &[LivenessResponseData],
+        current_epoch_responses: &[LivenessResponseData],
+        indices_map: &HashMap<u64, PublicKeyBytes>,
+    ) {
+        let liveness = previous_epoch_responses
+            .iter()
+            .chain(current_epoch_responses.iter())
+            .map(|response| ((response.index, response.epoch), response.is_live))
+            .collect::<HashMap<_, _>>();
+
+        // !! IMPORTANT !!
+        //
+        // There is a write-lock being held, avoid interacting with locks until it is dropped.
+        let mut doppelganger_states = self.doppelganger_states.write();
+
+        for (index, pubkey) in indices_map {
+            let doppelganger_state = match doppelganger_states.get_mut(pubkey) {
+                Some(state) if state.detection == DoppelgangerDetection::AwaitingLiveness => state,
+                _ => continue,
+            };
+            let last_signed_epoch = match doppelganger_state.last_signed_epoch {
+                Some(epoch) if epoch <= request_epoch => epoch,
+                _ => continue,
+            };
+            let following_epoch = last_signed_epoch.saturating_add(1_u64);
+
+            if liveness.get(&(*index, last_signed_epoch)) == Some(&true) {
+                doppelganger_state.live_in_last_signed_epoch = true;
+            }
+
+            if request_epoch <= following_epoch {
+                // Wait for the whole of the following epoch to pass.
+                continue;
+            }
+
+            let skip = if request_epoch == following_epoch.saturating_add(1_u64) {
+                match (
+                    liveness.get(&(*index, following_epoch)),
+                    liveness.get(&(*index, request_epoch)),
+                ) {
+                    (Some(_), Some(_)) => doppelganger_state.live_in_last_signed_epoch,
+                    // Wait for a response covering every epoch since the last signed epoch.
+                    _ => continue,
+                }
+            } else {
+                // The liveness data no longer covers every epoch since the last signed epoch.
+                false
+            };
+
+            if skip {
+                doppelganger_state.remaining_epochs = 0;
+                doppelganger_state.detection = DoppelgangerDetection::Skipped;
+
+                info!(
+                    self.log,
+                    "Doppelganger detection skipped";
+                    "msg" => "validator has been signing without interruption",
+                    "last_signed_epoch" => last_signed_epoch,
+                    "validator_index" => index
+                );
+            } else {
+                doppelganger_state.detection = DoppelgangerDetection::Shortened;
+
+                info!(
+                    self.log,
+                    "Doppelganger detection shortened";
+                    "msg" => "unable to show validator has been signing without interruption",
+                    "last_signed_epoch" => last_signed_epoch,
+                    "validator_index" => index
+                );
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -775,14 +936,31 @@ mod test {
             this
         }
 
+        pub fn register_all_with_last_signed_epoch(self, last_signed_epoch: Epoch) -> Self {
+            let mut this = self;
+            for i in 0..this.validators.len() {
+                this = this
+                    .register_validator_with_last_signed_epoch(i as u64, Some(last_signed_epoch));
+            }
+            this
+        }
+
         pub fn register_validator(self, index: u64) -> Self {
+            self.register_validator_with_last_signed_epoch(index, None)
+        }
+
+        pub fn register_validator_with_last_signed_epoch(
+            self,
+            index: u64,
+            last_signed_epoch: Option<Epoch>,
+        ) -> Self {
             let pubkey = *self
                 .validators
                 .get(index as usize)
                 .expect("index should exist");
 
             self.doppelganger
-                .register_new_validator::<E, _>(pubkey, &self.slot_clock)
+                .register_new_validator::<E, _>(pubkey, &self.slot_clock, last_signed_epoch)
                 .unwrap();
             self.doppelganger
                 .doppelganger_states
@@ -919,6 +1097,9 @@ mod test {
                 .assert_all_states(&DoppelgangerState {
                     next_check_epoch: genesis_epoch() + 1,
                     remaining_epochs: 0,
+                    last_signed_epoch: None,
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
                 });
         }
     }
@@ -936,6 +1117,9 @@ mod test {
                 .assert_all_states(&DoppelgangerState {
                     next_check_epoch: epoch + 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    last_signed_epoch: None,
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
                 });
         }
     }
@@ -956,6 +1140,9 @@ mod test {
                 &DoppelgangerState {
                     next_check_epoch: epoch + 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    last_signed_epoch: None,
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
                 },
             )
             // Ensure validator 2 was not registered.
@@ -1104,6 +1291,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: starting_epoch + 1,
                 remaining_epochs: u64::MAX,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             });
     }
 
@@ -1166,6 +1356,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             });
     }
 
@@ -1208,18 +1401,27 @@ mod test {
                 DoppelgangerState {
                     next_check_epoch: initial_epoch + 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    last_signed_epoch: None,
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
                 }
             } else if !is_satisfaction_slot {
                 DoppelgangerState {
                     next_check_epoch: epoch - 1,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS
                         .saturating_sub(epochs_since_start.saturating_sub(2)),
+                    last_signed_epoch: None,
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
                 }
             } else {
                 DoppelgangerState {
                     next_check_epoch: epoch,
                     remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS
                         .saturating_sub(epochs_since_start.saturating_sub(1)),
+                    last_signed_epoch: None,
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
                 }
             };
 
@@ -1237,6 +1439,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: activation_slot.epoch(E::slots_per_epoch()),
                 remaining_epochs: 0,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             });
     }
 
@@ -1267,6 +1472,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             })
             // Simulate a check in the skipped forward slot
             .simulate_detect_doppelgangers(
@@ -1283,6 +1491,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: skipped_forward_epoch,
                 remaining_epochs: 0,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             });
     }
 
@@ -1313,6 +1524,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             })
             // Simulate a check in the skipped forward slot
             .simulate_detect_doppelgangers(
@@ -1333,6 +1547,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: u64::max_value(),
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             });
     }
 
@@ -1363,6 +1580,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             })
             // Simulate a check in the skipped backward slot
             .simulate_detect_doppelgangers(
@@ -1380,6 +1600,9 @@ mod test {
             .assert_all_states(&DoppelgangerState {
                 next_check_epoch: initial_epoch + 1,
                 remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: None,
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Full,
             });
     }
 
@@ -1445,4 +1668,243 @@ mod test {
 
         scenario.assert_all_enabled();
     }
+
+    #[test]
+    fn register_after_recent_signing() {
+        let epoch = genesis_epoch() + 42;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+
+        for last_signed_epoch in [epoch - 1, epoch] {
+            TestBuilder::default()
+                .build()
+                .set_slot(slot)
+                .register_all_with_last_signed_epoch(last_signed_epoch)
+                .assert_all_disabled()
+                .assert_all_states(&DoppelgangerState {
+                    next_check_epoch: last_signed_epoch + 1,
+                    remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    last_signed_epoch: Some(last_signed_epoch),
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::AwaitingLiveness,
+                });
+        }
+    }
+
+    #[test]
+    fn register_after_stale_signing() {
+        let epoch = genesis_epoch() + 42;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+
+        // Signing records from before the previous epoch (or from the future) are ignored.
+        for last_signed_epoch in [epoch - 2, epoch + 1] {
+            TestBuilder::default()
+                .build()
+                .set_slot(slot)
+                .register_all_with_last_signed_epoch(last_signed_epoch)
+                .assert_all_disabled()
+                .assert_all_states(&DoppelgangerState {
+                    next_check_epoch: epoch + 1,
+                    remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                    last_signed_epoch: Some(last_signed_epoch),
+                    live_in_last_signed_epoch: false,
+                    detection: DoppelgangerDetection::Full,
+                });
+        }
+    }
+
+    /// Liveness responses in which each validator is live only in `live_epoch`.
+    fn get_responses_live_in(
+        current_epoch: Epoch,
+        detection_indices: &[u64],
+        live_epoch: Epoch,
+    ) -> LivenessResponses {
+        let mut liveness_responses = get_false_responses(current_epoch, detection_indices);
+        for response in liveness_responses
+            .previous_epoch_responses
+            .iter_mut()
+            .chain(liveness_responses.current_epoch_responses.iter_mut())
+        {
+            response.is_live = response.epoch == live_epoch;
+        }
+        liveness_responses
+    }
+
+    #[test]
+    fn skip_detection_when_live_in_last_signed_epoch() {
+        let epoch = genesis_epoch() + 42;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+        let next_slot = (epoch + 1).start_slot(E::slots_per_epoch());
+        let last_signed_epoch = epoch - 1;
+
+        TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_with_last_signed_epoch(last_signed_epoch)
+            .assert_all_disabled()
+            .simulate_detect_doppelgangers(
+                slot,
+                ShouldShutdown::No,
+                |current_epoch, detection_indices: Vec<_>| {
+                    assert_eq!(current_epoch, epoch);
+                    check_detection_indices(&detection_indices);
+                    future::ready(get_responses_live_in(
+                        current_epoch,
+                        &detection_indices,
+                        last_signed_epoch,
+                    ))
+                },
+            )
+            // The epoch following the last signed epoch must pass before detection is skipped.
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: true,
+                detection: DoppelgangerDetection::AwaitingLiveness,
+            })
+            .set_slot(next_slot)
+            .simulate_detect_doppelgangers(
+                next_slot,
+                ShouldShutdown::No,
+                |current_epoch, detection_indices: Vec<_>| {
+                    assert_eq!(current_epoch, epoch + 1);
+                    future::ready(get_false_responses(current_epoch, &detection_indices))
+                },
+            )
+            .assert_all_enabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch,
+                remaining_epochs: 0,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: true,
+                detection: DoppelgangerDetection::Skipped,
+            });
+    }
+
+    #[test]
+    fn shorten_detection_when_not_live_in_last_signed_epoch() {
+        let epoch = genesis_epoch() + 42;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+        let next_slot = (epoch + 1).start_slot(E::slots_per_epoch());
+        let satisfaction_slot = (epoch + 1).end_slot(E::slots_per_epoch());
+        let last_signed_epoch = epoch - 1;
+
+        let get_liveness = |current_epoch, detection_indices: Vec<_>| {
+            future::ready(get_false_responses(current_epoch, &detection_indices))
+        };
+
+        TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_with_last_signed_epoch(last_signed_epoch)
+            .simulate_detect_doppelgangers(slot, ShouldShutdown::No, get_liveness)
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::AwaitingLiveness,
+            })
+            .set_slot(next_slot)
+            .simulate_detect_doppelgangers(next_slot, ShouldShutdown::No, get_liveness)
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Shortened,
+            })
+            // Detection completes once the epoch following the last signed epoch is satisfied.
+            .set_slot(satisfaction_slot)
+            .simulate_detect_doppelgangers(satisfaction_slot, ShouldShutdown::No, get_liveness)
+            .assert_all_enabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch + 1,
+                remaining_epochs: 0,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::Shortened,
+            });
+    }
+
+    #[test]
+    fn shorten_detection_when_following_epoch_not_observed() {
+        let epoch = genesis_epoch() + 42;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+        let later_slot = (epoch + 2).start_slot(E::slots_per_epoch());
+        let last_signed_epoch = epoch - 1;
+
+        TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_with_last_signed_epoch(last_signed_epoch)
+            .simulate_detect_doppelgangers(
+                slot,
+                ShouldShutdown::No,
+                |current_epoch, detection_indices: Vec<_>| {
+                    future::ready(get_responses_live_in(
+                        current_epoch,
+                        &detection_indices,
+                        last_signed_epoch,
+                    ))
+                },
+            )
+            .assert_all_disabled()
+            // No liveness data was obtained for the epoch following the last signed epoch.
+            .set_slot(later_slot)
+            .simulate_detect_doppelgangers(
+                later_slot,
+                ShouldShutdown::No,
+                |current_epoch, detection_indices: Vec<_>| {
+                    future::ready(get_false_responses(current_epoch, &detection_indices))
+                },
+            )
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: true,
+                detection: DoppelgangerDetection::Shortened,
+            });
+    }
+
+    #[test]
+    fn detect_doppelganger_after_last_signed_epoch() {
+        let epoch = genesis_epoch() + 42;
+        let slot = epoch.start_slot(E::slots_per_epoch());
+        let last_signed_epoch = epoch - 1;
+
+        TestBuilder::default()
+            .build()
+            .set_slot(slot)
+            .register_all_with_last_signed_epoch(last_signed_epoch)
+            .simulate_detect_doppelgangers(
+                slot,
+                ShouldShutdown::Yes,
+                |current_epoch, detection_indices: Vec<_>| {
+                    let mut liveness_responses =
+                        get_false_responses(current_epoch, &detection_indices);
+
+                    for response in &mut liveness_responses.previous_epoch_responses {
+                        response.is_live = true;
+                    }
+                    // Some other client has signed since this validator client stopped.
+                    liveness_responses.current_epoch_responses[0].is_live = true;
+
+                    future::ready(liveness_responses)
+                },
+            )
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: epoch,
+                remaining_epochs: u64::MAX,
+                last_signed_epoch: Some(last_signed_epoch),
+                live_in_last_signed_epoch: false,
+                detection: DoppelgangerDetection::AwaitingLiveness,
+            });
+    }
 }
@@ -5,8 +5,8 @@ use std::io::{prelude::*, BufReader};
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use crate::graffiti_template::GraffitiTemplate;
 use bls::PublicKeyBytes;
-use types::{graffiti::GraffitiString, Graffiti};
 
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -24,11 +24,13 @@ pub enum Error {
 /// public_key1: graffiti1
 /// public_key2: graffiti2
 /// ...
+///
+/// Each graffiti may be a template containing variables, see `GraffitiTemplate`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraffitiFile {
     graffiti_path: PathBuf,
-    graffitis: HashMap<PublicKeyBytes, Graffiti>,
-    default: Option<Graffiti>,
+    graffitis: HashMap<PublicKeyBytes, GraffitiTemplate>,
+    default: Option<GraffitiTemplate>,
 }
 
 impl GraffitiFile {
@@ -48,9 +50,13 @@ impl GraffitiFile {
     pub fn load_graffiti(
         &mut self,
         public_key: &PublicKeyBytes,
-    ) -> Result<Option<Graffiti>, Error> {
+    ) -> Result<Option<GraffitiTemplate>, Error> {
         self.read_graffiti_file()?;
-        Ok(self.graffitis.get(public_key).copied().or(self.default))
+        Ok(self
+            .graffitis
+            .get(public_key)
+            .or(self.default.as_ref())
+            .cloned())
     }
 
     /// Reads from a graffiti file with the specified format and populates the default value
@@ -82,13 +88,12 @@ impl GraffitiFile {
 /// `Ok((None, graffiti))` represents the graffiti for the default key.
 /// `Ok((Some(pk), graffiti))` represents graffiti for the public key `pk`.
 /// Returns an error if the line is in the wrong format or does not contain a valid public key or graffiti.
-fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, Graffiti), Error> {
+fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, GraffitiTemplate), Error> {
     if let Some(i) = line.find(':') {
         let (key, value) = line.split_at(i);
         // Note: `value.len() >=1` so `value[1..]` is safe
-        let graffiti = GraffitiString::from_str(value[1..].trim())
-            .map_err(Error::InvalidGraffiti)?
-            .into();
+        let graffiti =
+            GraffitiTemplate::from_str(value[1..].trim()).map_err(Error::InvalidGraffiti)?;
         if key == "default" {
             Ok((None, graffiti))
         } else {
@@ -103,9 +108,11 @@ fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, Graffiti), Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graffiti_template::GraffitiVariables;
     use bls::Keypair;
     use std::io::LineWriter;
     use tempfile::TempDir;
+    use types::{graffiti::GraffitiString, Slot};
 
     const DEFAULT_GRAFFITI: &str = "lighthouse";
     const CUSTOM_GRAFFITI1: &str = "custom-graffiti1";
@@ -155,24 +162,81 @@ mod tests {
         gf.read_graffiti_file().unwrap();
 
         assert_eq!(
-            gf.load_graffiti(&pk1).unwrap().unwrap(),
+            gf.load_graffiti(&pk1)
+                .unwrap()
+                .unwrap()
+                .as_static()
+                .unwrap(),
             GraffitiString::from_str(CUSTOM_GRAFFITI1).unwrap().into()
         );
         assert_eq!(
-            gf.load_graffiti(&pk2).unwrap().unwrap(),
+            gf.load_graffiti(&pk2)
+                .unwrap()
+                .unwrap()
+                .as_static()
+                .unwrap(),
             GraffitiString::from_str(CUSTOM_GRAFFITI2).unwrap().into()
         );
 
         assert_eq!(
-            gf.load_graffiti(&pk3).unwrap().unwrap(),
+            gf.load_graffiti(&pk3)
+                .unwrap()
+                .unwrap()
+                .as_static()
+                .unwrap(),
             GraffitiString::from_str(EMPTY_GRAFFITI).unwrap().into()
         );
 
         // Random pk should return the default graffiti
         let random_pk = Keypair::random().pk.compress();
         assert_eq!(
-            gf.load_graffiti(&random_pk).unwrap().unwrap(),
+            gf.load_graffiti(&random_pk)
+                .unwrap()
+                .unwrap()
+                .as_static()
+                .unwrap(),
             GraffitiString::from_str(DEFAULT_GRAFFITI).unwrap().into()
         );
     }
+
+    #[test]
+    fn test_load_graffiti_template() {
+        let temp = TempDir::new().unwrap();
+        let pk1 = PublicKeyBytes::deserialize(&hex::decode(&PK1[2..]).unwrap()).unwrap();
+        let file_name = temp.path().join("graffiti.txt");
+        std::fs::write(
+            &file_name,
+            format!(
+                "default: {{unknown}} at {{slot}}\n{}: {}\n",
+                pk1.as_hex_string(),
+                "validator {validator_index} proposing slot {slot} with a long graffiti"
+            ),
+        )
+        .unwrap();
+
+        let mut gf = GraffitiFile::new(file_name);
+        let variables = GraffitiVariables {
+            slot: Slot::new(42),
+            validator_index: Some(7),
+            execution_client: None,
+        };
+
+        assert_eq!(
+            gf.load_graffiti(&pk1)
+                .unwrap()
+                .unwrap()
+                .expand(&variables)
+                .as_utf8_lossy(),
+            "validator 7 proposing slot 42 wi"
+        );
+        let random_pk = Keypair::random().pk.compress();
+        assert_eq!(
+            gf.load_graffiti(&random_pk)
+                .unwrap()
+                .unwrap()
+                .expand(&variables)
+                .as_utf8_lossy(),
+            "{unknown} at 42"
+        );
+    }
 }
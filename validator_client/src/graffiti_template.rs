@@ -0,0 +1,363 @@
+//! Graffiti containing variables which are expanded by the `BlockService` when a block is
+//! proposed, e.g. `{execution_code}{execution_version}-LH{lighthouse_version}`.
+//!
+//! Only the names of known variables are expanded, any other text (including braces) is left as it
+//! is, so that existing graffiti continues to be used unchanged.
+
+use eth2::lighthouse::ExecutionClientVersion;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use types::{graffiti::GraffitiString, Graffiti, Slot, GRAFFITI_BYTES_LEN};
+
+/// A variable which may be used in a graffiti template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+    /// The version of this validator client, e.g. `v4.0.0-67da032`.
+    LighthouseVersion,
+    /// The name of the beacon node's execution client, e.g. `Geth`.
+    ExecutionClient,
+    /// The two letter code of the beacon node's execution client, e.g. `GE`.
+    ExecutionCode,
+    /// The version of the beacon node's execution client, e.g. `v1.13.14-stable`.
+    ExecutionVersion,
+    /// The index of the proposing validator.
+    ValidatorIndex,
+    /// The slot of the proposal.
+    Slot,
+}
+
+impl FromStr for Variable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lighthouse_version" => Ok(Variable::LighthouseVersion),
+            "execution_client" => Ok(Variable::ExecutionClient),
+            "execution_code" => Ok(Variable::ExecutionCode),
+            "execution_version" => Ok(Variable::ExecutionVersion),
+            "validator_index" => Ok(Variable::ValidatorIndex),
+            "slot" => Ok(Variable::Slot),
+            other => Err(format!("unknown graffiti variable {{{}}}", other)),
+        }
+    }
+}
+
+impl Variable {
+    fn requires_execution_client(self) -> bool {
+        matches!(
+            self,
+            Variable::ExecutionClient | Variable::ExecutionCode | Variable::ExecutionVersion
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Variable(Variable),
+}
+
+/// The values substituted into a `GraffitiTemplate`.
+///
+/// Variables without a value are expanded to an empty string.
+#[derive(Debug, Clone, Copy)]
+pub struct GraffitiVariables<'a> {
+    pub slot: Slot,
+    pub validator_index: Option<u64>,
+    pub execution_client: Option<&'a ExecutionClientVersion>,
+}
+
+/// Graffiti which may contain variables.
+///
+/// Graffiti without variables must fit within `GRAFFITI_BYTES_LEN`, whilst the expansion of a
+/// template with variables is truncated to fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraffitiTemplate {
+    template: String,
+    parts: Vec<Part>,
+}
+
+impl FromStr for GraffitiTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut rest = s;
+
+        while let Some(start) = rest.find('{') {
+            let (before, from_brace) = rest.split_at(start);
+            text.push_str(before);
+
+            let variable = from_brace[1..]
+                .find('}')
+                .and_then(|end| Some((from_brace[1..=end].parse::<Variable>().ok()?, end)));
+
+            match variable {
+                Some((variable, end)) => {
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Variable(variable));
+                    rest = &from_brace[end + 2..];
+                }
+                None => {
+                    // Braces which do not enclose a known variable are literal text.
+                    text.push('{');
+                    rest = &from_brace[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        let template = Self {
+            template: s.to_string(),
+            parts,
+        };
+
+        if template.is_static() {
+            // Check the length of graffiti without variables, rather than silently truncating it.
+            GraffitiString::from_str(&template.expand_unchecked(None))?;
+        }
+
+        Ok(template)
+    }
+}
+
+impl From<Graffiti> for GraffitiTemplate {
+    /// Graffiti from the validator definitions, which is never expanded.
+    fn from(graffiti: Graffiti) -> Self {
+        let text = graffiti.as_utf8_lossy();
+        Self {
+            template: text.clone(),
+            parts: vec![Part::Text(text)],
+        }
+    }
+}
+
+impl fmt::Display for GraffitiTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+impl Serialize for GraffitiTemplate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GraffitiTemplate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl GraffitiTemplate {
+    /// Returns `true` if the template does not contain any variables.
+    pub fn is_static(&self) -> bool {
+        self.parts.iter().all(|part| matches!(part, Part::Text(_)))
+    }
+
+    /// Returns `true` if the template contains any variable describing the execution client.
+    pub fn requires_execution_client(&self) -> bool {
+        self.parts.iter().any(|part| match part {
+            Part::Variable(variable) => variable.requires_execution_client(),
+            Part::Text(_) => false,
+        })
+    }
+
+    /// Returns the graffiti if the template does not contain any variables.
+    pub fn as_static(&self) -> Option<Graffiti> {
+        self.is_static()
+            .then(|| to_graffiti(&self.expand_unchecked(None)))
+    }
+
+    /// Expands the template, truncating the result to `GRAFFITI_BYTES_LEN` bytes.
+    pub fn expand(&self, variables: &GraffitiVariables) -> Graffiti {
+        to_graffiti(&self.expand_unchecked(Some(variables)))
+    }
+
+    /// Expands the template without truncating the result.
+    fn expand_unchecked(&self, variables: Option<&GraffitiVariables>) -> String {
+        let mut graffiti = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => graffiti.push_str(text),
+                Part::Variable(variable) => {
+                    if let Some(value) = variables.and_then(|v| expand_variable(*variable, v)) {
+                        graffiti.push_str(&value)
+                    }
+                }
+            }
+        }
+        graffiti
+    }
+}
+
+fn expand_variable(variable: Variable, variables: &GraffitiVariables) -> Option<String> {
+    match variable {
+        Variable::LighthouseVersion => Some(
+            lighthouse_version::VERSION
+                .trim_start_matches("Lighthouse/")
+                .to_string(),
+        ),
+        Variable::ExecutionClient => variables.execution_client.map(|el| el.name.clone()),
+        Variable::ExecutionCode => variables.execution_client.map(|el| el.code.clone()),
+        Variable::ExecutionVersion => variables.execution_client.map(|el| el.version.clone()),
+        Variable::ValidatorIndex => variables.validator_index.map(|index| index.to_string()),
+        Variable::Slot => Some(variables.slot.to_string()),
+    }
+}
+
+/// Converts `graffiti` to bytes, truncating it to the last character boundary within
+/// `GRAFFITI_BYTES_LEN`.
+fn to_graffiti(graffiti: &str) -> Graffiti {
+    let mut len = std::cmp::min(graffiti.len(), GRAFFITI_BYTES_LEN);
+    while !graffiti.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    let mut bytes = [0; GRAFFITI_BYTES_LEN];
+    bytes[..len].copy_from_slice(&graffiti.as_bytes()[..len]);
+    bytes.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geth() -> ExecutionClientVersion {
+        ExecutionClientVersion {
+            code: "GE".to_string(),
+            name: "Geth".to_string(),
+            version: "v1.13.14-stable".to_string(),
+            commit: "0x2bd6bd01".to_string(),
+        }
+    }
+
+    fn expand(template: &str, variables: &GraffitiVariables) -> String {
+        GraffitiTemplate::from_str(template)
+            .unwrap()
+            .expand(variables)
+            .as_utf8_lossy()
+    }
+
+    #[test]
+    fn static_graffiti() {
+        let template = GraffitiTemplate::from_str("graffitiwall:720:641:#ffff00").unwrap();
+        assert!(template.is_static());
+        assert!(!template.requires_execution_client());
+        assert_eq!(
+            template.as_static().unwrap(),
+            GraffitiString::from_str("graffitiwall:720:641:#ffff00")
+                .unwrap()
+                .into()
+        );
+
+        let template = GraffitiTemplate::from_str("{{braces}}").unwrap();
+        assert_eq!(template.as_static().unwrap().as_utf8_lossy(), "{{braces}}");
+        assert_eq!(template.to_string(), "{{braces}}");
+
+        // Graffiti without variables must not be truncated.
+        assert!(GraffitiTemplate::from_str(&"a".repeat(GRAFFITI_BYTES_LEN)).is_ok());
+        assert!(GraffitiTemplate::from_str(&"a".repeat(GRAFFITI_BYTES_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn literal_braces() {
+        for graffiti in ["{unknown}", "{slot", "slot}", "}{", "{{slot }}", "{}"] {
+            let template = GraffitiTemplate::from_str(graffiti).unwrap();
+            assert!(template.is_static(), "{}", graffiti);
+            assert_eq!(template.as_static().unwrap().as_utf8_lossy(), graffiti);
+            assert_eq!(template.to_string(), graffiti);
+        }
+
+        let variables = GraffitiVariables {
+            slot: Slot::new(42),
+            validator_index: None,
+            execution_client: None,
+        };
+        assert_eq!(expand("{{slot}}", &variables), "{42}");
+        assert_eq!(expand("{mr f}@{slot}", &variables), "{mr f}@42");
+        assert_eq!(expand("{slot{slot}", &variables), "{slot42");
+    }
+
+    #[test]
+    fn expand_variables() {
+        let el = geth();
+        let variables = GraffitiVariables {
+            slot: Slot::new(42),
+            validator_index: Some(7),
+            execution_client: Some(&el),
+        };
+
+        let template = GraffitiTemplate::from_str("{execution_code}{validator_index}").unwrap();
+        assert!(!template.is_static());
+        assert!(template.requires_execution_client());
+        assert_eq!(template.as_static(), None);
+
+        assert_eq!(
+            expand("{execution_code} {validator_index}@{slot}", &variables),
+            "GE 7@42"
+        );
+        assert_eq!(
+            expand("{execution_client} {execution_version}", &variables),
+            "Geth v1.13.14-stable"
+        );
+        assert_eq!(
+            expand("LH{lighthouse_version}", &variables),
+            format!(
+                "LH{}",
+                lighthouse_version::VERSION.trim_start_matches("Lighthouse/")
+            )
+        );
+    }
+
+    #[test]
+    fn expand_missing_variables() {
+        let variables = GraffitiVariables {
+            slot: Slot::new(42),
+            validator_index: None,
+            execution_client: None,
+        };
+        assert_eq!(
+            expand("[{execution_code}][{validator_index}]", &variables),
+            "[][]"
+        );
+    }
+
+    #[test]
+    fn truncate_long_expansions() {
+        let variables = GraffitiVariables {
+            slot: Slot::new(42),
+            validator_index: None,
+            execution_client: None,
+        };
+
+        let prefix = "a".repeat(GRAFFITI_BYTES_LEN - 1);
+        assert_eq!(
+            expand(&format!("{}{{slot}}", prefix), &variables),
+            format!("{}4", prefix)
+        );
+
+        // A multi-byte character which would be split is dropped.
+        assert_eq!(expand(&format!("{}ü{{slot}}", prefix), &variables), prefix);
+    }
+
+    #[test]
+    fn from_graffiti() {
+        let graffiti: Graffiti = GraffitiString::from_str("{mr f}").unwrap().into();
+        let template = GraffitiTemplate::from(graffiti);
+        assert_eq!(template.as_static(), Some(graffiti));
+        assert_eq!(
+            template.to_string().parse::<GraffitiTemplate>(),
+            Ok(template)
+        );
+    }
+}
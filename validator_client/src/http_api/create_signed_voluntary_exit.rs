@@ -0,0 +1,74 @@
+use crate::ValidatorStore;
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use types::{Epoch, EthSpec, PublicKey, PublicKeyBytes, SignedVoluntaryExit, VoluntaryExit};
+
+/// Sign a `VoluntaryExit` for the validator with `pubkey`, without publishing it.
+///
+/// If `maybe_epoch` is not supplied the exit is created for the current epoch, according to
+/// `slot_clock`.
+pub async fn create_signed_voluntary_exit<T: 'static + SlotClock + Clone, E: EthSpec>(
+    pubkey: PublicKey,
+    maybe_epoch: Option<Epoch>,
+    validator_store: &ValidatorStore<T, E>,
+    slot_clock: &T,
+    log: &Logger,
+) -> Result<SignedVoluntaryExit, warp::Rejection> {
+    let epoch = match maybe_epoch {
+        Some(epoch) => epoch,
+        None => slot_clock
+            .now()
+            .map(|slot| slot.epoch(E::slots_per_epoch()))
+            .ok_or_else(|| {
+                warp_utils::reject::custom_server_error(
+                    "unable to determine the current epoch".to_string(),
+                )
+            })?,
+    };
+
+    if validator_store
+        .initialized_validators()
+        .read()
+        .is_enabled(&pubkey)
+        .is_none()
+    {
+        return Err(warp_utils::reject::custom_not_found(format!(
+            "no validator found with pubkey {:?}",
+            pubkey
+        )));
+    }
+
+    let pubkey_bytes = PublicKeyBytes::from(&pubkey);
+    let validator_index = validator_store
+        .validator_index(&pubkey_bytes)
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(format!(
+                "the validator index for {:?} is not known. The validator client may still be \
+                 initializing or the validator's deposit may not have been processed",
+                pubkey_bytes
+            ))
+        })?;
+
+    let voluntary_exit = VoluntaryExit {
+        epoch,
+        validator_index,
+    };
+
+    info!(
+        log,
+        "Signing voluntary exit";
+        "validator" => ?pubkey_bytes,
+        "validator_index" => validator_index,
+        "epoch" => epoch,
+    );
+
+    validator_store
+        .sign_voluntary_exit(pubkey_bytes, voluntary_exit)
+        .await
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "failed to sign voluntary exit: {:?}",
+                e
+            ))
+        })
+}
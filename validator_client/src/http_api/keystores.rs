@@ -4,10 +4,16 @@ use crate::{
     ValidatorStore,
 };
 use account_utils::ZeroizeString;
-use eth2::lighthouse_vc::std_types::{
-    DeleteKeystoreStatus, DeleteKeystoresRequest, DeleteKeystoresResponse, ImportKeystoreStatus,
-    ImportKeystoresRequest, ImportKeystoresResponse, InterchangeJsonStr, KeystoreJsonStr,
-    ListKeystoresResponse, SingleKeystoreResponse, Status,
+use eth2::lighthouse_vc::{
+    std_types::{
+        DeleteKeystoreStatus, DeleteKeystoresRequest, DeleteKeystoresResponse,
+        ImportKeystoreStatus, ImportKeystoresRequest, ImportKeystoresResponse, InterchangeJsonStr,
+        KeystoreJsonStr, ListKeystoresResponse, SingleKeystoreResponse, Status,
+    },
+    types::{
+        ExportKeystoreStatus, ExportKeystoresRequest, ExportKeystoresResponse,
+        SingleExportKeystoresResponse,
+    },
 };
 use eth2_keystore::Keystore;
 use slog::{info, warn, Logger};
@@ -41,7 +47,9 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
                         ref voting_keystore,
                         ..
                     } => (voting_keystore.path(), Some(false)),
-                    SigningMethod::Web3Signer { .. } => (None, Some(true)),
+                    SigningMethod::Web3Signer { .. } | SigningMethod::Distributed { .. } => {
+                        (None, Some(true))
+                    }
                 });
 
             SingleKeystoreResponse {
@@ -302,3 +310,83 @@ fn delete_single_keystore(
         Err("validator client shutdown".into())
     }
 }
+
+/// Export local keystore validators, returning each keystore and its password along with the
+/// slashing protection data of the validators. Nothing is deleted.
+///
+/// Only disabled validators are exported. Since a disabled validator does not sign, its slashing
+/// protection data cannot become stale once it has been returned.
+pub fn export<T: SlotClock + 'static, E: EthSpec>(
+    request: ExportKeystoresRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    log: Logger,
+) -> Result<ExportKeystoresResponse, Rejection> {
+    // Hold the lock until the slashing protection data has been exported, so that none of the
+    // validators can be enabled in the meantime.
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rwlock.read();
+
+    let data = request
+        .pubkeys
+        .iter()
+        .map(
+            |pubkey_bytes| match export_single_keystore(pubkey_bytes, &initialized_validators) {
+                Ok(Some((keystore, password))) => SingleExportKeystoresResponse {
+                    status: Status::ok(ExportKeystoreStatus::Exported),
+                    validating_keystore: Some(KeystoreJsonStr(keystore)),
+                    validating_keystore_password: Some(password),
+                },
+                Ok(None) => SingleExportKeystoresResponse {
+                    status: Status::ok(ExportKeystoreStatus::NotFound),
+                    validating_keystore: None,
+                    validating_keystore_password: None,
+                },
+                Err(error) => {
+                    warn!(
+                        log,
+                        "Error exporting keystore";
+                        "pubkey" => ?pubkey_bytes,
+                        "error" => ?error,
+                    );
+                    SingleExportKeystoresResponse {
+                        status: Status::error(ExportKeystoreStatus::Error, error),
+                        validating_keystore: None,
+                        validating_keystore_password: None,
+                    }
+                }
+            },
+        )
+        .collect();
+
+    let slashing_protection = validator_store
+        .export_slashing_protection_for_keys(&request.pubkeys)
+        .map_err(|e| {
+            custom_server_error(format!("error exporting slashing protection: {:?}", e))
+        })?;
+
+    Ok(ExportKeystoresResponse {
+        data,
+        slashing_protection,
+    })
+}
+
+fn export_single_keystore(
+    pubkey_bytes: &PublicKeyBytes,
+    initialized_validators: &InitializedValidators,
+) -> Result<Option<(Keystore, ZeroizeString)>, String> {
+    let pubkey = pubkey_bytes
+        .decompress()
+        .map_err(|e| format!("invalid pubkey, {:?}: {:?}", pubkey_bytes, e))?;
+
+    match initialized_validators.is_enabled(&pubkey) {
+        None => return Ok(None),
+        Some(true) => return Err("validator must be disabled before it is exported".into()),
+        Some(false) => (),
+    }
+
+    match initialized_validators.keystore_and_password(&pubkey) {
+        Ok(keystore_and_password) => Ok(Some(keystore_and_password)),
+        Err(Error::ValidatorNotInitialized(_)) => Ok(None),
+        Err(e) => Err(format!("unable to export keystore: {:?}", e)),
+    }
+}
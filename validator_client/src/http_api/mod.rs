@@ -1,19 +1,23 @@
 mod api_secret;
+mod create_signed_voluntary_exit;
 mod create_validator;
 mod keystores;
 mod remotekeys;
 mod tests;
 
-use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
+pub mod test_utils;
+
+use crate::{determine_graffiti, GraffitiFile, GraffitiTemplate, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
     validator_definitions::{SigningDefinition, ValidatorDefinition, Web3SignerDefinition},
 };
 pub use api_secret::ApiSecret;
+use create_signed_voluntary_exit::create_signed_voluntary_exit;
 use create_validator::{create_validators_mnemonic, create_validators_web3signer};
 use eth2::lighthouse_vc::{
     std_types::{AuthResponse, GetFeeRecipientResponse, GetGasLimitResponse},
-    types::{self as api_types, GenericResponse, Graffiti, PublicKey, PublicKeyBytes},
+    types::{self as api_types, GenericResponse, PublicKey, PublicKeyBytes},
 };
 use lighthouse_version::version_with_platform;
 use parking_lot::RwLock;
@@ -65,9 +69,10 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub slot_clock: T,
     pub validator_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
-    pub graffiti_flag: Option<Graffiti>,
+    pub graffiti_flag: Option<GraffitiTemplate>,
     pub spec: ChainSpec,
     pub config: Config,
     pub log: Logger,
@@ -94,6 +99,18 @@ impl Default for Config {
     }
 }
 
+/// Rejects a change to a setting which the validator's proposer config entry overrides, since the
+/// change would have no effect.
+fn proposer_config_override_rejection(
+    setting: &str,
+    validator_pubkey: &PublicKey,
+) -> warp::Rejection {
+    warp_utils::reject::custom_bad_request(format!(
+        "the {} for {:?} is set by the proposer config and cannot be changed via the API",
+        setting, validator_pubkey
+    ))
+}
+
 /// Creates a server that will serve requests using information from `ctx`.
 ///
 /// The server will shut down gracefully when the `shutdown` future resolves.
@@ -183,8 +200,11 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let inner_graffiti_file = ctx.graffiti_file.clone();
     let graffiti_file_filter = warp::any().map(move || inner_graffiti_file.clone());
 
-    let inner_graffiti_flag = ctx.graffiti_flag;
-    let graffiti_flag_filter = warp::any().map(move || inner_graffiti_flag);
+    let inner_graffiti_flag = ctx.graffiti_flag.clone();
+    let graffiti_flag_filter = warp::any().map(move || inner_graffiti_flag.clone());
+
+    let inner_slot_clock = ctx.slot_clock.clone();
+    let slot_clock_filter = warp::any().map(move || inner_slot_clock.clone());
 
     let inner_ctx = ctx.clone();
     let log_filter = warp::any().map(move || inner_ctx.log.clone());
@@ -320,6 +340,24 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/doppelganger
+    let get_lighthouse_doppelganger = warp::path("lighthouse")
+        .and(warp::path("doppelganger"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                let states = validator_store.doppelganger_states().ok_or_else(|| {
+                    warp_utils::reject::custom_not_found(
+                        "doppelganger protection is not enabled".to_string(),
+                    )
+                })?;
+
+                Ok(api_types::GenericResponse::from(states))
+            })
+        });
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -350,7 +388,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and_then(
             |validator_store: Arc<ValidatorStore<T, E>>,
              graffiti_file: Option<GraffitiFile>,
-             graffiti_flag: Option<Graffiti>,
+             graffiti_flag: Option<GraffitiTemplate>,
              signer,
              log| {
                 blocking_signed_json_task(signer, move || {
@@ -365,9 +403,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             &log,
                             graffiti_file.clone(),
                             graffiti_definition,
-                            graffiti_flag,
+                            graffiti_flag.clone(),
                         );
-                        result.insert(key.to_string(), graffiti.map(|g| g.as_utf8_lossy()));
+                        // The graffiti as configured, i.e. unchanged unless it has variables.
+                        result.insert(key.to_string(), graffiti.map(|g| g.to_string()));
                     }
                     Ok(api_types::GenericResponse::from(result))
                 })
@@ -612,6 +651,24 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
              signer,
              task_executor: TaskExecutor| {
                 blocking_signed_json_task(signer, move || {
+                    let proposer_config = validator_store
+                        .get_validator_proposer_config(&PublicKeyBytes::from(&validator_pubkey))
+                        .unwrap_or_default();
+                    if body.gas_limit.is_some() && proposer_config.gas_limit().is_some() {
+                        return Err(proposer_config_override_rejection(
+                            "gas limit",
+                            &validator_pubkey,
+                        ));
+                    }
+                    if body.builder_proposals.is_some()
+                        && proposer_config.builder_proposals().is_some()
+                    {
+                        return Err(proposer_config_override_rejection(
+                            "builder proposals",
+                            &validator_pubkey,
+                        ));
+                    }
+
                     let initialized_validators_rw_lock = validator_store.initialized_validators();
                     let mut initialized_validators = initialized_validators_rw_lock.write();
 
@@ -743,6 +800,16 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             validator_pubkey
                         )));
                     }
+                    if validator_store
+                        .get_validator_proposer_config(&PublicKeyBytes::from(&validator_pubkey))
+                        .and_then(|config| config.fee_recipient)
+                        .is_some()
+                    {
+                        return Err(proposer_config_override_rejection(
+                            "fee recipient",
+                            &validator_pubkey,
+                        ));
+                    }
                     validator_store
                         .initialized_validators()
                         .write()
@@ -780,6 +847,16 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             validator_pubkey
                         )));
                     }
+                    if validator_store
+                        .get_validator_proposer_config(&PublicKeyBytes::from(&validator_pubkey))
+                        .and_then(|config| config.fee_recipient)
+                        .is_some()
+                    {
+                        return Err(proposer_config_override_rejection(
+                            "fee recipient",
+                            &validator_pubkey,
+                        ));
+                    }
                     validator_store
                         .initialized_validators()
                         .write()
@@ -852,6 +929,16 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             validator_pubkey
                         )));
                     }
+                    if validator_store
+                        .get_validator_proposer_config(&PublicKeyBytes::from(&validator_pubkey))
+                        .and_then(|config| config.gas_limit())
+                        .is_some()
+                    {
+                        return Err(proposer_config_override_rejection(
+                            "gas limit",
+                            &validator_pubkey,
+                        ));
+                    }
                     validator_store
                         .initialized_validators()
                         .write()
@@ -889,6 +976,16 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             validator_pubkey
                         )));
                     }
+                    if validator_store
+                        .get_validator_proposer_config(&PublicKeyBytes::from(&validator_pubkey))
+                        .and_then(|config| config.gas_limit())
+                        .is_some()
+                    {
+                        return Err(proposer_config_override_rejection(
+                            "gas limit",
+                            &validator_pubkey,
+                        ));
+                    }
                     validator_store
                         .initialized_validators()
                         .write()
@@ -904,6 +1001,46 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         )
         .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
 
+    // POST /eth/v1/validator/{pubkey}/voluntary_exit
+    let post_validators_voluntary_exit = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("voluntary_exit"))
+        .and(warp::query::<api_types::VoluntaryExitQuery>())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(slot_clock_filter)
+        .and(log_filter.clone())
+        .and(signer.clone())
+        .and(task_executor_filter.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             query: api_types::VoluntaryExitQuery,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             slot_clock: T,
+             log,
+             signer,
+             task_executor: TaskExecutor| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let signed_voluntary_exit =
+                            handle.block_on(create_signed_voluntary_exit(
+                                validator_pubkey,
+                                query.epoch,
+                                &validator_store,
+                                &slot_clock,
+                                &log,
+                            ))?;
+                        Ok(GenericResponse::from(signed_voluntary_exit))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
     // GET /eth/v1/keystores
     let get_std_keystores = std_keystores
         .and(signer.clone())
@@ -941,6 +1078,21 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // POST /lighthouse/keystores/export
+    let post_lighthouse_keystores_export = warp::path("lighthouse")
+        .and(warp::path("keystores"))
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(log_filter.clone())
+        .and_then(|request, signer, validator_store, log| {
+            blocking_signed_json_task(signer, move || {
+                keystores::export(request, validator_store, log)
+            })
+        });
+
     // GET /eth/v1/remotekeys
     let get_std_remotekeys = std_remotekeys
         .and(signer.clone())
@@ -989,6 +1141,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_doppelganger)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_fee_recipient)
@@ -1003,6 +1156,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_web3signer)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
+                        .or(post_validators_voluntary_exit)
+                        .or(post_lighthouse_keystores_export)
                         .or(post_std_keystores)
                         .or(post_std_remotekeys),
                 ))
@@ -4,10 +4,11 @@
 mod keystores;
 
 use crate::doppelganger_service::DoppelgangerService;
+use crate::proposer_config::{BuilderConfig, ProposerConfig, ProposerConfigFile};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
-    Config, ValidatorDefinitions, ValidatorStore,
+    Config, GraffitiTemplate, ValidatorDefinitions, ValidatorStore,
 };
 use account_utils::{
     eth2_wallet::WalletBuilder, mnemonic_from_phrase, random_mnemonic, random_password,
@@ -20,6 +21,7 @@ use eth2::{
     Error as ApiError,
 };
 use eth2_keystore::KeystoreBuilder;
+use eth2_serde_utils::quoted_u64::MaybeQuoted;
 use logging::test_logger;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
@@ -99,11 +101,11 @@ impl ApiTester {
 
         let validator_store = Arc::new(ValidatorStore::<_, E>::new(
             initialized_validators,
-            slashing_protection,
+            Arc::new(slashing_protection),
             Hash256::repeat_byte(42),
             spec,
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
-            slot_clock,
+            slot_clock.clone(),
             &config,
             executor.clone(),
             log.clone(),
@@ -120,8 +122,9 @@ impl ApiTester {
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            slot_clock,
             graffiti_file: None,
-            graffiti_flag: Some(Graffiti::default()),
+            graffiti_flag: Some(GraffitiTemplate::from(Graffiti::default())),
             spec: E::default_spec(),
             config: HttpConfig {
                 enabled: true,
@@ -558,6 +561,75 @@ impl ApiTester {
         self
     }
 
+    /// Checks that the gas limit of the validator at `index` cannot be changed via the API, because
+    /// it is set by the validator's proposer config entry.
+    pub async fn assert_gas_limit_update_rejected(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let gas_limit = self.validator_store.get_gas_limit(&validator.voting_pubkey);
+
+        let err = self
+            .client
+            .patch_lighthouse_validators(&validator.voting_pubkey, None, Some(1), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+        let err = self
+            .client
+            .post_gas_limit(
+                &validator.voting_pubkey,
+                &UpdateGasLimitRequest { gas_limit: 1 },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+        let err = self
+            .client
+            .delete_gas_limit(&validator.voting_pubkey)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        assert_eq!(
+            self.validator_store.get_gas_limit(&validator.voting_pubkey),
+            gas_limit
+        );
+
+        self
+    }
+
+    /// Sets a proposer config with a default gas limit and per-validator gas limits for the
+    /// validators at the given indices.
+    pub async fn set_proposer_config_gas_limits(
+        self,
+        default_gas_limit: u64,
+        validator_gas_limits: &[(usize, u64)],
+    ) -> Self {
+        let validators = self.client.get_lighthouse_validators().await.unwrap().data;
+
+        let proposer_config = |gas_limit: u64| ProposerConfig {
+            fee_recipient: None,
+            builder: Some(BuilderConfig {
+                gas_limit: Some(MaybeQuoted { value: gas_limit }),
+                ..<_>::default()
+            }),
+        };
+        self.validator_store
+            .set_proposer_config(ProposerConfigFile {
+                proposer_config: validator_gas_limits
+                    .iter()
+                    .map(|(index, gas_limit)| {
+                        (
+                            validators[*index].voting_pubkey,
+                            proposer_config(*gas_limit),
+                        )
+                    })
+                    .collect(),
+                default_config: proposer_config(default_gas_limit),
+            });
+
+        self
+    }
+
     pub async fn set_builder_proposals(self, index: usize, builder_proposals: bool) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
@@ -585,6 +657,78 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_sign_voluntary_exit(self, index: usize, maybe_epoch: Option<Epoch>) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        // The validator index is usually learned from the beacon node, so set it manually.
+        self.initialized_validators
+            .write()
+            .set_index(&validator.voting_pubkey, index as u64);
+
+        let signed_exit = self
+            .client
+            .post_validator_voluntary_exit(&validator.voting_pubkey, maybe_epoch)
+            .await
+            .unwrap()
+            .data;
+
+        // The testing slot clock is at slot 0, so the exit defaults to epoch 0.
+        let expected_epoch = maybe_epoch.unwrap_or_else(|| Epoch::new(0));
+        assert_eq!(signed_exit.message.epoch, expected_epoch);
+        assert_eq!(signed_exit.message.validator_index, index as u64);
+
+        let spec = E::default_spec();
+        let domain = spec.get_domain(
+            expected_epoch,
+            Domain::VoluntaryExit,
+            &spec.fork_at_epoch(expected_epoch),
+            Hash256::repeat_byte(42),
+        );
+        assert!(signed_exit.signature.verify(
+            &validator.voting_pubkey.decompress().unwrap(),
+            signed_exit.message.signing_root(domain)
+        ));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_doppelganger(self) -> Self {
+        let validators = self.client.get_lighthouse_validators().await.unwrap().data;
+        let states = self
+            .client
+            .get_lighthouse_doppelganger()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(states.len(), validators.len());
+        for validator in validators {
+            let state = states
+                .iter()
+                .find(|state| state.pubkey == validator.voting_pubkey)
+                .expect("validator should be known to doppelganger service");
+
+            // The testing slot clock is in the genesis epoch, so no detection is required.
+            assert!(state.signing_enabled);
+            assert_eq!(state.remaining_epochs, 0);
+            assert_eq!(state.last_signed_epoch, None);
+            assert_eq!(state.detection, DoppelgangerDetection::Full);
+        }
+
+        self
+    }
+
+    pub async fn test_sign_voluntary_exit_unknown_validator(self) -> Self {
+        let keypair = Keypair::random();
+        let err = self
+            .client
+            .post_validator_voluntary_exit(&keypair.pk.compress(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        self
+    }
 }
 
 struct HdValidatorScenario {
@@ -642,6 +786,10 @@ fn routes_with_invalid_auth() {
                     .await
             })
             .await
+            .test_with_invalid_auth(
+                |client| async move { client.get_lighthouse_doppelganger().await },
+            )
+            .await
             .test_with_invalid_auth(|client| async move {
                 client
                     .post_lighthouse_validators(vec![ValidatorRequest {
@@ -720,6 +868,15 @@ fn routes_with_invalid_auth() {
                     .await
             })
             .await
+            .test_with_invalid_auth(|client| async move {
+                let keypair = Keypair::random();
+                client
+                    .post_lighthouse_keystores_export(&ExportKeystoresRequest {
+                        pubkeys: vec![keypair.pk.compress()],
+                    })
+                    .await
+            })
+            .await
     });
 }
 
@@ -840,6 +997,95 @@ fn validator_gas_limit() {
     });
 }
 
+#[test]
+fn validator_gas_limit_proposer_config() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .create_hd_validators(HdValidatorScenario {
+                count: 2,
+                specify_mnemonic: false,
+                key_derivation_path_offset: 0,
+                disabled: vec![],
+            })
+            .await
+            .set_gas_limit(0, 500)
+            .await
+            // The proposer config default is overridden by `validator_definitions.yml`.
+            .set_proposer_config_gas_limits(750, &[])
+            .await
+            .assert_gas_limit(0, 500)
+            .await
+            .assert_gas_limit(1, 750)
+            .await
+            // Per-validator proposer config entries override `validator_definitions.yml`.
+            .set_proposer_config_gas_limits(750, &[(0, 1000), (1, 2000)])
+            .await
+            .assert_gas_limit(0, 1000)
+            .await
+            .assert_gas_limit(1, 2000)
+            .await
+            // Changes via the API would have no effect, so they are rejected.
+            .assert_gas_limit_update_rejected(0)
+            .await
+            // Without a per-validator entry, `validator_definitions.yml` applies again.
+            .set_proposer_config_gas_limits(750, &[])
+            .await
+            .assert_gas_limit(0, 500)
+            .await
+            .set_gas_limit(0, 600)
+            .await
+            .assert_gas_limit(0, 600)
+            .await
+    });
+}
+
+#[test]
+fn validator_voluntary_exit() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .create_hd_validators(HdValidatorScenario {
+                count: 2,
+                specify_mnemonic: false,
+                key_derivation_path_offset: 0,
+                disabled: vec![],
+            })
+            .await
+            .test_sign_voluntary_exit(0, None)
+            .await
+            .test_sign_voluntary_exit(1, Some(Epoch::new(256)))
+            .await
+            .test_sign_voluntary_exit_unknown_validator()
+            .await
+    });
+}
+
+#[test]
+fn doppelganger_states() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .test_get_lighthouse_doppelganger()
+            .await
+            .create_hd_validators(HdValidatorScenario {
+                count: 2,
+                specify_mnemonic: false,
+                key_derivation_path_offset: 0,
+                disabled: vec![1],
+            })
+            .await
+            .test_get_lighthouse_doppelganger()
+            .await
+    });
+}
+
 #[test]
 fn validator_builder_proposals() {
     let runtime = build_runtime();
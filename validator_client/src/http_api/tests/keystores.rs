@@ -2,7 +2,9 @@ use super::super::super::validator_store::DEFAULT_GAS_LIMIT;
 use super::*;
 use account_utils::random_password_string;
 use bls::PublicKeyBytes;
-use eth2::lighthouse_vc::types::UpdateFeeRecipientRequest;
+use eth2::lighthouse_vc::types::{
+    ExportKeystoreStatus, ExportKeystoresRequest, UpdateFeeRecipientRequest,
+};
 use eth2::lighthouse_vc::{
     http_client::ValidatorClientHttpClient as HttpClient,
     std_types::{KeystoreJsonStr as Keystore, *},
@@ -1172,6 +1174,97 @@ fn generic_migration_test(
     });
 }
 
+#[test]
+fn export_keystores() {
+    run_test(|tester| async move {
+        let (keystores, passwords): (Vec<_>, Vec<_>) = (0..2)
+            .map(|_| {
+                let password = random_password_string();
+                (new_keystore(password.clone()), password)
+            })
+            .unzip();
+        let pubkeys = keystores.iter().map(keystore_pubkey).collect::<Vec<_>>();
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords: passwords.clone(),
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        let mut attestation = make_attestation(1, 2);
+        tester
+            .validator_store
+            .sign_attestation(pubkeys[0], 0, &mut attestation, Epoch::new(2))
+            .await
+            .unwrap();
+
+        // Enabled validators should not be exported.
+        let export_req = ExportKeystoresRequest {
+            pubkeys: pubkeys.clone(),
+        };
+        let export_res = tester
+            .client
+            .post_lighthouse_keystores_export(&export_req)
+            .await
+            .unwrap();
+        for response in &export_res.data {
+            assert_eq!(response.status.status, ExportKeystoreStatus::Error);
+            assert!(response.validating_keystore.is_none());
+            assert!(response.validating_keystore_password.is_none());
+        }
+
+        // Once disabled, each keystore should be returned with its password.
+        for pubkey in &pubkeys {
+            tester
+                .client
+                .patch_lighthouse_validators(pubkey, Some(false), None, None)
+                .await
+                .unwrap();
+        }
+        let export_res = tester
+            .client
+            .post_lighthouse_keystores_export(&export_req)
+            .await
+            .unwrap();
+        for ((response, keystore), password) in
+            export_res.data.iter().zip_eq(&keystores).zip(&passwords)
+        {
+            assert_eq!(response.status.status, ExportKeystoreStatus::Exported);
+            assert_eq!(response.validating_keystore.as_ref(), Some(keystore));
+            assert_eq!(
+                response
+                    .validating_keystore_password
+                    .as_ref()
+                    .unwrap()
+                    .as_str(),
+                password.as_str()
+            );
+        }
+        assert_eq!(export_res.slashing_protection.data.len(), 2);
+
+        // Exporting should not delete anything.
+        assert_eq!(tester.client.get_keystores().await.unwrap().data.len(), 2);
+
+        // Unknown validators should not be found.
+        let export_res = tester
+            .client
+            .post_lighthouse_keystores_export(&ExportKeystoresRequest {
+                pubkeys: vec![Keypair::random().pk.compress()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            export_res.data[0].status.status,
+            ExportKeystoreStatus::NotFound
+        );
+    })
+}
+
 #[test]
 fn delete_keystores_twice() {
     run_test(|tester| async move {
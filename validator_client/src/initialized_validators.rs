@@ -6,7 +6,8 @@
 //! The `InitializedValidators` struct in this file serves as the source-of-truth of which
 //! validators are managed by this validator client.
 
-use crate::signing_method::SigningMethod;
+use crate::password_source;
+use crate::signing_method::{KeyShare, SigningMethod};
 use account_utils::{
     read_password, read_password_from_user,
     validator_definitions::{
@@ -66,6 +67,8 @@ pub enum Error {
     UnableToDecryptKeystore(eth2_keystore::Error),
     /// There was a filesystem error when reading the keystore password from disk.
     UnableToReadVotingKeystorePassword(io::Error),
+    /// The keystore password could not be fetched from its password source.
+    UnableToFetchVotingKeystorePassword(password_source::Error),
     /// There was an error updating the on-disk validator definitions file.
     UnableToSaveDefinitions(validator_definitions::Error),
     /// It is not legal to try and initialize a disabled validator definition.
@@ -97,6 +100,24 @@ pub enum Error {
     UnableToBuildWeb3SignerClient(ReqwestError),
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
+    /// The password for a local keystore is neither in its definition nor in a password file.
+    UnknownVotingKeystorePassword,
+    /// The password for a local keystore is not valid UTF-8.
+    VotingKeystorePasswordNotUtf8,
+    /// The threshold of a distributed validator is zero or exceeds its number of key shares.
+    InvalidKeyShareThreshold {
+        threshold: usize,
+        num_shares: usize,
+    },
+    /// A key share index is zero or is used by more than one key share.
+    InvalidKeyShareIndex(u64),
+    /// A key share is itself defined as a distributed validator.
+    NestedDistributedSigningDefinition,
+    /// Unable to combine the public keys of the key shares.
+    KeyShareCombinationFailed(bls::Error),
+    /// The public keys of the key shares do not combine to the voting public key when the key
+    /// share with this index is included.
+    KeySharePublicKeyMismatch(u64),
 }
 
 impl From<LockfileError> for Error {
@@ -129,6 +150,8 @@ impl InitializedValidator {
             .ok(),
             // Web3Signer validators do not have any lockfiles.
             SigningMethod::Web3Signer { .. } => None,
+            // Distributed validators may hold several lockfiles, one for each local key share.
+            SigningMethod::Distributed { .. } => None,
         }
     }
 
@@ -181,129 +204,57 @@ impl InitializedValidator {
         }
 
         let signing_method = match def.signing_definition {
-            // Load the keystore, password, decrypt the keypair and create a lockfile for a
-            // EIP-2335 keystore on the local filesystem.
-            SigningDefinition::LocalKeystore {
-                voting_keystore_path,
-                voting_keystore_password_path,
-                voting_keystore_password,
-            } => {
-                use std::collections::hash_map::Entry::*;
-                let voting_keystore = match key_stores.entry(voting_keystore_path.clone()) {
-                    Vacant(entry) => entry.insert(open_keystore(&voting_keystore_path)?),
-                    Occupied(entry) => entry.into_mut(),
-                };
-
-                let voting_keypair = if let Some(keypair) = key_cache.get(voting_keystore.uuid()) {
-                    keypair
-                } else {
-                    let keystore = voting_keystore.clone();
-                    let keystore_path = voting_keystore_path.clone();
-                    // Decoding a local keystore can take several seconds, therefore it's best
-                    // to keep if off the core executor. This also has the fortunate effect of
-                    // interrupting the potentially long-running task during shut down.
-                    let (password, keypair) = tokio::task::spawn_blocking(move || {
-                        Result::<_, Error>::Ok(
-                            match (voting_keystore_password_path, voting_keystore_password) {
-                                // If the password is supplied, use it and ignore the path
-                                // (if supplied).
-                                (_, Some(password)) => (
-                                    password.as_ref().to_vec().into(),
-                                    keystore
-                                        .decrypt_keypair(password.as_ref())
-                                        .map_err(Error::UnableToDecryptKeystore)?,
-                                ),
-                                // If only the path is supplied, use the path.
-                                (Some(path), None) => {
-                                    let password = read_password(path)
-                                        .map_err(Error::UnableToReadVotingKeystorePassword)?;
-                                    let keypair = keystore
-                                        .decrypt_keypair(password.as_bytes())
-                                        .map_err(Error::UnableToDecryptKeystore)?;
-                                    (password, keypair)
-                                }
-                                // If there is no password available, maybe prompt for a password.
-                                (None, None) => {
-                                    let (password, keypair) = unlock_keystore_via_stdin_password(
-                                        &keystore,
-                                        &keystore_path,
-                                    )?;
-                                    (password.as_ref().to_vec().into(), keypair)
-                                }
-                            },
-                        )
-                    })
-                    .await
-                    .map_err(Error::TokioJoin)??;
-                    key_cache.add(keypair.clone(), voting_keystore.uuid(), password);
-                    keypair
-                };
-
-                if voting_keypair.pk != def.voting_public_key {
-                    return Err(Error::VotingPublicKeyMismatch {
-                        definition: Box::new(def.voting_public_key),
-                        keystore: Box::new(voting_keypair.pk),
+            SigningDefinition::Distributed { threshold, shares } => {
+                if threshold == 0 || threshold > shares.len() {
+                    return Err(Error::InvalidKeyShareThreshold {
+                        threshold,
+                        num_shares: shares.len(),
                     });
                 }
 
-                // Append a `.lock` suffix to the voting keystore.
-                let lockfile_path = get_lockfile_path(&voting_keystore_path)
-                    .ok_or_else(|| Error::BadVotingKeystorePath(voting_keystore_path.clone()))?;
-
-                let voting_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
+                let mut indices = HashSet::with_capacity(shares.len());
+                let mut key_shares = Vec::with_capacity(shares.len());
+                for share in shares {
+                    if share.index == 0 || !indices.insert(share.index) {
+                        return Err(Error::InvalidKeyShareIndex(share.index));
+                    }
 
-                SigningMethod::LocalKeystore {
-                    voting_keystore_path,
-                    voting_keystore_lockfile,
-                    voting_keystore: voting_keystore.clone(),
-                    voting_keypair: Arc::new(voting_keypair),
+                    // Key shares are kept out of the key cache, since it is indexed by the voting
+                    // public keys of the validator definitions.
+                    let signing_method = build_signing_method(
+                        share.signing_definition,
+                        share.public_key.clone(),
+                        None,
+                        key_stores,
+                        web3_signer_client_map,
+                    )
+                    .await?;
+
+                    key_shares.push(KeyShare {
+                        index: share.index,
+                        public_key: share.public_key,
+                        signing_method,
+                    });
                 }
-            }
-            SigningDefinition::Web3Signer(web3_signer) => {
-                let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
-                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
-
-                let request_timeout = web3_signer
-                    .request_timeout_ms
-                    .map(Duration::from_millis)
-                    .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
-
-                // Check if a client has already been initialized for this remote signer url.
-                let http_client = if let Some(client_map) = web3_signer_client_map {
-                    match client_map.get(&web3_signer) {
-                        Some(client) => client.clone(),
-                        None => {
-                            let client = build_web3_signer_client(
-                                web3_signer.root_certificate_path.clone(),
-                                web3_signer.client_identity_path.clone(),
-                                web3_signer.client_identity_password.clone(),
-                                request_timeout,
-                            )?;
-                            client_map.insert(web3_signer, client.clone());
-                            client
-                        }
-                    }
-                } else {
-                    // There are no clients in the map.
-                    let mut new_web3_signer_client_map: HashMap<Web3SignerDefinition, Client> =
-                        HashMap::new();
-                    let client = build_web3_signer_client(
-                        web3_signer.root_certificate_path.clone(),
-                        web3_signer.client_identity_path.clone(),
-                        web3_signer.client_identity_password.clone(),
-                        request_timeout,
-                    )?;
-                    new_web3_signer_client_map.insert(web3_signer, client.clone());
-                    *web3_signer_client_map = Some(new_web3_signer_client_map);
-                    client
-                };
 
-                SigningMethod::Web3Signer {
-                    signing_url,
-                    http_client,
+                check_key_share_public_keys(threshold, &key_shares, &def.voting_public_key)?;
+
+                SigningMethod::Distributed {
+                    threshold,
+                    key_shares,
                     voting_public_key: def.voting_public_key,
                 }
             }
+            signing_definition => {
+                build_signing_method(
+                    signing_definition,
+                    def.voting_public_key,
+                    Some(key_cache),
+                    key_stores,
+                    web3_signer_client_map,
+                )
+                .await?
+            }
         };
 
         Ok(Self {
@@ -322,11 +273,206 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
             SigningMethod::Web3Signer {
                 voting_public_key, ..
+            }
+            | SigningMethod::Distributed {
+                voting_public_key, ..
             } => voting_public_key,
         }
     }
 }
 
+/// Check that every key share lies on the same polynomial as the first `threshold - 1` shares,
+/// and that the polynomial is that of the `voting_public_key`.
+///
+/// A share that fails this check could sign correctly with its own key and still spoil every
+/// combined signature that includes it.
+fn check_key_share_public_keys(
+    threshold: usize,
+    key_shares: &[KeyShare],
+    voting_public_key: &PublicKey,
+) -> Result<(), Error> {
+    let (base_shares, other_shares) = key_shares.split_at(threshold - 1);
+    let base_shares = base_shares
+        .iter()
+        .map(|key_share| (key_share.index, &key_share.public_key))
+        .collect::<Vec<_>>();
+
+    for key_share in other_shares {
+        let mut shares = base_shares.clone();
+        shares.push((key_share.index, &key_share.public_key));
+
+        let combined =
+            PublicKey::combine_shares(&shares).map_err(Error::KeyShareCombinationFailed)?;
+        if combined != *voting_public_key {
+            return Err(Error::KeySharePublicKeyMismatch(key_share.index));
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the `SigningMethod` for a validator, or key share, with public key `voting_public_key`.
+///
+/// The `key_cache` is only consulted and updated when it is provided.
+async fn build_signing_method(
+    signing_definition: SigningDefinition,
+    voting_public_key: PublicKey,
+    key_cache: Option<&mut KeyCache>,
+    key_stores: &mut HashMap<PathBuf, Keystore>,
+    web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+) -> Result<SigningMethod, Error> {
+    let signing_method = match signing_definition {
+        // Load the keystore, password, decrypt the keypair and create a lockfile for a
+        // EIP-2335 keystore on the local filesystem.
+        SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            voting_keystore_password_path,
+            voting_keystore_password,
+            voting_keystore_password_source,
+        } => {
+            use std::collections::hash_map::Entry::*;
+            let voting_keystore = match key_stores.entry(voting_keystore_path.clone()) {
+                Vacant(entry) => entry.insert(open_keystore(&voting_keystore_path)?),
+                Occupied(entry) => entry.into_mut(),
+            };
+
+            let voting_keypair = if let Some(keypair) = key_cache
+                .as_ref()
+                .and_then(|key_cache| key_cache.get(voting_keystore.uuid()))
+            {
+                keypair
+            } else {
+                let keystore = voting_keystore.clone();
+                let keystore_path = voting_keystore_path.clone();
+                let public_key = voting_public_key.clone();
+                // Decoding a local keystore can take several seconds, therefore it's best
+                // to keep if off the core executor. This also has the fortunate effect of
+                // interrupting the potentially long-running task during shut down.
+                let (password, keypair) = tokio::task::spawn_blocking(move || {
+                    Result::<_, Error>::Ok(
+                        match (
+                            voting_keystore_password_path,
+                            voting_keystore_password,
+                            voting_keystore_password_source,
+                        ) {
+                            // If the password is supplied, use it and ignore the path and source
+                            // (if supplied).
+                            (_, Some(password), _) => (
+                                password.as_ref().to_vec().into(),
+                                keystore
+                                    .decrypt_keypair(password.as_ref())
+                                    .map_err(Error::UnableToDecryptKeystore)?,
+                            ),
+                            // If the path is supplied, use it and ignore the source (if supplied).
+                            (Some(path), None, _) => {
+                                let password = read_password(path)
+                                    .map_err(Error::UnableToReadVotingKeystorePassword)?;
+                                let keypair = keystore
+                                    .decrypt_keypair(password.as_bytes())
+                                    .map_err(Error::UnableToDecryptKeystore)?;
+                                (password, keypair)
+                            }
+                            // If only a password source is supplied, fetch the password from it.
+                            (None, None, Some(source)) => {
+                                let password =
+                                    password_source::fetch_password(&source, &public_key)
+                                        .map_err(Error::UnableToFetchVotingKeystorePassword)?;
+                                let keypair = keystore
+                                    .decrypt_keypair(password.as_ref())
+                                    .map_err(Error::UnableToDecryptKeystore)?;
+                                (password.as_ref().to_vec().into(), keypair)
+                            }
+                            // If there is no password available, maybe prompt for a password.
+                            (None, None, None) => {
+                                let (password, keypair) =
+                                    unlock_keystore_via_stdin_password(&keystore, &keystore_path)?;
+                                (password.as_ref().to_vec().into(), keypair)
+                            }
+                        },
+                    )
+                })
+                .await
+                .map_err(Error::TokioJoin)??;
+                if let Some(key_cache) = key_cache {
+                    key_cache.add(keypair.clone(), voting_keystore.uuid(), password);
+                }
+                keypair
+            };
+
+            if voting_keypair.pk != voting_public_key {
+                return Err(Error::VotingPublicKeyMismatch {
+                    definition: Box::new(voting_public_key),
+                    keystore: Box::new(voting_keypair.pk),
+                });
+            }
+
+            // Append a `.lock` suffix to the voting keystore.
+            let lockfile_path = get_lockfile_path(&voting_keystore_path)
+                .ok_or_else(|| Error::BadVotingKeystorePath(voting_keystore_path.clone()))?;
+
+            let voting_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
+
+            SigningMethod::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_lockfile,
+                voting_keystore: voting_keystore.clone(),
+                voting_keypair: Arc::new(voting_keypair),
+            }
+        }
+        SigningDefinition::Web3Signer(web3_signer) => {
+            let signing_url = build_web3_signer_url(&web3_signer.url, &voting_public_key)
+                .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+
+            let request_timeout = web3_signer
+                .request_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+
+            // Check if a client has already been initialized for this remote signer url.
+            let http_client = if let Some(client_map) = web3_signer_client_map {
+                match client_map.get(&web3_signer) {
+                    Some(client) => client.clone(),
+                    None => {
+                        let client = build_web3_signer_client(
+                            web3_signer.root_certificate_path.clone(),
+                            web3_signer.client_identity_path.clone(),
+                            web3_signer.client_identity_password.clone(),
+                            request_timeout,
+                        )?;
+                        client_map.insert(web3_signer, client.clone());
+                        client
+                    }
+                }
+            } else {
+                // There are no clients in the map.
+                let mut new_web3_signer_client_map: HashMap<Web3SignerDefinition, Client> =
+                    HashMap::new();
+                let client = build_web3_signer_client(
+                    web3_signer.root_certificate_path.clone(),
+                    web3_signer.client_identity_path.clone(),
+                    web3_signer.client_identity_password.clone(),
+                    request_timeout,
+                )?;
+                new_web3_signer_client_map.insert(web3_signer, client.clone());
+                *web3_signer_client_map = Some(new_web3_signer_client_map);
+                client
+            };
+
+            SigningMethod::Web3Signer {
+                signing_url,
+                http_client,
+                voting_public_key,
+            }
+        }
+        // Key shares must be held by a local keystore or a remote signer.
+        SigningDefinition::Distributed { .. } => {
+            return Err(Error::NestedDistributedSigningDefinition)
+        }
+    };
+
+    Ok(signing_method)
+}
+
 pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate, Error> {
     let mut buf = Vec::new();
     File::open(&pem_path)
@@ -399,6 +545,7 @@ fn unlock_keystore_via_stdin_password(
     eprintln!();
     eprintln!(" - voting_keystore_password");
     eprintln!(" - voting_keystore_password_path");
+    eprintln!(" - voting_keystore_password_source");
     eprintln!();
     eprintln!(
         "You may exit and update {} or enter a password. \
@@ -526,6 +673,71 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Returns the voting keystore and its password for the local keystore validator with
+    /// `pubkey`, so that it may be exported to another validator client.
+    ///
+    /// The password is read from the validator definition, a password file or an external
+    /// password source. Fetching it from a password source blocks the current thread, see
+    /// `password_source`.
+    pub fn keystore_and_password(
+        &self,
+        pubkey: &PublicKey,
+    ) -> Result<(Keystore, ZeroizeString), Error> {
+        let def = self
+            .definitions
+            .as_slice()
+            .iter()
+            .find(|def| &def.voting_public_key == pubkey)
+            .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+
+        let (voting_keystore_path, password) = match &def.signing_definition {
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password: Some(password),
+                ..
+            } => (voting_keystore_path, password.clone()),
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_path: Some(path),
+                ..
+            } => {
+                let password =
+                    read_password(path).map_err(Error::UnableToReadVotingKeystorePassword)?;
+                let password = String::from_utf8(password.as_bytes().to_vec())
+                    .map_err(|_| Error::VotingKeystorePasswordNotUtf8)?;
+                (voting_keystore_path, password.into())
+            }
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_source: Some(source),
+                ..
+            } => {
+                let password = password_source::fetch_password(source, pubkey)
+                    .map_err(Error::UnableToFetchVotingKeystorePassword)?;
+                (voting_keystore_path, password)
+            }
+            SigningDefinition::LocalKeystore { .. } => {
+                return Err(Error::UnknownVotingKeystorePassword)
+            }
+            SigningDefinition::Web3Signer(_) | SigningDefinition::Distributed { .. } => {
+                return Err(Error::InvalidActionOnValidator)
+            }
+        };
+
+        let keystore = match self
+            .validators
+            .get(&pubkey.compress())
+            .map(|validator| validator.signing_method.as_ref())
+        {
+            Some(SigningMethod::LocalKeystore {
+                voting_keystore, ..
+            }) => voting_keystore.clone(),
+            _ => open_keystore(voting_keystore_path)?,
+        };
+
+        Ok((keystore, password))
+    }
+
     /// Delete the validator definition and keystore for `pubkey`.
     ///
     /// The delete is carried out in stages so that the filesystem is never left in an inconsistent
@@ -908,8 +1120,8 @@ impl InitializedValidators {
                     };
                     definitions_map.insert(*key_store.uuid(), def);
                 }
-                // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signer and distributed validators don't interact with the key cache.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::Distributed { .. } => (),
             }
         }
 
@@ -936,11 +1148,24 @@ impl InitializedValidators {
                     voting_keystore_password_path,
                     voting_keystore_password,
                     voting_keystore_path,
+                    voting_keystore_password_source,
                 } => {
                     let pw = if let Some(p) = voting_keystore_password {
                         p.as_ref().to_vec().into()
                     } else if let Some(path) = voting_keystore_password_path {
                         read_password(path).map_err(Error::UnableToReadVotingKeystorePassword)?
+                    } else if let Some(source) = voting_keystore_password_source {
+                        let source = source.clone();
+                        let public_key = def.voting_public_key.clone();
+                        tokio::task::spawn_blocking(move || {
+                            password_source::fetch_password(&source, &public_key)
+                        })
+                        .await
+                        .map_err(Error::TokioJoin)?
+                        .map_err(Error::UnableToFetchVotingKeystorePassword)?
+                        .as_ref()
+                        .to_vec()
+                        .into()
                     } else {
                         let keystore = open_keystore(voting_keystore_path)?;
                         unlock_keystore_via_stdin_password(&keystore, voting_keystore_path)?
@@ -952,8 +1177,8 @@ impl InitializedValidators {
                     passwords.push(pw);
                     public_keys.push(def.voting_public_key.clone());
                 }
-                // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                // Remote signer and distributed validators don't interact with the key cache.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::Distributed { .. } => (),
             };
         }
 
@@ -1058,7 +1283,13 @@ impl InitializedValidators {
                             }
                         }
                     }
-                    SigningDefinition::Web3Signer(Web3SignerDefinition { .. }) => {
+                    SigningDefinition::Web3Signer(Web3SignerDefinition { .. })
+                    | SigningDefinition::Distributed { .. } => {
+                        let signing_method = match def.signing_definition {
+                            SigningDefinition::Distributed { .. } => "distributed",
+                            _ => "remote_signer",
+                        };
+
                         match InitializedValidator::from_definition(
                             def.clone(),
                             &mut key_cache,
@@ -1074,7 +1305,7 @@ impl InitializedValidators {
                                 info!(
                                     self.log,
                                     "Enabled validator";
-                                    "signing_method" => "remote_signer",
+                                    "signing_method" => signing_method,
                                     "voting_pubkey" => format!("{:?}", def.voting_public_key),
                                 );
                             }
@@ -1083,7 +1314,7 @@ impl InitializedValidators {
                                     self.log,
                                     "Failed to initialize validator";
                                     "error" => format!("{:?}", e),
-                                    "signing_method" => "remote_signer",
+                                    "signing_method" => signing_method,
                                     "validator" => format!("{:?}", def.voting_public_key)
                                 );
 
@@ -1104,8 +1335,10 @@ impl InitializedValidators {
                             disabled_uuids.insert(*key_store.uuid());
                         }
                     }
-                    // Remote signers do not interact with the key cache.
-                    SigningDefinition::Web3Signer { .. } => (),
+                    // Remote signers and distributed validators do not interact with the key
+                    // cache.
+                    SigningDefinition::Web3Signer { .. }
+                    | SigningDefinition::Distributed { .. } => (),
                 }
 
                 info!(
@@ -1161,3 +1394,61 @@ impl InitializedValidators {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::SecretKey;
+
+    fn public_key(secret: u64) -> PublicKey {
+        let mut bytes = [0; 32];
+        bytes[32 - 8..].copy_from_slice(&secret.to_be_bytes());
+        SecretKey::deserialize(&bytes).unwrap().public_key()
+    }
+
+    /// A key share whose public key is that of the secret key `secret`.
+    fn key_share(index: u64, secret: u64) -> KeyShare {
+        KeyShare {
+            index,
+            public_key: public_key(secret),
+            signing_method: SigningMethod::Web3Signer {
+                signing_url: Url::parse("http://127.0.0.1:9000/api/v1/eth2/sign").unwrap(),
+                http_client: Client::new(),
+                voting_public_key: public_key(secret),
+            },
+        }
+    }
+
+    #[test]
+    fn key_share_public_keys() {
+        // The shares of `f(x) = 1 + 2 * x + 3 * x^2`.
+        let secret_share = |index: u64| 1 + 2 * index + 3 * index * index;
+        let key_shares = (1..=4)
+            .map(|index| key_share(index, secret_share(index)))
+            .collect::<Vec<_>>();
+        let voting_public_key = public_key(1);
+
+        assert!(check_key_share_public_keys(3, &key_shares, &voting_public_key).is_ok());
+        assert!(check_key_share_public_keys(3, &key_shares[1..], &voting_public_key).is_ok());
+
+        // The shares do not determine the voting public key with too low a threshold.
+        assert!(matches!(
+            check_key_share_public_keys(2, &key_shares, &voting_public_key),
+            Err(Error::KeySharePublicKeyMismatch(2))
+        ));
+
+        // The shares of some other secret key.
+        assert!(matches!(
+            check_key_share_public_keys(3, &key_shares, &public_key(2)),
+            Err(Error::KeySharePublicKeyMismatch(3))
+        ));
+
+        // A share that is inconsistent with the others.
+        let mut key_shares = key_shares;
+        key_shares[3] = key_share(4, secret_share(4) + 1);
+        assert!(matches!(
+            check_key_share_public_keys(3, &key_shares, &voting_public_key),
+            Err(Error::KeySharePublicKeyMismatch(4))
+        ));
+    }
+}
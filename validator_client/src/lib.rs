@@ -6,11 +6,15 @@ mod cli;
 mod config;
 mod duties_service;
 mod graffiti_file;
+mod graffiti_template;
 mod http_metrics;
 mod key_cache;
 mod latency;
 mod notifier;
+mod password_source;
 mod preparation_service;
+mod proposer_config;
+mod signing_history;
 mod signing_method;
 mod sync_committee_service;
 
@@ -24,7 +28,9 @@ pub use config::Config;
 use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
-pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+pub use slashing_protection::{
+    SlashingDatabase, SlashingProtectionBackend, SLASHING_PROTECTION_FILENAME,
+};
 
 use crate::beacon_node_fallback::{
     start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode, OfflineOnFailure,
@@ -32,6 +38,7 @@ use crate::beacon_node_fallback::{
 };
 use crate::doppelganger_service::DoppelgangerService;
 use crate::graffiti_file::GraffitiFile;
+use crate::graffiti_template::GraffitiTemplate;
 use crate::initialized_validators::Error::UnableToOpenVotingKeystore;
 use account_utils::validator_definitions::ValidatorDefinitions;
 use attestation_service::{AttestationService, AttestationServiceBuilder};
@@ -45,6 +52,7 @@ use notifier::spawn_notifier;
 use parking_lot::RwLock;
 use preparation_service::{PreparationService, PreparationServiceBuilder};
 use reqwest::Certificate;
+use sensitive_url::SensitiveUrl;
 use slog::{error, info, warn, Logger};
 use slot_clock::SlotClock;
 use slot_clock::SystemTimeSlotClock;
@@ -197,7 +205,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 }
             })?;
 
-        let voting_pubkeys: Vec<_> = validators.iter_voting_pubkeys().collect();
+        let voting_pubkeys: Vec<_> = validators.iter_voting_pubkeys().copied().collect();
 
         info!(
             log,
@@ -219,32 +227,43 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         // Create the slashing database if there are no validators, even if
         // `init_slashing_protection` is not supplied. There is no risk in creating a slashing
         // database without any validators in it.
-        let slashing_db_path = config.validator_dir.join(SLASHING_PROTECTION_FILENAME);
-        let slashing_protection = if config.init_slashing_protection || voting_pubkeys.is_empty() {
-            SlashingDatabase::open_or_create(&slashing_db_path).map_err(|e| {
-                format!(
-                    "Failed to open or create slashing protection database: {:?}",
-                    e
-                )
-            })
-        } else {
-            SlashingDatabase::open(&slashing_db_path).map_err(|e| {
-                format!(
-                    "Failed to open slashing protection database: {:?}.\n\
-                     Ensure that `slashing_protection.sqlite` is in {:?} folder",
-                    e, config.validator_dir
-                )
-            })
-        }?;
+        let slashing_protection: Arc<dyn SlashingProtectionBackend> =
+            if let Some(url) = &config.slashing_protection_db_url {
+                info!(
+                    log,
+                    "Using PostgreSQL slashing protection database";
+                    "url" => %url,
+                );
+                open_postgres_slashing_protection(url)?
+            } else {
+                let slashing_db_path = config.validator_dir.join(SLASHING_PROTECTION_FILENAME);
+                let slashing_db = if config.init_slashing_protection || voting_pubkeys.is_empty() {
+                    SlashingDatabase::open_or_create(&slashing_db_path).map_err(|e| {
+                        format!(
+                            "Failed to open or create slashing protection database: {:?}",
+                            e
+                        )
+                    })
+                } else {
+                    SlashingDatabase::open(&slashing_db_path).map_err(|e| {
+                        format!(
+                            "Failed to open slashing protection database: {:?}.\n\
+                             Ensure that `slashing_protection.sqlite` is in {:?} folder",
+                            e, config.validator_dir
+                        )
+                    })
+                }?;
+                Arc::new(slashing_db)
+            };
 
         // Check validator registration with slashing protection, or auto-register all validators.
         if config.init_slashing_protection {
             slashing_protection
-                .register_validators(voting_pubkeys.iter().copied())
+                .register_validators(&voting_pubkeys)
                 .map_err(|e| format!("Error while registering slashing protection: {:?}", e))?;
         } else {
             slashing_protection
-                .check_validator_registrations(voting_pubkeys.iter().copied())
+                .check_validator_registrations(&voting_pubkeys)
                 .map_err(|e| {
                     format!(
                         "One or more validators not found in slashing protection database.\n\
@@ -395,6 +414,22 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         // Ensure all validators are registered in doppelganger protection.
         validator_store.register_all_in_doppelganger_protection_if_enabled()?;
 
+        // Load the proposer config up-front so that an invalid config prevents start-up, rather
+        // than being reported only when it is reloaded.
+        if let Some(source) = &config.proposer_config {
+            let proposer_config = source
+                .load()
+                .await
+                .map_err(|e| format!("Unable to load proposer config from {}: {:?}", source, e))?;
+            info!(
+                log,
+                "Loaded proposer config";
+                "source" => %source,
+                "validators" => proposer_config.proposer_config.len(),
+            );
+            validator_store.set_proposer_config(proposer_config);
+        }
+
         info!(
             log,
             "Loaded validator keypair store";
@@ -437,7 +472,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("block".into()))
-            .graffiti(config.graffiti)
+            .graffiti(config.graffiti.clone())
             .graffiti_file(config.graffiti_file.clone())
             .block_delay(config.block_delay)
             .build()?;
@@ -456,6 +491,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("preparation".into()))
             .builder_registration_timestamp_override(config.builder_registration_timestamp_override)
+            .proposer_config_source(config.proposer_config.clone())
             .build()?;
 
         let sync_committee_service = SyncCommitteeService::new(
@@ -539,9 +575,10 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                slot_clock: self.duties_service.slot_clock.clone(),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
-                graffiti_flag: self.config.graffiti,
+                graffiti_flag: self.config.graffiti.clone(),
                 spec: self.context.eth2_config.spec.clone(),
                 config: self.config.http_api.clone(),
                 log: log.clone(),
@@ -742,6 +779,28 @@ async fn poll_whilst_waiting_for_genesis<E: EthSpec>(
     }
 }
 
+/// Connect to the PostgreSQL slashing protection database at `url`, creating its tables if
+/// necessary.
+#[cfg(feature = "postgres")]
+fn open_postgres_slashing_protection(
+    url: &SensitiveUrl,
+) -> Result<Arc<dyn SlashingProtectionBackend>, String> {
+    slashing_protection::PostgresSlashingDatabase::open(url.full.as_str())
+        .map(|db| Arc::new(db) as Arc<dyn SlashingProtectionBackend>)
+        .map_err(|e| format!("Failed to open slashing protection database: {:?}", e))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn open_postgres_slashing_protection(
+    _url: &SensitiveUrl,
+) -> Result<Arc<dyn SlashingProtectionBackend>, String> {
+    Err(
+        "This Lighthouse binary does not support PostgreSQL slashing protection. \
+         Rebuild with the `slashing-protection-postgres` feature."
+            .to_string(),
+    )
+}
+
 pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate, String> {
     let mut buf = Vec::new();
     File::open(&pem_path)
@@ -753,13 +812,15 @@ pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate,
 
 // Given the various graffiti control methods, determine the graffiti that will be used for
 // the next block produced by the validator with the given public key.
+//
+// The graffiti may be a template, which must be expanded before it is used in a block.
 pub fn determine_graffiti(
     validator_pubkey: &PublicKeyBytes,
     log: &Logger,
     graffiti_file: Option<GraffitiFile>,
     validator_definition_graffiti: Option<Graffiti>,
-    graffiti_flag: Option<Graffiti>,
-) -> Option<Graffiti> {
+    graffiti_flag: Option<GraffitiTemplate>,
+) -> Option<GraffitiTemplate> {
     graffiti_file
         .and_then(|mut g| match g.load_graffiti(validator_pubkey) {
             Ok(g) => g,
@@ -768,6 +829,6 @@ pub fn determine_graffiti(
                 None
             }
         })
-        .or(validator_definition_graffiti)
+        .or_else(|| validator_definition_graffiti.map(GraffitiTemplate::from))
         .or(graffiti_flag)
 }
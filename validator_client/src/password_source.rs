@@ -0,0 +1,408 @@
+//! Fetches voting keystore passwords from the external sources described by
+//! `account_utils::validator_definitions::PasswordSource`.
+//!
+//! All functions in this module block the current thread and must not be called from an async
+//! context.
+
+use account_utils::{strip_off_newlines, validator_definitions::PasswordSource, ZeroizeString};
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use types::PublicKey;
+use zeroize::Zeroizing;
+
+/// The timeout applied to password commands, and to requests to a Unix socket agent or Vault
+/// server.
+const PASSWORD_SOURCE_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// How often to check whether a password command has exited.
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The header used to authenticate with a Vault server.
+const VAULT_TOKEN_HEADER: &str = "X-Vault-Token";
+
+/// The environment variable from which the Vault token is read, if no token file is supplied.
+const VAULT_TOKEN_ENV: &str = "VAULT_TOKEN";
+
+/// The field of a Vault secret which holds the password, if no field is supplied.
+const DEFAULT_VAULT_FIELD: &str = "password";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The password command could not be run.
+    UnableToRunCommand(PathBuf, io::Error),
+    /// The password command exited unsuccessfully.
+    CommandFailed(PathBuf, ExitStatus),
+    /// The password command did not exit before the timeout, and was killed.
+    CommandTimedOut(PathBuf),
+    /// There was an error communicating with the Unix socket agent.
+    UnixSocket(PathBuf, io::Error),
+    /// The Vault token file could not be read.
+    UnableToReadVaultToken(PathBuf, io::Error),
+    /// There is no Vault token file and the `VAULT_TOKEN` environment variable is not set.
+    MissingVaultToken,
+    /// The request to the Vault server failed.
+    VaultRequest(reqwest::Error),
+    /// The Vault secret does not have the given field, or it is not a string.
+    MissingVaultField(String),
+    /// The password is empty.
+    EmptyPassword,
+    /// The password is not valid UTF-8.
+    PasswordNotUtf8,
+}
+
+/// Fetches the password for the keystore of `voting_public_key` from `source`.
+///
+/// Any trailing newlines are removed from the password.
+pub fn fetch_password(
+    source: &PasswordSource,
+    voting_public_key: &PublicKey,
+) -> Result<ZeroizeString, Error> {
+    let bytes = match source {
+        PasswordSource::Command { command, args } => {
+            run_command(command, args, PASSWORD_SOURCE_TIMEOUT)?
+        }
+        PasswordSource::UnixSocket { socket_path, key } => {
+            let key = key
+                .clone()
+                .unwrap_or_else(|| voting_public_key.as_hex_string());
+            request_from_agent(socket_path, &key)?
+        }
+        PasswordSource::Vault {
+            url,
+            token_path,
+            field,
+        } => read_from_vault(
+            url,
+            token_path.as_deref(),
+            field.as_deref().unwrap_or(DEFAULT_VAULT_FIELD),
+        )?,
+    };
+
+    let bytes = Zeroizing::new(strip_off_newlines(bytes.to_vec()));
+    if bytes.is_empty() {
+        return Err(Error::EmptyPassword);
+    }
+    let password = std::str::from_utf8(&bytes).map_err(|_| Error::PasswordNotUtf8)?;
+
+    Ok(password.to_string().into())
+}
+
+/// Run `command`, returning its output. The command is killed if it doesn't exit within `timeout`.
+fn run_command(
+    command: &Path,
+    args: &[String],
+    timeout: Duration,
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let error = |e| Error::UnableToRunCommand(command.to_path_buf(), e);
+
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(error)?;
+
+    // Read the output on another thread, so that a command which fills the pipe can still exit.
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| error(io::ErrorKind::BrokenPipe.into()))?;
+    let reader = thread::spawn(move || {
+        let mut output = Zeroizing::new(vec![]);
+        stdout.read_to_end(&mut output).map(|_| output)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(error)? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            // Don't leave the command running in the background.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::CommandTimedOut(command.to_path_buf()));
+        }
+        thread::sleep(COMMAND_POLL_INTERVAL);
+    };
+
+    let stdout = reader
+        .join()
+        .unwrap_or_else(|_| Err(io::ErrorKind::Other.into()))
+        .map_err(error)?;
+
+    if !status.success() {
+        return Err(Error::CommandFailed(command.to_path_buf(), status));
+    }
+
+    Ok(stdout)
+}
+
+fn request_from_agent(socket_path: &Path, key: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let error = |e| Error::UnixSocket(socket_path.to_path_buf(), e);
+
+    let mut stream = UnixStream::connect(socket_path).map_err(error)?;
+    stream
+        .set_read_timeout(Some(PASSWORD_SOURCE_TIMEOUT))
+        .map_err(error)?;
+    stream
+        .set_write_timeout(Some(PASSWORD_SOURCE_TIMEOUT))
+        .map_err(error)?;
+
+    stream.write_all(key.as_bytes()).map_err(error)?;
+    stream.write_all(b"\n").map_err(error)?;
+
+    let mut response = Zeroizing::new(vec![]);
+    stream.read_to_end(&mut response).map_err(error)?;
+
+    Ok(response)
+}
+
+fn read_from_vault(
+    url: &str,
+    token_path: Option<&Path>,
+    field: &str,
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let token = match token_path {
+        Some(path) => fs::read_to_string(path)
+            .map(|token| Zeroizing::new(token.trim().to_string()))
+            .map_err(|e| Error::UnableToReadVaultToken(path.to_path_buf(), e))?,
+        None => std::env::var(VAULT_TOKEN_ENV)
+            .map(Zeroizing::new)
+            .map_err(|_| Error::MissingVaultToken)?,
+    };
+
+    let mut response: Value = Client::builder()
+        .timeout(PASSWORD_SOURCE_TIMEOUT)
+        .build()
+        .map_err(Error::VaultRequest)?
+        .get(url)
+        .header(VAULT_TOKEN_HEADER, token.as_str())
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json())
+        .map_err(Error::VaultRequest)?;
+
+    // Version 2 of the KV engine nests the secret within a second `data` object.
+    let password = response
+        .pointer_mut(&format!("/data/data/{}", field))
+        .filter(|password| password.is_string())
+        .map(Value::take)
+        .or_else(|| {
+            response
+                .pointer_mut(&format!("/data/{}", field))
+                .map(Value::take)
+        });
+
+    match password {
+        Some(Value::String(password)) => Ok(Zeroizing::new(password.into_bytes())),
+        _ => Err(Error::MissingVaultField(field.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+    use tempfile::tempdir;
+    use types::Keypair;
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    fn public_key() -> PublicKey {
+        Keypair::random().pk
+    }
+
+    #[test]
+    fn command() {
+        let source = PasswordSource::Command {
+            command: PathBuf::from("echo"),
+            args: vec![PASSWORD.to_string()],
+        };
+        assert_eq!(
+            fetch_password(&source, &public_key()).unwrap().as_str(),
+            PASSWORD
+        );
+
+        let source = PasswordSource::Command {
+            command: PathBuf::from("false"),
+            args: vec![],
+        };
+        assert!(matches!(
+            fetch_password(&source, &public_key()),
+            Err(Error::CommandFailed(..))
+        ));
+
+        let source = PasswordSource::Command {
+            command: PathBuf::from("true"),
+            args: vec![],
+        };
+        assert!(matches!(
+            fetch_password(&source, &public_key()),
+            Err(Error::EmptyPassword)
+        ));
+    }
+
+    #[test]
+    fn command_timeout() {
+        let dir = tempdir().unwrap();
+        let pid_path = dir.path().join("pid");
+        let args = vec![
+            "-c".to_string(),
+            format!("echo $$ > {}; exec sleep 60", pid_path.display()),
+        ];
+
+        let start = Instant::now();
+        assert!(matches!(
+            run_command(Path::new("sh"), &args, Duration::from_millis(500)),
+            Err(Error::CommandTimedOut(_))
+        ));
+        assert!(start.elapsed() < Duration::from_secs(30));
+
+        // The command should have been killed.
+        let pid = fs::read_to_string(&pid_path).unwrap();
+        assert!(!Path::new(&format!("/proc/{}", pid.trim())).exists());
+    }
+
+    #[test]
+    fn unix_socket() {
+        let dir = tempdir().unwrap();
+        let socket_path = dir.path().join("agent.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let public_key = public_key();
+        let expected_key = public_key.as_hex_string();
+
+        // A stand-in agent which only knows the password for the validator's public key.
+        let agent = thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut key = String::new();
+                BufReader::new(&mut stream).read_line(&mut key).unwrap();
+                if key.trim_end() == expected_key {
+                    writeln!(stream, "{}", PASSWORD).unwrap();
+                }
+            }
+        });
+
+        let source = PasswordSource::UnixSocket {
+            socket_path: socket_path.clone(),
+            key: None,
+        };
+        assert_eq!(
+            fetch_password(&source, &public_key).unwrap().as_str(),
+            PASSWORD
+        );
+
+        let source = PasswordSource::UnixSocket {
+            socket_path,
+            key: Some("unknown".to_string()),
+        };
+        assert!(matches!(
+            fetch_password(&source, &public_key),
+            Err(Error::EmptyPassword)
+        ));
+
+        agent.join().unwrap();
+    }
+
+    /// Serves a single HTTP response with `body`, returning the URL and a handle which resolves
+    /// to the request that was received.
+    fn vault_stand_in(body: String) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/v1/secret/data/validator",
+            listener.local_addr().unwrap()
+        );
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn vault_kv_v2() {
+        let dir = tempdir().unwrap();
+        let token_path = dir.path().join("token");
+        fs::write(&token_path, "s.token\n").unwrap();
+
+        let (url, server) = vault_stand_in(format!(
+            r#"{{"data": {{"data": {{"password": "{}"}}, "metadata": {{"version": 1}}}}}}"#,
+            PASSWORD
+        ));
+        let source = PasswordSource::Vault {
+            url,
+            token_path: Some(token_path),
+            field: None,
+        };
+        assert_eq!(
+            fetch_password(&source, &public_key()).unwrap().as_str(),
+            PASSWORD
+        );
+
+        let request = server.join().unwrap().to_lowercase();
+        assert!(request.starts_with("get /v1/secret/data/validator "));
+        assert!(request.contains("x-vault-token: s.token\r\n"));
+    }
+
+    #[test]
+    fn vault_kv_v1() {
+        let dir = tempdir().unwrap();
+        let token_path = dir.path().join("token");
+        fs::write(&token_path, "s.token").unwrap();
+
+        let (url, server) = vault_stand_in(format!(r#"{{"data": {{"secret": "{}"}}}}"#, PASSWORD));
+        let source = PasswordSource::Vault {
+            url: url.clone(),
+            token_path: Some(token_path.clone()),
+            field: Some("secret".to_string()),
+        };
+        assert_eq!(
+            fetch_password(&source, &public_key()).unwrap().as_str(),
+            PASSWORD
+        );
+        server.join().unwrap();
+
+        let (url, server) = vault_stand_in(format!(r#"{{"data": {{"secret": "{}"}}}}"#, PASSWORD));
+        let source = PasswordSource::Vault {
+            url,
+            token_path: Some(token_path),
+            field: None,
+        };
+        assert!(matches!(
+            fetch_password(&source, &public_key()),
+            Err(Error::MissingVaultField(_))
+        ));
+        server.join().unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::proposer_config::{ProposerConfigSource, RegistrationOverrides};
 use crate::validator_store::{DoppelgangerStatus, ValidatorStore};
 use crate::OfflineOnFailure;
 use bls::PublicKeyBytes;
@@ -33,6 +34,7 @@ pub struct PreparationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
     builder_registration_timestamp_override: Option<u64>,
+    proposer_config_source: Option<ProposerConfigSource>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> PreparationServiceBuilder<T, E> {
@@ -43,6 +45,7 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationServiceBuilder<T, E> {
             beacon_nodes: None,
             context: None,
             builder_registration_timestamp_override: None,
+            proposer_config_source: None,
         }
     }
 
@@ -74,6 +77,14 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationServiceBuilder<T, E> {
         self
     }
 
+    pub fn proposer_config_source(
+        mut self,
+        proposer_config_source: Option<ProposerConfigSource>,
+    ) -> Self {
+        self.proposer_config_source = proposer_config_source;
+        self
+    }
+
     pub fn build(self) -> Result<PreparationService<T, E>, String> {
         Ok(PreparationService {
             inner: Arc::new(Inner {
@@ -91,6 +102,7 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationServiceBuilder<T, E> {
                     .ok_or("Cannot build PreparationService without runtime_context")?,
                 builder_registration_timestamp_override: self
                     .builder_registration_timestamp_override,
+                proposer_config_source: self.proposer_config_source,
                 validator_registration_cache: RwLock::new(HashMap::new()),
             }),
         })
@@ -104,6 +116,7 @@ pub struct Inner<T, E: EthSpec> {
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
     builder_registration_timestamp_override: Option<u64>,
+    proposer_config_source: Option<ProposerConfigSource>,
     // Used to track unpublished validator registration changes.
     validator_registration_cache:
         RwLock<HashMap<ValidatorRegistrationKey, SignedValidatorRegistrationData>>,
@@ -114,6 +127,7 @@ pub struct ValidatorRegistrationKey {
     pub fee_recipient: Address,
     pub gas_limit: u64,
     pub pubkey: PublicKeyBytes,
+    pub registration_overrides: RegistrationOverrides,
 }
 
 impl From<ValidatorRegistrationData> for ValidatorRegistrationKey {
@@ -128,6 +142,7 @@ impl From<ValidatorRegistrationData> for ValidatorRegistrationKey {
             fee_recipient,
             gas_limit,
             pubkey,
+            registration_overrides: RegistrationOverrides::default(),
         }
     }
 }
@@ -155,10 +170,64 @@ impl<T, E: EthSpec> Deref for PreparationService<T, E> {
 
 impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
     pub fn start_update_service(self, spec: &ChainSpec) -> Result<(), String> {
+        self.clone().start_proposer_config_service(spec)?;
         self.clone().start_validator_registration_service(spec)?;
         self.start_proposer_prepare_service(spec)
     }
 
+    /// Starts the service which reloads the proposer config at the start of each epoch.
+    ///
+    /// The initial config is loaded at start-up, so this service only needs to pick up changes.
+    pub fn start_proposer_config_service(self, spec: &ChainSpec) -> Result<(), String> {
+        let source = if let Some(source) = self.proposer_config_source.clone() {
+            source
+        } else {
+            return Ok(());
+        };
+        let log = self.context.log().clone();
+
+        info!(
+            log,
+            "Proposer config service started";
+            "source" => %source,
+        );
+
+        let executor = self.context.executor.clone();
+        let epoch_duration = Duration::from_secs(spec.seconds_per_slot * E::slots_per_epoch());
+
+        let interval_fut = async move {
+            loop {
+                if let Some(duration_to_next_epoch) =
+                    self.slot_clock.duration_to_next_epoch(E::slots_per_epoch())
+                {
+                    sleep(duration_to_next_epoch).await;
+                } else {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another epoch.
+                    sleep(epoch_duration).await;
+                    continue;
+                }
+
+                match source.load().await {
+                    Ok(proposer_config) => {
+                        debug!(log, "Reloaded proposer config"; "source" => %source);
+                        self.validator_store.set_proposer_config(proposer_config);
+                    }
+                    Err(e) => error!(
+                        log,
+                        "Unable to reload proposer config";
+                        "msg" => "continuing with the previous config",
+                        "error" => ?e,
+                        "source" => %source,
+                    ),
+                }
+            }
+        };
+
+        executor.spawn(interval_fut, "proposer_config_service");
+        Ok(())
+    }
+
     /// Starts the service which periodically produces proposer preparations.
     pub fn start_proposer_prepare_service(self, spec: &ChainSpec) -> Result<(), String> {
         let log = self.context.log().clone();
@@ -298,6 +367,7 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                         fee_recipient,
                         gas_limit: proposal_data.gas_limit,
                         pubkey,
+                        registration_overrides: proposal_data.registration_overrides,
                     })
             })
         })
@@ -404,30 +474,37 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
             let signed_data = if let Some(signed_data) = cached_registration_opt {
                 signed_data
             } else {
-                let timestamp =
-                    if let Some(timestamp) = self.builder_registration_timestamp_override {
-                        timestamp
-                    } else {
-                        SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .map_err(|e| format!("{e:?}"))?
-                            .as_secs()
-                    };
-
                 let ValidatorRegistrationKey {
                     fee_recipient,
                     gas_limit,
                     pubkey,
+                    registration_overrides,
                 } = key.clone();
 
+                let timestamp = if let Some(timestamp) = registration_overrides
+                    .timestamp
+                    .map(|timestamp| timestamp.value)
+                    .or(self.builder_registration_timestamp_override)
+                {
+                    timestamp
+                } else {
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_err(|e| format!("{e:?}"))?
+                        .as_secs()
+                };
+
                 let signed_data = match self
                     .validator_store
-                    .sign_validator_registration_data(ValidatorRegistrationData {
-                        fee_recipient,
-                        gas_limit,
-                        timestamp,
+                    .sign_validator_registration_data_with_key(
                         pubkey,
-                    })
+                        ValidatorRegistrationData {
+                            fee_recipient,
+                            gas_limit,
+                            timestamp,
+                            pubkey: registration_overrides.public_key.unwrap_or(pubkey),
+                        },
+                    )
                     .await
                 {
                     Ok(data) => data,
@@ -482,4 +559,5 @@ pub struct ProposalData {
     pub(crate) fee_recipient: Option<Address>,
     pub(crate) gas_limit: u64,
     pub(crate) builder_proposals: bool,
+    pub(crate) registration_overrides: RegistrationOverrides,
 }
@@ -0,0 +1,355 @@
+use eth2_serde_utils::quoted_u64::MaybeQuoted;
+use reqwest::Client;
+use sensitive_url::SensitiveUrl;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use types::{Address, PublicKeyBytes};
+
+/// The timeout applied when downloading a proposer config from a URL.
+const PROPOSER_CONFIG_URL_TIMEOUT: Duration = Duration::from_secs(12);
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidFile(std::io::Error),
+    RequestFailed(reqwest::Error),
+    InvalidConfig(serde_yaml::Error),
+}
+
+/// The location from which the proposer config is loaded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProposerConfigSource {
+    File(PathBuf),
+    Url(SensitiveUrl),
+}
+
+impl ProposerConfigSource {
+    /// Loads and parses the proposer config.
+    ///
+    /// Returns an error if the config cannot be read or is invalid.
+    pub async fn load(&self) -> Result<ProposerConfigFile, Error> {
+        let bytes = match self {
+            ProposerConfigSource::File(path) => {
+                tokio::fs::read(path).await.map_err(Error::InvalidFile)?
+            }
+            ProposerConfigSource::Url(url) => Client::new()
+                .get(url.full.clone())
+                .timeout(PROPOSER_CONFIG_URL_TIMEOUT)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(Error::RequestFailed)?
+                .bytes()
+                .await
+                .map_err(Error::RequestFailed)?
+                .to_vec(),
+        };
+
+        ProposerConfigFile::from_bytes(&bytes)
+    }
+}
+
+impl FromStr for ProposerConfigSource {
+    type Err = String;
+
+    /// Interprets `http://` and `https://` URLs as remote configs, and anything else as a path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("http://") || s.starts_with("https://") {
+            SensitiveUrl::parse(s)
+                .map(ProposerConfigSource::Url)
+                .map_err(|e| format!("Unable to parse proposer config URL: {:?}", e))
+        } else {
+            Ok(ProposerConfigSource::File(PathBuf::from(s)))
+        }
+    }
+}
+
+impl fmt::Display for ProposerConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProposerConfigSource::File(path) => write!(f, "{}", path.display()),
+            ProposerConfigSource::Url(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Per-validator proposer settings, loaded from a JSON or YAML file compatible with Teku's
+/// `--validators-proposer-config`.
+///
+/// ```json
+/// {
+///   "proposer_config": {
+///     "0xa057816155ad77931185101128655c0191bd0214c201ca48ed887f6c4c6adf334070efcd75140eada5ac83a92506dd7a": {
+///       "fee_recipient": "0x50155530FCE8a85ec7055A5F8b2bE214B3DaeFd3",
+///       "builder": {
+///         "enabled": true,
+///         "gas_limit": "30000000",
+///         "registration_overrides": {
+///           "timestamp": "1672531200"
+///         }
+///       }
+///     }
+///   },
+///   "default_config": {
+///     "fee_recipient": "0x6e35733c5af9B61374A128e6F85f553aF09ff89A",
+///     "builder": {
+///       "enabled": false
+///     }
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProposerConfigFile {
+    #[serde(default)]
+    pub proposer_config: HashMap<PublicKeyBytes, ProposerConfig>,
+    #[serde(default)]
+    pub default_config: ProposerConfig,
+}
+
+impl ProposerConfigFile {
+    /// Parses a proposer config from JSON or YAML.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        // JSON is a subset of YAML, so the YAML parser handles both.
+        serde_yaml::from_slice(bytes).map_err(Error::InvalidConfig)
+    }
+
+    /// Returns the fee recipient configured specifically for `pubkey`.
+    pub fn validator_fee_recipient(&self, pubkey: &PublicKeyBytes) -> Option<Address> {
+        self.proposer_config.get(pubkey)?.fee_recipient
+    }
+
+    /// Returns the gas limit configured specifically for `pubkey`.
+    pub fn validator_gas_limit(&self, pubkey: &PublicKeyBytes) -> Option<u64> {
+        self.proposer_config.get(pubkey)?.gas_limit()
+    }
+
+    /// Returns the builder enablement configured specifically for `pubkey`.
+    pub fn validator_builder_proposals(&self, pubkey: &PublicKeyBytes) -> Option<bool> {
+        self.proposer_config.get(pubkey)?.builder_proposals()
+    }
+
+    /// Returns the registration overrides for `pubkey`, falling back to the default overrides
+    /// field-by-field.
+    pub fn registration_overrides(&self, pubkey: &PublicKeyBytes) -> RegistrationOverrides {
+        let default = self.default_config.registration_overrides();
+        let validator = self
+            .proposer_config
+            .get(pubkey)
+            .map(ProposerConfig::registration_overrides)
+            .unwrap_or_default();
+
+        RegistrationOverrides {
+            timestamp: validator.timestamp.or(default.timestamp),
+            public_key: validator.public_key.or(default.public_key),
+        }
+    }
+}
+
+/// Proposer settings for a single validator, or the defaults for all validators.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProposerConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builder: Option<BuilderConfig>,
+}
+
+impl ProposerConfig {
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.builder
+            .as_ref()?
+            .gas_limit
+            .map(|gas_limit| gas_limit.value)
+    }
+
+    pub fn builder_proposals(&self) -> Option<bool> {
+        self.builder.as_ref()?.enabled
+    }
+
+    pub fn registration_overrides(&self) -> RegistrationOverrides {
+        self.builder
+            .as_ref()
+            .and_then(|builder| builder.registration_overrides)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BuilderConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<MaybeQuoted<u64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_overrides: Option<RegistrationOverrides>,
+}
+
+/// Values which replace those that would otherwise be used in a validator's builder registration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistrationOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<MaybeQuoted<u64>>,
+    /// Replaces the validator's public key in the registration message, e.g. for a distributed
+    /// validator whose registration is signed by a key share.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PublicKeyBytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const PK1: &str = "0x800012708dc03f611751aad7a43a082142832b5c1aceed07ff9b543cf836381861352aa923c70eeb02018b638aa306aa";
+    const PK2: &str = "0x80001866ce324de7d80ec73be15e2d064dcf121adf1b34a0d679f2b9ecbab40ce021e03bb877e1a2fe72eaaf475e6e21";
+    const PK3: &str = "0x9035d41a8bc11b08c17d0d93d876087958c9d055afe86fce558e3b988d92434769c8d50b0b463708db80c6aae1160c02";
+
+    fn pubkey(s: &str) -> PublicKeyBytes {
+        PublicKeyBytes::from_str(s).unwrap()
+    }
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn json_config() -> String {
+        format!(
+            r#"{{
+                "proposer_config": {{
+                    "{PK1}": {{
+                        "fee_recipient": "{:?}",
+                        "builder": {{
+                            "enabled": true,
+                            "gas_limit": "31000000",
+                            "registration_overrides": {{
+                                "timestamp": "1672531200",
+                                "public_key": "{PK3}"
+                            }}
+                        }}
+                    }},
+                    "{PK2}": {{
+                        "builder": {{
+                            "enabled": false
+                        }}
+                    }}
+                }},
+                "default_config": {{
+                    "fee_recipient": "{:?}",
+                    "builder": {{
+                        "enabled": true,
+                        "gas_limit": 30000000,
+                        "registration_overrides": {{
+                            "timestamp": 1
+                        }}
+                    }}
+                }}
+            }}"#,
+            address(1),
+            address(2),
+        )
+    }
+
+    #[test]
+    fn parse_json() {
+        let config = ProposerConfigFile::from_bytes(json_config().as_bytes()).unwrap();
+
+        assert_eq!(
+            config.validator_fee_recipient(&pubkey(PK1)),
+            Some(address(1))
+        );
+        assert_eq!(config.validator_fee_recipient(&pubkey(PK2)), None);
+        assert_eq!(config.default_config.fee_recipient, Some(address(2)));
+
+        assert_eq!(config.validator_gas_limit(&pubkey(PK1)), Some(31_000_000));
+        assert_eq!(config.validator_gas_limit(&pubkey(PK2)), None);
+        assert_eq!(config.default_config.gas_limit(), Some(30_000_000));
+
+        assert_eq!(config.validator_builder_proposals(&pubkey(PK1)), Some(true));
+        assert_eq!(
+            config.validator_builder_proposals(&pubkey(PK2)),
+            Some(false)
+        );
+        assert_eq!(config.validator_builder_proposals(&pubkey(PK3)), None);
+
+        let overrides = config.registration_overrides(&pubkey(PK1));
+        assert_eq!(overrides.timestamp.map(|t| t.value), Some(1672531200));
+        assert_eq!(overrides.public_key, Some(pubkey(PK3)));
+
+        // Validators without overrides of their own inherit the default overrides.
+        let overrides = config.registration_overrides(&pubkey(PK2));
+        assert_eq!(overrides.timestamp.map(|t| t.value), Some(1));
+        assert_eq!(overrides.public_key, None);
+    }
+
+    #[test]
+    fn parse_yaml() {
+        let yaml = format!(
+            "proposer_config:\n  \"{PK1}\":\n    fee_recipient: \"{:?}\"\ndefault_config:\n  builder:\n    enabled: true\n",
+            address(1)
+        );
+        let config = ProposerConfigFile::from_bytes(yaml.as_bytes()).unwrap();
+
+        assert_eq!(
+            config.validator_fee_recipient(&pubkey(PK1)),
+            Some(address(1))
+        );
+        assert_eq!(config.default_config.fee_recipient, None);
+        assert_eq!(config.default_config.builder_proposals(), Some(true));
+    }
+
+    #[test]
+    fn parse_empty() {
+        let config = ProposerConfigFile::from_bytes(b"{}").unwrap();
+        assert_eq!(config, ProposerConfigFile::default());
+    }
+
+    #[test]
+    fn reject_unknown_fields() {
+        assert!(
+            ProposerConfigFile::from_bytes(br#"{"default_config": {"gas_limit": 1}}"#).is_err()
+        );
+        assert!(ProposerConfigFile::from_bytes(br#"{"proposer_config": {"0x00": {}}}"#).is_err());
+    }
+
+    #[test]
+    fn parse_source() {
+        assert_eq!(
+            ProposerConfigSource::from_str("/tmp/proposer_config.json").unwrap(),
+            ProposerConfigSource::File(PathBuf::from("/tmp/proposer_config.json"))
+        );
+        assert_eq!(
+            ProposerConfigSource::from_str("https://example.com/proposer_config.json").unwrap(),
+            ProposerConfigSource::Url(
+                SensitiveUrl::parse("https://example.com/proposer_config.json").unwrap()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn load_from_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("proposer_config.json");
+        let source = ProposerConfigSource::File(path.clone());
+
+        assert!(matches!(source.load().await, Err(Error::InvalidFile(_))));
+
+        fs::write(&path, json_config()).unwrap();
+        let config = source.load().await.unwrap();
+        assert_eq!(
+            config.validator_fee_recipient(&pubkey(PK1)),
+            Some(address(1))
+        );
+
+        // Changes to the file are picked up by the next load.
+        fs::write(&path, "{}").unwrap();
+        assert_eq!(source.load().await.unwrap(), ProposerConfigFile::default());
+    }
+}
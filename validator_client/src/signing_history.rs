@@ -0,0 +1,166 @@
+//! A record of the latest epoch in which each validator signed a block or attestation, kept by this
+//! validator client.
+//!
+//! The record is written to the validator directory when the validator client shuts down and is
+//! consumed (i.e., deleted) when it next starts, so that doppelganger protection can tell whether a
+//! validator was recently signing *with this validator client*. Unlike the slashing protection
+//! database, it never contains messages signed elsewhere: slashing protection data may have been
+//! imported from another validator client, or may be shared with one.
+use parking_lot::Mutex;
+use serde_derive::{Deserialize, Serialize};
+use slog::{debug, warn, Logger};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use types::{Epoch, PublicKeyBytes};
+
+/// The file written to the validator directory at shutdown.
+pub const SIGNING_HISTORY_FILENAME: &str = "signing_history.json";
+
+#[derive(Debug)]
+pub enum Error {
+    UnableToReadFile(io::Error),
+    UnableToRemoveFile(io::Error),
+    UnableToWriteFile(io::Error),
+    UnableToParseFile(serde_json::Error),
+    UnableToEncodeFile(serde_json::Error),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SignedEpoch {
+    pubkey: PublicKeyBytes,
+    epoch: Epoch,
+}
+
+pub struct SigningHistory {
+    path: PathBuf,
+    last_signed_epochs: Mutex<HashMap<PublicKeyBytes, Epoch>>,
+}
+
+impl SigningHistory {
+    /// Load the signing history written when the validator client last shut down, deleting the
+    /// file so that it is only ever used once.
+    ///
+    /// Any error is logged and results in an empty history, which is always safe.
+    pub fn load(validator_dir: &Path, log: &Logger) -> Self {
+        let path = validator_dir.join(SIGNING_HISTORY_FILENAME);
+        let last_signed_epochs = match Self::take_file(&path) {
+            Ok(epochs) => {
+                debug!(
+                    log,
+                    "Loaded signing history";
+                    "validators" => epochs.len(),
+                );
+                epochs
+            }
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to load signing history";
+                    "msg" => "doppelganger protection will not be shortened",
+                    "error" => ?e,
+                    "path" => ?path,
+                );
+                HashMap::new()
+            }
+        };
+
+        Self {
+            path,
+            last_signed_epochs: Mutex::new(last_signed_epochs),
+        }
+    }
+
+    fn take_file(path: &Path) -> Result<HashMap<PublicKeyBytes, Epoch>, Error> {
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let bytes = fs::read(path).map_err(Error::UnableToReadFile)?;
+        fs::remove_file(path).map_err(Error::UnableToRemoveFile)?;
+
+        let signed_epochs: Vec<SignedEpoch> =
+            serde_json::from_slice(&bytes).map_err(Error::UnableToParseFile)?;
+        Ok(signed_epochs
+            .into_iter()
+            .map(|signed| (signed.pubkey, signed.epoch))
+            .collect())
+    }
+
+    /// Record that `pubkey` signed a message in `epoch`.
+    pub fn record(&self, pubkey: PublicKeyBytes, epoch: Epoch) {
+        let mut last_signed_epochs = self.last_signed_epochs.lock();
+        let last_signed_epoch = last_signed_epochs.entry(pubkey).or_insert(epoch);
+        *last_signed_epoch = std::cmp::max(*last_signed_epoch, epoch);
+    }
+
+    /// The latest epoch in which `pubkey` signed a message with this validator client, if known.
+    pub fn last_signed_epoch(&self, pubkey: &PublicKeyBytes) -> Option<Epoch> {
+        self.last_signed_epochs.lock().get(pubkey).copied()
+    }
+
+    /// Write the signing history to the validator directory.
+    ///
+    /// This must only be called once the validator client has stopped signing.
+    pub fn persist(&self) -> Result<(), Error> {
+        let last_signed_epochs = self.last_signed_epochs.lock();
+        if last_signed_epochs.is_empty() {
+            return Ok(());
+        }
+
+        let signed_epochs = last_signed_epochs
+            .iter()
+            .map(|(pubkey, epoch)| SignedEpoch {
+                pubkey: *pubkey,
+                epoch: *epoch,
+            })
+            .collect::<Vec<_>>();
+        let bytes = serde_json::to_vec(&signed_epochs).map_err(Error::UnableToEncodeFile)?;
+        fs::write(&self.path, bytes).map_err(Error::UnableToWriteFile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logging::test_logger;
+    use tempfile::tempdir;
+    use types::Keypair;
+
+    #[test]
+    fn persist_and_load() {
+        let dir = tempdir().unwrap();
+        let log = test_logger();
+        let pubkey = Keypair::random().pk.compress();
+        let other_pubkey = Keypair::random().pk.compress();
+
+        let history = SigningHistory::load(dir.path(), &log);
+        assert_eq!(history.last_signed_epoch(&pubkey), None);
+
+        history.record(pubkey, Epoch::new(4));
+        history.record(pubkey, Epoch::new(3));
+        assert_eq!(history.last_signed_epoch(&pubkey), Some(Epoch::new(4)));
+        history.persist().unwrap();
+
+        let history = SigningHistory::load(dir.path(), &log);
+        assert_eq!(history.last_signed_epoch(&pubkey), Some(Epoch::new(4)));
+        assert_eq!(history.last_signed_epoch(&other_pubkey), None);
+
+        // The file should only be used once.
+        assert!(!dir.path().join(SIGNING_HISTORY_FILENAME).exists());
+        let history = SigningHistory::load(dir.path(), &log);
+        assert_eq!(history.last_signed_epoch(&pubkey), None);
+    }
+
+    #[test]
+    fn corrupt_file_is_ignored() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(SIGNING_HISTORY_FILENAME);
+        fs::write(&path, b"not json").unwrap();
+
+        let history = SigningHistory::load(dir.path(), &test_logger());
+        assert!(history.last_signed_epochs.lock().is_empty());
+        assert!(!path.exists());
+    }
+}
@@ -2,9 +2,11 @@
 //!
 //! - Via a local `Keypair`.
 //! - Via a remote signer (Web3Signer)
+//! - Via a threshold of key shares, each held locally or by a remote signer
 
 use crate::http_metrics::metrics;
 use eth2_keystore::Keystore;
+use futures::stream::{FuturesUnordered, StreamExt};
 use lockfile::Lockfile;
 use parking_lot::Mutex;
 use reqwest::Client;
@@ -31,6 +33,14 @@ pub enum Error {
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
+    UnsupportedKeyShareSigner,
+    InvalidKeyShareSignature,
+    InsufficientKeyShareSignatures {
+        threshold: usize,
+        share_errors: Vec<(u64, Error)>,
+    },
+    KeyShareCombinationFailed(bls::Error),
+    InvalidCombinedSignature,
 }
 
 /// Enumerates all messages that can be signed by a validator.
@@ -47,6 +57,7 @@ pub enum SignableMessage<'a, T: EthSpec, Payload: AbstractExecPayload<T> = FullP
     },
     SignedContributionAndProof(&'a ContributionAndProof<T>),
     ValidatorRegistration(&'a ValidatorRegistrationData),
+    VoluntaryExit(&'a VoluntaryExit),
 }
 
 impl<'a, T: EthSpec, Payload: AbstractExecPayload<T>> SignableMessage<'a, T, Payload> {
@@ -67,6 +78,7 @@ impl<'a, T: EthSpec, Payload: AbstractExecPayload<T>> SignableMessage<'a, T, Pay
             } => beacon_block_root.signing_root(domain),
             SignableMessage::SignedContributionAndProof(c) => c.signing_root(domain),
             SignableMessage::ValidatorRegistration(v) => v.signing_root(domain),
+            SignableMessage::VoluntaryExit(exit) => exit.signing_root(domain),
         }
     }
 }
@@ -91,6 +103,22 @@ pub enum SigningMethod {
         http_client: Client,
         voting_public_key: PublicKey,
     },
+    /// A validator whose key is split into shares, any `threshold` of which can jointly produce
+    /// a signature.
+    Distributed {
+        threshold: usize,
+        key_shares: Vec<KeyShare>,
+        voting_public_key: PublicKey,
+    },
+}
+
+/// One share of a distributed validator key.
+pub struct KeyShare {
+    /// The non-zero point at which the dealer's polynomial was evaluated to produce this share.
+    pub index: u64,
+    pub public_key: PublicKey,
+    /// How to sign with this share, either `LocalKeystore` or `Web3Signer`.
+    pub signing_method: SigningMethod,
 }
 
 /// The additional information used to construct a signature. Mostly used for protection from replay
@@ -153,18 +181,7 @@ impl SigningMethod {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::LOCAL_KEYSTORE]);
 
-                let voting_keypair = voting_keypair.clone();
-                // Spawn a blocking task to produce the signature. This avoids blocking the core
-                // tokio executor.
-                let signature = executor
-                    .spawn_blocking_handle(
-                        move || voting_keypair.sk.sign(signing_root),
-                        "local_keystore_signer",
-                    )
-                    .ok_or(Error::ShuttingDown)?
-                    .await
-                    .map_err(|e| Error::TokioJoin(e.to_string()))?;
-                Ok(signature)
+                sign_with_keypair(voting_keypair, signing_root, executor).await
             }
             SigningMethod::Web3Signer {
                 signing_url,
@@ -174,70 +191,373 @@ impl SigningMethod {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::WEB3SIGNER]);
 
-                // Map the message into a Web3Signer type.
-                let object = match signable_message {
-                    SignableMessage::RandaoReveal(epoch) => {
-                        Web3SignerObject::RandaoReveal { epoch }
-                    }
-                    SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
-                    SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
-                    SignableMessage::SignedAggregateAndProof(a) => {
-                        Web3SignerObject::AggregateAndProof(a)
-                    }
-                    SignableMessage::SelectionProof(slot) => {
-                        Web3SignerObject::AggregationSlot { slot }
-                    }
-                    SignableMessage::SyncSelectionProof(s) => {
-                        Web3SignerObject::SyncAggregatorSelectionData(s)
-                    }
-                    SignableMessage::SyncCommitteeSignature {
-                        beacon_block_root,
-                        slot,
-                    } => Web3SignerObject::SyncCommitteeMessage {
-                        beacon_block_root,
-                        slot,
-                    },
-                    SignableMessage::SignedContributionAndProof(c) => {
-                        Web3SignerObject::ContributionAndProof(c)
-                    }
-                    SignableMessage::ValidatorRegistration(v) => {
-                        Web3SignerObject::ValidatorRegistration(v)
+                let request = web3signer_request(signable_message, signing_root, fork_info)?;
+                request_web3signer_signature(signing_url, http_client, &request).await
+            }
+            SigningMethod::Distributed {
+                threshold,
+                key_shares,
+                voting_public_key,
+            } => {
+                let _timer =
+                    metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::DISTRIBUTED]);
+
+                let request = web3signer_request(signable_message, signing_root, fork_info)?;
+                let request = &request;
+
+                // Request a partial signature from every share in parallel, ceasing to wait for
+                // the stragglers as soon as the threshold is reached.
+                let mut pending = key_shares
+                    .iter()
+                    .map(|key_share| async move {
+                        let result = key_share
+                            .get_partial_signature(signing_root, request, executor)
+                            .await;
+                        (key_share.index, result)
+                    })
+                    .collect::<FuturesUnordered<_>>();
+
+                let mut partial_signatures = Vec::with_capacity(*threshold);
+                let mut share_errors = vec![];
+                while let Some((index, result)) = pending.next().await {
+                    match result {
+                        Ok(signature) => {
+                            partial_signatures.push((index, signature));
+                            if partial_signatures.len() == *threshold {
+                                break;
+                            }
+                        }
+                        Err(e) => share_errors.push((index, e)),
                     }
-                };
+                }
+
+                if partial_signatures.len() < *threshold {
+                    return Err(Error::InsufficientKeyShareSignatures {
+                        threshold: *threshold,
+                        share_errors,
+                    });
+                }
 
-                // Determine the Web3Signer message type.
-                let message_type = object.message_type();
+                let partial_signatures = partial_signatures
+                    .iter()
+                    .map(|(index, signature)| (*index, signature))
+                    .collect::<Vec<_>>();
+                let signature = Signature::combine_shares(&partial_signatures)
+                    .map_err(Error::KeyShareCombinationFailed)?;
 
-                if matches!(
-                    object,
-                    Web3SignerObject::Deposit { .. } | Web3SignerObject::ValidatorRegistration(_)
-                ) && fork_info.is_some()
-                {
-                    return Err(Error::GenesisForkVersionRequired);
+                // The shares are checked against the voting public key when they are loaded, but
+                // never publish a signature that would be rejected by the network.
+                if signature.verify(voting_public_key, signing_root) {
+                    Ok(signature)
+                } else {
+                    Err(Error::InvalidCombinedSignature)
                 }
+            }
+        }
+    }
+}
 
-                let request = SigningRequest {
-                    message_type,
-                    fork_info,
-                    signing_root,
-                    object,
-                };
-
-                // Request a signature from the Web3Signer instance via HTTP(S).
-                let response: SigningResponse = http_client
-                    .post(signing_url.clone())
-                    .json(&request)
-                    .send()
-                    .await
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .error_for_status()
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .json()
-                    .await
-                    .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
-
-                Ok(response.signature)
+impl KeyShare {
+    /// Return the signature of this share across `signing_root`, verified against the share's
+    /// public key so that a faulty signer cannot spoil the combined signature.
+    async fn get_partial_signature<T: EthSpec, Payload: AbstractExecPayload<T>>(
+        &self,
+        signing_root: Hash256,
+        request: &SigningRequest<'_, T, Payload>,
+        executor: &TaskExecutor,
+    ) -> Result<Signature, Error> {
+        let signature = match &self.signing_method {
+            SigningMethod::LocalKeystore { voting_keypair, .. } => {
+                sign_with_keypair(voting_keypair, signing_root, executor).await?
             }
+            SigningMethod::Web3Signer {
+                signing_url,
+                http_client,
+                ..
+            } => request_web3signer_signature(signing_url, http_client, request).await?,
+            SigningMethod::Distributed { .. } => return Err(Error::UnsupportedKeyShareSigner),
+        };
+
+        if signature.verify(&self.public_key, signing_root) {
+            Ok(signature)
+        } else {
+            Err(Error::InvalidKeyShareSignature)
         }
     }
 }
+
+/// Sign `signing_root` with `voting_keypair`.
+async fn sign_with_keypair(
+    voting_keypair: &Arc<Keypair>,
+    signing_root: Hash256,
+    executor: &TaskExecutor,
+) -> Result<Signature, Error> {
+    let voting_keypair = voting_keypair.clone();
+    // Spawn a blocking task to produce the signature. This avoids blocking the core tokio
+    // executor.
+    executor
+        .spawn_blocking_handle(
+            move || voting_keypair.sk.sign(signing_root),
+            "local_keystore_signer",
+        )
+        .ok_or(Error::ShuttingDown)?
+        .await
+        .map_err(|e| Error::TokioJoin(e.to_string()))
+}
+
+/// Map `signable_message` into a request for a Web3Signer instance.
+fn web3signer_request<T: EthSpec, Payload: AbstractExecPayload<T>>(
+    signable_message: SignableMessage<'_, T, Payload>,
+    signing_root: Hash256,
+    fork_info: Option<ForkInfo>,
+) -> Result<SigningRequest<'_, T, Payload>, Error> {
+    // Map the message into a Web3Signer type.
+    let object = match signable_message {
+        SignableMessage::RandaoReveal(epoch) => Web3SignerObject::RandaoReveal { epoch },
+        SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
+        SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
+        SignableMessage::SignedAggregateAndProof(a) => Web3SignerObject::AggregateAndProof(a),
+        SignableMessage::SelectionProof(slot) => Web3SignerObject::AggregationSlot { slot },
+        SignableMessage::SyncSelectionProof(s) => Web3SignerObject::SyncAggregatorSelectionData(s),
+        SignableMessage::SyncCommitteeSignature {
+            beacon_block_root,
+            slot,
+        } => Web3SignerObject::SyncCommitteeMessage {
+            beacon_block_root,
+            slot,
+        },
+        SignableMessage::SignedContributionAndProof(c) => Web3SignerObject::ContributionAndProof(c),
+        SignableMessage::ValidatorRegistration(v) => Web3SignerObject::ValidatorRegistration(v),
+        SignableMessage::VoluntaryExit(e) => Web3SignerObject::VoluntaryExit(e),
+    };
+
+    // Determine the Web3Signer message type.
+    let message_type = object.message_type();
+
+    if matches!(
+        object,
+        Web3SignerObject::Deposit { .. } | Web3SignerObject::ValidatorRegistration(_)
+    ) && fork_info.is_some()
+    {
+        return Err(Error::GenesisForkVersionRequired);
+    }
+
+    Ok(SigningRequest {
+        message_type,
+        fork_info,
+        signing_root,
+        object,
+    })
+}
+
+/// Request a signature from the Web3Signer instance at `signing_url` via HTTP(S).
+async fn request_web3signer_signature<T: EthSpec, Payload: AbstractExecPayload<T>>(
+    signing_url: &Url,
+    http_client: &Client,
+    request: &SigningRequest<'_, T, Payload>,
+) -> Result<Signature, Error> {
+    let response: SigningResponse = http_client
+        .post(signing_url.clone())
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+        .error_for_status()
+        .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+        .json()
+        .await
+        .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
+
+    Ok(response.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2_keystore::{
+        json_keystore::{Kdf, Pbkdf2, Prf},
+        KeystoreBuilder,
+    };
+    use task_executor::test_utils::TestRuntime;
+
+    type E = MainnetEthSpec;
+
+    /// The shares are evaluations of `f(x) = 1 + 2 * x`, so any two of them can sign for the secret
+    /// key `f(0)`.
+    const THRESHOLD: usize = 2;
+
+    fn secret_key(value: u64) -> SecretKey {
+        let mut bytes = [0; 32];
+        bytes[32 - 8..].copy_from_slice(&value.to_be_bytes());
+        SecretKey::deserialize(&bytes).unwrap()
+    }
+
+    fn keypair(value: u64) -> Keypair {
+        Keypair::from_components(secret_key(value).public_key(), secret_key(value))
+    }
+
+    fn local_keystore(keypair: Keypair) -> SigningMethod {
+        // Use a cheap KDF, the keystore is never decrypted.
+        let kdf = Kdf::Pbkdf2(Pbkdf2 {
+            c: 1,
+            dklen: 32,
+            prf: Prf::HmacSha256,
+            salt: vec![42; 32].into(),
+        });
+        let voting_keystore = KeystoreBuilder::new(&keypair, b"password", String::new())
+            .unwrap()
+            .kdf(kdf)
+            .build()
+            .unwrap();
+
+        SigningMethod::LocalKeystore {
+            voting_keystore_path: PathBuf::new(),
+            voting_keystore_lockfile: Mutex::new(None),
+            voting_keystore,
+            voting_keypair: Arc::new(keypair),
+        }
+    }
+
+    /// A key share that signs correctly with the secret key share at `index`.
+    fn valid_share(index: u64) -> KeyShare {
+        let keypair = keypair(1 + 2 * index);
+        KeyShare {
+            index,
+            public_key: keypair.pk.clone(),
+            signing_method: local_keystore(keypair),
+        }
+    }
+
+    /// A key share whose signer produces signatures that are not valid for its public key.
+    fn invalid_share(index: u64) -> KeyShare {
+        KeyShare {
+            index,
+            public_key: keypair(1 + 2 * index).pk,
+            signing_method: local_keystore(keypair(1000 + index)),
+        }
+    }
+
+    /// A key share whose remote signer cannot be reached.
+    fn unreachable_share(index: u64) -> KeyShare {
+        let public_key = keypair(1 + 2 * index).pk;
+        KeyShare {
+            index,
+            public_key: public_key.clone(),
+            signing_method: SigningMethod::Web3Signer {
+                signing_url: Url::parse("http://127.0.0.1:1/api/v1/eth2/sign").unwrap(),
+                http_client: Client::new(),
+                voting_public_key: public_key,
+            },
+        }
+    }
+
+    fn distributed(key_shares: Vec<KeyShare>) -> SigningMethod {
+        SigningMethod::Distributed {
+            threshold: THRESHOLD,
+            key_shares,
+            voting_public_key: keypair(1).pk,
+        }
+    }
+
+    async fn sign(
+        signing_method: &SigningMethod,
+        executor: &TaskExecutor,
+    ) -> Result<Signature, Error> {
+        signing_method
+            .get_signature_from_root::<E, FullPayload<E>>(
+                SignableMessage::RandaoReveal(Epoch::new(1)),
+                signing_root(),
+                executor,
+                None,
+            )
+            .await
+    }
+
+    fn signing_root() -> Hash256 {
+        Hash256::repeat_byte(42)
+    }
+
+    #[tokio::test]
+    async fn threshold_reached() {
+        let runtime = TestRuntime::default();
+        let voting_keypair = keypair(1);
+
+        for key_shares in [
+            vec![valid_share(1), valid_share(2)],
+            vec![valid_share(3), valid_share(1), valid_share(2)],
+        ] {
+            let signature = sign(&distributed(key_shares), &runtime.task_executor)
+                .await
+                .unwrap();
+            assert_eq!(signature, voting_keypair.sk.sign(signing_root()));
+        }
+    }
+
+    #[tokio::test]
+    async fn share_signer_failing() {
+        let runtime = TestRuntime::default();
+
+        // The remaining shares still reach the threshold.
+        let signing_method =
+            distributed(vec![unreachable_share(1), valid_share(2), valid_share(3)]);
+        let signature = sign(&signing_method, &runtime.task_executor).await.unwrap();
+        assert!(signature.verify(&keypair(1).pk, signing_root()));
+
+        let signing_method = distributed(vec![unreachable_share(1), valid_share(2)]);
+        match sign(&signing_method, &runtime.task_executor).await {
+            Err(Error::InsufficientKeyShareSignatures {
+                threshold,
+                share_errors,
+            }) => {
+                assert_eq!(threshold, THRESHOLD);
+                assert_eq!(share_errors.len(), 1);
+                assert_eq!(share_errors[0].0, 1);
+                assert!(matches!(
+                    share_errors[0].1,
+                    Error::Web3SignerRequestFailed(_)
+                ));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_share() {
+        let runtime = TestRuntime::default();
+
+        // The invalid share is excluded from the combined signature.
+        let signing_method = distributed(vec![invalid_share(1), valid_share(2), valid_share(3)]);
+        let signature = sign(&signing_method, &runtime.task_executor).await.unwrap();
+        assert!(signature.verify(&keypair(1).pk, signing_root()));
+
+        let signing_method = distributed(vec![valid_share(1), invalid_share(2)]);
+        assert_eq!(
+            sign(&signing_method, &runtime.task_executor).await,
+            Err(Error::InsufficientKeyShareSignatures {
+                threshold: THRESHOLD,
+                share_errors: vec![(2, Error::InvalidKeyShareSignature)],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_combined_signature() {
+        let runtime = TestRuntime::default();
+
+        // Shares that are each valid for their own public key, but not for the voting public key.
+        let key_shares = (1..=2)
+            .map(|index| {
+                let keypair = keypair(5 + index);
+                KeyShare {
+                    index,
+                    public_key: keypair.pk.clone(),
+                    signing_method: local_keystore(keypair),
+                }
+            })
+            .collect();
+        assert_eq!(
+            sign(&distributed(key_shares), &runtime.task_executor).await,
+            Err(Error::InvalidCombinedSignature)
+        );
+    }
+}
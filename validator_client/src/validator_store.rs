@@ -2,13 +2,16 @@ use crate::{
     doppelganger_service::DoppelgangerService,
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
+    proposer_config::{ProposerConfig, ProposerConfigFile, RegistrationOverrides},
+    signing_history::SigningHistory,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
     Config,
 };
 use account_utils::{validator_definitions::ValidatorDefinition, ZeroizeString};
+use eth2::lighthouse_vc::types::DoppelgangerData;
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
-    interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
+    interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingProtectionBackend,
 };
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -22,8 +25,9 @@ use types::{
     AggregateAndProof, Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof,
     Domain, Epoch, EthSpec, Fork, Graffiti, Hash256, Keypair, PublicKeyBytes, SelectionProof,
     Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
-    SignedValidatorRegistrationData, Slot, SyncAggregatorSelectionData, SyncCommitteeContribution,
-    SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData,
+    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncAggregatorSelectionData,
+    SyncCommitteeContribution, SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
+    ValidatorRegistrationData, VoluntaryExit,
 };
 use validator_dir::ValidatorDir;
 
@@ -86,7 +90,7 @@ impl PartialEq for LocalValidator {
 
 pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
-    slashing_protection: SlashingDatabase,
+    slashing_protection: Arc<dyn SlashingProtectionBackend>,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
@@ -96,6 +100,8 @@ pub struct ValidatorStore<T, E: EthSpec> {
     fee_recipient_process: Option<Address>,
     gas_limit: Option<u64>,
     builder_proposals: bool,
+    proposer_config: RwLock<ProposerConfigFile>,
+    signing_history: SigningHistory,
     task_executor: TaskExecutor,
     _phantom: PhantomData<E>,
 }
@@ -106,7 +112,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         validators: InitializedValidators,
-        slashing_protection: SlashingDatabase,
+        slashing_protection: Arc<dyn SlashingProtectionBackend>,
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
@@ -115,6 +121,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         task_executor: TaskExecutor,
         log: Logger,
     ) -> Self {
+        let signing_history = SigningHistory::load(&config.validator_dir, &log);
+
         Self {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
@@ -127,6 +135,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             fee_recipient_process: config.fee_recipient,
             gas_limit: config.gas_limit,
             builder_proposals: config.builder_proposals,
+            proposer_config: RwLock::new(ProposerConfigFile::default()),
+            signing_history,
             task_executor,
             _phantom: PhantomData,
         }
@@ -139,18 +149,40 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     pub fn register_all_in_doppelganger_protection_if_enabled(&self) -> Result<(), String> {
         if let Some(doppelganger_service) = &self.doppelganger_service {
             for pubkey in self.validators.read().iter_voting_pubkeys() {
-                doppelganger_service.register_new_validator::<E, _>(*pubkey, &self.slot_clock)?
+                doppelganger_service.register_new_validator::<E, _>(
+                    *pubkey,
+                    &self.slot_clock,
+                    self.last_signed_epoch(pubkey),
+                )?
             }
         }
 
         Ok(())
     }
 
+    /// Returns the latest epoch in which `pubkey` signed a block or attestation with this
+    /// validator client, either since it started or before it last shut down.
+    ///
+    /// The slashing protection database is deliberately not used, since it may contain messages
+    /// signed by another validator client (e.g., an imported interchange file or a shared
+    /// database).
+    fn last_signed_epoch(&self, pubkey: &PublicKeyBytes) -> Option<Epoch> {
+        self.signing_history.last_signed_epoch(pubkey)
+    }
+
     /// Returns `true` if doppelganger protection is enabled, or else `false`.
     pub fn doppelganger_protection_enabled(&self) -> bool {
         self.doppelganger_service.is_some()
     }
 
+    /// Returns the doppelganger protection state of each validator, or `None` if doppelganger
+    /// protection is disabled.
+    pub fn doppelganger_states(&self) -> Option<Vec<DoppelgangerData>> {
+        self.doppelganger_service
+            .as_ref()
+            .map(|doppelganger_service| doppelganger_service.validator_states())
+    }
+
     pub fn initialized_validators(&self) -> Arc<RwLock<InitializedValidators>> {
         self.validators.clone()
     }
@@ -203,8 +235,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map_err(|e| format!("failed to register validator: {:?}", e))?;
 
         if let Some(doppelganger_service) = &self.doppelganger_service {
-            doppelganger_service
-                .register_new_validator::<E, _>(validator_pubkey, &self.slot_clock)?;
+            doppelganger_service.register_new_validator::<E, _>(
+                validator_pubkey,
+                &self.slot_clock,
+                self.last_signed_epoch(&validator_pubkey),
+            )?;
         }
 
         self.validators
@@ -226,10 +261,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map(|validator| ProposalData {
                 validator_index: validator.get_index(),
                 fee_recipient: self
-                    .get_fee_recipient_defaulting(validator.get_suggested_fee_recipient()),
-                gas_limit: self.get_gas_limit_defaulting(validator.get_gas_limit()),
+                    .get_fee_recipient_defaulting(pubkey, validator.get_suggested_fee_recipient()),
+                gas_limit: self.get_gas_limit_defaulting(pubkey, validator.get_gas_limit()),
                 builder_proposals: self
-                    .get_builder_proposals_defaulting(validator.get_builder_proposals()),
+                    .get_builder_proposals_defaulting(pubkey, validator.get_builder_proposals()),
+                registration_overrides: self.get_registration_overrides(pubkey),
             })
     }
 
@@ -392,19 +428,53 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.validators.read().graffiti(validator_pubkey)
     }
 
+    /// Replaces the proposer config, which is consulted by `get_fee_recipient`, `get_gas_limit`,
+    /// `get_builder_proposals` and `proposal_data`.
+    pub fn set_proposer_config(&self, proposer_config: ProposerConfigFile) {
+        *self.proposer_config.write() = proposer_config;
+    }
+
+    /// Returns the proposer config entry for the given public key, if any.
+    ///
+    /// Settings in this entry take precedence over those set via the HTTP API.
+    pub fn get_validator_proposer_config(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> Option<ProposerConfig> {
+        self.proposer_config
+            .read()
+            .proposer_config
+            .get(validator_pubkey)
+            .cloned()
+    }
+
     /// Returns the fee recipient for the given public key. The priority order for fetching
     /// the fee recipient is:
-    /// 1. validator_definitions.yml
-    /// 2. process level fee recipient
+    /// 1. proposer config entry for the validator
+    /// 2. validator_definitions.yml
+    /// 3. proposer config default
+    /// 4. process level fee recipient
     pub fn get_fee_recipient(&self, validator_pubkey: &PublicKeyBytes) -> Option<Address> {
         // If there is a `suggested_fee_recipient` in the validator definitions yaml
         // file, use that value.
-        self.get_fee_recipient_defaulting(self.suggested_fee_recipient(validator_pubkey))
+        self.get_fee_recipient_defaulting(
+            validator_pubkey,
+            self.suggested_fee_recipient(validator_pubkey),
+        )
     }
 
-    pub fn get_fee_recipient_defaulting(&self, fee_recipient: Option<Address>) -> Option<Address> {
-        // If there's nothing in the file, try the process-level default value.
-        fee_recipient.or(self.fee_recipient_process)
+    pub fn get_fee_recipient_defaulting(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        fee_recipient: Option<Address>,
+    ) -> Option<Address> {
+        let proposer_config = self.proposer_config.read();
+        proposer_config
+            .validator_fee_recipient(validator_pubkey)
+            .or(fee_recipient)
+            .or(proposer_config.default_config.fee_recipient)
+            // If there's nothing in either file, try the process-level default value.
+            .or(self.fee_recipient_process)
     }
 
     /// Returns the suggested_fee_recipient from `validator_definitions.yml` if any.
@@ -418,18 +488,29 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// Returns the gas limit for the given public key. The priority order for fetching
     /// the gas limit is:
     ///
-    /// 1. validator_definitions.yml
-    /// 2. process level gas limit
-    /// 3. `DEFAULT_GAS_LIMIT`
+    /// 1. proposer config entry for the validator
+    /// 2. validator_definitions.yml
+    /// 3. proposer config default
+    /// 4. process level gas limit
+    /// 5. `DEFAULT_GAS_LIMIT`
     pub fn get_gas_limit(&self, validator_pubkey: &PublicKeyBytes) -> u64 {
-        self.get_gas_limit_defaulting(self.validators.read().gas_limit(validator_pubkey))
+        let gas_limit = self.validators.read().gas_limit(validator_pubkey);
+        self.get_gas_limit_defaulting(validator_pubkey, gas_limit)
     }
 
-    fn get_gas_limit_defaulting(&self, gas_limit: Option<u64>) -> u64 {
-        // If there is a `gas_limit` in the validator definitions yaml
-        // file, use that value.
-        gas_limit
-            // If there's nothing in the file, try the process-level default value.
+    fn get_gas_limit_defaulting(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        gas_limit: Option<u64>,
+    ) -> u64 {
+        let proposer_config = self.proposer_config.read();
+        proposer_config
+            .validator_gas_limit(validator_pubkey)
+            // If there is a `gas_limit` in the validator definitions yaml
+            // file, use that value.
+            .or(gas_limit)
+            .or_else(|| proposer_config.default_config.gas_limit())
+            // If there's nothing in either file, try the process-level default value.
             .or(self.gas_limit)
             // If there's no process-level default, use the `DEFAULT_GAS_LIMIT`.
             .unwrap_or(DEFAULT_GAS_LIMIT)
@@ -438,22 +519,42 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// Returns a `bool` for the given public key that denotes whther this validator should use the
     /// builder API. The priority order for fetching this value is:
     ///
-    /// 1. validator_definitions.yml
-    /// 2. process level flag
+    /// 1. proposer config entry for the validator
+    /// 2. validator_definitions.yml
+    /// 3. proposer config default
+    /// 4. process level flag
     pub fn get_builder_proposals(&self, validator_pubkey: &PublicKeyBytes) -> bool {
         // If there is a `suggested_fee_recipient` in the validator definitions yaml
         // file, use that value.
-        self.get_builder_proposals_defaulting(
-            self.validators.read().builder_proposals(validator_pubkey),
-        )
+        let builder_proposals = self.validators.read().builder_proposals(validator_pubkey);
+        self.get_builder_proposals_defaulting(validator_pubkey, builder_proposals)
     }
 
-    fn get_builder_proposals_defaulting(&self, builder_proposals: Option<bool>) -> bool {
-        builder_proposals
-            // If there's nothing in the file, try the process-level default value.
+    fn get_builder_proposals_defaulting(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        builder_proposals: Option<bool>,
+    ) -> bool {
+        let proposer_config = self.proposer_config.read();
+        proposer_config
+            .validator_builder_proposals(validator_pubkey)
+            .or(builder_proposals)
+            .or_else(|| proposer_config.default_config.builder_proposals())
+            // If there's nothing in either file, try the process-level default value.
             .unwrap_or(self.builder_proposals)
     }
 
+    /// Returns the builder registration overrides for the given public key from the proposer
+    /// config.
+    pub fn get_registration_overrides(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> RegistrationOverrides {
+        self.proposer_config
+            .read()
+            .registration_overrides(validator_pubkey)
+    }
+
     pub async fn sign_block<Payload: AbstractExecPayload<E>>(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -499,6 +600,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                         &self.task_executor,
                     )
                     .await?;
+                self.signing_history.record(validator_pubkey, signing_epoch);
                 Ok(SignedBeaconBlock::from_block(block, signature))
             }
             Ok(Safe::SameData) => {
@@ -571,6 +673,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 attestation
                     .add_signature(&signature, validator_committee_position)
                     .map_err(Error::UnableToSignAttestation)?;
+                self.signing_history.record(validator_pubkey, signing_epoch);
 
                 metrics::inc_counter_vec(&metrics::SIGNED_ATTESTATIONS_TOTAL, &[metrics::SUCCESS]);
 
@@ -619,12 +722,25 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     pub async fn sign_validator_registration_data(
         &self,
         validator_registration_data: ValidatorRegistrationData,
+    ) -> Result<SignedValidatorRegistrationData, Error> {
+        self.sign_validator_registration_data_with_key(
+            validator_registration_data.pubkey,
+            validator_registration_data,
+        )
+        .await
+    }
+
+    /// Signs `validator_registration_data` with the key of `validator_pubkey`, which may differ
+    /// from the public key in the registration if it has been overridden by the proposer config.
+    pub async fn sign_validator_registration_data_with_key(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        validator_registration_data: ValidatorRegistrationData,
     ) -> Result<SignedValidatorRegistrationData, Error> {
         let domain_hash = self.spec.get_builder_domain();
         let signing_root = validator_registration_data.signing_root(domain_hash);
 
-        let signing_method =
-            self.doppelganger_bypassed_signing_method(validator_registration_data.pubkey)?;
+        let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
         let signature = signing_method
             .get_signature_from_root::<E, BlindedPayload<E>>(
                 SignableMessage::ValidatorRegistration(&validator_registration_data),
@@ -645,6 +761,35 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         })
     }
 
+    /// Signs a `VoluntaryExit` for the given validator, without broadcasting it.
+    ///
+    /// Exits are not slashable, so doppelganger protection is bypassed.
+    pub async fn sign_voluntary_exit(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        voluntary_exit: VoluntaryExit,
+    ) -> Result<SignedVoluntaryExit, Error> {
+        let signing_epoch = voluntary_exit.epoch;
+        let signing_context = self.signing_context(Domain::VoluntaryExit, signing_epoch);
+        let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
+
+        let signature = signing_method
+            .get_signature::<E, BlindedPayload<E>>(
+                SignableMessage::VoluntaryExit(&voluntary_exit),
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await?;
+
+        metrics::inc_counter_vec(&metrics::SIGNED_VOLUNTARY_EXITS_TOTAL, &[metrics::SUCCESS]);
+
+        Ok(SignedVoluntaryExit {
+            message: voluntary_exit,
+            signature,
+        })
+    }
+
     /// Signs an `AggregateAndProof` for a given validator.
     ///
     /// The resulting `SignedAggregateAndProof` is sent on the aggregation channel and cannot be
@@ -845,28 +990,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         pubkeys: &[PublicKeyBytes],
     ) -> Result<Interchange, InterchangeError> {
-        self.slashing_protection.with_transaction(|txn| {
-            let known_pubkeys = pubkeys
-                .iter()
-                .filter_map(|pubkey| {
-                    let validator_id = self
-                        .slashing_protection
-                        .get_validator_id_ignoring_status(txn, pubkey)
-                        .ok()?;
-
-                    Some(
-                        self.slashing_protection
-                            .update_validator_status(txn, validator_id, false)
-                            .map(|()| *pubkey),
-                    )
-                })
-                .collect::<Result<Vec<PublicKeyBytes>, _>>()?;
-            self.slashing_protection.export_interchange_info_in_txn(
-                self.genesis_validators_root,
-                Some(&known_pubkeys),
-                txn,
-            )
-        })
+        self.slashing_protection
+            .disable_and_export_interchange_info(self.genesis_validators_root, pubkeys)
     }
 
     /// Prune the slashing protection database so that it remains performant.
@@ -904,7 +1029,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         if let Err(e) = self
             .slashing_protection
-            .prune_all_signed_attestations(all_pubkeys.iter(), new_min_target_epoch)
+            .prune_all_signed_attestations(&all_pubkeys, new_min_target_epoch)
         {
             error!(
                 self.log,
@@ -916,7 +1041,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         if let Err(e) = self
             .slashing_protection
-            .prune_all_signed_blocks(all_pubkeys.iter(), new_min_slot)
+            .prune_all_signed_blocks(&all_pubkeys, new_min_slot)
         {
             error!(
                 self.log,
@@ -931,3 +1056,17 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         info!(self.log, "Completed pruning of slashing protection DB");
     }
 }
+
+impl<T, E: EthSpec> Drop for ValidatorStore<T, E> {
+    /// Write the signing history once nothing else can sign with this validator store, so that
+    /// doppelganger protection can be shortened when the validator client next starts.
+    fn drop(&mut self) {
+        if let Err(e) = self.signing_history.persist() {
+            warn!(
+                self.log,
+                "Unable to persist signing history";
+                "error" => ?e,
+            );
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Manages validators across one or more running validator clients, via their HTTP APIs.
+use clap::{App, ArgMatches};
+use environment::Environment;
+use types::EthSpec;
+
+pub mod move_validators;
+
+pub const CMD: &str = "validator_manager";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .visible_aliases(&["vm", "validator-manager", CMD])
+        .about("Utilities for managing validators on running validator clients.")
+        .subcommand(move_validators::cli_app())
+}
+
+/// Run the validator manager, returning an error if the operation did not succeed.
+pub fn run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
+    match matches.subcommand() {
+        (move_validators::CMD, Some(matches)) => move_validators::cli_run(matches, env),
+        (unknown, _) => Err(format!(
+            "{} is not a valid {} command. See --help.",
+            unknown, CMD
+        )),
+    }
+}
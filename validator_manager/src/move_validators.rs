@@ -0,0 +1,583 @@
+//! Moves validators from one validator client to another.
+//!
+//! Each validator is moved individually: it is disabled on the source VC, exported from the source
+//! along with its slashing protection data and then imported into the destination VC. It is only
+//! deleted from the source once it is enabled on the destination. Both VCs are queried between
+//! each step to ensure that a validator is never enabled on both of them at once.
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use eth2::lighthouse_vc::{
+    http_client::ValidatorClientHttpClient,
+    std_types::{
+        DeleteKeystoreStatus, DeleteKeystoresRequest, ImportKeystoreStatus, ImportKeystoresRequest,
+        InterchangeJsonStr,
+    },
+    types::{
+        ExportKeystoreStatus, ExportKeystoresRequest, UpdateFeeRecipientRequest,
+        UpdateGasLimitRequest,
+    },
+};
+use filesystem::create_with_600_perms;
+use sensitive_url::SensitiveUrl;
+use slashing_protection::interchange::InterchangeData;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use types::{EthSpec, PublicKeyBytes};
+
+pub const CMD: &str = "move";
+pub const SRC_VC_URL_FLAG: &str = "src-vc-url";
+pub const SRC_VC_TOKEN_FLAG: &str = "src-vc-token";
+pub const DEST_VC_URL_FLAG: &str = "dest-vc-url";
+pub const DEST_VC_TOKEN_FLAG: &str = "dest-vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Moves validators from one validator client to another, along with their slashing \
+            protection data. Validators are disabled on the source before they are imported to \
+            the destination, and are only deleted from the source once they are enabled on the \
+            destination. The two validator clients are checked at every step so that a validator \
+            is never enabled on both at once. Both validator clients must be running with their \
+            HTTP APIs enabled.",
+        )
+        .arg(
+            Arg::with_name(SRC_VC_URL_FLAG)
+                .long(SRC_VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("The HTTP API address of the validator client to move validators from.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(SRC_VC_TOKEN_FLAG)
+                .long(SRC_VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The path to the API token file of the source validator client.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(DEST_VC_URL_FLAG)
+                .long(DEST_VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("The HTTP API address of the validator client to move validators to.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(DEST_VC_TOKEN_FLAG)
+                .long(DEST_VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The path to the API token file of the destination validator client.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to move: either \"all\" to move every enabled local keystore \
+                    validator, or a comma-separated list of 0x-prefixed public keys.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+/// The set of validators to be moved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Validators {
+    All,
+    Specific(Vec<PublicKeyBytes>),
+}
+
+impl FromStr for Validators {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Validators::All),
+            other => other
+                .split(',')
+                .map(PublicKeyBytes::from_str)
+                .collect::<Result<_, _>>()
+                .map(Validators::Specific),
+        }
+    }
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<(), String> {
+    let src_vc_url: SensitiveUrl = clap_utils::parse_required(matches, SRC_VC_URL_FLAG)?;
+    let src_vc_token: PathBuf = clap_utils::parse_required(matches, SRC_VC_TOKEN_FLAG)?;
+    let dest_vc_url: SensitiveUrl = clap_utils::parse_required(matches, DEST_VC_URL_FLAG)?;
+    let dest_vc_token: PathBuf = clap_utils::parse_required(matches, DEST_VC_TOKEN_FLAG)?;
+    let validators: Validators = clap_utils::parse_required(matches, VALIDATORS_FLAG)?;
+
+    if src_vc_url.full == dest_vc_url.full {
+        return Err("The source and destination validator clients must differ".to_string());
+    }
+
+    let src = vc_http_client(src_vc_url, src_vc_token)?;
+    let dest = vc_http_client(dest_vc_url, dest_vc_token)?;
+
+    let moved = env
+        .runtime()
+        .block_on(move_validators(&src, &dest, &validators))?;
+
+    eprintln!("Successfully moved {} validators", moved.len());
+
+    Ok(())
+}
+
+/// Builds an HTTP client for the validator client at `url`, using the API token at `token_path`.
+fn vc_http_client(
+    url: SensitiveUrl,
+    token_path: PathBuf,
+) -> Result<ValidatorClientHttpClient, String> {
+    let token = fs::read_to_string(&token_path)
+        .map_err(|e| format!("Unable to read API token {:?}: {:?}", token_path, e))?;
+    ValidatorClientHttpClient::new(url.clone(), token.trim().to_string())
+        .map_err(|e| format!("Unable to create client for {}: {:?}", url, e))
+}
+
+/// Moves `validators` from `src` to `dest`, returning the public keys of the moved validators.
+///
+/// Validators are moved one at a time and the process stops at the first error, leaving any
+/// remaining validators on `src`.
+pub async fn move_validators(
+    src: &ValidatorClientHttpClient,
+    dest: &ValidatorClientHttpClient,
+    validators: &Validators,
+) -> Result<Vec<PublicKeyBytes>, String> {
+    let src_keystores = src
+        .get_keystores()
+        .await
+        .map_err(|e| format!("Unable to list keystores on source: {:?}", e))?
+        .data;
+    let is_local_keystore = |pubkey: &PublicKeyBytes| {
+        src_keystores.iter().any(|keystore| {
+            keystore.validating_pubkey == *pubkey && keystore.readonly != Some(true)
+        })
+    };
+
+    let pubkeys = match validators {
+        Validators::All => src_keystores
+            .iter()
+            .map(|keystore| keystore.validating_pubkey)
+            .filter(|pubkey| is_local_keystore(pubkey))
+            .collect::<Vec<_>>(),
+        Validators::Specific(pubkeys) => {
+            if let Some(pubkey) = pubkeys.iter().find(|pubkey| !is_local_keystore(pubkey)) {
+                return Err(format!(
+                    "Validator {:?} is not an enabled local keystore on the source",
+                    pubkey
+                ));
+            }
+            pubkeys.clone()
+        }
+    };
+
+    if pubkeys.is_empty() {
+        return Err("There are no validators to move".to_string());
+    }
+
+    // Refuse to start if any of the validators are known to the destination, even if disabled.
+    let dest_validators = dest
+        .get_lighthouse_validators()
+        .await
+        .map_err(|e| format!("Unable to list validators on destination: {:?}", e))?
+        .data;
+    if let Some(existing) = dest_validators
+        .iter()
+        .find(|validator| pubkeys.contains(&validator.voting_pubkey))
+    {
+        return Err(format!(
+            "Validator {:?} already exists on the destination. Remove it before moving",
+            existing.voting_pubkey
+        ));
+    }
+
+    eprintln!("Moving {} validators", pubkeys.len());
+
+    for (i, pubkey) in pubkeys.iter().enumerate() {
+        move_validator(src, dest, pubkey).await?;
+        println!("{}/{}\t{:?}", i + 1, pubkeys.len(), pubkey);
+    }
+
+    Ok(pubkeys)
+}
+
+/// Moves a single validator from `src` to `dest`.
+async fn move_validator(
+    src: &ValidatorClientHttpClient,
+    dest: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+) -> Result<(), String> {
+    ensure_not_enabled(dest, pubkey, "destination").await?;
+
+    // Read the fee recipient and gas limit before the validator is removed from the source.
+    let fee_recipient = src
+        .get_fee_recipient(pubkey)
+        .await
+        .ok()
+        .map(|response| response.ethaddress);
+    let gas_limit = src
+        .get_gas_limit(pubkey)
+        .await
+        .ok()
+        .map(|response| response.gas_limit);
+
+    // Disable the validator on the source. It stays on the source, disabled, until it has been
+    // enabled on the destination.
+    set_enabled(src, pubkey, false)
+        .await
+        .map_err(|e| format!("Unable to disable {:?} on source: {}", pubkey, e))?;
+    ensure_not_enabled(src, pubkey, "source").await?;
+
+    // Export the keystore and slashing protection data. The validator is disabled, so its slashing
+    // protection data can't change after this point.
+    let import_request = match export_keystore(src, pubkey).await {
+        Ok(request) => request,
+        Err(e) => {
+            return Err(match restore_on_source(src, dest, pubkey).await {
+                Ok(()) => format!(
+                    "Unable to export {:?} from source, it has been re-enabled on the source: {}",
+                    pubkey, e
+                ),
+                Err(restore_error) => format!(
+                    "Unable to export {:?} from source ({}) or re-enable it on the source ({}). \
+                     It remains disabled on the source",
+                    pubkey, e, restore_error
+                ),
+            });
+        }
+    };
+
+    if let Err(e) = import_and_ensure_enabled(dest, &import_request, pubkey).await {
+        return Err(roll_back(src, dest, pubkey, import_request, e).await);
+    }
+
+    // The validator has now moved, so failing to copy its settings isn't fatal.
+    if let Some(ethaddress) = fee_recipient {
+        if let Err(e) = dest
+            .post_fee_recipient(pubkey, &UpdateFeeRecipientRequest { ethaddress })
+            .await
+        {
+            eprintln!(
+                "WARNING: unable to set fee recipient {:?} for {:?} on destination: {:?}",
+                ethaddress, pubkey, e
+            );
+        }
+    }
+    if let Some(gas_limit) = gas_limit {
+        if let Err(e) = dest
+            .post_gas_limit(pubkey, &UpdateGasLimitRequest { gas_limit })
+            .await
+        {
+            eprintln!(
+                "WARNING: unable to set gas limit {} for {:?} on destination: {:?}",
+                gas_limit, pubkey, e
+            );
+        }
+    }
+
+    ensure_not_enabled(src, pubkey, "source").await?;
+    delete_keystore(src, pubkey).await.map_err(|e| {
+        format!(
+            "Validator {:?} has moved to the destination but could not be deleted from the \
+             source, where it is disabled. Delete it from the source before continuing: {}",
+            pubkey, e
+        )
+    })
+}
+
+/// Undoes a failed import of `pubkey` into `dest`, returning a description of the failure.
+///
+/// The validator is removed from `dest` and is only re-enabled on `src` once it is known not to
+/// be enabled on `dest`, and not to have signed anything there. Otherwise it is left disabled on
+/// both and the information required to recover it is written to a file.
+async fn roll_back(
+    src: &ValidatorClientHttpClient,
+    dest: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+    mut import_request: ImportKeystoresRequest,
+    import_error: String,
+) -> String {
+    let restore_result = match remove_from_destination(dest, pubkey, &mut import_request).await {
+        Ok(()) => restore_on_source(src, dest, pubkey).await,
+        Err(e) => Err(e),
+    };
+    let restore_error = match restore_result {
+        Ok(()) => {
+            return format!(
+                "Unable to import {:?} into destination, it has been re-enabled on the source: {}",
+                pubkey, import_error
+            )
+        }
+        Err(e) => e,
+    };
+
+    let recovery = match write_stranded_validator(pubkey, &import_request) {
+        Ok(path) => format!(
+            "Its keystore, password and slashing protection data have been written to {:?}",
+            path
+        ),
+        Err(e) => format!("Its keystore could not be written to a file: {}", e),
+    };
+    format!(
+        "Unable to import {:?} into destination ({}) or re-enable it on the source ({}). It is \
+         NOT enabled on either validator client. {}",
+        pubkey, import_error, restore_error, recovery
+    )
+}
+
+/// Deletes `pubkey` from `dest` after a failed import, checking that `dest` has not signed any
+/// messages with it.
+///
+/// The slashing protection data in `import_request` is replaced with that returned by `dest`, so
+/// that it includes anything `dest` signed.
+async fn remove_from_destination(
+    dest: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+    import_request: &mut ImportKeystoresRequest,
+) -> Result<(), String> {
+    let response = dest
+        .delete_keystores(&DeleteKeystoresRequest {
+            pubkeys: vec![*pubkey],
+        })
+        .await
+        .map_err(|e| format!("unable to delete from destination: {:?}", e))?;
+    match response.data.as_slice() {
+        [status]
+            if matches!(
+                status.status,
+                DeleteKeystoreStatus::Deleted
+                    | DeleteKeystoreStatus::NotActive
+                    | DeleteKeystoreStatus::NotFound
+            ) => {}
+        other => return Err(format!("unable to delete from destination: {:?}", other)),
+    }
+    ensure_not_enabled(dest, pubkey, "destination").await?;
+
+    let find_data =
+        |data: &[InterchangeData]| data.iter().find(|data| data.pubkey == *pubkey).cloned();
+    let exported = import_request
+        .slashing_protection
+        .as_ref()
+        .and_then(|InterchangeJsonStr(interchange)| find_data(&interchange.data));
+    if let Some(returned) = find_data(&response.slashing_protection.data) {
+        let signed_on_dest = has_new_signatures(exported.as_ref(), &returned);
+        import_request.slashing_protection = Some(InterchangeJsonStr(response.slashing_protection));
+        if signed_on_dest {
+            return Err("the destination signed messages with it before it was removed".into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `after` contains a block or attestation later than any in `before`.
+fn has_new_signatures(before: Option<&InterchangeData>, after: &InterchangeData) -> bool {
+    let max_block_slot =
+        |data: &InterchangeData| data.signed_blocks.iter().map(|block| block.slot).max();
+    let max_attestation_epochs = |data: &InterchangeData| {
+        (
+            data.signed_attestations
+                .iter()
+                .map(|att| att.source_epoch)
+                .max(),
+            data.signed_attestations
+                .iter()
+                .map(|att| att.target_epoch)
+                .max(),
+        )
+    };
+
+    let (after_source, after_target) = max_attestation_epochs(after);
+    let (before_slot, (before_source, before_target)) = match before {
+        Some(before) => (max_block_slot(before), max_attestation_epochs(before)),
+        None => (None, (None, None)),
+    };
+
+    max_block_slot(after) > before_slot
+        || after_source > before_source
+        || after_target > before_target
+}
+
+/// Re-enables `pubkey` on `src`, after checking that it is not enabled on `dest`.
+async fn restore_on_source(
+    src: &ValidatorClientHttpClient,
+    dest: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+) -> Result<(), String> {
+    ensure_not_enabled(dest, pubkey, "destination").await?;
+    set_enabled(src, pubkey, true).await
+}
+
+/// Enables or disables `pubkey` on `client`.
+async fn set_enabled(
+    client: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+    enabled: bool,
+) -> Result<(), String> {
+    client
+        .patch_lighthouse_validators(pubkey, Some(enabled), None, None)
+        .await
+        .map_err(|e| format!("{:?}", e))
+}
+
+/// Returns an error if `pubkey` is enabled on `client`, or if that cannot be determined.
+async fn ensure_not_enabled(
+    client: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+    name: &str,
+) -> Result<(), String> {
+    match client.get_lighthouse_validators_pubkey(pubkey).await {
+        Ok(None) => Ok(()),
+        Ok(Some(validator)) if !validator.data.enabled => Ok(()),
+        Ok(Some(_)) => Err(format!(
+            "Validator {:?} is unexpectedly enabled on the {}",
+            pubkey, name
+        )),
+        Err(e) => Err(format!(
+            "Unable to check status of {:?} on the {}: {:?}",
+            pubkey, name, e
+        )),
+    }
+}
+
+/// Exports the keystore, password and slashing protection data of the disabled validator `pubkey`
+/// from `client`, in the form of a request to import them elsewhere.
+async fn export_keystore(
+    client: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+) -> Result<ImportKeystoresRequest, String> {
+    let mut response = client
+        .post_lighthouse_keystores_export(&ExportKeystoresRequest {
+            pubkeys: vec![*pubkey],
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    if response.data.len() != 1 {
+        return Err(format!("{} responses", response.data.len()));
+    }
+    let export = response.data.remove(0);
+    match (
+        export.status.status,
+        export.validating_keystore,
+        export.validating_keystore_password,
+    ) {
+        (ExportKeystoreStatus::Exported, Some(keystore), Some(password)) => {
+            Ok(ImportKeystoresRequest {
+                keystores: vec![keystore],
+                passwords: vec![password],
+                slashing_protection: Some(InterchangeJsonStr(response.slashing_protection)),
+            })
+        }
+        (status, _, _) => Err(format!(
+            "{:?} {}",
+            status,
+            export.status.message.unwrap_or_default()
+        )),
+    }
+}
+
+/// Imports the single keystore in `request` into `client` and checks that it is enabled.
+async fn import_and_ensure_enabled(
+    client: &ValidatorClientHttpClient,
+    request: &ImportKeystoresRequest,
+    pubkey: &PublicKeyBytes,
+) -> Result<(), String> {
+    let response = client
+        .post_keystores(request)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    match response.data.as_slice() {
+        [status] if status.status == ImportKeystoreStatus::Imported => (),
+        other => return Err(format!("{:?}", other)),
+    }
+
+    match client.get_lighthouse_validators_pubkey(pubkey).await {
+        Ok(Some(validator)) if validator.data.enabled => Ok(()),
+        other => Err(format!("not enabled after import: {:?}", other)),
+    }
+}
+
+/// Deletes the single keystore `pubkey` from `client`.
+async fn delete_keystore(
+    client: &ValidatorClientHttpClient,
+    pubkey: &PublicKeyBytes,
+) -> Result<(), String> {
+    let response = client
+        .delete_keystores(&DeleteKeystoresRequest {
+            pubkeys: vec![*pubkey],
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    match response.data.as_slice() {
+        [status] if status.status == DeleteKeystoreStatus::Deleted => Ok(()),
+        other => Err(format!("{:?}", other)),
+    }
+}
+
+/// Writes everything required to manually recover a validator which is enabled on neither VC to a
+/// file in the working directory, returning its path.
+///
+/// The file is readable only by its owner and contains a request body which may be sent to the
+/// standard `POST /eth/v1/keystores` endpoint of any validator client.
+fn write_stranded_validator(
+    pubkey: &PublicKeyBytes,
+    request: &ImportKeystoresRequest,
+) -> Result<PathBuf, String> {
+    let path = env::current_dir()
+        .map_err(|e| format!("Unable to read working directory: {:?}", e))?
+        .join(format!("stranded-validator-{:?}.json", pubkey));
+    let bytes = serde_json::to_vec(request)
+        .map_err(|e| format!("Unable to serialize keystore: {:?}", e))?;
+    create_with_600_perms(&path, &bytes)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use slashing_protection::interchange::{SignedAttestation, SignedBlock};
+    use types::{Epoch, Keypair, Slot};
+
+    #[test]
+    fn new_signatures() {
+        let pubkey = Keypair::random().pk.compress();
+        let data = |slots: &[u64], epochs: &[(u64, u64)]| InterchangeData {
+            pubkey,
+            signed_blocks: slots
+                .iter()
+                .map(|slot| SignedBlock {
+                    slot: Slot::new(*slot),
+                    signing_root: None,
+                })
+                .collect(),
+            signed_attestations: epochs
+                .iter()
+                .map(|(source, target)| SignedAttestation {
+                    source_epoch: Epoch::new(*source),
+                    target_epoch: Epoch::new(*target),
+                    signing_root: None,
+                })
+                .collect(),
+        };
+
+        let before = data(&[10], &[(1, 2)]);
+        assert!(!has_new_signatures(Some(&before), &before));
+        assert!(!has_new_signatures(Some(&before), &data(&[5], &[(0, 1)])));
+        assert!(!has_new_signatures(None, &data(&[], &[])));
+        assert!(has_new_signatures(Some(&before), &data(&[11], &[(1, 2)])));
+        assert!(has_new_signatures(Some(&before), &data(&[10], &[(1, 3)])));
+        assert!(has_new_signatures(Some(&before), &data(&[10], &[(2, 2)])));
+        assert!(has_new_signatures(None, &data(&[0], &[])));
+    }
+}
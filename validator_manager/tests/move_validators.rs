@@ -0,0 +1,162 @@
+#![cfg(not(debug_assertions))] // Tests are too slow in debug.
+
+use account_utils::random_password_string;
+use eth2::lighthouse_vc::{
+    std_types::{ImportKeystoresRequest, KeystoreJsonStr},
+    types::UpdateFeeRecipientRequest,
+};
+use eth2_keystore::KeystoreBuilder;
+use std::sync::Arc;
+use types::{
+    Address, AggregateSignature, Attestation, AttestationData, BitList, Checkpoint, Epoch, EthSpec,
+    Hash256, Keypair, MainnetEthSpec, Unsigned,
+};
+use validator_client::http_api::test_utils::ApiTester;
+use validator_manager::move_validators::{move_validators, Validators};
+
+type E = MainnetEthSpec;
+
+fn run_dual_vc_test<F, V>(f: F)
+where
+    F: FnOnce(ApiTester, ApiTester) -> V,
+    V: std::future::Future<Output = ()>,
+{
+    let runtime = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap(),
+    );
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        let src = ApiTester::new(weak_runtime.clone()).await;
+        let dest = ApiTester::new(weak_runtime).await;
+        f(src, dest).await
+    });
+}
+
+fn new_keystore() -> (KeystoreJsonStr, account_utils::ZeroizeString) {
+    let password = random_password_string();
+    let keystore = KeystoreBuilder::new(&Keypair::random(), password.as_ref(), String::new())
+        .unwrap()
+        .build()
+        .unwrap();
+    (KeystoreJsonStr(keystore), password)
+}
+
+fn make_attestation(source_epoch: u64, target_epoch: u64) -> Attestation<E> {
+    Attestation {
+        aggregation_bits: BitList::with_capacity(
+            <E as EthSpec>::MaxValidatorsPerCommittee::to_usize(),
+        )
+        .unwrap(),
+        data: AttestationData {
+            source: Checkpoint {
+                epoch: Epoch::new(source_epoch),
+                root: Hash256::from_low_u64_le(source_epoch),
+            },
+            target: Checkpoint {
+                epoch: Epoch::new(target_epoch),
+                root: Hash256::from_low_u64_le(target_epoch),
+            },
+            ..AttestationData::default()
+        },
+        signature: AggregateSignature::empty(),
+    }
+}
+
+#[test]
+fn move_validators_between_vcs() {
+    run_dual_vc_test(|src, dest| async move {
+        let (keystores, passwords): (Vec<_>, Vec<_>) = (0..3).map(|_| new_keystore()).unzip();
+        let pubkeys = keystores
+            .iter()
+            .map(|keystore| keystore.0.public_key().unwrap().compress())
+            .collect::<Vec<_>>();
+
+        src.client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords,
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+
+        let fee_recipient = Address::repeat_byte(0xff);
+        src.client
+            .post_fee_recipient(
+                &pubkeys[0],
+                &UpdateFeeRecipientRequest {
+                    ethaddress: fee_recipient,
+                },
+            )
+            .await
+            .unwrap();
+
+        for pubkey in &pubkeys {
+            let mut attestation = make_attestation(1, 2);
+            src.validator_store
+                .sign_attestation(*pubkey, 0, &mut attestation, Epoch::new(2))
+                .await
+                .unwrap();
+        }
+
+        // Move the first two validators.
+        let moved = move_validators(
+            &src.client,
+            &dest.client,
+            &Validators::Specific(pubkeys[..2].to_vec()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved, pubkeys[..2]);
+
+        let src_keystores = src.client.get_keystores().await.unwrap().data;
+        assert_eq!(src_keystores.len(), 1);
+        assert_eq!(src_keystores[0].validating_pubkey, pubkeys[2]);
+        let dest_validators = dest.client.get_lighthouse_validators().await.unwrap().data;
+        assert_eq!(
+            dest_validators
+                .iter()
+                .map(|validator| (validator.voting_pubkey, validator.enabled))
+                .collect::<Vec<_>>(),
+            vec![(pubkeys[0], true), (pubkeys[1], true)]
+        );
+
+        // The fee recipient and slashing protection should have moved with the validators.
+        assert_eq!(
+            dest.client
+                .get_fee_recipient(&pubkeys[0])
+                .await
+                .unwrap()
+                .ethaddress,
+            fee_recipient
+        );
+        for pubkey in &pubkeys[..2] {
+            let mut attestation = make_attestation(0, 2);
+            dest.validator_store
+                .sign_attestation(*pubkey, 0, &mut attestation, Epoch::new(2))
+                .await
+                .unwrap_err();
+        }
+
+        // Moving validators which are no longer on the source, or already on the destination,
+        // should fail without modifying either validator client.
+        move_validators(
+            &src.client,
+            &dest.client,
+            &Validators::Specific(pubkeys[..1].to_vec()),
+        )
+        .await
+        .unwrap_err();
+        move_validators(&dest.client, &src.client, &Validators::All)
+            .await
+            .unwrap();
+        assert_eq!(src.client.get_keystores().await.unwrap().data.len(), 3);
+        move_validators(&dest.client, &src.client, &Validators::All)
+            .await
+            .unwrap_err();
+        assert!(dest.client.get_keystores().await.unwrap().data.is_empty());
+    })
+}